@@ -36,6 +36,8 @@ fn get_labels_german() -> FileDialogLabels {
         audio_dir: "🎵  Audio".to_string(),
         pictures_dir: "🖼  Fotos".to_string(),
         videos_dir: "🎞  Videos".to_string(),
+        public_dir: "🌐  Öffentlich".to_string(),
+        templates_dir: "🗋  Vorlagen".to_string(),
 
         pin_folder: "📌 Ordner anheften".to_string(),
         unpin_folder: "✖ Ordner loslösen".to_string(),
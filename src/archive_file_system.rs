@@ -0,0 +1,425 @@
+//! File system that allows zip archives to be browsed like directories.
+
+use egui::mutex::Mutex;
+use std::collections::{BTreeMap, HashMap};
+use std::io::{self, Read};
+use std::ops::ControlFlow;
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
+use std::time::SystemTime;
+
+use crate::data::{Disks, Metadata, UserDirectories};
+use crate::FileSystem;
+
+/// Implementation of `FileSystem` that displays zip archives as read-only directories.
+///
+/// All calls for paths outside of an archive are forwarded to the wrapped file system.
+/// The entries inside an archive are addressed by appending their path inside the archive to
+/// the path of the archive, for example `/home/user/assets.zip/textures/grass.png`.
+/// Such a virtual path is also returned when the user picks an entry inside an archive.
+/// Use `ArchiveFileSystem::split_archive_path` to get the path of the archive and the
+/// path of the entry inside the archive.
+///
+/// The archives themselves are read using the standard library, so they must exist on the
+/// native file system. Directories can not be created inside an archive.
+///
+/// Requires the `zip` feature.
+pub struct ArchiveFileSystem {
+    /// The file system used for all paths outside of an archive.
+    inner: Arc<dyn FileSystem + Send + Sync>,
+    /// The archives that have already been read, mapped by their path.
+    archives: Mutex<HashMap<PathBuf, Arc<ArchiveIndex>>>,
+}
+
+impl std::fmt::Debug for ArchiveFileSystem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ArchiveFileSystem")
+            .field("inner", &self.inner)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The entries of a single archive.
+struct ArchiveIndex {
+    /// The last modification time of the archive when it was read.
+    /// Used to read the archive again when it changed.
+    last_modified: Option<SystemTime>,
+    /// The entries mapped by their path inside the archive.
+    entries: BTreeMap<PathBuf, ArchiveEntry>,
+}
+
+/// A single file or directory inside an archive.
+struct ArchiveEntry {
+    /// The name of the entry as stored in the archive.
+    /// None for directories that are not stored explicitly.
+    name: Option<String>,
+    is_dir: bool,
+    size: u64,
+}
+
+impl ArchiveIndex {
+    /// Reads the entries of the given archive.
+    fn read(path: &Path) -> io::Result<Self> {
+        let file = std::fs::File::open(path)?;
+        let last_modified = file.metadata()?.modified().ok();
+        let mut archive = zip::ZipArchive::new(file)?;
+
+        let mut entries = BTreeMap::new();
+
+        for i in 0..archive.len() {
+            let file = archive.by_index_raw(i)?;
+
+            // Entries that would escape the archive, like `../a.txt`, are ignored
+            let Some(entry_path) = file.enclosed_name() else {
+                continue;
+            };
+
+            // Directories are not required to be stored in the archive
+            for parent in entry_path.ancestors().skip(1) {
+                if parent.as_os_str().is_empty() {
+                    break;
+                }
+
+                entries.entry(parent.to_path_buf()).or_insert(ArchiveEntry {
+                    name: None,
+                    is_dir: true,
+                    size: 0,
+                });
+            }
+
+            entries.insert(
+                entry_path,
+                ArchiveEntry {
+                    name: Some(file.name().to_string()),
+                    is_dir: file.is_dir(),
+                    size: file.size(),
+                },
+            );
+        }
+
+        Ok(Self {
+            last_modified,
+            entries,
+        })
+    }
+
+    /// Returns the paths of the direct children of the given directory inside the archive.
+    fn children(&self, dir: &Path) -> Vec<PathBuf> {
+        self.entries
+            .keys()
+            .filter(|path| path.parent() == Some(dir))
+            .cloned()
+            .collect()
+    }
+}
+
+/// Error returned when trying to modify an archive.
+fn read_only_error() -> io::Error {
+    io::Error::new(io::ErrorKind::PermissionDenied, "archives are read-only")
+}
+
+/// Checks if the path has the extension of a supported archive.
+fn has_archive_extension(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("zip"))
+}
+
+impl ArchiveFileSystem {
+    /// Creates a new file system that forwards all paths outside of an archive to the
+    /// given file system.
+    pub fn new(inner: Arc<dyn FileSystem + Send + Sync>) -> Self {
+        Self {
+            inner,
+            archives: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Splits the given path into the path of the archive and the path of the entry
+    /// inside the archive.
+    ///
+    /// Returns None if the path is not inside an archive. The path of the entry is empty
+    /// if the path points to the archive itself.
+    pub fn split_archive_path(&self, path: &Path) -> Option<(PathBuf, PathBuf)> {
+        let archive = path
+            .ancestors()
+            .filter(|p| has_archive_extension(p) && self.inner.is_file(p))
+            .last()?;
+
+        let entry = path.strip_prefix(archive).ok()?;
+
+        // Prevent leaving the archive using paths like `assets.zip/../a.txt`
+        if entry
+            .components()
+            .any(|c| !matches!(c, Component::Normal(_)))
+        {
+            return None;
+        }
+
+        Some((archive.to_path_buf(), entry.to_path_buf()))
+    }
+
+    /// Gets the index of the given archive.
+    /// The archive is only read again if it was modified since it was last read.
+    fn index(&self, archive: &Path) -> io::Result<Arc<ArchiveIndex>> {
+        let last_modified = std::fs::metadata(archive)?.modified().ok();
+
+        if let Some(index) = self.archives.lock().get(archive) {
+            if index.last_modified == last_modified {
+                return Ok(index.clone());
+            }
+        }
+
+        let index = Arc::new(ArchiveIndex::read(archive)?);
+        self.archives
+            .lock()
+            .insert(archive.to_path_buf(), index.clone());
+
+        Ok(index)
+    }
+
+    /// Runs the given function with the entry at the given path.
+    /// Returns None if the path is not inside an archive.
+    /// The path of the entry is empty if the path points to the archive itself.
+    fn with_entry<T>(
+        &self,
+        path: &Path,
+        f: impl FnOnce(&Path, &Path, io::Result<Arc<ArchiveIndex>>) -> T,
+    ) -> Option<T> {
+        let (archive, entry) = self.split_archive_path(path)?;
+        let index = self.index(&archive);
+
+        Some(f(&archive, &entry, index))
+    }
+}
+
+impl FileSystem for ArchiveFileSystem {
+    fn metadata(&self, path: &Path) -> io::Result<Metadata> {
+        self.with_entry(path, |_, entry, index| {
+            if entry.as_os_str().is_empty() {
+                return self.inner.metadata(path);
+            }
+
+            let index = index?;
+            let entry = index
+                .entries
+                .get(entry)
+                .ok_or_else(|| io::Error::from(io::ErrorKind::NotFound))?;
+
+            let mut metadata = Metadata::default();
+            if !entry.is_dir {
+                metadata.size = Some(entry.size);
+            }
+
+            Ok(metadata)
+        })
+        .unwrap_or_else(|| self.inner.metadata(path))
+    }
+
+    fn is_dir(&self, path: &Path) -> bool {
+        self.with_entry(path, |_, entry, index| {
+            entry.as_os_str().is_empty()
+                || index.is_ok_and(|index| index.entries.get(entry).is_some_and(|e| e.is_dir))
+        })
+        .unwrap_or_else(|| self.inner.is_dir(path))
+    }
+
+    fn is_file(&self, path: &Path) -> bool {
+        self.with_entry(path, |_, entry, index| {
+            !entry.as_os_str().is_empty()
+                && index.is_ok_and(|index| index.entries.get(entry).is_some_and(|e| !e.is_dir))
+        })
+        .unwrap_or_else(|| self.inner.is_file(path))
+    }
+
+    fn exists(&self, path: &Path) -> bool {
+        self.with_entry(path, |_, entry, index| {
+            entry.as_os_str().is_empty()
+                || index.is_ok_and(|index| index.entries.contains_key(entry))
+        })
+        .unwrap_or_else(|| self.inner.exists(path))
+    }
+
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<PathBuf>> {
+        self.with_entry(path, |archive, entry, index| {
+            Ok(index?
+                .children(entry)
+                .into_iter()
+                .map(|child| archive.join(child))
+                .collect())
+        })
+        .unwrap_or_else(|| self.inner.read_dir(path))
+    }
+
+    fn read_dir_incremental(
+        &self,
+        path: &Path,
+        on_entry: &mut dyn FnMut(io::Result<PathBuf>) -> ControlFlow<()>,
+    ) -> io::Result<()> {
+        if self.split_archive_path(path).is_none() {
+            return self.inner.read_dir_incremental(path, on_entry);
+        }
+
+        // The index of an archive is read at once, so its children are passed one by one
+        for entry in self.read_dir(path)? {
+            if on_entry(Ok(entry)).is_break() {
+                break;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn get_disks(&self, canonicalize_paths: bool) -> Disks {
+        self.inner.get_disks(canonicalize_paths)
+    }
+
+    fn is_path_hidden(&self, path: &Path) -> bool {
+        self.with_entry(path, |_, entry, _| {
+            if entry.as_os_str().is_empty() {
+                return self.inner.is_path_hidden(path);
+            }
+
+            path.file_name()
+                .is_some_and(|name| name.as_encoded_bytes().starts_with(b"."))
+        })
+        .unwrap_or_else(|| self.inner.is_path_hidden(path))
+    }
+
+    fn create_dir(&self, path: &Path) -> io::Result<()> {
+        if self.is_read_only(path.parent().unwrap_or(path)) {
+            return Err(read_only_error());
+        }
+
+        self.inner.create_dir(path)
+    }
+
+    fn user_dirs(&self, canonicalize_paths: bool) -> Option<UserDirectories> {
+        self.inner.user_dirs(canonicalize_paths)
+    }
+
+    fn current_dir(&self) -> io::Result<PathBuf> {
+        self.inner.current_dir()
+    }
+
+    fn load_text_file_preview(&self, path: &Path, max_chars: usize) -> io::Result<String> {
+        self.with_entry(path, |archive, entry, index| {
+            let name = index?
+                .entries
+                .get(entry)
+                .and_then(|e| e.name.clone())
+                .ok_or_else(|| io::Error::from(io::ErrorKind::NotFound))?;
+
+            let mut archive = zip::ZipArchive::new(std::fs::File::open(archive)?)?;
+            let file = archive.by_name(&name)?;
+
+            let mut text = String::new();
+            file.take(max_chars as u64).read_to_string(&mut text)?;
+
+            Ok(text)
+        })
+        .unwrap_or_else(|| self.inner.load_text_file_preview(path, max_chars))
+    }
+
+    fn read_to_string(&self, path: &Path) -> io::Result<String> {
+        if self.split_archive_path(path).is_some() {
+            self.load_text_file_preview(path, usize::MAX)
+        } else {
+            self.inner.read_to_string(path)
+        }
+    }
+
+    fn canonicalize(&self, path: &Path) -> io::Result<PathBuf> {
+        self.with_entry(path, |archive, entry, _| {
+            Ok(self.inner.canonicalize(archive)?.join(entry))
+        })
+        .unwrap_or_else(|| self.inner.canonicalize(path))
+    }
+
+    fn rename(&self, from: &Path, to: &Path) -> io::Result<()> {
+        if self.split_archive_path(from).is_some() || self.split_archive_path(to).is_some() {
+            return Err(read_only_error());
+        }
+
+        self.inner.rename(from, to)
+    }
+
+    fn remove(&self, path: &Path) -> io::Result<()> {
+        if self.split_archive_path(path).is_some() {
+            return Err(read_only_error());
+        }
+
+        self.inner.remove(path)
+    }
+
+    fn is_read_only(&self, path: &Path) -> bool {
+        self.split_archive_path(path).is_some() || self.inner.is_read_only(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::NativeFileSystem;
+    use std::io::Write;
+
+    /// Creates an archive containing `readme.txt` and `textures/grass.png`.
+    fn create_archive(path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+        let mut writer = zip::ZipWriter::new(std::fs::File::create(path)?);
+        let options = zip::write::SimpleFileOptions::default()
+            .compression_method(zip::CompressionMethod::Stored);
+
+        writer.start_file("readme.txt", options)?;
+        writer.write_all(b"hello")?;
+        writer.start_file("textures/grass.png", options)?;
+        writer.write_all(&[0; 16])?;
+        writer.start_file("../escape.txt", options)?;
+        writer.finish()?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn browse_archive() -> Result<(), Box<dyn std::error::Error>> {
+        let dir = std::env::temp_dir().join(format!("egui-file-dialog-{}", std::process::id()));
+        std::fs::create_dir_all(&dir)?;
+        let archive = dir.join("assets.zip");
+        create_archive(&archive)?;
+
+        let fs = ArchiveFileSystem::new(Arc::new(NativeFileSystem));
+
+        assert!(fs.is_dir(&archive));
+        assert!(!fs.is_file(&archive));
+        assert!(fs.is_read_only(&archive));
+        assert!(!fs.is_read_only(&dir));
+
+        let mut children = fs.read_dir(&archive)?;
+        children.sort();
+        assert_eq!(
+            children,
+            [archive.join("readme.txt"), archive.join("textures")]
+        );
+
+        let grass = archive.join("textures").join("grass.png");
+        assert!(fs.is_dir(&archive.join("textures")));
+        assert!(fs.is_file(&grass));
+        assert_eq!(fs.metadata(&grass)?.size, Some(16));
+        assert_eq!(
+            fs.read_dir(&archive.join("textures"))?,
+            std::slice::from_ref(&grass)
+        );
+        assert_eq!(
+            fs.split_archive_path(&grass),
+            Some((archive.clone(), Path::new("textures").join("grass.png")))
+        );
+        assert_eq!(
+            fs.load_text_file_preview(&archive.join("readme.txt"), 100)?,
+            "hello"
+        );
+        assert!(fs.create_dir(&archive.join("new")).is_err());
+        assert!(fs.split_archive_path(&archive.join("..")).is_none());
+
+        std::fs::remove_dir_all(&dir)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,150 @@
+use egui::mutex::Mutex;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
+use std::thread;
+
+/// Cache of values that are looked up for single paths, like the number of items inside
+/// a directory.
+///
+/// The requested paths are looked up one after another on a single thread, so that the
+/// number of threads stays bounded no matter how many paths are requested. The thread
+/// is started with the first request and stopped as soon as the cache is cleared.
+/// Values can also be looked up on the calling thread and inserted directly.
+pub struct BackgroundLookup<T> {
+    /// The values that are looked up, mapped by their path.
+    /// None if the value of the path is currently being looked up.
+    values: HashMap<PathBuf, Option<T>>,
+    /// Sender passing the requested paths to the thread looking up the values.
+    /// None if the thread was not started yet.
+    requests: Option<mpsc::Sender<PathBuf>>,
+    /// Receiver for the values that were looked up on the other thread.
+    /// Wrapped in a mutex, because the receiver cannot be shared between threads otherwise.
+    receiver: Option<Mutex<mpsc::Receiver<(PathBuf, T)>>>,
+    /// Flag used to tell the thread looking up the values to stop.
+    /// It is also passed to the lookup, so that long running lookups can stop early.
+    cancelled: Arc<AtomicBool>,
+}
+
+impl<T> Default for BackgroundLookup<T> {
+    fn default() -> Self {
+        Self {
+            values: HashMap::new(),
+            requests: None,
+            receiver: None,
+            cancelled: Arc::new(AtomicBool::new(false)),
+        }
+    }
+}
+
+impl<T: std::fmt::Debug> std::fmt::Debug for BackgroundLookup<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BackgroundLookup")
+            .field("values", &self.values)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T: Copy + Send + 'static> BackgroundLookup<T> {
+    /// Gets the value of the given path.
+    /// Returns None if the path was not requested or its value is still being looked up.
+    pub fn get(&self, path: &Path) -> Option<T> {
+        self.values.get(path).copied().flatten()
+    }
+
+    /// Checks if the given path was already requested or inserted.
+    pub fn contains(&self, path: &Path) -> bool {
+        self.values.contains_key(path)
+    }
+
+    /// Inserts a value that was looked up on the calling thread.
+    pub fn insert(&mut self, path: PathBuf, value: T) {
+        self.values.insert(path, Some(value));
+    }
+
+    /// Starts looking up the value of the given path on the background thread.
+    /// The thread is started using `lookup` if it is not running yet, otherwise the path
+    /// is looked up using the lookup the thread was started with.
+    pub fn request<F>(&mut self, path: &Path, lookup: F)
+    where
+        F: Fn(&Path, &AtomicBool) -> T + Send + 'static,
+    {
+        if self.requests.is_none() {
+            self.requests = Some(self.spawn_worker(lookup));
+        }
+
+        if self
+            .requests
+            .as_ref()
+            .is_some_and(|requests| requests.send(path.to_path_buf()).is_ok())
+        {
+            self.values.insert(path.to_path_buf(), None);
+        }
+    }
+
+    /// Starts the thread looking up the values of the requested paths one after another.
+    /// Returns the sender used to request the paths.
+    fn spawn_worker<F>(&mut self, lookup: F) -> mpsc::Sender<PathBuf>
+    where
+        F: Fn(&Path, &AtomicBool) -> T + Send + 'static,
+    {
+        let (request_sender, request_receiver) = mpsc::channel::<PathBuf>();
+        let (sender, receiver) = mpsc::channel();
+        self.receiver = Some(Mutex::new(receiver));
+
+        let cancelled = self.cancelled.clone();
+        thread::spawn(move || {
+            // Stops once the sender of the requests is dropped by `clear`
+            for path in request_receiver {
+                if cancelled.load(Ordering::Relaxed) {
+                    break;
+                }
+
+                let value = lookup(&path, &cancelled);
+
+                if cancelled.load(Ordering::Relaxed) || sender.send((path, value)).is_err() {
+                    break;
+                }
+            }
+        });
+
+        request_sender
+    }
+
+    /// Receives the values that were looked up on the other thread.
+    /// Returns true if any values were received.
+    pub fn update(&mut self) -> bool {
+        let Some(recv) = &self.receiver else {
+            return false;
+        };
+
+        let received: Vec<(PathBuf, T)> = recv.lock().try_iter().collect();
+        let mut changed = false;
+
+        for (path, value) in received {
+            // The path may have been cleared in the meantime
+            if let Some(v) = self.values.get_mut(&path) {
+                *v = Some(value);
+                changed = true;
+            }
+        }
+
+        changed
+    }
+
+    /// Checks if there are still values being looked up.
+    pub fn is_pending(&self) -> bool {
+        self.values.values().any(Option::is_none)
+    }
+
+    /// Stops looking up values and removes all cached values,
+    /// so that they are looked up again when requested.
+    pub fn clear(&mut self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+        self.cancelled = Arc::new(AtomicBool::new(false));
+        self.requests = None;
+        self.receiver = None;
+        self.values.clear();
+    }
+}
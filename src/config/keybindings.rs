@@ -113,12 +113,26 @@ pub struct FileDialogKeyBindings {
     pub edit_path: Vec<KeyBinding>,
     /// Shortcut to switch to the home directory and text edit the current path
     pub home_edit_path: Vec<KeyBinding>,
+    /// Shortcut to open a popup to enter the path of a folder to open
+    pub go_to: Vec<KeyBinding>,
     /// Shortcut to move the selection one item up
     pub selection_up: Vec<KeyBinding>,
     /// Shortcut to move the selection one item down
     pub selection_down: Vec<KeyBinding>,
+    /// Shortcut to select the first item
+    pub selection_first: Vec<KeyBinding>,
+    /// Shortcut to select the last item
+    pub selection_last: Vec<KeyBinding>,
+    /// Shortcut to move the selection one page up
+    pub selection_page_up: Vec<KeyBinding>,
+    /// Shortcut to move the selection one page down
+    pub selection_page_down: Vec<KeyBinding>,
     /// Shortcut to select every item when the dialog is in `DialogMode::SelectMultiple` mode
     pub select_all: Vec<KeyBinding>,
+    /// Shortcut to move the keyboard focus to the next area of the dialog
+    pub focus_next: Vec<KeyBinding>,
+    /// Shortcut to move the keyboard focus to the previous area of the dialog
+    pub focus_previous: Vec<KeyBinding>,
 }
 
 impl FileDialogKeyBindings {
@@ -156,15 +170,25 @@ impl Default for FileDialogKeyBindings {
                 KeyBinding::keyboard_shortcut(Modifiers::ALT, Key::ArrowRight),
             ],
             reload: vec![KeyBinding::key(egui::Key::F5)],
-            new_folder: vec![KeyBinding::keyboard_shortcut(Modifiers::COMMAND, Key::N)],
+            new_folder: vec![
+                KeyBinding::keyboard_shortcut(Modifiers::COMMAND | Modifiers::SHIFT, Key::N),
+                KeyBinding::keyboard_shortcut(Modifiers::COMMAND, Key::N),
+            ],
             edit_path: vec![KeyBinding::key(Key::Slash)],
             home_edit_path: vec![
                 KeyBinding::keyboard_shortcut(Modifiers::SHIFT, egui::Key::Backtick),
                 KeyBinding::text("~".to_string()),
             ],
+            go_to: vec![KeyBinding::keyboard_shortcut(Modifiers::COMMAND, Key::L)],
             selection_up: vec![KeyBinding::key(Key::ArrowUp)],
             selection_down: vec![KeyBinding::key(Key::ArrowDown)],
+            selection_first: vec![KeyBinding::key(Key::Home)],
+            selection_last: vec![KeyBinding::key(Key::End)],
+            selection_page_up: vec![KeyBinding::key(Key::PageUp)],
+            selection_page_down: vec![KeyBinding::key(Key::PageDown)],
             select_all: vec![KeyBinding::keyboard_shortcut(Modifiers::COMMAND, Key::A)],
+            focus_next: vec![KeyBinding::keyboard_shortcut(Modifiers::NONE, Key::Tab)],
+            focus_previous: vec![KeyBinding::keyboard_shortcut(Modifiers::SHIFT, Key::Tab)],
         }
     }
 }
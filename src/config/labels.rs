@@ -36,9 +36,38 @@ pub struct FileDialogLabels {
     pub cancel: String,
     /// Text displayed in the buttons to overwrite something, such as a file.
     pub overwrite: String,
+    /// Hover text and accessible name of the icon buttons to confirm an input,
+    /// such as the path edit or the name of a new folder.
+    pub confirm: String,
 
     // ------------------------------------------------------------------------
     // Top panel:
+    /// Hover text of the button to open the parent directory.
+    pub tooltip_parent_directory: String,
+    /// Hover text of the button to open the parent directory if there is no parent directory.
+    pub tooltip_no_parent_directory: String,
+    /// Hover text of the button to open the previous directory.
+    pub tooltip_back: String,
+    /// Hover text of the button to open the previous directory if there is no
+    /// previous directory.
+    pub tooltip_no_previous_directory: String,
+    /// Hover text of the button to open the next directory.
+    pub tooltip_forward: String,
+    /// Hover text of the button to open the next directory if there is no next directory.
+    pub tooltip_no_next_directory: String,
+    /// Hover text of the button to create a new folder.
+    pub tooltip_new_folder: String,
+    /// Hover text of the button to open the menu.
+    pub tooltip_menu: String,
+    /// Hover text of the button to text edit the current path.
+    pub tooltip_edit_path: String,
+    /// Hover text of the button to reverse the sort order.
+    pub tooltip_sort_order: String,
+    /// Hover text of the button to pin the current directory.
+    pub tooltip_pin_directory: String,
+    /// Hover text of the button to unpin the current directory.
+    pub tooltip_unpin_directory: String,
+
     /// Text used for the option to reload the file dialog.
     pub reload: String,
     /// Text used for the option to open the working directory.
@@ -47,6 +76,8 @@ pub struct FileDialogLabels {
     pub show_hidden: String,
     /// Text used for the option to show or hide system files.
     pub show_system_files: String,
+    /// Text used for the option to show or hide entries matched by a `.gitignore` file.
+    pub show_ignored: String,
 
     // ------------------------------------------------------------------------
     // Left panel:
@@ -58,6 +89,10 @@ pub struct FileDialogLabels {
     pub heading_devices: String,
     /// Heading of the "Removable Devices" section in the left panel
     pub heading_removable_devices: String,
+    /// Text used for the option to eject a removable device.
+    pub eject_device: String,
+    /// Tooltip of entries in the left panel whose path no longer exists.
+    pub location_unavailable: String,
 
     /// Name of the home directory
     pub home_dir: String,
@@ -73,6 +108,10 @@ pub struct FileDialogLabels {
     pub pictures_dir: String,
     /// Name of the videos directory
     pub videos_dir: String,
+    /// Name of the directory used to share files with other users
+    pub public_dir: String,
+    /// Name of the templates directory
+    pub templates_dir: String,
 
     // ------------------------------------------------------------------------
     // Central panel:
@@ -80,6 +119,63 @@ pub struct FileDialogLabels {
     pub pin_folder: String,
     /// Text used for the option to unpin a folder.
     pub unpin_folder: String,
+    /// Text used for the option to open a file with the default application of the system.
+    pub open_externally: String,
+    /// Text used for the option to create a new folder in the context menu
+    /// of the directory content.
+    pub new_folder: String,
+    /// Text used for the option to open the path in the clipboard in the context menu
+    /// of the directory content.
+    pub paste_path: String,
+    /// Heading of the folders inside the directory content, if group headers are enabled.
+    pub heading_folders: String,
+    /// Heading of the files inside the directory content, if group headers are enabled.
+    pub heading_files: String,
+    /// Tooltip of the chevron used to open a directory, if directory chevrons are enabled.
+    pub open_directory: String,
+    /// Header of the name column, if the details view is used.
+    pub column_name: String,
+    /// Header of the size column, if the details view is used.
+    pub column_size: String,
+    /// Header of the column displaying the date the entries were last modified,
+    /// if the details view is used.
+    pub column_modified: String,
+    /// Format of the dates displayed in the column of the details view, in the local time
+    /// zone. See the `chrono` crate for the supported syntax.
+    pub column_modified_format: String,
+    /// Text used for the option to move a column of the details view to the left.
+    pub column_move_left: String,
+    /// Text used for the option to move a column of the details view to the right.
+    pub column_move_right: String,
+    /// Text displayed while a directory is loading, after the number of entries read so far.
+    pub loading_entries: String,
+    /// Text of the button to load a directory again after it was not responding.
+    pub retry: String,
+    /// Text of the button to return to the previous directory after the directory
+    /// that is loading was not responding.
+    pub go_back: String,
+    /// Text displayed at the end of the directory content if not all entries of the
+    /// directory were loaded. The number of loaded and all entries is appended.
+    pub entries_truncated: String,
+    /// Text of the button to load all entries of a directory that were not loaded
+    /// because of the maximum number of entries.
+    pub show_all_entries: String,
+    /// Text of the button to load all entries of a directory, displayed instead of
+    /// `show_all_entries` while searching.
+    pub search_all_entries: String,
+    /// Notice displayed when the open directory no longer exists and the nearest existing
+    /// parent directory was opened instead. The path of the parent directory is appended.
+    pub directory_removed: String,
+    /// Text displayed in front of the number of files hidden by the size and date filters.
+    pub files_hidden_by_filters: String,
+    /// Text displayed in front of the number of entries of the current directory
+    /// that could not be read.
+    pub entries_unreadable: String,
+
+    // ------------------------------------------------------------------------
+    // Preview panel:
+    /// Text displayed in the preview panel if the selected item cannot be previewed.
+    pub no_preview_available: String,
 
     // ------------------------------------------------------------------------
     // Bottom panel:
@@ -91,6 +187,9 @@ pub struct FileDialogLabels {
     pub selected_items: String,
     /// Text that appears in front of the file name input in the bottom panel.
     pub file_name: String,
+    /// Text that appears in front of the suggested non-colliding file name in the
+    /// bottom panel.
+    pub file_name_suggestion: String,
     /// Text displayed in the file filter dropdown for the "All Files" option.
     pub file_filter_all_files: String,
     /// Text displayed in the save extension dropdown for the "Any" option.
@@ -103,6 +202,18 @@ pub struct FileDialogLabels {
     /// Button text to cancel the dialog.
     pub cancel_button: String,
 
+    // ------------------------------------------------------------------------
+    // Inline picker:
+    /// Text displayed inside the inline picker if no file has been picked yet.
+    pub inline_picker_empty: String,
+    /// Button text of the inline picker to open the file dialog.
+    pub inline_picker_browse: String,
+
+    // ------------------------------------------------------------------------
+    // Go to dialog:
+    /// Title of the popup to enter the path of a folder to open.
+    pub go_to_folder: String,
+
     // ------------------------------------------------------------------------
     // Modal windows:
     /// Text displayed after the path within the modal to overwrite the selected file.
@@ -118,6 +229,32 @@ pub struct FileDialogLabels {
     pub err_directory_exists: String,
     /// Error if the file already exists.
     pub err_file_exists: String,
+    /// Error if the user tries to open a hidden directory while this is not allowed.
+    pub err_hidden_navigation: String,
+    /// Error if the extension of the file name to save is not allowed.
+    /// The allowed extensions are appended to the message, like "File must end in .txt or .md".
+    pub err_invalid_save_extension: String,
+    /// Word used to join the last two allowed extensions in `err_invalid_save_extension`.
+    pub err_invalid_save_extension_or: String,
+    /// Error if the directory in which the file should be saved does not exist.
+    pub err_parent_directory_missing: String,
+    /// Error if the missing parent directories of the file could not be created.
+    /// The error message of the operating system is appended to the message.
+    pub err_create_directories: String,
+    /// Error if the path entered in the go to dialog is not an existing folder.
+    pub err_folder_not_found: String,
+    /// Error if a removable device could not be ejected.
+    /// The error message of the operating system is appended to the message.
+    pub err_eject_device: String,
+    /// Error if a file could not be opened with the default application of the system.
+    /// The error message of the operating system is appended to the message.
+    pub err_open_externally: String,
+    /// Error displayed if the user cancelled loading a directory and there is
+    /// no previous directory to return to.
+    pub err_loading_cancelled: String,
+    /// Error displayed if a directory did not finish loading within the configured timeout,
+    /// for example because it is located on a network mount that is no longer reachable.
+    pub err_not_responding: String,
 }
 
 impl Default for FileDialogLabels {
@@ -131,16 +268,33 @@ impl Default for FileDialogLabels {
 
             cancel: "Cancel".to_string(),
             overwrite: "Overwrite".to_string(),
+            confirm: "Confirm".to_string(),
+
+            tooltip_parent_directory: "Go to parent directory".to_string(),
+            tooltip_no_parent_directory: "No parent directory".to_string(),
+            tooltip_back: "Back".to_string(),
+            tooltip_no_previous_directory: "No previous directory".to_string(),
+            tooltip_forward: "Forward".to_string(),
+            tooltip_no_next_directory: "No next directory".to_string(),
+            tooltip_new_folder: "Create new folder".to_string(),
+            tooltip_menu: "Menu".to_string(),
+            tooltip_edit_path: "Edit path".to_string(),
+            tooltip_sort_order: "Reverse sort order".to_string(),
+            tooltip_pin_directory: "Pin current folder".to_string(),
+            tooltip_unpin_directory: "Unpin current folder".to_string(),
 
             reload: "⟲  Reload".to_string(),
             working_directory: "↗  Go to working directory".to_string(),
             show_hidden: " Show hidden".to_string(),
             show_system_files: " Show system files".to_string(),
+            show_ignored: " Show ignored".to_string(),
 
             heading_pinned: "Pinned".to_string(),
             heading_places: "Places".to_string(),
             heading_devices: "Devices".to_string(),
             heading_removable_devices: "Removable Devices".to_string(),
+            eject_device: "⏏ Eject".to_string(),
+            location_unavailable: "Location unavailable".to_string(),
 
             home_dir: "🏠  Home".to_string(),
             desktop_dir: "🖵  Desktop".to_string(),
@@ -149,14 +303,40 @@ impl Default for FileDialogLabels {
             audio_dir: "🎵  Audio".to_string(),
             pictures_dir: "🖼  Pictures".to_string(),
             videos_dir: "🎞  Videos".to_string(),
+            public_dir: "🌐  Public".to_string(),
+            templates_dir: "🗋  Templates".to_string(),
 
             pin_folder: "📌 Pin folder".to_string(),
             unpin_folder: "✖ Unpin folder".to_string(),
+            open_externally: "⮫ Open externally".to_string(),
+            new_folder: "🗀 New folder".to_string(),
+            paste_path: "📋 Paste path".to_string(),
+            heading_folders: "Folders".to_string(),
+            heading_files: "Files".to_string(),
+            open_directory: "Open folder".to_string(),
+            column_name: "Name".to_string(),
+            column_size: "Size".to_string(),
+            column_modified: "Modified".to_string(),
+            column_modified_format: "%d.%m.%Y %H:%M".to_string(),
+            column_move_left: "⏴ Move left".to_string(),
+            column_move_right: "⏵ Move right".to_string(),
+            loading_entries: "items…".to_string(),
+            retry: "⟲ Retry".to_string(),
+            go_back: "⏴ Go back".to_string(),
+            entries_truncated: "Entries loaded:".to_string(),
+            show_all_entries: "Show all".to_string(),
+            search_all_entries: "Search all".to_string(),
+            directory_removed: "The folder no longer exists; moved up to".to_string(),
+            files_hidden_by_filters: "Files hidden by size or date filters:".to_string(),
+            entries_unreadable: "Items that could not be read:".to_string(),
+
+            no_preview_available: "No preview available".to_string(),
 
             selected_directory: "Selected directory:".to_string(),
             selected_file: "Selected file:".to_string(),
             selected_items: "Selected items:".to_string(),
             file_name: "File name:".to_string(),
+            file_name_suggestion: "Save as copy:".to_string(),
             file_filter_all_files: "All Files".to_string(),
             save_extension_any: "Any".to_string(),
 
@@ -164,12 +344,27 @@ impl Default for FileDialogLabels {
             save_button: "📥  Save".to_string(),
             cancel_button: "🚫 Cancel".to_string(),
 
+            inline_picker_empty: "No file selected".to_string(),
+            inline_picker_browse: "🗁 Browse…".to_string(),
+
+            go_to_folder: "Go to folder".to_string(),
+
             overwrite_file_modal_text: "already exists. Do you want to overwrite it?".to_string(),
 
             err_empty_folder_name: "Name of the folder cannot be empty".to_string(),
             err_empty_file_name: "The file name cannot be empty".to_string(),
             err_directory_exists: "A directory with the name already exists".to_string(),
             err_file_exists: "A file with the name already exists".to_string(),
+            err_hidden_navigation: "Opening hidden directories is not allowed".to_string(),
+            err_invalid_save_extension: "File must end in".to_string(),
+            err_invalid_save_extension_or: "or".to_string(),
+            err_parent_directory_missing: "The directory does not exist".to_string(),
+            err_create_directories: "Failed to create the directory:".to_string(),
+            err_folder_not_found: "The folder does not exist".to_string(),
+            err_eject_device: "Failed to eject the device:".to_string(),
+            err_open_externally: "Failed to open the file:".to_string(),
+            err_loading_cancelled: "Loading the folder was cancelled".to_string(),
+            err_not_responding: "This location is not responding".to_string(),
         }
     }
 }
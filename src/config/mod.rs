@@ -5,10 +5,12 @@ mod keybindings;
 pub use keybindings::{FileDialogKeyBindings, KeyBinding};
 
 use std::fmt::Display;
+use std::ops::RangeInclusive;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::time::{Duration, SystemTime};
 
-use crate::{FileSystem, NativeFileSystem};
+use crate::{DialogMode, FileSystem, NativeFileSystem, SizeUnits};
 
 /// Contains data of the `FileDialog` that should be stored persistently.
 #[derive(Debug, Clone)]
@@ -24,6 +26,17 @@ pub struct FileDialogStorage {
     pub last_visited_dir: Option<PathBuf>,
     /// The last directory from which the user picked an item.
     pub last_picked_dir: Option<PathBuf>,
+    /// If the entries inside the directory view are sorted in descending order.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub sort_descending: bool,
+    /// If entries matched by a `.gitignore` file should be listed inside the directory view.
+    /// Only used if `FileDialogConfig::respect_ignore_files` is enabled.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub show_ignored: bool,
+    /// The order, widths and visibility of the columns of `ViewMode::Details`,
+    /// ordered from left to right.
+    #[cfg_attr(feature = "serde", serde(default = "default_details_columns"))]
+    pub details_columns: Vec<DetailsColumnState>,
 }
 
 impl Default for FileDialogStorage {
@@ -35,10 +48,29 @@ impl Default for FileDialogStorage {
             show_system_files: false,
             last_visited_dir: None,
             last_picked_dir: None,
+            sort_descending: false,
+            show_ignored: false,
+            details_columns: default_details_columns(),
         }
     }
 }
 
+/// The columns of the details view displayed by default.
+fn default_details_columns() -> Vec<DetailsColumnState> {
+    vec![
+        DetailsColumnState {
+            column: DetailsColumn::LastModified,
+            width: 120.0,
+            visible: true,
+        },
+        DetailsColumnState {
+            column: DetailsColumn::Size,
+            width: 80.0,
+            visible: true,
+        },
+    ]
+}
+
 /// Sets which directory is loaded when opening the file dialog.
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub enum OpeningMode {
@@ -51,6 +83,113 @@ pub enum OpeningMode {
     LastPickedDir,
 }
 
+/// A user directory displayed in the Places section of the left panel.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum Place {
+    /// The home directory of the user.
+    Home,
+    /// The desktop directory.
+    Desktop,
+    /// The documents directory.
+    Documents,
+    /// The downloads directory.
+    Downloads,
+    /// The audio directory.
+    Audio,
+    /// The pictures directory.
+    Pictures,
+    /// The videos directory.
+    Videos,
+    /// The directory used to share files with other users.
+    Public,
+    /// The templates directory.
+    Templates,
+}
+
+/// Sets how the entries inside the directory content are displayed.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub enum ViewMode {
+    /// Only the names of the entries are displayed.
+    #[default]
+    List,
+    /// The size and the last modification date of the entries are displayed in columns
+    /// next to their names, below a header that can be clicked to sort the entries.
+    Details,
+}
+
+/// The property by which the entries inside the directory content are sorted.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub enum FileSorting {
+    /// Sort the entries by their name.
+    #[default]
+    Name,
+    /// Sort the entries by their size. Entries with the same size are sorted by their name.
+    Size,
+    /// Sort the entries by the date they were last modified.
+    /// Entries with the same date are sorted by their name.
+    LastModified,
+}
+
+/// A column of the details view that the user can resize, hide and move.
+/// The name column is always displayed and takes the remaining width.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum DetailsColumn {
+    /// The size of the files.
+    Size,
+    /// The date the entries were last modified.
+    LastModified,
+}
+
+impl DetailsColumn {
+    /// Gets the property by which the entries are sorted when the header of the column
+    /// is clicked.
+    pub const fn sorting(self) -> FileSorting {
+        match self {
+            Self::Size => FileSorting::Size,
+            Self::LastModified => FileSorting::LastModified,
+        }
+    }
+}
+
+/// The width and visibility of a column of the details view.
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct DetailsColumnState {
+    /// The column this state belongs to.
+    pub column: DetailsColumn,
+    /// The width of the column in points.
+    pub width: f32,
+    /// If the column is displayed.
+    pub visible: bool,
+}
+
+/// A collapsible section of the left panel.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum LeftPanelSection {
+    /// The user directories, like the home and documents directories.
+    Places,
+    /// The devices like system disks.
+    Devices,
+    /// The removable devices like USB drives.
+    RemovableDevices,
+}
+
+/// Sets how the dialog is rendered by `FileDialog::show`.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub enum RenderMode {
+    /// The dialog is rendered inside a floating `egui::Window`.
+    #[default]
+    Window,
+    /// The dialog is rendered inside its own viewport, which is a separate OS window if the
+    /// backend supports multiple viewports, like eframe on desktop platforms.
+    /// Falls back to a floating `egui::Window` otherwise.
+    Viewport,
+    /// The dialog is rendered directly into the given `egui::Ui`,
+    /// for example to embed it into a panel of the application.
+    InlineUi,
+}
+
 /// Contains configuration values of a file dialog.
 ///
 /// The configuration of a file dialog can be set using `FileDialog::with_config`.
@@ -97,11 +236,18 @@ pub struct FileDialogConfig {
     // General options:
     /// Sets which directory is loaded when opening the file dialog.
     pub opening_mode: OpeningMode,
+    /// If the dialog should display an overview of all devices when it is opened,
+    /// instead of loading a directory. Ignores the `opening_mode`.
+    pub start_at_devices: bool,
     /// If the file dialog should be visible as a modal window.
     /// This means that the input outside the window is not registered.
     pub as_modal: bool,
+    /// How the dialog is rendered when using `FileDialog::show`.
+    pub render_mode: RenderMode,
     /// Color of the overlay that is displayed under the modal to prevent user interaction.
     pub modal_overlay_color: egui::Color32,
+    /// If clicking the overlay displayed under the modal should cancel the dialog.
+    pub cancel_on_overlay_click: bool,
     /// The first directory that will be opened when the dialog opens.
     pub initial_directory: PathBuf,
     /// The default filename when opening the dialog in `DialogMode::SaveFile` mode.
@@ -109,6 +255,22 @@ pub struct FileDialogConfig {
     /// If the user is allowed to select an already existing file when the dialog is
     /// in `DialogMode::SaveFile` mode.
     pub allow_file_overwrite: bool,
+    /// If the user is allowed to create new folders, using the new folder button, the
+    /// context menu of the directory content or the `new_folder` keybinding.
+    pub allow_create_folder: bool,
+    /// If missing parent directories of the file should be created when the user saves
+    /// a file in `DialogMode::SaveFile` mode. The file itself is never created.
+    pub create_missing_directories: bool,
+    /// If saving a file with the name of an existing directory should open the directory
+    /// in `DialogMode::SaveFile` mode, instead of displaying an error.
+    pub open_directory_on_save: bool,
+    /// If a file name that does not collide with an existing file, like "report (1).pdf",
+    /// should be suggested when the user selects an existing file in
+    /// `DialogMode::SaveFile` mode.
+    pub suggest_copy_name: bool,
+    /// The extensions, without the leading dot, that the file name must end with
+    /// in `DialogMode::SaveFile` mode. Any extension is allowed if the list is empty.
+    pub allowed_save_extensions: Vec<String>,
     /// If the path edit is allowed to select the path as the file to save
     /// if it does not have an extension.
     ///
@@ -118,6 +280,11 @@ pub struct FileDialogConfig {
     ///
     /// This only affects the `DialogMode::SaveFile` mode.
     pub allow_path_edit_to_save_file_without_extension: bool,
+    /// If the user is allowed to pick a file that does not exist yet by entering its path
+    /// in the path edit. Only the parent directory of the file must exist.
+    ///
+    /// This only affects the `DialogMode::PickFile` mode.
+    pub allow_nonexistent_file: bool,
     /// Sets the separator of the directories when displaying a path.
     /// Currently only used when the current path is displayed in the top panel.
     pub directory_separator: String,
@@ -127,8 +294,90 @@ pub struct FileDialogConfig {
     /// This prevents the application from blocking when loading large directories
     /// or from slow hard drives.
     pub load_via_thread: bool,
+    /// The time after which a location is considered not responding, like a network mount
+    /// whose server is no longer reachable. Only used if `load_via_thread` is enabled.
+    pub file_system_timeout: Duration,
     /// If we should truncate the filenames in the middle
     pub truncate_filenames: bool,
+    /// If directories should be listed before files.
+    /// If disabled, directories and files are sorted together by name.
+    pub folders_first: bool,
+    /// The maximum number of entries loaded when opening a directory.
+    /// The user can load the remaining entries using the button at the end of the list.
+    /// If None, all entries are loaded.
+    pub max_entries: Option<usize>,
+    /// How the entries inside the directory content are displayed.
+    pub view_mode: ViewMode,
+    /// The property by which the entries inside the directory content are sorted.
+    /// Not used if a custom `sort_comparator` is set.
+    pub file_sorting: FileSorting,
+    /// If a ".." entry should be displayed at the top of the directory content that
+    /// opens the parent directory when double-clicked or submitted.
+    pub show_parent_entry: bool,
+    /// If the folders and files inside the directory content should be displayed below
+    /// separate headers that can be collapsed.
+    /// Requires `folders_first` to be enabled.
+    pub show_group_headers: bool,
+    /// If double-clicking a directory in `DialogMode::PickDirectory` should pick the
+    /// directory and finish the dialog instead of opening it.
+    pub pick_directory_on_double_click: bool,
+    /// If directory entries should display a chevron at the end of their row that opens
+    /// the directory with a single click, while clicking the name only selects it.
+    pub show_directory_chevrons: bool,
+    /// If the entries of the directory content can be dragged out of the dialog.
+    /// The path of the entry is used as the egui drag-and-drop payload.
+    pub draggable_entries: bool,
+    /// If the number of items inside a directory should be displayed next to its name.
+    pub show_directory_item_count: bool,
+    /// If the total size of the directories should be computed and displayed in the size
+    /// column of `ViewMode::Details`.
+    pub show_directory_sizes: bool,
+    /// The maximum depth of subdirectories visited when computing the size of a directory.
+    pub directory_size_max_depth: usize,
+    /// The maximum time spent computing the size of a single directory,
+    /// or of all directories if `load_via_thread` is disabled.
+    pub directory_size_timeout: Duration,
+    /// If entries matched by the `.gitignore` files of the current directory and its parents
+    /// should be hidden. Ignored entries can be displayed dimmed using
+    /// `FileDialogStorage::show_ignored`.
+    pub respect_ignore_files: bool,
+    /// If the Unix permissions of the entries should be displayed in a column on the right
+    /// side of the directory content. Entries the user cannot write to are marked with a
+    /// padlock.
+    #[cfg(unix)]
+    pub show_permissions: bool,
+    /// If the name of the user owning the entries should be displayed in a column on the
+    /// right side of the directory content.
+    #[cfg(unix)]
+    pub show_owner: bool,
+    /// If the name of the group owning the entries should be displayed in a column on the
+    /// right side of the directory content.
+    #[cfg(unix)]
+    pub show_group: bool,
+    /// The unit system used to display file sizes, for example in the information panel
+    /// and in the summary of the selected files.
+    pub size_units: SizeUnits,
+    /// If set, only files that were modified within the given duration are displayed.
+    /// Directories are always displayed so that the user can still navigate.
+    pub modified_within: Option<Duration>,
+    /// If set, only files with at least the given size in bytes are displayed.
+    /// The size and date filters are reset when the dialog is opened, unless they were
+    /// specified again using the methods of `FileDialog`.
+    pub min_file_size: Option<u64>,
+    /// If set, only files with at most the given size in bytes are displayed.
+    pub max_file_size: Option<u64>,
+    /// If set, only files that were modified after the given time are displayed.
+    pub modified_after: Option<SystemTime>,
+    /// If the user is allowed to open hidden directories.
+    /// If disabled, hidden directories and their subdirectories cannot be opened,
+    /// regardless of whether hidden files and folders are displayed.
+    pub allow_hidden_navigation: bool,
+    /// If the callback set using `FileDialog::on_directory_changed` should also be called
+    /// when the currently open directory is reloaded.
+    pub notify_directory_reload: bool,
+    /// If the history of opened directories, used by the back and forward buttons,
+    /// should be kept when the dialog is opened again.
+    pub preserve_history: bool,
 
     /// The icon that is used to display error messages.
     pub err_icon: String,
@@ -138,17 +387,28 @@ pub struct FileDialogConfig {
     pub default_file_icon: String,
     /// The default icon used to display folders.
     pub default_folder_icon: String,
+    /// The icon used to display executable files.
+    pub executable_file_icon: String,
     /// The icon used to display pinned paths in the left panel.
     pub pinned_icon: String,
     /// The icon used to display devices in the left panel.
     pub device_icon: String,
     /// The icon used to display removable devices in the left panel.
     pub removable_device_icon: String,
+    /// Background color of the selected entries in the central panel.
+    /// If None, the selection color of the current theme is used.
+    pub selection_fill: Option<egui::Color32>,
+    /// Text color of the selected entries in the central panel.
+    /// If None, the selection color of the current theme is used.
+    pub selection_text_color: Option<egui::Color32>,
 
     /// File filters presented to the user in a dropdown.
     pub file_filters: Vec<FileFilter>,
     /// Name of the file filter to be selected by default.
     pub default_file_filter: Option<String>,
+    /// If file filters that match none of the files inside the current directory should be
+    /// hidden from the dropdown. The option to display all files is always visible.
+    pub hide_empty_filters: bool,
     /// File extensions presented to the user in a dropdown when saving a file.
     pub save_extensions: Vec<SaveExtension>,
     /// Name of the file extension selected by default.
@@ -156,6 +416,30 @@ pub struct FileDialogConfig {
     /// Sets custom icons for different files or folders.
     /// Use `FileDialogConfig::set_file_icon` to add a new icon to this list.
     pub file_icon_filters: Vec<IconFilter>,
+    /// Glob pattern, like `*.tar.{gz,zst}`, the files must match to be displayed.
+    /// Use `FileDialog::set_glob_filter` to change the pattern while the dialog is open.
+    pub glob_filter: Option<String>,
+    /// Regular expression the file names must match to be displayed.
+    /// Use `FileDialog::set_regex_filter` to change the expression while the dialog is open.
+    #[cfg(feature = "regex")]
+    pub regex_filter: Option<regex::Regex>,
+    /// Custom filter the files must pass to be displayed.
+    /// The filter is evaluated once per file when a directory is loaded.
+    /// Use `FileDialog::set_filter` to change the filter while the dialog is open.
+    pub path_filter: Option<PathFilter>,
+    /// Predicate that decides which entries are loaded at all, including directories.
+    /// Rejected entries are skipped before their metadata is read.
+    /// Use `FileDialog::set_entry_predicate` to change the predicate while the dialog is open.
+    pub entry_predicate: Option<PathFilter>,
+    /// Custom comparator used to sort the directory content instead of sorting by name.
+    /// Use `FileDialog::set_sort_comparator` to change the comparator while the dialog is open.
+    pub sort_comparator: Option<SortComparator>,
+    /// Custom validation of the items selected by the user.
+    /// The submit button is disabled if the validation fails.
+    pub selection_validator: Option<SelectionValidator>,
+    /// Custom validation of the picked items, executed once when the user confirms the
+    /// selection. The dialog stays open if the validation fails.
+    pub confirm_validator: Option<SelectionValidator>,
 
     /// Custom sections added to the left sidebar for quick access.
     /// Use `FileDialogConfig::add_quick_access` to add a new section to this list.
@@ -181,11 +465,17 @@ pub struct FileDialogConfig {
     /// The anchor of the window.
     pub anchor: Option<(egui::Align2, egui::Vec2)>,
     /// If the window is resizable.
+    /// A window that is not resizable always has the default size.
     pub resizable: bool,
+    /// If the window should take the size of its content instead of using the default size.
+    /// An auto sized window cannot be resized by the user.
+    pub auto_sized: bool,
     /// If the window is movable.
     pub movable: bool,
     /// If the title bar of the window is shown.
     pub title_bar: bool,
+    /// If the window can be collapsed by clicking the button in its title bar.
+    pub collapsible: bool,
 
     // ------------------------------------------------------------------------
     // Feature options:
@@ -199,6 +489,7 @@ pub struct FileDialogConfig {
     /// Whether the forward button should be visible at the top.
     pub show_forward_button: bool,
     /// If the button to create a new folder should be visible at the top.
+    /// Use `allow_create_folder` to prevent the user from creating folders entirely.
     pub show_new_folder_button: bool,
     /// If the current path display in the top panel should be visible.
     pub show_current_path: bool,
@@ -208,6 +499,11 @@ pub struct FileDialogConfig {
     pub show_menu_button: bool,
     /// If the reload button inside the top panel menu should be visible.
     pub show_reload_button: bool,
+    /// If the button to reverse the sort order in the top panel should be visible.
+    pub show_sort_order_button: bool,
+    /// If the button to pin or unpin the current directory in the top panel should be visible.
+    /// Has no effect if `show_pinned_folders` is disabled.
+    pub show_pin_button: bool,
     /// If the working directory shortcut in the hamburger menu should be visible.
     pub show_working_directory_button: bool,
     /// If the show hidden files and folders option inside the top panel menu should be visible.
@@ -219,19 +515,44 @@ pub struct FileDialogConfig {
 
     /// Set the width of the right panel, if used
     pub right_panel_width: Option<f32>,
+    /// Set the range in which the user can resize the right panel, if used.
+    /// If None, the width is not restricted.
+    pub right_panel_width_range: Option<RangeInclusive<f32>>,
+
+    /// If the panel showing a preview of the selected file should be visible.
+    /// The preview is only shown when the dialog is in `DialogMode::PickFile` mode.
+    pub show_preview: bool,
 
     /// If the sidebar with the shortcut directories such as
     /// “Home”, “Documents” etc. should be visible.
     pub show_left_panel: bool,
+    /// The initial width of the left sidebar.
+    pub left_panel_width: f32,
+    /// The range in which the user can resize the left sidebar.
+    pub left_panel_width_range: RangeInclusive<f32>,
     /// If pinned folders should be listed in the left sidebar.
     /// Disabling this will also disable the functionality to pin a folder.
     pub show_pinned_folders: bool,
+    /// If entries in the left sidebar whose path no longer exists, like a pinned folder that
+    /// was deleted, should be displayed dimmed with a warning instead of opening on click.
+    pub mark_unavailable_locations: bool,
     /// If the Places section in the left sidebar should be visible.
     pub show_places: bool,
+    /// The entries of the Places section that are hidden.
+    /// The section is hidden entirely if all of its entries are hidden.
+    pub hidden_places: Vec<Place>,
     /// If the Devices section in the left sidebar should be visible.
     pub show_devices: bool,
     /// If the Removable Devices section in the left sidebar should be visible.
     pub show_removable_devices: bool,
+    /// If removable devices can be ejected using the context menu of their entry
+    /// in the left sidebar.
+    #[cfg(feature = "eject")]
+    pub show_eject_button: bool,
+    /// If files can be opened with the default application of the system using the
+    /// context menu of their entry in the directory content.
+    #[cfg(feature = "open_externally")]
+    pub show_open_externally: bool,
 }
 
 impl Default for FileDialogConfig {
@@ -242,6 +563,7 @@ impl Default for FileDialogConfig {
 
 impl FileDialogConfig {
     /// Creates a new configuration with default values
+    #[allow(clippy::too_many_lines)] // Long due to the number of configuration options
     pub fn default_from_filesystem(file_system: Arc<dyn FileSystem + Send + Sync>) -> Self {
         Self {
             storage: FileDialogStorage::default(),
@@ -249,12 +571,21 @@ impl FileDialogConfig {
             keybindings: FileDialogKeyBindings::default(),
 
             opening_mode: OpeningMode::LastPickedDir,
+            start_at_devices: false,
             as_modal: true,
+            render_mode: RenderMode::Window,
             modal_overlay_color: egui::Color32::from_rgba_premultiplied(0, 0, 0, 120),
+            cancel_on_overlay_click: false,
             initial_directory: file_system.current_dir().unwrap_or_default(),
             default_file_name: String::from("Untitled"),
             allow_file_overwrite: true,
+            allow_create_folder: true,
+            create_missing_directories: false,
+            open_directory_on_save: false,
+            suggest_copy_name: false,
+            allowed_save_extensions: Vec::new(),
             allow_path_edit_to_save_file_without_extension: false,
+            allow_nonexistent_file: false,
             directory_separator: String::from(">"),
             canonicalize_paths: true,
 
@@ -262,22 +593,63 @@ impl FileDialogConfig {
             load_via_thread: false,
             #[cfg(not(target_arch = "wasm32"))]
             load_via_thread: true,
+            file_system_timeout: Duration::from_secs(10),
 
             truncate_filenames: true,
+            folders_first: true,
+            max_entries: None,
+            view_mode: ViewMode::List,
+            file_sorting: FileSorting::Name,
+            show_parent_entry: false,
+            show_group_headers: false,
+            pick_directory_on_double_click: false,
+            show_directory_chevrons: false,
+            draggable_entries: false,
+            show_directory_item_count: false,
+            show_directory_sizes: false,
+            directory_size_max_depth: 16,
+            directory_size_timeout: Duration::from_secs(5),
+            respect_ignore_files: false,
+            #[cfg(unix)]
+            show_permissions: false,
+            #[cfg(unix)]
+            show_owner: false,
+            #[cfg(unix)]
+            show_group: false,
+            size_units: SizeUnits::default(),
+            modified_within: None,
+            min_file_size: None,
+            max_file_size: None,
+            modified_after: None,
+            allow_hidden_navigation: true,
+            notify_directory_reload: false,
+            preserve_history: false,
 
             err_icon: String::from("⚠"),
             warn_icon: String::from("⚠"),
             default_file_icon: String::from("🗋"),
             default_folder_icon: String::from("🗀"),
+            executable_file_icon: String::from("⚙"),
             pinned_icon: String::from("📌"),
             device_icon: String::from("🖴"),
             removable_device_icon: String::from("💾"),
+            selection_fill: None,
+            selection_text_color: None,
 
             file_filters: Vec::new(),
             default_file_filter: None,
+            hide_empty_filters: false,
             save_extensions: Vec::new(),
             default_save_extension: None,
             file_icon_filters: Vec::new(),
+            glob_filter: None,
+            #[cfg(feature = "regex")]
+            regex_filter: None,
+            path_filter: None,
+            entry_predicate: None,
+            sort_comparator: None,
+            selection_validator: None,
+            confirm_validator: None,
 
             quick_accesses: Vec::new(),
 
@@ -291,7 +663,9 @@ impl FileDialogConfig {
             anchor: None,
             resizable: true,
             movable: true,
+            auto_sized: false,
             title_bar: true,
+            collapsible: false,
 
             show_top_panel: true,
             show_parent_button: true,
@@ -302,17 +676,29 @@ impl FileDialogConfig {
             show_path_edit_button: true,
             show_menu_button: true,
             show_reload_button: true,
+            show_sort_order_button: false,
+            show_pin_button: false,
             show_working_directory_button: true,
             show_hidden_option: true,
             show_system_files_option: true,
             show_search: true,
 
             right_panel_width: None,
+            right_panel_width_range: None,
+            show_preview: false,
             show_left_panel: true,
+            left_panel_width: 150.0,
+            left_panel_width_range: 90.0..=250.0,
             show_pinned_folders: true,
+            mark_unavailable_locations: true,
             show_places: true,
+            hidden_places: Vec::new(),
             show_devices: true,
             show_removable_devices: true,
+            #[cfg(feature = "eject")]
+            show_eject_button: true,
+            #[cfg(feature = "open_externally")]
+            show_open_externally: true,
 
             file_system,
         }
@@ -525,6 +911,55 @@ impl std::fmt::Debug for IconFilter {
     }
 }
 
+/// Custom filter that receives the full path of a directory entry.
+///
+/// Used for both the `path_filter`, which only applies to files, and the
+/// `entry_predicate`, which applies to all entries including directories.
+#[derive(Clone)]
+pub struct PathFilter {
+    /// The function that returns true if the given entry should be kept.
+    pub filter: Filter<Path>,
+}
+
+impl std::fmt::Debug for PathFilter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PathFilter").finish_non_exhaustive()
+    }
+}
+
+/// Function that compares two paths to sort the directory content.
+pub type Comparator = Arc<dyn Fn(&Path, &Path) -> std::cmp::Ordering + Send + Sync>;
+
+/// Sets a custom order of the directory content.
+#[derive(Clone)]
+pub struct SortComparator {
+    /// The function that compares two paths.
+    pub comparator: Comparator,
+}
+
+impl std::fmt::Debug for SortComparator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SortComparator").finish_non_exhaustive()
+    }
+}
+
+/// Function that checks if the given path can be selected in the given mode.
+/// Returns an error message that is displayed to the user if the path is invalid.
+pub type PathValidator = Arc<dyn Fn(&Path, DialogMode) -> Result<(), String> + Send + Sync>;
+
+/// Sets a custom validation of the items selected by the user.
+#[derive(Clone)]
+pub struct SelectionValidator {
+    /// The function that validates a selected path.
+    pub validator: PathValidator,
+}
+
+impl std::fmt::Debug for SelectionValidator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SelectionValidator").finish_non_exhaustive()
+    }
+}
+
 /// Stores the display name and the actual path of a quick access link.
 #[derive(Debug, Clone)]
 pub struct QuickAccessPath {
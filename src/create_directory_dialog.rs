@@ -1,6 +1,7 @@
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
+use crate::ui::ui_error_label;
 use crate::{FileDialogConfig, FileDialogLabels, FileSystem};
 
 pub struct CreateDirectoryResponse {
@@ -131,12 +132,27 @@ impl CreateDirectoryDialog {
 
             let apply_button_response =
                 ui.add_enabled(self.error.is_none(), egui::Button::new("✔"));
+            apply_button_response.widget_info(|| {
+                egui::WidgetInfo::labeled(
+                    egui::WidgetType::Button,
+                    apply_button_response.enabled(),
+                    &config.labels.confirm,
+                )
+            });
+            let apply_button_response = apply_button_response.on_hover_text(&config.labels.confirm);
 
             if apply_button_response.clicked() {
                 result = self.submit();
             }
 
-            if ui.button("✖").clicked()
+            let cancel_button_response = ui.button("✖");
+            cancel_button_response.widget_info(|| {
+                egui::WidgetInfo::labeled(egui::WidgetType::Button, true, &config.labels.cancel)
+            });
+
+            if cancel_button_response
+                .on_hover_text(&config.labels.cancel)
+                .clicked()
                 || (text_edit_response.lost_focus() && !apply_button_response.contains_pointer())
             {
                 self.close();
@@ -146,18 +162,7 @@ impl CreateDirectoryDialog {
         if let Some(err) = &self.error {
             ui.add_space(5.0);
 
-            let response = ui
-                .horizontal_wrapped(|ui| {
-                    ui.spacing_mut().item_spacing.x = 0.0;
-
-                    ui.colored_label(
-                        ui.style().visuals.error_fg_color,
-                        format!("{} ", config.err_icon),
-                    );
-
-                    ui.label(err);
-                })
-                .response;
+            let response = ui_error_label(ui, &config.err_icon, err);
 
             if self.scroll_to_error {
                 response.scroll_to_me(Some(egui::Align::Center));
@@ -1,9 +1,14 @@
-use crate::config::{FileDialogConfig, FileFilter};
+use crate::config::{FileDialogConfig, FileFilter, FileSorting};
+use crate::file_system::paths_equal;
+use crate::glob::Glob;
+use crate::ignore_rules::IgnoreRules;
 use crate::FileSystem;
 use egui::mutex::Mutex;
+use std::ops::ControlFlow;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::{mpsc, Arc};
-use std::time::SystemTime;
+use std::time::{Duration, SystemTime};
 use std::{io, thread};
 
 /// Contains the metadata of a directory item.
@@ -14,6 +19,39 @@ pub struct Metadata {
     pub(crate) last_modified: Option<SystemTime>,
     pub(crate) created: Option<SystemTime>,
     pub(crate) file_type: Option<String>,
+    /// The Unix mode bits, containing the file type and permissions.
+    #[cfg(unix)]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) permissions: Option<u32>,
+    /// If the current user is not allowed to write to the item.
+    #[cfg(unix)]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) read_only: bool,
+    /// The ID of the user owning the item.
+    /// Its name is only looked up when the directory is loaded while the owner column
+    /// is displayed.
+    #[cfg(unix)]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) owner_id: Option<u32>,
+    /// The ID of the group owning the item.
+    /// Its name is only looked up when the directory is loaded while the group column
+    /// is displayed.
+    #[cfg(unix)]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) group_id: Option<u32>,
+    /// The name of the user owning the item, if it was provided by the file system
+    /// or looked up when loading the directory.
+    #[cfg(unix)]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) owner: Option<String>,
+    /// The name of the group owning the item, if it was provided by the file system
+    /// or looked up when loading the directory.
+    #[cfg(unix)]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) group: Option<String>,
+    /// If the item is an executable file.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) executable: bool,
 }
 
 impl Metadata {
@@ -29,14 +67,64 @@ impl Metadata {
             last_modified,
             created,
             file_type,
+            #[cfg(unix)]
+            permissions: None,
+            #[cfg(unix)]
+            read_only: false,
+            #[cfg(unix)]
+            owner_id: None,
+            #[cfg(unix)]
+            group_id: None,
+            #[cfg(unix)]
+            owner: None,
+            #[cfg(unix)]
+            group: None,
+            executable: false,
         }
     }
+
+    /// Sets if the item is an executable file.
+    pub const fn with_executable(mut self, executable: bool) -> Self {
+        self.executable = executable;
+        self
+    }
+
+    /// Sets the Unix mode bits of the item and if the current user is not allowed
+    /// to write to it.
+    #[cfg(unix)]
+    pub const fn with_permissions(mut self, mode: u32, read_only: bool) -> Self {
+        self.permissions = Some(mode);
+        self.read_only = read_only;
+        self
+    }
+
+    /// Sets the IDs of the user and group owning the item.
+    /// Their names are looked up when loading the directory while the owner or group
+    /// column is displayed, unless the names are set using `Metadata::with_owner`.
+    #[cfg(unix)]
+    pub const fn with_owner_ids(mut self, uid: u32, gid: u32) -> Self {
+        self.owner_id = Some(uid);
+        self.group_id = Some(gid);
+        self
+    }
+
+    /// Sets the names of the user and group owning the item.
+    #[cfg(unix)]
+    pub fn with_owner(mut self, owner: String, group: String) -> Self {
+        self.owner = Some(owner);
+        self.group = Some(group);
+        self
+    }
 }
 
 /// Contains the information of a directory item.
 ///
 /// This struct is mainly there so that the information and metadata can be loaded once and not that
 /// a request has to be sent to the OS every frame using, for example, `path.is_file()`.
+///
+/// The entries are created while loading the directory, which happens on a separate thread
+/// if `FileDialog::load_via_thread` is enabled. Sorting and filtering only use the
+/// cached values, so no metadata is queried on the UI thread.
 #[derive(Debug, Default, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct DirectoryEntry {
@@ -45,7 +133,20 @@ pub struct DirectoryEntry {
     is_directory: bool,
     is_system_file: bool,
     is_hidden: bool,
+    /// If the entry is matched by the rules of a `.gitignore` file.
+    #[cfg_attr(feature = "serde", serde(default))]
+    is_ignored: bool,
     icon: String,
+    error: Option<String>,
+    /// The total size of all files inside the directory and its subdirectories,
+    /// if it was computed.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    directory_size: Option<u64>,
+    /// If the file name does not match the regex filter of the directory content.
+    /// Cached so that the expression is not evaluated every frame.
+    #[cfg(feature = "regex")]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    hidden_by_regex: bool,
     /// If the item is marked as selected as part of a multi selection.
     pub selected: bool,
 }
@@ -53,13 +154,27 @@ pub struct DirectoryEntry {
 impl DirectoryEntry {
     /// Creates a new directory entry from a path
     pub fn from_path(config: &FileDialogConfig, path: &Path, file_system: &dyn FileSystem) -> Self {
+        let is_directory = file_system.is_dir(path);
+
+        let (metadata, error) = match file_system.metadata(path) {
+            Ok(metadata) => (metadata, None),
+            Err(err) => (Metadata::default(), Some(err.to_string())),
+        };
+
+        let icon = gen_path_icon(config, path, is_directory, metadata.executable);
+
         Self {
             path: path.to_path_buf(),
-            metadata: file_system.metadata(path).unwrap_or_default(),
-            is_directory: file_system.is_dir(path),
-            is_system_file: !file_system.is_dir(path) && !file_system.is_file(path),
-            icon: gen_path_icon(config, path, file_system),
+            metadata,
+            is_directory,
+            is_system_file: !is_directory && !file_system.is_file(path),
+            icon,
             is_hidden: file_system.is_path_hidden(path),
+            is_ignored: false,
+            error,
+            directory_size: None,
+            #[cfg(feature = "regex")]
+            hidden_by_regex: false,
             selected: false,
         }
     }
@@ -69,9 +184,28 @@ impl DirectoryEntry {
         &self.metadata
     }
 
+    /// Looks up the names of the user and group owning the entry for the columns that are
+    /// displayed, unless the names were already provided by the file system.
+    #[cfg(unix)]
+    pub(crate) fn resolve_owner_names(&mut self, config: &FileDialogConfig) {
+        let metadata = &mut self.metadata;
+
+        if config.show_owner && metadata.owner.is_none() {
+            metadata.owner = metadata.owner_id.map(crate::permissions::user_name);
+        }
+
+        if config.show_group && metadata.group.is_none() {
+            metadata.group = metadata.group_id.map(crate::permissions::group_name);
+        }
+    }
+
+    /// The owner and group columns are only available on Unix platforms.
+    #[cfg(not(unix))]
+    pub(crate) const fn resolve_owner_names(&mut self, _config: &FileDialogConfig) {}
+
     /// Checks if the path of the current directory entry matches the other directory entry.
     pub fn path_eq(&self, other: &Self) -> bool {
-        other.as_path() == self.as_path()
+        paths_equal(other.as_path(), self.as_path())
     }
 
     /// Returns true if the item is a directory.
@@ -88,6 +222,12 @@ impl DirectoryEntry {
         !self.is_directory
     }
 
+    /// Returns the size the entry is sorted by when sorting by size. This is the total size
+    /// of a directory if it was computed, otherwise the size of the metadata.
+    fn sort_size(&self) -> Option<u64> {
+        self.directory_size.or(self.metadata.size)
+    }
+
     /// Returns true if the item is a system file.
     pub const fn is_system_file(&self) -> bool {
         self.is_system_file
@@ -108,6 +248,14 @@ impl DirectoryEntry {
         self.path.clone()
     }
 
+    /// Updates if the entry is hidden by the given regex filter.
+    /// Directories are never hidden.
+    #[cfg(feature = "regex")]
+    fn update_regex_match(&mut self, regex_filter: Option<&regex::Regex>) {
+        self.hidden_by_regex =
+            self.is_file() && regex_filter.is_some_and(|regex| !regex.is_match(self.file_name()));
+    }
+
     /// Returns the file name of the directory item.
     pub fn file_name(&self) -> &str {
         self.path
@@ -147,6 +295,26 @@ impl DirectoryEntry {
     pub const fn is_hidden(&self) -> bool {
         self.is_hidden
     }
+
+    /// Returns whether the item is an executable file.
+    /// On Unix, these are files with an execute permission and on Windows files with
+    /// extensions like `.exe` or `.bat`.
+    pub const fn is_executable(&self) -> bool {
+        self.metadata.executable
+    }
+
+    /// Returns whether the path is matched by the rules of a `.gitignore` file.
+    /// Always false if `FileDialog::respect_ignore_files` is disabled.
+    pub const fn is_ignored(&self) -> bool {
+        self.is_ignored
+    }
+
+    /// Returns the error that occurred when querying the metadata of the item,
+    /// for example if the user is missing the permissions or the item is a broken link.
+    /// Returns None if the item is accessible.
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
 }
 
 /// Contains the state of the directory content.
@@ -166,24 +334,84 @@ pub enum DirectoryContentState {
 }
 
 type DirectoryContentReceiver =
-    Option<Arc<Mutex<mpsc::Receiver<Result<Vec<DirectoryEntry>, std::io::Error>>>>>;
+    Option<Arc<Mutex<mpsc::Receiver<Result<LoadedDirectory, std::io::Error>>>>>;
+
+/// The progress of a directory that is loading, shared with the thread loading it.
+#[derive(Default)]
+struct LoadProgress {
+    /// The number of entries read so far.
+    entries: AtomicUsize,
+    /// If the loading was cancelled and the thread should stop.
+    cancelled: AtomicBool,
+    /// The time the last entry was read, or None if no entry was read yet.
+    last_update: Mutex<Option<SystemTime>>,
+}
+
+/// The entries of a directory, as returned by `load_directory`.
+struct LoadedDirectory {
+    /// The path of the directory, canonicalized if `canonicalize_paths` is enabled.
+    path: PathBuf,
+    /// The loaded entries.
+    entries: Vec<DirectoryEntry>,
+    /// Number of files hidden by the file size and modification date filters.
+    filtered_count: usize,
+    /// Number of entries that could not be read while listing the directory.
+    unreadable_count: usize,
+    /// The number of all entries inside the directory, if loading stopped after
+    /// the maximum number of entries.
+    truncated_total: Option<usize>,
+    /// The IDs of the file filters matching at least one of the files inside the directory,
+    /// if `hide_empty_filters` is enabled.
+    matched_filters: Option<Vec<egui::Id>>,
+}
 
 /// Contains the content of a directory.
 pub struct DirectoryContent {
     /// Current state of the directory content.
     state: DirectoryContentState,
+    /// The path of the loaded directory, canonicalized if `canonicalize_paths` is enabled.
+    /// None if the content did not finish loading.
+    path: Option<PathBuf>,
+    /// If loading failed because the directory does not exist.
+    not_found: bool,
     /// The loaded directory contents.
     content: Vec<DirectoryEntry>,
+    /// Number of files hidden by the file size and modification date filters.
+    filtered_count: usize,
+    /// Number of entries that could not be read while listing the directory.
+    unreadable_count: usize,
+    /// The number of all entries inside the directory, if only part of them was loaded.
+    truncated_total: Option<usize>,
+    /// The IDs of the file filters matching at least one of the files inside the directory.
+    /// None if the matching filters were not determined.
+    matched_filters: Option<Vec<egui::Id>>,
     /// Receiver when the content is loaded on a different thread.
     content_recv: DirectoryContentReceiver,
+    /// The progress while the content is loaded on a different thread.
+    progress: Arc<LoadProgress>,
+    /// Glob pattern the files must match to be visible.
+    glob_filter: Option<Glob>,
+    /// Regular expression the file names must match to be visible.
+    #[cfg(feature = "regex")]
+    regex_filter: Option<regex::Regex>,
 }
 
 impl Default for DirectoryContent {
     fn default() -> Self {
         Self {
             state: DirectoryContentState::Success,
+            path: None,
+            not_found: false,
             content: Vec::new(),
+            filtered_count: 0,
+            unreadable_count: 0,
+            truncated_total: None,
+            matched_filters: None,
             content_recv: None,
+            progress: Arc::default(),
+            glob_filter: None,
+            #[cfg(feature = "regex")]
+            regex_filter: None,
         }
     }
 }
@@ -193,6 +421,7 @@ impl std::fmt::Debug for DirectoryContent {
         f.debug_struct("DirectoryContent")
             .field("state", &self.state)
             .field("content", &self.content)
+            .field("glob_filter", &self.glob_filter)
             .field(
                 "content_recv",
                 if self.content_recv.is_some() {
@@ -206,6 +435,25 @@ impl std::fmt::Debug for DirectoryContent {
 }
 
 impl DirectoryContent {
+    /// Creates a new `DirectoryContent` object that failed to load with the given error message.
+    pub fn from_error(message: String) -> Self {
+        Self {
+            state: DirectoryContentState::Errored(message),
+            path: None,
+            not_found: false,
+            content: Vec::new(),
+            filtered_count: 0,
+            unreadable_count: 0,
+            truncated_total: None,
+            matched_filters: None,
+            content_recv: None,
+            progress: Arc::default(),
+            glob_filter: None,
+            #[cfg(feature = "regex")]
+            regex_filter: None,
+        }
+    }
+
     /// Create a new `DirectoryContent` object and loads the contents of the given path.
     /// Use `include_files` to include or exclude files in the content list.
     pub fn from_path(
@@ -214,15 +462,17 @@ impl DirectoryContent {
         include_files: bool,
         file_filter: Option<&FileFilter>,
         filter_extension: Option<&str>,
+        max_entries: Option<usize>,
         file_system: Arc<dyn FileSystem + Sync + Send + 'static>,
     ) -> Self {
-        if config.load_via_thread {
+        let mut content = if config.load_via_thread {
             Self::with_thread(
                 config,
                 path,
                 include_files,
                 file_filter,
                 filter_extension,
+                max_entries,
                 file_system,
             )
         } else {
@@ -232,9 +482,16 @@ impl DirectoryContent {
                 include_files,
                 file_filter,
                 filter_extension,
+                max_entries,
                 &*file_system,
             )
-        }
+        };
+
+        content.glob_filter = config.glob_filter.as_deref().map(Glob::new);
+        #[cfg(feature = "regex")]
+        content.set_regex_filter(config.regex_filter.clone());
+
+        content
     }
 
     fn with_thread(
@@ -243,11 +500,14 @@ impl DirectoryContent {
         include_files: bool,
         file_filter: Option<&FileFilter>,
         filter_extension: Option<&str>,
+        max_entries: Option<usize>,
         file_system: Arc<dyn FileSystem + Send + Sync + 'static>,
     ) -> Self {
         let (tx, rx) = mpsc::channel();
+        let progress = Arc::new(LoadProgress::default());
 
         let c = config.clone();
+        let pr = progress.clone();
         let p = path.to_path_buf();
         let f = file_filter.cloned();
         let fe = filter_extension.map(str::to_string);
@@ -258,14 +518,26 @@ impl DirectoryContent {
                 include_files,
                 f.as_ref(),
                 fe.as_deref(),
+                max_entries,
                 &*file_system,
+                &pr,
             ));
         });
 
         Self {
             state: DirectoryContentState::Pending(SystemTime::now()),
+            path: None,
+            not_found: false,
             content: Vec::new(),
+            filtered_count: 0,
+            unreadable_count: 0,
+            truncated_total: None,
+            matched_filters: None,
             content_recv: Some(Arc::new(Mutex::new(rx))),
+            progress,
+            glob_filter: None,
+            #[cfg(feature = "regex")]
+            regex_filter: None,
         }
     }
 
@@ -275,6 +547,7 @@ impl DirectoryContent {
         include_files: bool,
         file_filter: Option<&FileFilter>,
         filter_extension: Option<&str>,
+        max_entries: Option<usize>,
         file_system: &dyn FileSystem,
     ) -> Self {
         match load_directory(
@@ -283,17 +556,39 @@ impl DirectoryContent {
             include_files,
             file_filter,
             filter_extension,
+            max_entries,
             file_system,
+            &LoadProgress::default(),
         ) {
-            Ok(c) => Self {
+            Ok(loaded) => Self {
                 state: DirectoryContentState::Success,
-                content: c,
+                path: Some(loaded.path),
+                not_found: false,
+                content: loaded.entries,
+                filtered_count: loaded.filtered_count,
+                unreadable_count: loaded.unreadable_count,
+                truncated_total: loaded.truncated_total,
+                matched_filters: loaded.matched_filters,
                 content_recv: None,
+                progress: Arc::default(),
+                glob_filter: None,
+                #[cfg(feature = "regex")]
+                regex_filter: None,
             },
             Err(err) => Self {
                 state: DirectoryContentState::Errored(err.to_string()),
+                path: None,
+                not_found: err.kind() == io::ErrorKind::NotFound,
                 content: Vec::new(),
+                filtered_count: 0,
+                unreadable_count: 0,
+                truncated_total: None,
+                matched_filters: None,
                 content_recv: None,
+                progress: Arc::default(),
+                glob_filter: None,
+                #[cfg(feature = "regex")]
+                regex_filter: None,
             },
         }
     }
@@ -318,13 +613,21 @@ impl DirectoryContent {
             let value = recv.lock().try_recv();
             match value {
                 Ok(result) => match result {
-                    Ok(content) => {
+                    Ok(loaded) => {
                         self.state = DirectoryContentState::Finished;
-                        self.content = content;
+                        self.path = Some(loaded.path);
+                        self.content = loaded.entries;
+                        self.filtered_count = loaded.filtered_count;
+                        self.unreadable_count = loaded.unreadable_count;
+                        self.truncated_total = loaded.truncated_total;
+                        self.matched_filters = loaded.matched_filters;
+                        #[cfg(feature = "regex")]
+                        self.update_regex_matches();
                         update_content_recv = false;
                     }
                     Err(err) => {
                         self.state = DirectoryContentState::Errored(err.to_string());
+                        self.not_found = err.kind() == io::ErrorKind::NotFound;
                         update_content_recv = false;
                     }
                 },
@@ -345,13 +648,93 @@ impl DirectoryContent {
         &self.state
     }
 
-    /// Returns an iterator in the given range of the directory cotnents.
-    /// No filters are applied using this iterator.
-    pub fn iter_range_mut(
-        &mut self,
+    /// Returns an iterator in the given range of the directory contents
+    /// with the search value applied.
+    pub fn filtered_iter_range_mut<'s>(
+        &'s mut self,
         range: std::ops::Range<usize>,
-    ) -> impl Iterator<Item = &mut DirectoryEntry> {
-        self.content[range].iter_mut()
+        search_value: &'s str,
+    ) -> impl Iterator<Item = &'s mut DirectoryEntry> + 's {
+        let glob_filter = self.glob_filter.as_ref();
+
+        self.content[range]
+            .iter_mut()
+            .filter(move |p| apply_filters(p, search_value, glob_filter))
+    }
+
+    /// Checks if the content is currently loading on a different thread.
+    pub const fn is_pending(&self) -> bool {
+        matches!(self.state, DirectoryContentState::Pending(_))
+    }
+
+    /// Checks if the content finished loading without an error.
+    pub const fn is_loaded(&self) -> bool {
+        matches!(
+            self.state,
+            DirectoryContentState::Finished | DirectoryContentState::Success
+        )
+    }
+
+    /// Returns the path of the loaded directory, canonicalized if `canonicalize_paths`
+    /// is enabled. Returns None if the content did not finish loading.
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    /// Checks if loading failed because the directory does not exist,
+    /// for example because it was removed.
+    pub const fn is_not_found(&self) -> bool {
+        self.not_found
+    }
+
+    /// Returns the number of entries read so far while the content is loading.
+    pub fn loaded_entries(&self) -> usize {
+        self.progress.entries.load(Ordering::Relaxed)
+    }
+
+    /// Checks if the content is loading and no entry was read within the given timeout,
+    /// for example because the directory is located on a network mount that is no longer
+    /// reachable.
+    pub fn is_unresponsive(&self, timeout: Duration) -> bool {
+        let DirectoryContentState::Pending(started) = self.state else {
+            return false;
+        };
+
+        let last_update = self.progress.last_update.lock().unwrap_or(started);
+
+        SystemTime::now()
+            .duration_since(last_update)
+            .unwrap_or_default()
+            > timeout
+    }
+
+    /// Stops loading the content on the other thread.
+    /// The thread stops as soon as it reads the next entry, the result is discarded.
+    pub fn cancel(&mut self) {
+        self.progress.cancelled.store(true, Ordering::Relaxed);
+        self.content_recv = None;
+    }
+
+    /// Checks if the given entry matches the search value and the glob and regex filters
+    /// of the directory content.
+    pub fn matches_filters(&self, entry: &DirectoryEntry, search_value: &str) -> bool {
+        #[cfg(feature = "regex")]
+        if entry.is_file()
+            && self
+                .regex_filter
+                .as_ref()
+                .is_some_and(|regex| !regex.is_match(entry.file_name()))
+        {
+            return false;
+        }
+
+        apply_filters(entry, search_value, self.glob_filter.as_ref())
+    }
+
+    /// Returns an iterator over all entries of the directory content,
+    /// ignoring the search value and filters.
+    pub fn iter(&self) -> impl Iterator<Item = &DirectoryEntry> {
+        self.content.iter()
     }
 
     pub fn filtered_iter<'s>(
@@ -360,16 +743,43 @@ impl DirectoryContent {
     ) -> impl Iterator<Item = &'s DirectoryEntry> + 's {
         self.content
             .iter()
-            .filter(|p| apply_search_value(p, search_value))
+            .filter(|p| apply_filters(p, search_value, self.glob_filter.as_ref()))
     }
 
     pub fn filtered_iter_mut<'s>(
         &'s mut self,
         search_value: &'s str,
     ) -> impl Iterator<Item = &'s mut DirectoryEntry> + 's {
+        let glob_filter = self.glob_filter.as_ref();
+
         self.content
             .iter_mut()
-            .filter(|p| apply_search_value(p, search_value))
+            .filter(move |p| apply_filters(p, search_value, glob_filter))
+    }
+
+    /// Sets the glob pattern the files must match to be visible.
+    /// Directories are not affected by the pattern.
+    pub fn set_glob_filter(&mut self, glob_filter: Option<Glob>) {
+        self.glob_filter = glob_filter;
+    }
+
+    /// Sets the regular expression the file names must match to be visible.
+    /// Directories are not affected by the expression.
+    ///
+    /// The expression is evaluated once for each file and the result is cached
+    /// until the expression or the content changes.
+    #[cfg(feature = "regex")]
+    pub fn set_regex_filter(&mut self, regex_filter: Option<regex::Regex>) {
+        self.regex_filter = regex_filter;
+        self.update_regex_matches();
+    }
+
+    /// Evaluates the regex filter for each entry of the content.
+    #[cfg(feature = "regex")]
+    fn update_regex_matches(&mut self) {
+        for item in &mut self.content {
+            item.update_regex_match(self.regex_filter.as_ref());
+        }
     }
 
     /// Marks each element in the content as unselected.
@@ -384,12 +794,85 @@ impl DirectoryContent {
         self.content.len()
     }
 
+    /// Returns the number of files that were hidden by the file size
+    /// and modification date filters when the directory was loaded.
+    pub const fn filtered_count(&self) -> usize {
+        self.filtered_count
+    }
+
+    /// Returns the number of entries that could not be read when the directory was listed,
+    /// for example because they were removed while listing or a network mount stopped
+    /// responding. These entries are not part of the content, because their paths are unknown.
+    pub const fn unreadable_count(&self) -> usize {
+        self.unreadable_count
+    }
+
+    /// Checks if the file filter with the given ID matches at least one of the files
+    /// inside the directory. Always true if the matching filters were not determined.
+    pub fn is_file_filter_available(&self, id: egui::Id) -> bool {
+        self.matched_filters
+            .as_ref()
+            .is_none_or(|matched| matched.contains(&id))
+    }
+
+    /// Returns the number of all entries inside the directory if only part of them was
+    /// loaded because the directory contains more than the maximum number of entries.
+    /// The number includes entries that are not displayed, for example hidden files.
+    pub const fn truncated_total(&self) -> Option<usize> {
+        self.truncated_total
+    }
+
+    /// Returns the number of directories at the beginning of the content.
+    /// This is the number of all directories if directories are listed before files.
+    pub fn leading_dir_count(&self) -> usize {
+        self.content.partition_point(DirectoryEntry::is_dir)
+    }
+
+    /// Sorts the loaded content again using the current configuration,
+    /// without reading the directory again.
+    pub fn sort(&mut self, config: &FileDialogConfig) {
+        sort_entries(config, &mut self.content);
+    }
+
+    /// Stores the computed total sizes of the directories inside the content, so that
+    /// directories are sorted by their total size when sorting by size.
+    pub fn set_directory_sizes(
+        &mut self,
+        config: &FileDialogConfig,
+        directory_size: impl Fn(&Path) -> Option<u64>,
+    ) {
+        for entry in self.content.iter_mut().filter(|entry| entry.is_dir()) {
+            entry.directory_size = directory_size(entry.as_path());
+        }
+
+        if config.file_sorting == FileSorting::Size {
+            self.sort(config);
+        }
+    }
+
     /// Pushes a new item to the content.
     pub fn push(&mut self, item: DirectoryEntry) {
         self.content.push(item);
+
+        #[cfg(feature = "regex")]
+        if let Some(item) = self.content.last_mut() {
+            item.update_regex_match(self.regex_filter.as_ref());
+        }
     }
 }
 
+/// Checks if the entry matches the search value and, if it is a file,
+/// the glob and regex filter.
+fn apply_filters(entry: &DirectoryEntry, search_value: &str, glob_filter: Option<&Glob>) -> bool {
+    #[cfg(feature = "regex")]
+    if entry.hidden_by_regex {
+        return false;
+    }
+
+    apply_search_value(entry, search_value)
+        && (entry.is_dir() || glob_filter.is_none_or(|glob| glob.is_match(entry.file_name())))
+}
+
 fn apply_search_value(entry: &DirectoryEntry, value: &str) -> bool {
     value.is_empty()
         || entry
@@ -399,78 +882,483 @@ fn apply_search_value(entry: &DirectoryEntry, value: &str) -> bool {
 }
 
 /// Loads the contents of the given directory.
+///
+/// If `max_entries` is set, loading stops as soon as that many entries were loaded,
+/// so that the metadata of the remaining entries is never read.
+#[allow(clippy::too_many_arguments)] // Called with the options of the file dialog
 fn load_directory(
     config: &FileDialogConfig,
     path: &Path,
     include_files: bool,
     file_filter: Option<&FileFilter>,
     filter_extension: Option<&str>,
+    max_entries: Option<usize>,
     file_system: &dyn FileSystem,
-) -> io::Result<Vec<DirectoryEntry>> {
+    progress: &LoadProgress,
+) -> io::Result<LoadedDirectory> {
+    // Canonicalized here instead of on the UI thread, because the call blocks as long as
+    // the directory is not responding, like on a network mount that is no longer reachable
+    let path = &if config.canonicalize_paths {
+        file_system
+            .canonicalize(path)
+            .unwrap_or_else(|_| path.to_path_buf())
+    } else {
+        path.to_path_buf()
+    };
+
     let mut result: Vec<DirectoryEntry> = Vec::new();
-    for path in file_system.read_dir(path)? {
-        let entry = DirectoryEntry::from_path(config, &path, file_system);
+    let mut filtered_count = 0;
+    let mut unreadable_count = 0;
+    let mut truncated_total = None;
+    let mut matched_filters = config.hide_empty_filters.then(Vec::new);
 
-        if !config.storage.show_system_files && entry.is_system_file() {
-            continue;
+    let ignore_rules = config
+        .respect_ignore_files
+        .then(|| IgnoreRules::load(file_system, path));
+
+    // The progress is updated while the directory is listed, so that a directory that
+    // is slow to list is not reported as unresponsive as long as entries keep arriving
+    let mut paths = Vec::new();
+    file_system.read_dir_incremental(path, &mut |entry| {
+        if progress.cancelled.load(Ordering::Relaxed) {
+            return ControlFlow::Break(());
         }
 
-        if !include_files && entry.is_file() {
-            continue;
+        match entry {
+            Ok(entry) => paths.push(entry),
+            // The path of the entry is unknown, so it can only be counted
+            Err(_) => unreadable_count += 1,
         }
 
-        if !config.storage.show_hidden && entry.is_hidden() {
-            continue;
+        progress.entries.fetch_add(1, Ordering::Relaxed);
+        *progress.last_update.lock() = Some(SystemTime::now());
+
+        ControlFlow::Continue(())
+    })?;
+
+    if progress.cancelled.load(Ordering::Relaxed) {
+        return Err(io::Error::new(
+            io::ErrorKind::Interrupted,
+            "loading cancelled",
+        ));
+    }
+
+    let total = paths.len();
+
+    // The order returned by the file system is arbitrary, so the paths are sorted first
+    // to load the same entries every time
+    if max_entries.is_some_and(|max| total > max) {
+        paths.sort_by(|a, b| {
+            compare_file_names(
+                &a.file_name().unwrap_or_default().to_string_lossy(),
+                &b.file_name().unwrap_or_default().to_string_lossy(),
+            )
+        });
+    }
+
+    for path in paths {
+        if progress.cancelled.load(Ordering::Relaxed) {
+            return Err(io::Error::new(
+                io::ErrorKind::Interrupted,
+                "loading cancelled",
+            ));
         }
 
-        if let Some(file_filter) = file_filter {
-            if entry.is_file() && !(file_filter.filter)(entry.as_path()) {
+        *progress.last_update.lock() = Some(SystemTime::now());
+
+        if max_entries.is_some_and(|max| result.len() >= max) {
+            truncated_total = Some(total);
+            break;
+        }
+
+        // Checked first so that the metadata of rejected entries is never read
+        if let Some(predicate) = &config.entry_predicate {
+            if !(predicate.filter)(&path) {
                 continue;
             }
         }
 
-        if let Some(ex) = filter_extension {
-            if entry.is_file()
-                && path
-                    .extension()
-                    .unwrap_or_default()
-                    .to_str()
-                    .unwrap_or_default()
-                    != ex
-            {
-                continue;
+        let mut entry = DirectoryEntry::from_path(config, &path, file_system);
+
+        if !is_entry_listed(config, &mut entry, ignore_rules.as_ref(), include_files) {
+            continue;
+        }
+
+        if let Some(matched) = &mut matched_filters {
+            if entry.is_file() {
+                update_matched_filters(config, &entry, matched);
             }
         }
 
+        if !apply_file_filters(config, &entry, file_filter, filter_extension) {
+            continue;
+        }
+
+        if entry.is_file() && !apply_metadata_filters(config, &entry) {
+            filtered_count += 1;
+            continue;
+        }
+
+        entry.resolve_owner_names(config);
         result.push(entry);
     }
 
-    result.sort_by(|a, b| {
-        if a.is_dir() == b.is_dir() {
-            a.file_name().cmp(b.file_name())
+    sort_entries(config, &mut result);
+
+    Ok(LoadedDirectory {
+        path: path.clone(),
+        entries: result,
+        filtered_count,
+        unreadable_count,
+        truncated_total,
+        matched_filters,
+    })
+}
+
+/// Loads the entry of the given path if it would be listed when loading its parent
+/// directory with the given options. Returns None if the entry is filtered out.
+pub fn load_filtered_entry(
+    config: &FileDialogConfig,
+    path: &Path,
+    include_files: bool,
+    file_filter: Option<&FileFilter>,
+    filter_extension: Option<&str>,
+    file_system: &dyn FileSystem,
+) -> Option<DirectoryEntry> {
+    if let Some(predicate) = &config.entry_predicate {
+        if !(predicate.filter)(path) {
+            return None;
+        }
+    }
+
+    let mut entry = DirectoryEntry::from_path(config, path, file_system);
+
+    let ignore_rules = config
+        .respect_ignore_files
+        .then(|| IgnoreRules::load(file_system, path.parent().unwrap_or(path)));
+
+    let visible = is_entry_listed(config, &mut entry, ignore_rules.as_ref(), include_files)
+        && apply_file_filters(config, &entry, file_filter, filter_extension)
+        && (!entry.is_file() || apply_metadata_filters(config, &entry));
+
+    if !visible {
+        return None;
+    }
+
+    entry.resolve_owner_names(config);
+    Some(entry)
+}
+
+/// Checks if the entry is listed with the ignore rules and the options to show hidden files,
+/// system files and ignored entries. Marks the entry as ignored if it is matched by the rules.
+fn is_entry_listed(
+    config: &FileDialogConfig,
+    entry: &mut DirectoryEntry,
+    ignore_rules: Option<&IgnoreRules>,
+    include_files: bool,
+) -> bool {
+    if let Some(rules) = ignore_rules {
+        entry.is_ignored = rules.is_ignored(entry.as_path(), entry.is_dir());
+
+        if entry.is_ignored && !config.storage.show_ignored {
+            return false;
+        }
+    }
+
+    // Entries that could not be accessed are kept so that the user knows they exist
+    if !config.storage.show_system_files && entry.is_system_file() && entry.error().is_none() {
+        return false;
+    }
+
+    if !include_files && entry.is_file() {
+        return false;
+    }
+
+    config.storage.show_hidden || !entry.is_hidden()
+}
+
+/// Checks if the file passes the selected file filter, the path filter and the selected
+/// save extension. Directories are never filtered out.
+fn apply_file_filters(
+    config: &FileDialogConfig,
+    entry: &DirectoryEntry,
+    file_filter: Option<&FileFilter>,
+    filter_extension: Option<&str>,
+) -> bool {
+    if !entry.is_file() {
+        return true;
+    }
+
+    let path = entry.as_path();
+
+    file_filter.is_none_or(|filter| (filter.filter)(path))
+        && config
+            .path_filter
+            .as_ref()
+            .is_none_or(|filter| (filter.filter)(path))
+        && filter_extension.is_none_or(|ex| {
+            path.extension()
+                .unwrap_or_default()
+                .to_str()
+                .unwrap_or_default()
+                == ex
+        })
+}
+
+/// Adds the IDs of the file filters matching the given file that were not matched before.
+fn update_matched_filters(
+    config: &FileDialogConfig,
+    entry: &DirectoryEntry,
+    matched: &mut Vec<egui::Id>,
+) {
+    for filter in &config.file_filters {
+        if !matched.contains(&filter.id) && (filter.filter)(entry.as_path()) {
+            matched.push(filter.id);
+        }
+    }
+}
+
+/// Checks if the file passes the file size and modification date filters.
+/// Files without the required metadata are not filtered out.
+fn apply_metadata_filters(config: &FileDialogConfig, entry: &DirectoryEntry) -> bool {
+    let metadata = entry.metadata();
+
+    if let Some(size) = metadata.size {
+        if config.min_file_size.is_some_and(|min| size < min)
+            || config.max_file_size.is_some_and(|max| size > max)
+        {
+            return false;
+        }
+    }
+
+    if let (Some(modified_after), Some(last_modified)) =
+        (config.modified_after, metadata.last_modified)
+    {
+        if last_modified < modified_after {
+            return false;
+        }
+    }
+
+    config
+        .modified_within
+        .is_none_or(|duration| is_modified_within(entry, duration))
+}
+
+/// Sorts the given entries by the configured `FileSorting` or using the custom sort comparator.
+/// Directories stay in front of the files if `folders_first` is enabled, regardless
+/// of the sort order.
+fn sort_entries(config: &FileDialogConfig, entries: &mut [DirectoryEntry]) {
+    entries.sort_by(|a, b| {
+        if !config.folders_first || a.is_dir() == b.is_dir() {
+            let ordering = config.sort_comparator.as_ref().map_or_else(
+                || compare_entries(config.file_sorting, a, b),
+                |sort| (sort.comparator)(a.as_path(), b.as_path()),
+            );
+
+            if config.storage.sort_descending {
+                ordering.reverse()
+            } else {
+                ordering
+            }
         } else if a.is_dir() {
             std::cmp::Ordering::Less
         } else {
             std::cmp::Ordering::Greater
         }
     });
+}
+
+/// Compares two entries by the given property.
+/// Entries that are equal in that property are ordered by their name.
+fn compare_entries(
+    sorting: FileSorting,
+    a: &DirectoryEntry,
+    b: &DirectoryEntry,
+) -> std::cmp::Ordering {
+    let ordering = match sorting {
+        FileSorting::Name => std::cmp::Ordering::Equal,
+        FileSorting::Size => a.sort_size().cmp(&b.sort_size()),
+        FileSorting::LastModified => a.metadata.last_modified.cmp(&b.metadata.last_modified),
+    };
+
+    ordering.then_with(|| compare_file_names(a.file_name(), b.file_name()))
+}
 
-    Ok(result)
+/// Compares two file names for sorting.
+///
+/// The names are compared case-insensitively and accented latin letters are sorted
+/// together with their base letter, so that "Äpfel" comes before "Zebra".
+/// Names that only differ in case or accents are ordered by their code points.
+fn compare_file_names(a: &str, b: &str) -> std::cmp::Ordering {
+    fold_file_name(a)
+        .cmp(fold_file_name(b))
+        .then_with(|| a.cmp(b))
+}
+
+/// Converts the name to lowercase and removes accents from latin letters.
+fn fold_file_name(name: &str) -> impl Iterator<Item = char> + '_ {
+    name.chars().flat_map(char::to_lowercase).map(fold_accent)
+}
+
+/// Maps accented latin letters to their base letter.
+/// The letter must already be in lowercase.
+const fn fold_accent(c: char) -> char {
+    match c {
+        'à'..='å' => 'a',
+        'ç' => 'c',
+        'è'..='ë' => 'e',
+        'ì'..='ï' => 'i',
+        'ñ' => 'n',
+        'ò'..='ö' | 'ø' => 'o',
+        'ù'..='ü' => 'u',
+        'ý' | 'ÿ' => 'y',
+        _ => c,
+    }
+}
+
+/// Checks if the given entry was modified within the given duration.
+/// Returns true if the modification time of the entry is unknown.
+fn is_modified_within(entry: &DirectoryEntry, duration: Duration) -> bool {
+    let Some(last_modified) = entry.metadata().last_modified else {
+        return true;
+    };
+
+    // Modification times in the future count as recently modified
+    SystemTime::now()
+        .duration_since(last_modified)
+        .map_or(true, |age| age <= duration)
 }
 
 /// Generates the icon for the specific path.
-/// The default icon configuration is taken into account, as well as any configured
-/// file icon filters.
-fn gen_path_icon(config: &FileDialogConfig, path: &Path, file_system: &dyn FileSystem) -> String {
+/// The file icon filters configured using `set_file_icon` take precedence over the icon
+/// of executable files and the default icons.
+fn gen_path_icon(
+    config: &FileDialogConfig,
+    path: &Path,
+    is_directory: bool,
+    executable: bool,
+) -> String {
     for def in &config.file_icon_filters {
         if (def.filter)(path) {
             return def.icon.clone();
         }
     }
 
-    if file_system.is_dir(path) {
+    if is_directory {
         config.default_folder_icon.clone()
+    } else if executable {
+        config.executable_file_icon.clone()
     } else {
         config.default_file_icon.clone()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cmp::Ordering;
+
+    fn file_entry(size: Option<u64>, last_modified: Option<SystemTime>) -> DirectoryEntry {
+        DirectoryEntry {
+            metadata: Metadata::new(size, last_modified, None, None),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn metadata_filters() {
+        let now = SystemTime::now();
+        let week = Duration::from_hours(7 * 24);
+
+        let config = FileDialogConfig {
+            min_file_size: Some(1024),
+            max_file_size: Some(4096),
+            modified_after: Some(now - week),
+            ..Default::default()
+        };
+
+        assert!(apply_metadata_filters(
+            &config,
+            &file_entry(Some(2048), Some(now))
+        ));
+        assert!(!apply_metadata_filters(
+            &config,
+            &file_entry(Some(512), Some(now))
+        ));
+        assert!(!apply_metadata_filters(
+            &config,
+            &file_entry(Some(8192), Some(now))
+        ));
+        assert!(!apply_metadata_filters(
+            &config,
+            &file_entry(Some(2048), Some(now - week * 2))
+        ));
+
+        // Files without the required metadata are not filtered out
+        assert!(apply_metadata_filters(&config, &file_entry(None, None)));
+    }
+
+    #[test]
+    fn compare_file_names_case_insensitive() {
+        assert_eq!(compare_file_names("apple", "Banana"), Ordering::Less);
+        assert_eq!(compare_file_names("Zebra", "apple"), Ordering::Greater);
+        assert_eq!(compare_file_names("file10", "File2"), Ordering::Less);
+    }
+
+    #[test]
+    fn compare_file_names_accents() {
+        assert_eq!(compare_file_names("Äpfel", "Zebra"), Ordering::Less);
+        assert_eq!(compare_file_names("école", "Frühling"), Ordering::Less);
+        assert_eq!(compare_file_names("Öl", "Olive"), Ordering::Less);
+    }
+
+    #[test]
+    fn compare_file_names_is_total() {
+        assert_eq!(compare_file_names("a", "A"), Ordering::Greater);
+        assert_eq!(compare_file_names("a", "ä"), Ordering::Less);
+        assert_eq!(compare_file_names("same", "same"), Ordering::Equal);
+    }
+
+    #[test]
+    fn compare_entries_by_metadata() {
+        let now = SystemTime::now();
+        let small = file_entry(Some(10), Some(now));
+        let large = file_entry(Some(20), Some(now - Duration::from_mins(1)));
+
+        assert_eq!(
+            compare_entries(FileSorting::Size, &small, &large),
+            Ordering::Less
+        );
+        assert_eq!(
+            compare_entries(FileSorting::LastModified, &small, &large),
+            Ordering::Greater
+        );
+        assert_eq!(
+            compare_entries(FileSorting::Name, &small, &large),
+            Ordering::Equal
+        );
+    }
+
+    #[test]
+    fn executable_icon() {
+        let mut config = FileDialogConfig::default();
+        let path = Path::new("/bin/tool");
+
+        assert_eq!(
+            gen_path_icon(&config, path, false, true),
+            config.executable_file_icon
+        );
+        assert_eq!(
+            gen_path_icon(&config, path, false, false),
+            config.default_file_icon
+        );
+        assert_eq!(
+            gen_path_icon(&config, path, true, true),
+            config.default_folder_icon
+        );
+
+        // Custom icons take precedence
+        config = config.set_file_icon("🔧", Arc::new(|path| path.ends_with("tool")));
+        assert_eq!(gen_path_icon(&config, path, false, true), "🔧");
+    }
+}
@@ -25,6 +25,7 @@ impl Disk {
             display_name: gen_display_name(
                 name.unwrap_or_default(),
                 mount_point.to_str().unwrap_or_default(),
+                is_removable,
             ),
             is_removable,
         }
@@ -40,6 +41,20 @@ impl Disk {
         )
     }
 
+    /// Create a new Disk object based on the data of a `sysinfo::Disk` using the
+    /// macOS naming conventions (macos only)
+    #[cfg(target_os = "macos")]
+    pub fn from_macos_volume(disk: &sysinfo::Disk, canonicalize_paths: bool) -> Self {
+        Self {
+            mount_point: canonicalize(disk.mount_point(), canonicalize_paths),
+            display_name: gen_macos_display_name(
+                disk.name().to_str().unwrap_or_default(),
+                disk.mount_point(),
+            ),
+            is_removable: disk.is_removable(),
+        }
+    }
+
     /// Create a new Disk object based on its path (macos only)
     #[cfg(target_os = "macos")]
     pub fn from_path(path: &Path, canonicalize_paths: bool) -> Self {
@@ -128,20 +143,12 @@ fn canonicalize(path: &Path, canonicalize: bool) -> PathBuf {
 }
 
 #[cfg(windows)]
-fn gen_display_name(name: &str, mount_point: &str) -> String {
-    let mount_point = mount_point.replace('\\', "");
-
-    // Try using the mount point as the display name if the specified name
-    // from sysinfo::Disk is empty or contains invalid characters
-    if name.is_empty() {
-        return mount_point;
-    }
-
-    format!("{name} ({mount_point})")
+fn gen_display_name(name: &str, mount_point: &str, is_removable: bool) -> String {
+    gen_windows_display_name(name, mount_point, is_removable)
 }
 
 #[cfg(not(windows))]
-fn gen_display_name(name: &str, mount_point: &str) -> String {
+fn gen_display_name(name: &str, mount_point: &str, _is_removable: bool) -> String {
     // Try using the mount point as the display name if the specified name
     // from sysinfo::Disk is empty or contains invalid characters
     if name.is_empty() {
@@ -151,6 +158,87 @@ fn gen_display_name(name: &str, mount_point: &str) -> String {
     name.to_string()
 }
 
+/// Generates the display name of a drive on Windows, like `Local Disk (C:)`.
+///
+/// The volume label is followed by the drive letter. Drives without a volume label are
+/// named "Local Disk" or "Removable Disk". Drives that are not mounted at a drive letter
+/// display their mount point instead.
+#[cfg(any(windows, test))]
+fn gen_windows_display_name(label: &str, mount_point: &str, is_removable: bool) -> String {
+    let label = if label.is_empty() {
+        if is_removable {
+            "Removable Disk"
+        } else {
+            "Local Disk"
+        }
+    } else {
+        label
+    };
+
+    drive_letter(mount_point).map_or_else(
+        || format!("{label} ({})", mount_point.trim_end_matches('\\')),
+        |letter| format!("{label} ({letter}:)"),
+    )
+}
+
+/// Gets the uppercase drive letter of a mount point like `C:\`.
+/// Returns None if the mount point is not the root of a drive letter.
+#[cfg(any(windows, test))]
+fn drive_letter(mount_point: &str) -> Option<char> {
+    let mut chars = mount_point.chars();
+    let letter = chars.next().filter(char::is_ascii_alphabetic)?;
+
+    match (chars.next(), chars.as_str()) {
+        (Some(':'), "" | "\\" | "/") => Some(letter.to_ascii_uppercase()),
+        _ => None,
+    }
+}
+
+/// Display name of the internal startup disk on macOS.
+#[cfg(any(target_os = "macos", test))]
+const MACOS_STARTUP_DISK_NAME: &str = "Macintosh HD";
+
+/// Generates the display name of a volume on macOS.
+///
+/// The startup disk mounted at `/` is named "Macintosh HD" if it has no proper volume name,
+/// volumes mounted inside `/Volumes` are named after their mount folder.
+/// Raw BSD device nodes like `disk3s1s1` are never used as display names.
+#[cfg(any(target_os = "macos", test))]
+fn gen_macos_display_name(name: &str, mount_point: &Path) -> String {
+    let has_volume_name = !name.is_empty() && !is_bsd_device_name(name);
+
+    if mount_point == Path::new("/") {
+        return if has_volume_name {
+            name.to_string()
+        } else {
+            MACOS_STARTUP_DISK_NAME.to_string()
+        };
+    }
+
+    if mount_point.parent() == Some(Path::new("/Volumes")) {
+        if let Some(folder) = mount_point.file_name() {
+            return folder.to_string_lossy().into_owned();
+        }
+    }
+
+    if has_volume_name {
+        name.to_string()
+    } else {
+        mount_point.to_string_lossy().into_owned()
+    }
+}
+
+/// Checks if the name is a raw BSD device node like `/dev/disk3s1s1` or `disk3s1s1`.
+#[cfg(any(target_os = "macos", test))]
+fn is_bsd_device_name(name: &str) -> bool {
+    let name = name.strip_prefix("/dev/").unwrap_or(name);
+
+    name.strip_prefix("disk").is_some_and(|rest| {
+        rest.starts_with(|c: char| c.is_ascii_digit())
+            && rest.chars().all(|c| c.is_ascii_digit() || c == 's')
+    })
+}
+
 #[cfg(windows)]
 fn load_disks(canonicalize_paths: bool) -> Vec<Disk> {
     let mut disks: Vec<Disk> = sysinfo::Disks::new_with_refreshed_list()
@@ -179,6 +267,12 @@ fn load_disks(canonicalize_paths: bool) -> Vec<Disk> {
         letter += 1;
     }
 
+    // List the drives by their letter, followed by drives mounted at other locations
+    disks.sort_by_key(|d| {
+        let letter = drive_letter(d.mount_point.to_str().unwrap_or_default());
+        (letter.is_none(), letter)
+    });
+
     disks
 }
 
@@ -201,17 +295,21 @@ fn load_disks(canonicalize_paths: bool) -> Vec<Disk> {
     let mut result = Vec::new();
     let mut seen_mount_points = std::collections::HashSet::new();
 
-    // Collect disks from sysinfo
+    // Collect disks from sysinfo.
+    // The system volumes inside `/System/Volumes` are part of the startup disk and
+    // are therefore not listed separately.
     for disk in &sysinfo::Disks::new_with_refreshed_list() {
         let mount_point = disk.mount_point();
-        if mount_point != Path::new("/")
+        if !mount_point.starts_with("/System/Volumes")
             && seen_mount_points.insert(mount_point.to_path_buf())
-            && disk.mount_point() != Path::new("/System/Volumes/Data")
         {
-            result.push(Disk::from_sysinfo_disk(disk, canonicalize_paths));
+            result.push(Disk::from_macos_volume(disk, canonicalize_paths));
         }
     }
 
+    // List the startup disk first
+    result.sort_by_key(|disk| disk.mount_point != Path::new("/"));
+
     // Collect volumes from /Volumes
     if let Ok(entries) = fs::read_dir("/Volumes") {
         for entry in entries.filter_map(Result::ok) {
@@ -230,3 +328,61 @@ fn load_disks(canonicalize_paths: bool) -> Vec<Disk> {
 
     result
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bsd_device_names() {
+        assert!(is_bsd_device_name("disk3s1s1"));
+        assert!(is_bsd_device_name("/dev/disk0s2"));
+        assert!(!is_bsd_device_name("disk"));
+        assert!(!is_bsd_device_name("diskette"));
+        assert!(!is_bsd_device_name("Macintosh HD"));
+    }
+
+    #[test]
+    fn windows_display_names() {
+        assert_eq!(
+            gen_windows_display_name("", "C:\\", false),
+            "Local Disk (C:)"
+        );
+        assert_eq!(
+            gen_windows_display_name("", "e:\\", true),
+            "Removable Disk (E:)"
+        );
+        assert_eq!(gen_windows_display_name("Data", "D:\\", false), "Data (D:)");
+        assert_eq!(
+            gen_windows_display_name("Backup", "C:\\Mount\\Backup\\", false),
+            "Backup (C:\\Mount\\Backup)"
+        );
+    }
+
+    #[test]
+    fn drive_letters() {
+        assert_eq!(drive_letter("C:\\"), Some('C'));
+        assert_eq!(drive_letter("d:"), Some('D'));
+        assert_eq!(drive_letter("C:\\Mount"), None);
+        assert_eq!(drive_letter("\\\\server\\share"), None);
+        assert_eq!(drive_letter("/"), None);
+    }
+
+    #[test]
+    fn macos_display_names() {
+        assert_eq!(
+            gen_macos_display_name("disk3s1s1", Path::new("/")),
+            "Macintosh HD"
+        );
+        assert_eq!(gen_macos_display_name("", Path::new("/")), "Macintosh HD");
+        assert_eq!(gen_macos_display_name("Work", Path::new("/")), "Work");
+        assert_eq!(
+            gen_macos_display_name("disk4s1", Path::new("/Volumes/USB Stick")),
+            "USB Stick"
+        );
+        assert_eq!(
+            gen_macos_display_name("disk5s1", Path::new("/private/mnt")),
+            "/private/mnt"
+        );
+    }
+}
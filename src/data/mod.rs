@@ -1,5 +1,7 @@
 mod directory_content;
-pub use directory_content::{DirectoryContent, DirectoryContentState, DirectoryEntry, Metadata};
+pub use directory_content::{
+    load_filtered_entry, DirectoryContent, DirectoryContentState, DirectoryEntry, Metadata,
+};
 
 mod disks;
 pub use disks::{Disk, Disks};
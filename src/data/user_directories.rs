@@ -12,6 +12,8 @@ pub struct UserDirectories {
     download_dir: Option<PathBuf>,
     picture_dir: Option<PathBuf>,
     video_dir: Option<PathBuf>,
+    public_dir: Option<PathBuf>,
+    template_dir: Option<PathBuf>,
 }
 
 impl UserDirectories {
@@ -33,9 +35,25 @@ impl UserDirectories {
             download_dir,
             picture_dir,
             video_dir,
+            public_dir: None,
+            template_dir: None,
         }
     }
 
+    /// Sets the directory used to share files with other users, like `~/Public`.
+    #[must_use]
+    pub fn with_public_dir(mut self, public_dir: Option<PathBuf>) -> Self {
+        self.public_dir = public_dir;
+        self
+    }
+
+    /// Sets the directory containing the templates of new documents, like `~/Templates`.
+    #[must_use]
+    pub fn with_template_dir(mut self, template_dir: Option<PathBuf>) -> Self {
+        self.template_dir = template_dir;
+        self
+    }
+
     pub(crate) fn home_dir(&self) -> Option<&Path> {
         self.home_dir.as_deref()
     }
@@ -64,6 +82,14 @@ impl UserDirectories {
         self.video_dir.as_deref()
     }
 
+    pub(crate) fn public_dir(&self) -> Option<&Path> {
+        self.public_dir.as_deref()
+    }
+
+    pub(crate) fn template_dir(&self) -> Option<&Path> {
+        self.template_dir.as_deref()
+    }
+
     /// Canonicalizes the given paths. Returns None if an error occurred.
     pub(crate) fn canonicalize(path: Option<&Path>, canonicalize: bool) -> Option<PathBuf> {
         if !canonicalize {
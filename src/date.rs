@@ -0,0 +1,32 @@
+//! Formatting of the dates displayed in the directory content.
+
+use std::fmt::Write;
+use std::time::SystemTime;
+
+/// Formats the date in the local time zone using the given `chrono` format string,
+/// like `%d.%m.%Y %H:%M`. Returns an empty string if the format string is invalid.
+pub fn format_date(time: SystemTime, format: &str) -> String {
+    let date: chrono::DateTime<chrono::Local> = time.into();
+
+    let mut text = String::new();
+    if write!(text, "{}", date.format(format)).is_err() {
+        text.clear();
+    }
+
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn format() {
+        // 15.06.2024 12:00 UTC, which is the same year and month in every time zone
+        let date = SystemTime::UNIX_EPOCH + Duration::from_hours(477_348);
+
+        assert_eq!(format_date(date, "%m.%Y"), "06.2024");
+        assert_eq!(format_date(date, "%Q"), "");
+    }
+}
@@ -0,0 +1,120 @@
+use std::path::Path;
+
+use crate::background_lookup::BackgroundLookup;
+use crate::ignore_rules::IgnoreRules;
+use crate::{FileDialogConfig, FileSystem};
+
+/// The state of the item count of a single directory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ItemCount {
+    /// The count is currently being loaded.
+    Pending,
+    /// The number of items inside the directory.
+    Loaded(usize),
+    /// The content of the directory could not be read.
+    Unavailable,
+}
+
+/// The options deciding which items inside a directory are counted.
+/// Only the items that would be listed when opening the directory are counted.
+#[derive(Debug, Clone, Copy)]
+struct ItemCountOptions {
+    show_hidden: bool,
+    /// If items matched by the rules of a `.gitignore` file are not counted.
+    skip_ignored: bool,
+}
+
+impl ItemCountOptions {
+    const fn new(config: &FileDialogConfig) -> Self {
+        Self {
+            show_hidden: config.storage.show_hidden,
+            skip_ignored: config.respect_ignore_files && !config.storage.show_ignored,
+        }
+    }
+}
+
+/// Cache of the number of items inside the directories of the currently open directory.
+/// The counts are loaded lazily, only for directories that are requested. All counts are
+/// loaded one after another on a single thread, which is stopped as soon as the counts
+/// are cleared.
+#[derive(Debug, Default)]
+pub struct DirectoryItemCounts {
+    /// The counts that are loaded or currently loading, mapped by the path of the directory.
+    counts: BackgroundLookup<ItemCount>,
+}
+
+impl DirectoryItemCounts {
+    /// Gets the item count of the given directory without loading it.
+    /// Returns None if the count was not requested yet.
+    pub fn get(&self, path: &Path) -> Option<ItemCount> {
+        self.counts
+            .contains(path)
+            .then(|| self.counts.get(path).unwrap_or(ItemCount::Pending))
+    }
+
+    /// Starts loading the item count of the given directory if it is not already loaded.
+    /// The count is loaded via a separate thread if `load_via_thread` is enabled.
+    pub fn request(&mut self, config: &FileDialogConfig, path: &Path) {
+        if self.counts.contains(path) {
+            return;
+        }
+
+        let options = ItemCountOptions::new(config);
+
+        if !config.load_via_thread {
+            let count = load_item_count(&*config.file_system, path, options);
+            self.counts.insert(path.to_path_buf(), count.into());
+            return;
+        }
+
+        let file_system = config.file_system.clone();
+        self.counts.request(path, move |path, _| {
+            load_item_count(&*file_system, path, options).into()
+        });
+    }
+
+    /// Receives the counts that finished loading on the other thread.
+    /// Returns true if there are still counts loading.
+    pub fn update(&mut self) -> bool {
+        self.counts.update();
+        self.counts.is_pending()
+    }
+
+    /// Stops loading the counts and removes all cached counts,
+    /// so that they are loaded again when requested.
+    pub fn clear(&mut self) {
+        self.counts.clear();
+    }
+}
+
+impl From<Option<usize>> for ItemCount {
+    fn from(value: Option<usize>) -> Self {
+        value.map_or(Self::Unavailable, Self::Loaded)
+    }
+}
+
+/// Counts the items inside the given directory that are listed with the given options.
+/// Returns None if the directory could not be read.
+fn load_item_count(
+    file_system: &dyn FileSystem,
+    path: &Path,
+    options: ItemCountOptions,
+) -> Option<usize> {
+    let items = file_system.read_dir(path).ok()?;
+
+    let ignore_rules = options
+        .skip_ignored
+        .then(|| IgnoreRules::load(file_system, path));
+
+    let count = items
+        .iter()
+        .filter(|item| options.show_hidden || !file_system.is_path_hidden(item))
+        .filter(|item| {
+            ignore_rules
+                .as_ref()
+                .is_none_or(|rules| !rules.is_ignored(item, file_system.is_dir(item)))
+        })
+        .count();
+
+    Some(count)
+}
@@ -0,0 +1,170 @@
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Instant;
+
+use crate::background_lookup::BackgroundLookup;
+use crate::{FileDialogConfig, FileSystem};
+
+/// The state of the recursive size of a single directory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DirectorySize {
+    /// The size is currently being computed.
+    Pending,
+    /// The total size of all files inside the directory and its subdirectories.
+    Loaded(u64),
+    /// The size of the files found before the depth or time budget was exceeded.
+    /// The actual size of the directory is larger.
+    Partial(u64),
+    /// The content of the directory could not be read.
+    Unavailable,
+}
+
+impl DirectorySize {
+    /// Returns the number of bytes found inside the directory, if the size was computed.
+    pub const fn bytes(self) -> Option<u64> {
+        match self {
+            Self::Loaded(size) | Self::Partial(size) => Some(size),
+            Self::Pending | Self::Unavailable => None,
+        }
+    }
+}
+
+/// Cache of the recursive sizes of the directories inside the currently open directory.
+/// All sizes are computed one after another on a single thread, which is cancelled
+/// as soon as the sizes are cleared.
+#[derive(Debug, Default)]
+pub struct DirectorySizes {
+    /// The directory whose subdirectories are measured.
+    directory: Option<PathBuf>,
+    /// The sizes that are computed or currently computing, mapped by the path of the directory.
+    sizes: BackgroundLookup<DirectorySize>,
+    /// If sizes were computed since `take_changed` was last called.
+    changed: bool,
+}
+
+impl DirectorySizes {
+    /// Gets the size of the given directory.
+    /// Returns None if the size was not requested.
+    pub fn get(&self, path: &Path) -> Option<DirectorySize> {
+        self.sizes
+            .contains(path)
+            .then(|| self.sizes.get(path).unwrap_or(DirectorySize::Pending))
+    }
+
+    /// Checks if the sizes of the subdirectories of `directory` were already requested.
+    pub fn is_requested(&self, directory: &Path) -> bool {
+        self.directory.as_deref() == Some(directory)
+    }
+
+    /// Starts computing the sizes of the given subdirectories of `directory`.
+    /// The sizes requested for a different directory before are removed.
+    ///
+    /// The sizes are computed via a separate thread if `load_via_thread` is enabled, with
+    /// `directory_size_timeout` as the budget of every directory. Otherwise, all directories
+    /// share the budget, so that the UI blocks for at most the timeout. The sizes of the
+    /// directories remaining when the budget is exceeded are not computed.
+    pub fn request(
+        &mut self,
+        config: &FileDialogConfig,
+        directory: &Path,
+        subdirectories: Vec<PathBuf>,
+    ) {
+        self.clear();
+        self.directory = Some(directory.to_path_buf());
+
+        let max_depth = config.directory_size_max_depth;
+        let timeout = config.directory_size_timeout;
+
+        if !config.load_via_thread {
+            let deadline = Instant::now() + timeout;
+            let cancelled = AtomicBool::new(false);
+
+            for path in subdirectories {
+                if Instant::now() > deadline {
+                    break;
+                }
+
+                let size =
+                    compute_size(&*config.file_system, &path, max_depth, deadline, &cancelled);
+                self.sizes.insert(path, size);
+            }
+
+            self.changed = true;
+            return;
+        }
+
+        for path in &subdirectories {
+            let file_system = config.file_system.clone();
+            self.sizes.request(path, move |path, cancelled| {
+                let deadline = Instant::now() + timeout;
+                compute_size(&*file_system, path, max_depth, deadline, cancelled)
+            });
+        }
+    }
+
+    /// Receives the sizes that finished computing on the other thread.
+    /// Returns true if there are still sizes computing.
+    pub fn update(&mut self) -> bool {
+        if self.sizes.update() {
+            self.changed = true;
+        }
+
+        self.sizes.is_pending()
+    }
+
+    /// Checks if sizes were computed since this function was last called.
+    pub fn take_changed(&mut self) -> bool {
+        std::mem::take(&mut self.changed)
+    }
+
+    /// Cancels the computation of the sizes and removes all cached sizes,
+    /// so that they are computed again when requested.
+    pub fn clear(&mut self) {
+        self.sizes.clear();
+        self.directory = None;
+    }
+}
+
+/// Computes the total size of all files inside the given directory and its subdirectories.
+///
+/// Stops with a partial size if subdirectories deeper than `max_depth` are found
+/// or the computation is still running at the `deadline`.
+fn compute_size(
+    file_system: &dyn FileSystem,
+    path: &Path,
+    max_depth: usize,
+    deadline: Instant,
+    cancelled: &AtomicBool,
+) -> DirectorySize {
+    let mut total = 0;
+    let mut stack = vec![(path.to_path_buf(), 0)];
+
+    while let Some((directory, depth)) = stack.pop() {
+        if cancelled.load(Ordering::Relaxed) || Instant::now() > deadline {
+            return DirectorySize::Partial(total);
+        }
+
+        let Ok(items) = file_system.read_dir(&directory) else {
+            // Only the directory itself is required to be readable
+            if depth == 0 {
+                return DirectorySize::Unavailable;
+            }
+
+            continue;
+        };
+
+        for item in items {
+            if file_system.is_dir(&item) {
+                if depth >= max_depth {
+                    return DirectorySize::Partial(total);
+                }
+
+                stack.push((item, depth + 1));
+            } else if let Ok(metadata) = file_system.metadata(&item) {
+                total += metadata.size.unwrap_or_default();
+            }
+        }
+    }
+
+    DirectorySize::Loaded(total)
+}
@@ -0,0 +1,93 @@
+//! Best-effort ejecting of removable devices using the tools of the platform.
+
+use std::io;
+use std::path::Path;
+use std::process::Command;
+
+/// Unmounts and, if supported by the platform, ejects the device mounted at the given path.
+///
+/// The device is ejected using the command line tools of the platform:
+///   - Linux and other Unix platforms: `gio mount --eject`, falling back to `umount`
+///   - macOS: `diskutil eject`
+///   - Windows: the "Eject" verb of the Windows shell
+///
+/// This blocks until the command finished.
+pub fn eject(mount_point: &Path) -> io::Result<()> {
+    let mut last_error = io::Error::new(io::ErrorKind::Unsupported, "no eject command available");
+
+    for mut command in commands(mount_point) {
+        match run(&mut command) {
+            Ok(()) => return Ok(()),
+            Err(err) => last_error = err,
+        }
+    }
+
+    Err(last_error)
+}
+
+/// Runs the command and turns a failure into an error containing the output of the command.
+fn run(command: &mut Command) -> io::Result<()> {
+    let output = command.output()?;
+
+    if output.status.success() {
+        return Ok(());
+    }
+
+    let message = String::from_utf8_lossy(&output.stderr).trim().to_string();
+
+    Err(io::Error::other(if message.is_empty() {
+        output.status.to_string()
+    } else {
+        message
+    }))
+}
+
+/// The commands that are tried in order to eject the device.
+#[cfg(all(unix, not(target_os = "macos")))]
+fn commands(mount_point: &Path) -> Vec<Command> {
+    let mut gio = Command::new("gio");
+    gio.args(["mount", "--eject"]).arg(mount_point);
+
+    let mut umount = Command::new("umount");
+    umount.arg(mount_point);
+
+    vec![gio, umount]
+}
+
+/// The commands that are tried in order to eject the device.
+#[cfg(target_os = "macos")]
+fn commands(mount_point: &Path) -> Vec<Command> {
+    let mut diskutil = Command::new("diskutil");
+    diskutil.arg("eject").arg(mount_point);
+
+    vec![diskutil]
+}
+
+/// The commands that are tried in order to eject the device.
+#[cfg(windows)]
+fn commands(mount_point: &Path) -> Vec<Command> {
+    // The shell expects the drive without a trailing backslash, like `E:`
+    let drive = mount_point
+        .to_string_lossy()
+        .trim_end_matches('\\')
+        .replace('\'', "''");
+
+    let mut powershell = Command::new("powershell");
+    powershell.args([
+        "-NoProfile",
+        "-NonInteractive",
+        "-Command",
+        &format!(
+            "(New-Object -ComObject Shell.Application).Namespace(17).ParseName('{drive}')\
+             .InvokeVerb('Eject')"
+        ),
+    ]);
+
+    vec![powershell]
+}
+
+/// Ejecting devices is not supported on other platforms.
+#[cfg(not(any(unix, windows)))]
+fn commands(_mount_point: &Path) -> Vec<Command> {
+    Vec::new()
+}
@@ -1,18 +1,49 @@
 use crate::config::{
-    FileDialogConfig, FileDialogKeyBindings, FileDialogLabels, FileDialogStorage, FileFilter,
-    Filter, OpeningMode, QuickAccess, SaveExtension,
+    Comparator, DetailsColumn, FileDialogConfig, FileDialogKeyBindings, FileDialogLabels,
+    FileDialogStorage, FileFilter, FileSorting, Filter, KeyBinding, LeftPanelSection, OpeningMode,
+    PathFilter, PathValidator, Place, QuickAccess, RenderMode, SaveExtension, SelectionValidator,
+    SortComparator, ViewMode,
 };
 use crate::create_directory_dialog::CreateDirectoryDialog;
 use crate::data::{
-    DirectoryContent, DirectoryContentState, DirectoryEntry, Disk, Disks, UserDirectories,
+    load_filtered_entry, DirectoryContent, DirectoryContentState, DirectoryEntry, Disk, Disks,
+    UserDirectories,
 };
+use crate::directory_item_counts::{DirectoryItemCounts, ItemCount};
+use crate::directory_sizes::{DirectorySize, DirectorySizes};
+use crate::file_preview::FilePreview;
+use crate::file_system::paths_equal;
+use crate::glob::Glob;
+use crate::go_to_dialog::GoToDialog;
+use crate::location_availability::LocationAvailability;
 use crate::modals::{FileDialogModal, ModalAction, ModalState, OverwriteFileModal};
-use crate::{FileSystem, NativeFileSystem};
+use crate::ui::ui_error_label;
+use crate::{format_size, FileSystem, NativeFileSystem, SizeUnits};
 use egui::text::{CCursor, CCursorRange};
 use egui::{Ui, UiKind};
+use std::any::Any;
+use std::collections::HashMap;
 use std::fmt::Debug;
-use std::path::{Path, PathBuf};
-use std::sync::Arc;
+use std::ops::{Range, RangeInclusive};
+use std::path::{Component, Path, PathBuf};
+use std::sync::{mpsc, Arc, Mutex};
+use std::time::{Duration, SystemTime};
+
+/// The operation ID used when the dialog is opened by `FileDialog::inline_picker`.
+const INLINE_PICKER_OPERATION_ID: &str = "egui_file_dialog::inline_picker";
+/// The width of the columns displaying the owner and group of the entries.
+const OWNER_COLUMN_WIDTH: f32 = 80.0;
+/// The width of the chevron used to open directories, if directory chevrons are enabled.
+const DIRECTORY_CHEVRON_WIDTH: f32 = 20.0;
+/// The range in which the user can resize the columns of the details view.
+const DETAILS_COLUMN_WIDTH_RANGE: RangeInclusive<f32> = 40.0..=400.0;
+/// The number of counters tried when suggesting a name for a file that already exists.
+const MAX_COPY_NAME_PROBES: usize = 100;
+/// The width the directory content keeps at least when the window is resized.
+const MIN_CENTRAL_PANEL_WIDTH: f32 = 120.0;
+/// The minimum width of side panels without a configured width range,
+/// the same as the default of `egui::SidePanel`.
+const MIN_SIDE_PANEL_WIDTH: f32 = 96.0;
 
 /// Represents the mode the file dialog is currently in.
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
@@ -49,6 +80,131 @@ pub enum DialogState {
     Cancelled,
 }
 
+/// The result of a finished dialog, returned by `FileDialog::take_result`.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum DialogResult {
+    /// The user selected a folder or file or specified a destination path for saving a file.
+    Selected(PathBuf),
+
+    /// The user selected multiple files and folders in `DialogMode::PickMultiple` mode.
+    SelectedMultiple(Vec<PathBuf>),
+
+    /// The user cancelled the dialog and didn't select anything.
+    Cancelled,
+}
+
+/// The response returned by `FileDialog::update`.
+///
+/// Contains the result of the dialog only in the frame in which the user finished
+/// the dialog, so it can be handled directly without checking the `DialogState`.
+#[derive(Debug, Clone, Default)]
+pub struct FileDialogResponse {
+    /// The egui response of the area the dialog was rendered in.
+    /// None if the dialog was not rendered in this frame.
+    response: Option<egui::Response>,
+    /// The state the dialog was finished with in this frame.
+    result: Option<DialogState>,
+}
+
+impl FileDialogResponse {
+    /// Returns the egui response of the area the dialog was rendered in.
+    /// Returns None if the dialog is not open and was therefore not rendered in this frame.
+    pub const fn response(&self) -> Option<&egui::Response> {
+        self.response.as_ref()
+    }
+
+    /// Returns the rect the dialog was rendered in.
+    /// Returns None if the dialog was not rendered in this frame.
+    pub fn rect(&self) -> Option<egui::Rect> {
+        self.response.as_ref().map(|r| r.rect)
+    }
+
+    /// Returns true if the dialog is still open after this frame.
+    pub const fn is_open(&self) -> bool {
+        self.response.is_some() && self.result.is_none()
+    }
+
+    /// Returns the path the user picked in this frame.
+    ///
+    /// Only returns `Some` in the frame in which the user confirmed the dialog
+    /// in the `DialogMode::PickFile`, `DialogMode::PickDirectory` or `DialogMode::SaveFile` mode.
+    pub fn selected(&self) -> Option<&Path> {
+        match &self.result {
+            Some(DialogState::Picked(path)) => Some(path.as_path()),
+            _ => None,
+        }
+    }
+
+    /// Returns the paths the user picked in this frame.
+    ///
+    /// Only returns `Some` in the frame in which the user confirmed the dialog
+    /// in the `DialogMode::PickMultiple` mode.
+    pub fn selected_multiple(&self) -> Option<Vec<&Path>> {
+        match &self.result {
+            Some(DialogState::PickedMultiple(paths)) => {
+                Some(paths.iter().map(PathBuf::as_path).collect())
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns true if the user cancelled the dialog in this frame.
+    pub fn cancelled(&self) -> bool {
+        self.result == Some(DialogState::Cancelled)
+    }
+}
+
+/// An area of the dialog that can receive keyboard focus using the
+/// `focus_next` and `focus_previous` keybindings.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum FocusTarget {
+    /// The list containing the directory content.
+    List,
+    /// A single widget like the search input or the action buttons.
+    Widget(egui::Id),
+}
+
+/// A group of entries inside the directory content that is displayed below a header,
+/// if `FileDialog::show_group_headers` is enabled.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum EntryGroup {
+    Folders,
+    Files,
+}
+
+/// A section of consecutive rows inside the directory content list.
+#[derive(Debug, PartialEq, Eq, Clone)]
+enum ContentSegment {
+    /// The ".." entry to open the parent directory.
+    ParentEntry,
+    /// The header of a group with the number of entries inside the group.
+    Header(EntryGroup, usize),
+    /// The directory entries in the given range.
+    Entries(Range<usize>),
+    /// The row at the end of the list if not all entries of the directory were loaded,
+    /// with the number of all entries.
+    Truncated(usize),
+}
+
+impl ContentSegment {
+    /// Returns the number of rows of the segment.
+    fn len(&self) -> usize {
+        match self {
+            Self::ParentEntry | Self::Header(..) | Self::Truncated(_) => 1,
+            Self::Entries(range) => range.len(),
+        }
+    }
+}
+
+/// The directory that was open before the directory that is currently loading.
+/// Restored if the user cancels loading the directory.
+#[derive(Debug)]
+struct PreviousDirectory {
+    content: DirectoryContent,
+    directory_stack: Vec<PathBuf>,
+    directory_offset: usize,
+}
+
 /// Represents a file dialog instance.
 ///
 /// The `FileDialog` instance can be used multiple times and for different actions.
@@ -68,7 +224,7 @@ pub enum DialogState {
 ///             self.file_dialog.pick_file();
 ///         }
 ///
-///         if let Some(path) = self.file_dialog.update(ctx).picked() {
+///         if let Some(path) = self.file_dialog.update(ctx, ui).selected() {
 ///             println!("Picked file: {:?}", path);
 ///         }
 ///     }
@@ -96,6 +252,9 @@ pub struct FileDialog {
     /// for which action the user has selected an item.
     /// This ID is not used internally.
     operation_id: Option<String>,
+    /// Optional data of any type that can be attached to the operation for which the dialog
+    /// was opened. Like the `operation_id`, this is not used internally.
+    user_data: Option<Box<dyn Any + Send + Sync>>,
 
     /// The currently used window ID.
     window_id: egui::Id,
@@ -106,6 +265,9 @@ pub struct FileDialog {
     /// The currently mounted system disks.
     /// These are loaded once when the dialog is created or when the `refresh()` method is called.
     system_disks: Disks,
+    /// Receiver for the user directories and system disks while they are loaded on a
+    /// separate thread.
+    devices_recv: Option<DevicesReceiver>,
 
     /// Contains the directories that the user opened. Every newly opened directory
     /// is pushed to the vector.
@@ -118,9 +280,52 @@ pub struct FileDialog {
     directory_offset: usize,
     /// The content of the currently open directory
     directory_content: DirectoryContent,
+    /// The `directory_stack` and `directory_offset` at the time the current directory
+    /// content was requested.
+    content_navigation: (Vec<PathBuf>, usize),
+    /// The directory that is restored if the user cancels loading the current
+    /// directory content.
+    previous_directory: Option<PreviousDirectory>,
+    /// If the dialog was not yet updated with the directory content that was requested last,
+    /// like with the canonicalized path of the directory.
+    directory_load_pending: bool,
+    /// If the directory content that is loading is a reload of the open directory.
+    /// The nearest existing parent is opened instead if the directory was removed.
+    reloading_directory: bool,
 
     /// The dialog that is shown when the user wants to create a new directory.
     create_directory_dialog: CreateDirectoryDialog,
+    /// The popup that is shown when the user wants to enter the path of a folder to open.
+    go_to_dialog: GoToDialog,
+    /// The preview of the selected file.
+    file_preview: FilePreview,
+    /// The cached number of items inside the displayed directories.
+    directory_item_counts: DirectoryItemCounts,
+    /// The recursive sizes of the directories inside the currently open directory.
+    directory_sizes: DirectorySizes,
+    /// Cache of whether the paths listed in the left panel exist.
+    location_availability: LocationAvailability,
+    /// If the overview of all devices is displayed instead of the directory content.
+    /// This is the case after opening the dialog with `start_at_devices` enabled,
+    /// until the first directory is loaded.
+    device_overview: bool,
+    /// If the size and date filters were specified since the dialog was last opened.
+    /// Otherwise the filters are reset when the dialog is opened.
+    entry_filters_specified: bool,
+    /// The mount point of the device the user wants to eject.
+    /// The device is ejected after the left panel was updated.
+    #[cfg(feature = "eject")]
+    eject_request: Option<PathBuf>,
+    /// The mount point of the device that is currently ejected on a separate thread and the
+    /// receiver for the result.
+    #[cfg(feature = "eject")]
+    ejecting_device: Option<(PathBuf, EjectReceiver)>,
+    /// The mount point of the device that could not be ejected and the error message.
+    #[cfg(feature = "eject")]
+    eject_error: Option<(PathBuf, String)>,
+    /// The file that could not be opened with the default application and the error message.
+    #[cfg(feature = "open_externally")]
+    open_externally_error: Option<(PathBuf, String)>,
 
     /// Whether the text edit is open for editing the current path.
     path_edit_visible: bool,
@@ -131,15 +336,31 @@ pub struct FileDialog {
     path_edit_activate: bool,
     /// If the text edit of the path should request focus in the next frame.
     path_edit_request_focus: bool,
+    /// If the user requested to open the path in the clipboard.
+    /// The pasted text is received in one of the next frames.
+    paste_path_requested: bool,
 
     /// The item that the user currently selected.
     /// Can be a directory or a folder.
     selected_item: Option<DirectoryEntry>,
+    /// If the ".." entry used to open the parent directory is currently selected.
+    parent_entry_selected: bool,
     /// Buffer for the input of the file name when the dialog is in `SaveFile` mode.
     file_name_input: String,
     /// This variables contains the error message if the `file_name_input` is invalid.
     /// This can be the case, for example, if a file or folder with the name already exists.
     file_name_input_error: Option<String>,
+    /// A variant of `file_name_input` that does not collide with an existing file.
+    /// Only set if `FileDialogConfig::suggest_copy_name` is enabled and a file with the
+    /// entered name already exists.
+    file_name_suggestion: Option<String>,
+    /// Results of the selection validator, cached per path so that the validator
+    /// is not executed in every frame.
+    selection_validation_cache: HashMap<PathBuf, Result<(), String>>,
+    /// The error returned by the confirm validator when the user last tried to
+    /// submit the dialog, or by the selection validator when the user last tried to
+    /// submit a path entered in the path edit.
+    confirm_validation_error: Option<String>,
     /// If the file name input text field should request focus in the next frame.
     file_name_input_request_focus: bool,
     /// The file filter the user selected.
@@ -149,15 +370,64 @@ pub struct FileDialog {
 
     /// If we should scroll to the item selected by the user in the next frame.
     scroll_to_selection: bool,
+    /// The position of the selected item in the directory content before the directory was
+    /// reloaded. Once the reloaded content is available, the dialog only scrolls to the
+    /// selection if the item still exists and moved to a different position.
+    reload_selection_index: Option<usize>,
+    /// The number of rows that fit inside the visible area of the central panel.
+    /// Used to move the selection by one page.
+    rows_per_page: usize,
+    /// The groups of the directory content the user collapsed.
+    collapsed_groups: Vec<EntryGroup>,
     /// Buffer containing the value of the search input.
     search_value: String,
     /// If the search should be initialized in the next frame.
     init_search: bool,
 
+    /// The IDs of the widgets the user can cycle through using the focus keybindings.
+    /// These are collected every frame while rendering the UI.
+    search_input_id: Option<egui::Id>,
+    file_name_input_id: Option<egui::Id>,
+    submit_button_id: Option<egui::Id>,
+    cancel_button_id: Option<egui::Id>,
+    /// If the directory list currently has the keyboard focus.
+    list_focused: bool,
+    /// The area that should receive the keyboard focus at the end of the frame.
+    pending_focus: Option<FocusTarget>,
+
+    /// Custom widgets rendered inside the bottom panel.
+    custom_bottom_ui: Option<Callback<CustomBottomUi>>,
+    /// Custom panel rendered on the right side of the dialog.
+    custom_right_panel: Option<Callback<CustomRightPanelUi>>,
+    /// Called every time the user navigates to a different directory.
+    on_directory_changed: Option<Callback<DirectoryChangedCallback>>,
+    /// The directory `on_directory_changed` is called with once its content finished loading.
+    pending_directory_change: Option<PathBuf>,
+    /// Called every time the user created a new directory.
+    on_directory_created: Option<Callback<DirectoryCreatedCallback>>,
+
     /// If any widget was focused in the last frame.
     /// This is used to prevent the dialog from closing when pressing the escape key
     /// inside a text input.
     any_focused_last_frame: bool,
+    /// The area the dialog was rendered in during the last frame.
+    /// Used to only react to the extra mouse buttons while the dialog is hovered.
+    rect_last_frame: Option<egui::Rect>,
+    /// The directory in which all entries should be loaded, even if it contains more
+    /// than the configured maximum number of entries.
+    load_all_entries_in: Option<PathBuf>,
+    /// If the window should be centered inside the viewport the next time it is shown.
+    /// Set when the dialog is opened.
+    center_window: bool,
+    /// Notice displayed above the directory content, for example if the open directory
+    /// was removed and the dialog moved up to its parent directory.
+    directory_notice: Option<String>,
+    /// The sections of the left panel that are currently collapsed by the user.
+    collapsed_sections: Vec<LeftPanelSection>,
+
+    /// The path last picked using the inline picker.
+    /// This is kept when the dialog is opened again.
+    inline_picker_path: Option<PathBuf>,
 }
 
 /// This tests if file dialog is send and sync.
@@ -188,6 +458,55 @@ impl Debug for dyn FileDialogModal + Send + Sync {
 /// inside the closure.
 type FileDialogUiCallback<'a> = dyn FnMut(&mut egui::Ui, &mut FileDialog) + 'a;
 
+/// Callback type to render custom widgets inside the bottom panel of the file dialog.
+/// See `FileDialog::custom_bottom_ui` for more information.
+pub type CustomBottomUi = Box<dyn FnMut(&mut egui::Ui, &CustomUiContext) + Send + Sync>;
+
+/// Callback type to render a custom right panel inside the file dialog.
+/// The callback receives the path of the currently selected item.
+/// See `FileDialog::custom_right_panel` for more information.
+pub type CustomRightPanelUi = Box<dyn FnMut(&mut egui::Ui, Option<&Path>) + Send + Sync>;
+
+/// Read-only information about the current state of the file dialog
+/// that is passed to custom UI callbacks.
+#[derive(Debug)]
+pub struct CustomUiContext<'a> {
+    /// The mode the dialog is currently in.
+    pub mode: DialogMode,
+    /// The directory that is currently open.
+    pub current_directory: Option<&'a Path>,
+    /// The currently selected entry. See `FileDialog::selected_entry`.
+    pub selected_entry: Option<&'a DirectoryEntry>,
+    /// The entries selected in `DialogMode::PickMultiple` mode.
+    /// See `FileDialog::selected_entries`.
+    pub selected_entries: Vec<&'a DirectoryEntry>,
+}
+
+/// Callback type that is called with the new directory every time the user
+/// navigates to a different directory.
+/// See `FileDialog::on_directory_changed` for more information.
+pub type DirectoryChangedCallback = Box<dyn FnMut(&Path) + Send + Sync>;
+
+/// Callback type that is called with the path of every directory the user created.
+/// See `FileDialog::on_directory_created` for more information.
+pub type DirectoryCreatedCallback = Box<dyn FnMut(&Path) + Send + Sync>;
+
+/// Receiver for the user directories and system disks loaded on a separate thread.
+type DevicesReceiver = Mutex<mpsc::Receiver<(Option<UserDirectories>, Disks)>>;
+
+/// Receiver for the result of ejecting a device on a separate thread.
+#[cfg(feature = "eject")]
+type EjectReceiver = Mutex<mpsc::Receiver<std::io::Result<()>>>;
+
+/// Wrapper around a user callback, so that the file dialog can still implement `Debug`.
+struct Callback<T>(T);
+
+impl<T> Debug for Callback<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "<Callback>")
+    }
+}
+
 impl FileDialog {
     // ------------------------------------------------------------------------
     // Creation:
@@ -203,35 +522,84 @@ impl FileDialog {
             state: DialogState::Closed,
             show_files: true,
             operation_id: None,
+            user_data: None,
 
             window_id: egui::Id::new("file_dialog"),
 
             user_directories: None,
             system_disks: Disks::new_empty(),
+            devices_recv: None,
 
             directory_stack: Vec::new(),
             directory_offset: 0,
             directory_content: DirectoryContent::default(),
+            content_navigation: (Vec::new(), 0),
+            previous_directory: None,
+            directory_load_pending: false,
+            reloading_directory: false,
 
             create_directory_dialog: CreateDirectoryDialog::from_filesystem(file_system.clone()),
+            go_to_dialog: GoToDialog::default(),
+            file_preview: FilePreview::default(),
+            directory_item_counts: DirectoryItemCounts::default(),
+            directory_sizes: DirectorySizes::default(),
+            location_availability: LocationAvailability::default(),
+            device_overview: false,
+            entry_filters_specified: false,
+            #[cfg(feature = "eject")]
+            eject_request: None,
+            #[cfg(feature = "eject")]
+            ejecting_device: None,
+            #[cfg(feature = "eject")]
+            eject_error: None,
+            #[cfg(feature = "open_externally")]
+            open_externally_error: None,
 
             path_edit_visible: false,
+            paste_path_requested: false,
             path_edit_value: String::new(),
             path_edit_activate: false,
             path_edit_request_focus: false,
 
             selected_item: None,
+            parent_entry_selected: false,
             file_name_input: String::new(),
             file_name_input_error: None,
+            file_name_suggestion: None,
+            selection_validation_cache: HashMap::new(),
+            confirm_validation_error: None,
             file_name_input_request_focus: true,
             selected_file_filter: None,
             selected_save_extension: None,
 
             scroll_to_selection: false,
+            reload_selection_index: None,
+            rows_per_page: 1,
+            collapsed_groups: Vec::new(),
             search_value: String::new(),
             init_search: false,
 
+            search_input_id: None,
+            file_name_input_id: None,
+            submit_button_id: None,
+            cancel_button_id: None,
+            list_focused: false,
+            pending_focus: None,
+
+            custom_bottom_ui: None,
+            custom_right_panel: None,
+            on_directory_changed: None,
+            pending_directory_change: None,
+            on_directory_created: None,
+
             any_focused_last_frame: false,
+            rect_last_frame: None,
+            load_all_entries_in: None,
+            center_window: false,
+            directory_notice: None,
+            collapsed_sections: Vec::new(),
+
+            inline_picker_path: None,
 
             config: FileDialogConfig::default_from_filesystem(file_system),
         }
@@ -249,10 +617,29 @@ impl FileDialog {
     pub fn with_file_system(file_system: Arc<dyn FileSystem + Send + Sync>) -> Self {
         let mut obj = Self::new();
         obj.config.initial_directory = file_system.current_dir().unwrap_or_default();
+        obj.create_directory_dialog = CreateDirectoryDialog::from_filesystem(file_system.clone());
         obj.config.file_system = file_system;
         obj
     }
 
+    /// Allows the user to browse zip archives like directories.
+    ///
+    /// The currently used file system is wrapped in an `ArchiveFileSystem`, so this should be
+    /// called after `FileDialog::with_file_system`. Archives are displayed as folders and
+    /// can therefore not be picked as files. Entries inside an archive are returned as
+    /// virtual paths like `/home/user/assets.zip/textures/grass.png`. See `ArchiveFileSystem`
+    /// for more information.
+    ///
+    /// Requires the `zip` feature.
+    #[cfg(feature = "zip")]
+    #[must_use]
+    pub fn browse_archives(mut self) -> Self {
+        self.config.file_system = Arc::new(crate::ArchiveFileSystem::new(
+            self.config.file_system.clone(),
+        ));
+        self
+    }
+
     // -------------------------------------------------
     // Open, Update:
 
@@ -301,7 +688,7 @@ impl FileDialog {
     ///             let _ = self.file_dialog.open(DialogMode::PickFile, true, Some("pick_b"));
     ///         }
     ///
-    ///         self.file_dialog.update(ctx);
+    ///         self.file_dialog.update(ctx, ui);
     ///
     ///         if let Some(path) = self.file_dialog.picked() {
     ///             if self.file_dialog.operation_id() == Some("pick_a") {
@@ -315,6 +702,13 @@ impl FileDialog {
     /// }
     /// ```
     pub fn open(&mut self, mode: DialogMode, mut show_files: bool, operation_id: Option<&str>) {
+        // The size and date filters only apply until the dialog is opened again
+        if !self.entry_filters_specified {
+            self.config.min_file_size = None;
+            self.config.max_file_size = None;
+            self.config.modified_after = None;
+        }
+
         self.reset();
         self.refresh();
 
@@ -336,15 +730,21 @@ impl FileDialog {
 
         self.mode = mode;
         self.state = DialogState::Open;
+        self.center_window = true;
         self.show_files = show_files;
         self.operation_id = operation_id.map(String::from);
+        self.user_data = None;
 
         self.window_id = self
             .config
             .id
             .map_or_else(|| egui::Id::new(self.get_window_title()), |id| id);
 
-        self.load_directory(&self.get_initial_directory());
+        if self.config.start_at_devices {
+            self.device_overview = true;
+        } else {
+            self.load_directory(&self.get_initial_directory());
+        }
     }
 
     /// Shortcut function to open the file dialog to prompt the user to pick a directory.
@@ -389,218 +789,504 @@ impl FileDialog {
     /// The main update method that should be called every frame if the dialog is to be visible.
     ///
     /// This function has no effect if the dialog state is currently not `DialogState::Open`.
-    pub fn update(&mut self, ctx: &egui::Context, ui: &mut Ui) -> &Self {
+    ///
+    /// The returned response contains the result of the dialog only in the frame
+    /// in which the user picked something or cancelled the dialog.
+    pub fn update(&mut self, ctx: &egui::Context, ui: &mut Ui) -> FileDialogResponse {
         if self.state != DialogState::Open {
-            return self;
+            return FileDialogResponse::default();
+        }
+
+        if self.config.as_modal {
+            self.ui_update_modal_overlay(ctx, ui.layer_id());
+
+            // The user cancelled the dialog by clicking the overlay
+            if self.state != DialogState::Open {
+                return FileDialogResponse {
+                    response: None,
+                    result: Some(self.state.clone()),
+                };
+            }
         }
 
+        self.update_paste_path(ctx);
         self.update_keybindings(ctx);
-        self.update_ui(None, ctx, ui);
+        self.update_devices(ctx);
+        #[cfg(feature = "eject")]
+        self.update_ejecting_device(ctx);
+        if self.location_availability.update() {
+            ctx.request_repaint();
+        }
+        // The custom right panel is rendered using the same panel as any other right panel UI
+        let mut custom_right_panel = self.custom_right_panel.take();
+        let mut right_panel_fn = custom_right_panel.as_mut().map(|custom_ui| {
+            move |ui: &mut egui::Ui, dialog: &mut Self| {
+                (custom_ui.0)(
+                    ui,
+                    dialog.selected_item.as_ref().map(DirectoryEntry::as_path),
+                );
+            }
+        });
 
-        self
-    }
+        let response = ui
+            .scope(|ui| {
+                let right_panel_fn = right_panel_fn
+                    .as_mut()
+                    .map(|f| f as &mut FileDialogUiCallback);
+                self.update_ui(right_panel_fn, ctx, ui)
+            })
+            .response;
+        self.custom_right_panel = custom_right_panel;
+        self.update_loaded_directory();
+        self.update_focus(ctx);
 
-    /// Sets the width of the right panel.
-    pub fn set_right_panel_width(&mut self, width: f32) {
-        self.config.right_panel_width = Some(width);
+        // Used to prevent the dialog from closing when pressing escape inside a text input.
+        self.any_focused_last_frame = ctx.memory(egui::Memory::focused).is_some();
+        self.rect_last_frame = Some(response.rect);
+
+        FileDialogResponse {
+            response: Some(response),
+            result: (self.state != DialogState::Open).then(|| self.state.clone()),
+        }
     }
 
-    /// Clears the width of the right panel by setting it to None.
-    pub fn clear_right_panel_width(&mut self) {
-        self.config.right_panel_width = None;
+    /// Updates the dialog and renders it depending on the configured `RenderMode`:
+    ///   - `RenderMode::Window` renders the dialog inside the window created by
+    ///     `FileDialog::window`. When the dialog is opened, the window is centered inside
+    ///     the viewport, unless a position or anchor is set.
+    ///   - `RenderMode::Viewport` renders the dialog inside the root `egui::CentralPanel` of
+    ///     its own viewport. Closing the viewport cancels the dialog.
+    ///   - `RenderMode::InlineUi` renders the dialog directly into the given `ui`.
+    ///
+    /// Like `FileDialog::update`, this should be called every frame if the dialog is to be
+    /// visible and has no effect if the dialog is not open.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use egui_file_dialog::{FileDialog, RenderMode};
+    ///
+    /// fn update(ui: &mut egui::Ui, file_dialog: &mut FileDialog) {
+    ///     if let Some(path) = file_dialog.show(ui).selected() {
+    ///         println!("Picked file: {path:?}");
+    ///     }
+    /// }
+    ///
+    /// let file_dialog = FileDialog::new().render_mode(RenderMode::Viewport);
+    /// ```
+    pub fn show(&mut self, ui: &mut Ui) -> FileDialogResponse {
+        if self.state != DialogState::Open {
+            return FileDialogResponse::default();
+        }
+
+        let ctx = ui.ctx().clone();
+
+        match self.config.render_mode {
+            RenderMode::Window => self.show_window(&ctx),
+            RenderMode::Viewport => self.show_viewport(&ctx),
+            RenderMode::InlineUi => self.update(&ctx, ui),
+        }
     }
 
-    // -------------------------------------------------
-    // Setter:
+    /// Renders a single line form field that displays the path last picked by the user and
+    /// a button to open the dialog in the given mode.
+    ///
+    /// The dialog is rendered using `FileDialog::show` while it was opened by the inline
+    /// picker, so neither `FileDialog::update` nor `FileDialog::show` must be called
+    /// additionally. The dialog can still be opened for other operations, the results of
+    /// these are not consumed by the inline picker.
+    ///
+    /// Returns the picked path only in the frame in which the user picked it.
+    /// In `DialogMode::PickMultiple` mode, the first of the picked paths is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use egui_file_dialog::{DialogMode, FileDialog};
+    ///
+    /// fn update(ui: &mut egui::Ui, file_dialog: &mut FileDialog) {
+    ///     if let Some(path) = file_dialog.inline_picker(ui, DialogMode::PickFile) {
+    ///         println!("Picked file: {path:?}");
+    ///     }
+    /// }
+    /// ```
+    pub fn inline_picker(&mut self, ui: &mut Ui, mode: DialogMode) -> Option<PathBuf> {
+        ui.horizontal(|ui| {
+            if ui
+                .button(&self.config.labels.inline_picker_browse)
+                .clicked()
+            {
+                self.open(
+                    mode,
+                    mode != DialogMode::PickDirectory,
+                    Some(INLINE_PICKER_OPERATION_ID),
+                );
+            }
 
-    /// Mutably borrow internal `config`.
-    pub fn config_mut(&mut self) -> &mut FileDialogConfig {
-        &mut self.config
+            match &self.inline_picker_path {
+                Some(path) => ui.add(egui::Label::new(path.display().to_string()).truncate()),
+                None => ui.weak(&self.config.labels.inline_picker_empty),
+            };
+        });
+
+        // Dialogs opened for other operations are handled by the user
+        if self.operation_id() != Some(INLINE_PICKER_OPERATION_ID) {
+            return None;
+        }
+
+        self.show(ui);
+
+        let path = match self.take_result()? {
+            DialogResult::Selected(path) => path,
+            DialogResult::SelectedMultiple(paths) => paths.into_iter().next()?,
+            DialogResult::Cancelled => return None,
+        };
+
+        self.inline_picker_path = Some(path.clone());
+        Some(path)
     }
 
-    /// Sets the storage used by the file dialog.
-    /// Storage includes all data that is persistently stored between multiple
-    /// file dialog instances.
-    pub fn storage(mut self, storage: FileDialogStorage) -> Self {
-        self.config.storage = storage;
-        self
+    /// Updates the dialog inside the window created by `FileDialog::window`.
+    fn show_window(&mut self, ctx: &egui::Context) -> FileDialogResponse {
+        let mut response = FileDialogResponse::default();
+        let mut window = self.window();
+
+        // Otherwise egui restores the position the window had the last time it was open,
+        // which might be outside of the viewport by now.
+        // The position is only set for one frame, so the user can still move the window.
+        if std::mem::take(&mut self.center_window)
+            && self.config.default_pos.is_none()
+            && self.config.fixed_pos.is_none()
+            && self.config.anchor.is_none()
+        {
+            window = window.current_pos(self.centered_window_pos(ctx));
+        }
+
+        window.show(ctx, |ui| response = self.update(ctx, ui));
+
+        response
     }
 
-    /// Mutably borrow internal storage.
-    pub fn storage_mut(&mut self) -> &mut FileDialogStorage {
-        &mut self.config.storage
+    /// Gets the position of the top left corner of the window so that the window
+    /// is centered inside the screen rect of the viewport.
+    /// Uses the size the window had the last time it was shown, or the default size.
+    fn centered_window_pos(&self, ctx: &egui::Context) -> egui::Pos2 {
+        let screen_rect = ctx.screen_rect();
+        let size = ctx
+            .memory(|mem| mem.area_rect(self.window_id))
+            .map_or(self.config.default_size, |rect| rect.size())
+            .min(screen_rect.size());
+
+        (screen_rect.center() - size / 2.0).max(screen_rect.min)
     }
 
-    /// Sets the keybindings used by the file dialog.
-    pub fn keybindings(mut self, keybindings: FileDialogKeyBindings) -> Self {
-        self.config.keybindings = keybindings;
-        self
+    /// Updates the dialog inside its own immediate viewport.
+    /// Falls back to a window if the backend does not support multiple viewports.
+    fn show_viewport(&mut self, ctx: &egui::Context) -> FileDialogResponse {
+        let mut builder = egui::ViewportBuilder::default()
+            .with_title(self.get_window_title())
+            .with_inner_size(self.config.default_size)
+            .with_min_inner_size(self.config.min_size)
+            .with_resizable(self.config.resizable);
+
+        if let Some(max_size) = self.config.max_size {
+            builder = builder.with_max_inner_size(max_size);
+        }
+
+        let viewport_id = egui::ViewportId(self.window_id.with("viewport"));
+
+        ctx.show_viewport_immediate(viewport_id, builder, |ctx, class| {
+            if class == egui::ViewportClass::Embedded {
+                return self.show_window(ctx);
+            }
+
+            let mut response = egui::CentralPanel::default()
+                .show(ctx, |ui| self.update(ctx, ui))
+                .inner;
+
+            if self.state == DialogState::Open && ctx.input(|i| i.viewport().close_requested()) {
+                self.cancel();
+                response.result = Some(self.state.clone());
+            }
+
+            response
+        })
     }
 
-    /// Sets the labels the file dialog uses.
+    /// Creates an `egui::Window` using the window options of the dialog, like the title,
+    /// size, `resizable` and `auto_sized`, in which the dialog can be rendered.
     ///
-    /// Used to enable multiple language support.
+    /// Unlike `FileDialog::show`, the returned window is not centered inside the viewport
+    /// when the dialog is opened.
     ///
-    /// See `FileDialogLabels` for more information.
-    pub fn labels(mut self, labels: FileDialogLabels) -> Self {
-        self.config.labels = labels;
-        self
-    }
+    /// # Examples
+    ///
+    /// ```
+    /// use egui_file_dialog::FileDialog;
+    ///
+    /// fn update(ctx: &egui::Context, file_dialog: &mut FileDialog) {
+    ///     file_dialog
+    ///         .window()
+    ///         .show(ctx, |ui| file_dialog.update(ctx, ui));
+    /// }
+    /// ```
+    pub fn window(&self) -> egui::Window<'static> {
+        let min_size = self.window_min_size();
+
+        let mut window = egui::Window::new(self.get_window_title().clone())
+            .id(self.window_id)
+            .default_size(self.config.default_size)
+            .min_size(min_size)
+            .resizable(self.config.resizable)
+            .movable(self.config.movable)
+            .title_bar(self.config.title_bar)
+            .collapsible(self.config.collapsible);
+
+        if let Some(max_size) = self.config.max_size {
+            window = window.max_size(max_size.max(min_size));
+        }
 
-    /// Mutably borrow internal `config.labels`.
-    pub fn labels_mut(&mut self) -> &mut FileDialogLabels {
-        &mut self.config.labels
+        if self.config.auto_sized {
+            window = window.auto_sized();
+        } else if !self.config.resizable {
+            // Otherwise the window would grow with its content
+            window = window.fixed_size(self.config.default_size.max(min_size));
+        }
+
+        if let Some(pos) = self.config.default_pos {
+            window = window.default_pos(pos);
+        }
+
+        if let Some(pos) = self.config.fixed_pos {
+            window = window.fixed_pos(pos);
+        }
+
+        if let Some((align, offset)) = self.config.anchor {
+            window = window.anchor(align, offset);
+        }
+
+        window
     }
 
-    /// Sets which directory is loaded when opening the file dialog.
-    pub const fn opening_mode(mut self, opening_mode: OpeningMode) -> Self {
-        self.config.opening_mode = opening_mode;
-        self
+    /// Gets the minimum size of the window. The configured minimum size is increased
+    /// so that the visible side panels fit next to the directory content, even if the
+    /// window is resized to its smallest size.
+    fn window_min_size(&self) -> egui::Vec2 {
+        let [left, right, preview] = self.side_panel_min_widths(self.custom_right_panel.is_some());
+        let width = MIN_CENTRAL_PANEL_WIDTH + left + right + preview;
+
+        self.config.min_size.max(egui::vec2(width, 0.0))
     }
 
-    /// If the file dialog window should be displayed as a modal.
-    ///
-    /// If the window is displayed as modal, the area outside the dialog can no longer be
-    /// interacted with and an overlay is displayed.
-    pub const fn as_modal(mut self, as_modal: bool) -> Self {
-        self.config.as_modal = as_modal;
-        self
+    /// Sets the width of the right panel.
+    pub fn set_right_panel_width(&mut self, width: f32) {
+        self.config.right_panel_width = Some(width);
     }
 
-    /// Sets the color of the overlay when the dialog is displayed as a modal window.
-    pub const fn modal_overlay_color(mut self, modal_overlay_color: egui::Color32) -> Self {
-        self.config.modal_overlay_color = modal_overlay_color;
-        self
+    /// Clears the width of the right panel by setting it to None.
+    pub fn clear_right_panel_width(&mut self) {
+        self.config.right_panel_width = None;
     }
 
-    /// Sets the first loaded directory when the dialog opens.
-    /// If the path is a file, the file's parent directory is used. If the path then has no
-    /// parent directory or cannot be loaded, the user will receive an error.
-    /// However, the user directories and system disk allow the user to still select a file in
-    /// the event of an error.
+    /// Sets if the entries inside the directory view should be sorted in descending order.
     ///
-    /// Since `fs::canonicalize` is used, both absolute paths and relative paths are allowed.
-    /// See `FileDialog::canonicalize_paths` for more information.
-    pub fn initial_directory(mut self, directory: PathBuf) -> Self {
-        self.config.initial_directory = directory;
-        self
-    }
+    /// The currently loaded directory content is sorted again without reading the directory
+    /// and the selection is kept. The sort order is part of the `FileDialogStorage`, so
+    /// it can be persisted together with the other storage values.
+    pub fn set_sort_descending(&mut self, descending: bool) {
+        if self.config.storage.sort_descending == descending {
+            return;
+        }
 
-    /// Sets the default file name when opening the dialog in `DialogMode::SaveFile` mode.
-    pub fn default_file_name(mut self, name: &str) -> Self {
-        name.clone_into(&mut self.config.default_file_name);
-        self
+        self.config.storage.sort_descending = descending;
+        self.directory_content.sort(&self.config);
+        self.scroll_to_selection = true;
     }
 
-    /// Sets if the user is allowed to select an already existing file when the dialog is in
-    /// `DialogMode::SaveFile` mode.
+    /// Sets the property by which the entries inside the directory content are sorted.
+    /// This is also changed when the user clicks a column header of the details view.
     ///
-    /// If this is enabled, the user will receive a modal asking whether the user really
-    /// wants to overwrite an existing file.
-    pub const fn allow_file_overwrite(mut self, allow_file_overwrite: bool) -> Self {
-        self.config.allow_file_overwrite = allow_file_overwrite;
-        self
+    /// The currently loaded directory content is sorted again without reading the directory
+    /// and the selection is kept.
+    pub fn set_file_sorting(&mut self, sorting: FileSorting) {
+        if self.config.file_sorting == sorting {
+            return;
+        }
+
+        self.config.file_sorting = sorting;
+        self.directory_content.sort(&self.config);
+        self.scroll_to_selection = true;
     }
 
-    /// Sets if the path edit is allowed to select the path as the file to save
-    /// if it does not have an extension.
+    /// Sets a custom comparator that is used to sort the directory content instead of
+    /// sorting the entries by the property set using `FileDialog::set_file_sorting`.
     ///
-    /// This can lead to confusion if the user wants to open a directory with the path edit,
-    /// types it incorrectly and the dialog tries to select the incorrectly typed folder as
-    /// the file to be saved.
+    /// If `FileDialog::folders_first` is enabled, the folders are still listed before the
+    /// files and the comparator only decides the order within the folders and within the
+    /// files. The order returned by the comparator is reversed if the sort order is set to
+    /// descending using `FileDialog::set_sort_descending` or the sort order button.
     ///
-    /// This only affects the `DialogMode::SaveFile` mode.
-    pub const fn allow_path_edit_to_save_file_without_extension(mut self, allow: bool) -> Self {
-        self.config.allow_path_edit_to_save_file_without_extension = allow;
-        self
+    /// The currently loaded directory content is sorted again without reading the directory.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    /// use egui_file_dialog::FileDialog;
+    ///
+    /// // Sort by file extension
+    /// let mut dialog = FileDialog::new();
+    /// dialog.set_sort_comparator(Arc::new(|a, b| a.extension().cmp(&b.extension())));
+    /// ```
+    pub fn set_sort_comparator(&mut self, comparator: Comparator) {
+        self.config.sort_comparator = Some(SortComparator { comparator });
+        self.directory_content.sort(&self.config);
+        self.scroll_to_selection = true;
     }
 
-    /// Sets the separator of the directories when displaying a path.
-    /// Currently only used when the current path is displayed in the top panel.
-    pub fn directory_separator(mut self, separator: &str) -> Self {
-        self.config.directory_separator = separator.to_string();
-        self
+    /// Removes the comparator set using `FileDialog::set_sort_comparator`
+    /// and sorts the directory content by name again.
+    pub fn clear_sort_comparator(&mut self) {
+        self.config.sort_comparator = None;
+        self.directory_content.sort(&self.config);
+        self.scroll_to_selection = true;
     }
 
-    /// Sets if the paths in the file dialog should be canonicalized before use.
+    /// Selects the item with the given path and scrolls the list to it in the next frame.
     ///
-    /// By default, all paths are canonicalized. This has the advantage that the paths are
-    /// all brought to a standard and are therefore compatible with each other.
-    ///
-    /// On Windows, however, this results in the namespace prefix `\\?\` being set in
-    /// front of the path, which may not be compatible with other applications.
-    /// In addition, canonicalizing converts all relative paths to absolute ones.
+    /// Only items that are currently visible in the open directory can be selected,
+    /// so the item must not be hidden by the search or a file filter.
+    /// Returns false if no such item exists.
+    pub fn scroll_to(&mut self, path: &Path) -> bool {
+        self.directory_content.reset_multi_selection();
+
+        let mut directory_content = std::mem::take(&mut self.directory_content);
+        let search_value = std::mem::take(&mut self.search_value);
+
+        let found = if let Some(item) = directory_content
+            .filtered_iter_mut(&search_value)
+            .find(|p| paths_equal(p.as_path(), path))
+        {
+            self.select_item(item);
+            self.scroll_to_selection = true;
+            true
+        } else {
+            false
+        };
+
+        self.directory_content = directory_content;
+        self.search_value = search_value;
+
+        found
+    }
+
+    /// Sets the history of opened directories used by the back and forward buttons,
+    /// for example to restore the history of a previous session.
     ///
-    /// See: [Rust docs](https://doc.rust-lang.org/std/fs/fn.canonicalize.html)
-    /// for more information.
+    /// `position` is the index of the currently open directory inside `history`.
+    /// Directories that no longer exist are removed from the history. If the directory at
+    /// `position` was removed, the last directory of the history is opened instead.
     ///
-    /// In general, it is only recommended to disable canonicalization if
-    /// you know what you are doing and have a reason for it.
-    /// Disabling canonicalization can lead to unexpected behavior, for example if an
-    /// already canonicalized path is then set as the initial directory.
-    pub const fn canonicalize_paths(mut self, canonicalize: bool) -> Self {
-        self.config.canonicalize_paths = canonicalize;
-        self
+    /// The history is cleared when the dialog is opened,
+    /// unless `FileDialog::preserve_history` is enabled.
+    pub fn set_history(&mut self, history: Vec<PathBuf>, position: usize) {
+        let current = history.get(position).cloned();
+
+        let history: Vec<PathBuf> = history
+            .into_iter()
+            .filter(|p| self.config.file_system.is_dir(p))
+            .collect();
+
+        let last = history.len().saturating_sub(1);
+        let position = current
+            .and_then(|c| history.iter().position(|p| *p == c))
+            .unwrap_or(last);
+
+        self.directory_stack = history;
+        self.directory_offset = last - position;
+
+        if self.state == DialogState::Open {
+            self.reload_directory();
+        }
     }
 
-    /// If the directory content should be loaded via a separate thread.
-    /// This prevents the application from blocking when loading large directories
-    /// or from slow hard drives.
-    pub const fn load_via_thread(mut self, load_via_thread: bool) -> Self {
-        self.config.load_via_thread = load_via_thread;
-        self
+    /// Sets the value of the search input that filters the content of the open directory.
+    /// The first entry matching the search value is selected.
+    ///
+    /// The search value is cleared when the dialog is opened or another directory is loaded,
+    /// so this must be called after the dialog has been opened.
+    pub fn set_search_value(&mut self, search_value: &str) {
+        search_value.clone_into(&mut self.search_value);
+
+        self.selected_item = None;
+        self.select_first_visible_item();
     }
 
-    /// Sets if long filenames should be truncated in the middle.
-    /// The extension, if available, will be preserved.
+    /// Sets a glob pattern that the files must match to be displayed,
+    /// for example `*.tar.{gz,zst}` or `IMG_????.jpg`.
     ///
-    /// Warning! If this is disabled, the scroll-to-selection might not work correctly and have
-    /// an offset for large directories.
-    pub const fn truncate_filenames(mut self, truncate_filenames: bool) -> Self {
-        self.config.truncate_filenames = truncate_filenames;
-        self
+    /// Supported are `*`, `?`, character classes like `[a-z]` or `[!0-9]` and alternatives
+    /// like `{gz,zst}`. The pattern is matched against the file name, case-insensitively
+    /// on Windows. It is applied in addition to the search value entered by the user and
+    /// does not affect directories.
+    ///
+    /// In contrast to the search value, the pattern is kept when the dialog is opened again.
+    pub fn set_glob_filter(&mut self, pattern: &str) {
+        self.config.glob_filter = Some(pattern.to_string());
+        self.directory_content
+            .set_glob_filter(Some(Glob::new(pattern)));
     }
 
-    /// Sets the icon that is used to display errors.
-    pub fn err_icon(mut self, icon: &str) -> Self {
-        self.config.err_icon = icon.to_string();
-        self
+    /// Removes the glob pattern set using `FileDialog::set_glob_filter`.
+    pub fn clear_glob_filter(&mut self) {
+        self.config.glob_filter = None;
+        self.directory_content.set_glob_filter(None);
     }
 
-    /// Sets the default icon that is used to display files.
-    pub fn default_file_icon(mut self, icon: &str) -> Self {
-        self.config.default_file_icon = icon.to_string();
-        self
+    /// Returns the glob pattern set using `FileDialog::set_glob_filter`.
+    pub fn glob_filter(&self) -> Option<&str> {
+        self.config.glob_filter.as_deref()
     }
 
-    /// Sets the default icon that is used to display folders.
-    pub fn default_folder_icon(mut self, icon: &str) -> Self {
-        self.config.default_folder_icon = icon.to_string();
-        self
+    /// Returns the name of the file filter that is currently selected, for example by the
+    /// user using the drop-down menu at the bottom of the dialog.
+    /// Returns None if all files are displayed.
+    pub fn active_filter(&self) -> Option<&str> {
+        self.get_selected_file_filter().map(|f| f.name.as_str())
     }
 
-    /// Sets the icon that is used to display devices in the left panel.
-    pub fn device_icon(mut self, icon: &str) -> Self {
-        self.config.device_icon = icon.to_string();
-        self
-    }
+    /// Selects the file filter with the given name, as added using
+    /// `FileDialog::add_file_filter` or `FileDialog::file_filters`.
+    /// This can be used to control the filter from elsewhere in the application,
+    /// for example to sync it with a file type selection.
+    ///
+    /// The currently open directory is filtered again immediately.
+    /// Returns false and does not change the selected filter if no filter with the
+    /// given name exists.
+    pub fn set_active_filter(&mut self, name: &str) -> bool {
+        let Some(filter) = self
+            .config
+            .file_filters
+            .iter()
+            .find(|f| f.name == name)
+            .cloned()
+        else {
+            return false;
+        };
 
-    /// Sets the icon that is used to display removable devices in the left panel.
-    pub fn removable_device_icon(mut self, icon: &str) -> Self {
-        self.config.removable_device_icon = icon.to_string();
-        self
+        self.select_file_filter(Some(filter));
+        true
     }
 
-    /// Adds a new file filter the user can select from a dropdown widget.
+    /// Sets a custom filter that decides which files are displayed, for example only files
+    /// that can be parsed by the application.
     ///
-    /// NOTE: The name must be unique. If a filter with the same name already exists,
-    ///       it will be overwritten.
-    ///
-    /// # Arguments
+    /// The filter receives the full path of the file and is applied in addition to the
+    /// other filters, including the predicate set using `FileDialog::entry_predicate`. It is evaluated once per file when a directory is loaded, not every
+    /// frame, so the currently open directory is reloaded. Directories are not affected by
+    /// the filter so that the user can still navigate.
     ///
-    /// * `name` - Display name of the filter
-    /// * `filter` - Sets a filter function that checks whether a given
-    ///   Path matches the criteria for this filter.
+    /// In contrast to the search value, the filter is kept when the dialog is opened again.
     ///
     /// # Examples
     ///
@@ -608,425 +1294,1698 @@ impl FileDialog {
     /// use std::sync::Arc;
     /// use egui_file_dialog::FileDialog;
     ///
-    /// FileDialog::new()
-    ///     .add_file_filter(
-    ///         "PNG files",
-    ///         Arc::new(|path| path.extension().unwrap_or_default() == "png"))
-    ///     .add_file_filter(
-    ///         "JPG files",
-    ///         Arc::new(|path| path.extension().unwrap_or_default() == "jpg"));
+    /// let mut dialog = FileDialog::new();
+    /// dialog.set_filter(Arc::new(|path| path.with_extension("lock").exists()));
     /// ```
-    pub fn add_file_filter(mut self, name: &str, filter: Filter<Path>) -> Self {
-        self.config = self.config.add_file_filter(name, filter);
-        self
+    pub fn set_filter(&mut self, filter: Filter<Path>) {
+        self.config.path_filter = Some(PathFilter { filter });
+        self.reload_directory();
     }
 
-    /// Name of the file filter to be selected by default.
+    /// Sets the minimum and maximum size in bytes of the files that are displayed.
+    /// Pass `None` to remove a bound.
     ///
-    /// No file filter is selected if there is no file filter with that name.
-    pub fn default_file_filter(mut self, name: &str) -> Self {
-        self.config.default_file_filter = Some(name.to_string());
-        self
+    /// Like `FileDialog::min_file_size` and `FileDialog::max_file_size`, the range is reset
+    /// the next time the dialog is opened unless it is set again before.
+    pub fn set_file_size_range(&mut self, min: Option<u64>, max: Option<u64>) {
+        self.config.min_file_size = min;
+        self.config.max_file_size = max;
+        self.entry_filters_specified = true;
+        self.reload_directory();
     }
 
-    /// Adds a new file extension that the user can select in a dropdown widget when
-    /// saving a file.
-    ///
-    /// NOTE: The name must be unique. If an extension with the same name already exists,
-    ///       it will be overwritten.
-    ///
-    /// # Arguments
-    ///
-    /// * `name` - Display name of the save extension.
-    /// * `file_extension` - The file extension to use.
-    ///
-    /// # Examples
-    ///
-    /// ```
-    /// use std::sync::Arc;
-    /// use egui_file_dialog::FileDialog;
+    /// Sets a point in time to only display files that were modified after it.
+    /// Pass `None` to display files regardless of their modification time.
     ///
-    /// let config = FileDialog::default()
-    ///     .add_save_extension("PNG files", "png")
-    ///     .add_save_extension("JPG files", "jpg");
-    /// ```
-    pub fn add_save_extension(mut self, name: &str, file_extension: &str) -> Self {
-        self.config = self.config.add_save_extension(name, file_extension);
-        self
+    /// Like `FileDialog::modified_after`, the filter is reset the next time the dialog
+    /// is opened unless it is set again before.
+    pub fn set_modified_after(&mut self, time: Option<SystemTime>) {
+        self.config.modified_after = time;
+        self.entry_filters_specified = true;
+        self.reload_directory();
     }
 
-    /// Name of the file extension to be selected by default when saving a file.
-    ///
-    /// No file extension is selected if there is no extension with that name.
-    pub fn default_save_extension(mut self, name: &str) -> Self {
-        self.config.default_save_extension = Some(name.to_string());
-        self
+    /// Removes the filter set using `FileDialog::set_filter`.
+    pub fn clear_filter(&mut self) {
+        self.config.path_filter = None;
+        self.reload_directory();
     }
 
-    /// Sets a new icon for specific files or folders.
-    ///
-    /// # Arguments
-    ///
-    /// * `icon` - The icon that should be used.
-    /// * `filter` - Sets a filter function that checks whether a given
-    ///   Path matches the criteria for this icon.
-    ///
-    /// # Examples
+    /// Sets the predicate that decides which entries of a directory are loaded at all,
+    /// including directories. The currently open directory is reloaded.
     ///
-    /// ```
-    /// use std::sync::Arc;
-    /// use egui_file_dialog::FileDialog;
-    ///
-    /// FileDialog::new()
-    ///     // .png files should use the "document with picture (U+1F5BB)" icon.
-    ///     .set_file_icon("🖻", Arc::new(|path| path.extension().unwrap_or_default() == "png"))
-    ///     // .git directories should use the "web-github (U+E624)" icon.
-    ///     .set_file_icon("", Arc::new(|path| path.file_name().unwrap_or_default() == ".git"));
-    /// ```
-    pub fn set_file_icon(mut self, icon: &str, filter: Filter<std::path::Path>) -> Self {
-        self.config = self.config.set_file_icon(icon, filter);
-        self
+    /// See `FileDialog::entry_predicate` for how the predicate differs from
+    /// `FileDialog::set_filter`.
+    pub fn set_entry_predicate(&mut self, predicate: Filter<Path>) {
+        self.config.entry_predicate = Some(PathFilter { filter: predicate });
+        self.reload_directory();
     }
 
-    /// Adds a new custom quick access section to the left panel.
+    /// Removes the predicate set using `FileDialog::entry_predicate` or
+    /// `FileDialog::set_entry_predicate`.
+    pub fn clear_entry_predicate(&mut self) {
+        self.config.entry_predicate = None;
+        self.reload_directory();
+    }
+
+    /// Sets a regular expression that the file names must match to be displayed.
     ///
-    /// # Examples
+    /// The expression is applied in addition to the glob filter and the search value entered
+    /// by the user and does not affect directories. It is evaluated once per file when the
+    /// directory is loaded or the expression changes, not every frame.
     ///
-    /// ```
-    /// use egui_file_dialog::FileDialog;
+    /// In contrast to the search value, the expression is kept when the dialog is opened again.
     ///
-    /// FileDialog::new()
-    ///     .add_quick_access("My App", |s| {
-    ///         s.add_path("Config", "/app/config");
-    ///         s.add_path("Themes", "/app/themes");
-    ///         s.add_path("Languages", "/app/languages");
-    ///     });
-    /// ```
-    // pub fn add_quick_access(mut self, heading: &str, builder: &fn(&mut QuickAccess)) -> Self {
-    pub fn add_quick_access(
-        mut self,
-        heading: &str,
-        builder: impl FnOnce(&mut QuickAccess),
-    ) -> Self {
-        self.config = self.config.add_quick_access(heading, builder);
-        self
+    /// Requires the `regex` feature.
+    #[cfg(feature = "regex")]
+    pub fn set_regex_filter(&mut self, regex: regex::Regex) {
+        self.config.regex_filter = Some(regex.clone());
+        self.directory_content.set_regex_filter(Some(regex));
     }
 
-    /// Overwrites the window title.
-    ///
-    /// By default, the title is set dynamically, based on the `DialogMode`
-    /// the dialog is currently in.
-    pub fn title(mut self, title: &str) -> Self {
-        self.config.title = Some(title.to_string());
-        self
+    /// Removes the regular expression set using `FileDialog::set_regex_filter`.
+    #[cfg(feature = "regex")]
+    pub fn clear_regex_filter(&mut self) {
+        self.config.regex_filter = None;
+        self.directory_content.set_regex_filter(None);
     }
 
-    /// Sets the ID of the window.
-    pub fn id(mut self, id: impl Into<egui::Id>) -> Self {
-        self.config.id = Some(id.into());
-        self
+    /// Returns the regular expression set using `FileDialog::set_regex_filter`.
+    #[cfg(feature = "regex")]
+    pub const fn regex_filter(&self) -> Option<&regex::Regex> {
+        self.config.regex_filter.as_ref()
     }
 
-    /// Sets the default position of the window.
-    pub fn default_pos(mut self, default_pos: impl Into<egui::Pos2>) -> Self {
-        self.config.default_pos = Some(default_pos.into());
-        self
+    // -------------------------------------------------
+    // Setter:
+
+    /// Mutably borrow internal `config`.
+    pub fn config_mut(&mut self) -> &mut FileDialogConfig {
+        &mut self.config
     }
 
-    /// Sets the window position and prevents it from being dragged around.
-    pub fn fixed_pos(mut self, pos: impl Into<egui::Pos2>) -> Self {
-        self.config.fixed_pos = Some(pos.into());
+    /// Sets the storage used by the file dialog.
+    /// Storage includes all data that is persistently stored between multiple
+    /// file dialog instances.
+    pub fn storage(mut self, storage: FileDialogStorage) -> Self {
+        self.config.storage = storage;
         self
     }
 
-    /// Sets the default size of the window.
-    pub fn default_size(mut self, size: impl Into<egui::Vec2>) -> Self {
-        self.config.default_size = size.into();
-        self
+    /// Mutably borrow internal storage.
+    pub fn storage_mut(&mut self) -> &mut FileDialogStorage {
+        &mut self.config.storage
     }
 
-    /// Sets the maximum size of the window.
-    pub fn max_size(mut self, max_size: impl Into<egui::Vec2>) -> Self {
-        self.config.max_size = Some(max_size.into());
+    /// Sets the keybindings used by the file dialog.
+    pub fn keybindings(mut self, keybindings: FileDialogKeyBindings) -> Self {
+        self.config.keybindings = keybindings;
         self
     }
 
-    /// Sets the minimum size of the window.
+    /// Sets the labels the file dialog uses.
     ///
-    /// Specifying a smaller minimum size than the default can lead to unexpected behavior.
-    pub fn min_size(mut self, min_size: impl Into<egui::Vec2>) -> Self {
-        self.config.min_size = min_size.into();
+    /// Used to enable multiple language support.
+    ///
+    /// See `FileDialogLabels` for more information.
+    pub fn labels(mut self, labels: FileDialogLabels) -> Self {
+        self.config.labels = labels;
         self
     }
 
-    /// Sets the anchor of the window.
-    pub fn anchor(mut self, align: egui::Align2, offset: impl Into<egui::Vec2>) -> Self {
-        self.config.anchor = Some((align, offset.into()));
-        self
+    /// Mutably borrow internal `config.labels`.
+    pub fn labels_mut(&mut self) -> &mut FileDialogLabels {
+        &mut self.config.labels
     }
 
-    /// Sets if the window is resizable.
-    pub const fn resizable(mut self, resizable: bool) -> Self {
-        self.config.resizable = resizable;
+    /// Sets which directory is loaded when opening the file dialog.
+    pub const fn opening_mode(mut self, opening_mode: OpeningMode) -> Self {
+        self.config.opening_mode = opening_mode;
         self
     }
 
-    /// Sets if the window is movable.
+    /// Sets if the dialog should display an overview of all devices when it is opened,
+    /// similar to "This PC" on Windows, instead of loading a directory.
     ///
-    /// Has no effect if an anchor is set.
-    pub const fn movable(mut self, movable: bool) -> Self {
-        self.config.movable = movable;
+    /// The devices are displayed as large tiles in place of the directory content.
+    /// Clicking a device opens its root directory, after which the dialog behaves as usual.
+    /// The `opening_mode` is ignored if this is enabled.
+    pub const fn start_at_devices(mut self, start_at_devices: bool) -> Self {
+        self.config.start_at_devices = start_at_devices;
         self
     }
 
-    /// Sets if the title bar of the window is shown.
-    pub const fn title_bar(mut self, title_bar: bool) -> Self {
-        self.config.title_bar = title_bar;
+    /// If the file dialog window should be displayed as a modal.
+    ///
+    /// If the window is displayed as modal, the area outside the dialog can no longer be
+    /// interacted with and an overlay is displayed.
+    ///
+    /// If the dialog is rendered inside the background layer, for example inside an
+    /// `egui::CentralPanel`, the overlay is displayed around the area the dialog took
+    /// in the previous frame, so it starts to take effect in the second frame.
+    pub const fn as_modal(mut self, as_modal: bool) -> Self {
+        self.config.as_modal = as_modal;
         self
     }
 
-    /// Sets if the top panel with the navigation buttons, current path display
-    /// and search input should be visible.
-    pub const fn show_top_panel(mut self, show_top_panel: bool) -> Self {
-        self.config.show_top_panel = show_top_panel;
+    /// Sets how the dialog is rendered when using `FileDialog::show`: inside a floating
+    /// window, inside its own viewport or directly into the `egui::Ui` passed to `show`.
+    ///
+    /// Has no effect on `FileDialog::update`, which always renders into the given `egui::Ui`.
+    pub const fn render_mode(mut self, render_mode: RenderMode) -> Self {
+        self.config.render_mode = render_mode;
         self
     }
 
-    /// Sets whether the parent folder button should be visible in the top panel.
-    ///
-    /// Has no effect when `FileDialog::show_top_panel` is disabled.
-    pub const fn show_parent_button(mut self, show_parent_button: bool) -> Self {
-        self.config.show_parent_button = show_parent_button;
+    /// Sets the color of the overlay when the dialog is displayed as a modal window.
+    pub const fn modal_overlay_color(mut self, modal_overlay_color: egui::Color32) -> Self {
+        self.config.modal_overlay_color = modal_overlay_color;
         self
     }
 
-    /// Sets whether the back button should be visible in the top panel.
+    /// Sets if clicking the overlay displayed under the modal should cancel the dialog.
     ///
-    /// Has no effect when `FileDialog::show_top_panel` is disabled.
-    pub const fn show_back_button(mut self, show_back_button: bool) -> Self {
-        self.config.show_back_button = show_back_button;
+    /// Has no effect if `FileDialog::as_modal` is disabled.
+    pub const fn cancel_on_overlay_click(mut self, cancel_on_overlay_click: bool) -> Self {
+        self.config.cancel_on_overlay_click = cancel_on_overlay_click;
         self
     }
 
-    /// Sets whether the forward button should be visible in the top panel.
+    /// Sets the first loaded directory when the dialog opens.
+    /// If the path is a file, the file's parent directory is used. If the path then has no
+    /// parent directory or cannot be loaded, the user will receive an error.
+    /// However, the user directories and system disk allow the user to still select a file in
+    /// the event of an error.
     ///
-    /// Has no effect when `FileDialog::show_top_panel` is disabled.
-    pub const fn show_forward_button(mut self, show_forward_button: bool) -> Self {
-        self.config.show_forward_button = show_forward_button;
+    /// Since `fs::canonicalize` is used, both absolute paths and relative paths are allowed.
+    /// See `FileDialog::canonicalize_paths` for more information.
+    pub fn initial_directory(mut self, directory: PathBuf) -> Self {
+        self.config.initial_directory = directory;
         self
     }
 
-    /// Sets whether the button to create a new folder should be visible in the top panel.
-    ///
-    /// Has no effect when `FileDialog::show_top_panel` is disabled.
-    pub const fn show_new_folder_button(mut self, show_new_folder_button: bool) -> Self {
-        self.config.show_new_folder_button = show_new_folder_button;
+    /// Sets the default file name when opening the dialog in `DialogMode::SaveFile` mode.
+    pub fn default_file_name(mut self, name: &str) -> Self {
+        name.clone_into(&mut self.config.default_file_name);
         self
     }
 
-    /// Sets whether the current path should be visible in the top panel.
+    /// Sets if the user is allowed to select an already existing file when the dialog is in
+    /// `DialogMode::SaveFile` mode.
     ///
-    /// Has no effect when `FileDialog::show_top_panel` is disabled.
-    pub const fn show_current_path(mut self, show_current_path: bool) -> Self {
-        self.config.show_current_path = show_current_path;
+    /// If this is enabled, the user will receive a modal asking whether the user really
+    /// wants to overwrite an existing file.
+    pub const fn allow_file_overwrite(mut self, allow_file_overwrite: bool) -> Self {
+        self.config.allow_file_overwrite = allow_file_overwrite;
         self
     }
 
-    /// Sets whether the button to text edit the current path should be visible in the top panel.
+    /// Sets if the user is allowed to create new folders inside the dialog.
     ///
-    /// has no effect when `FileDialog::show_top_panel` is disabled.
-    pub const fn show_path_edit_button(mut self, show_path_edit_button: bool) -> Self {
-        self.config.show_path_edit_button = show_path_edit_button;
+    /// If disabled, the new folder button, the entry in the context menu of the directory
+    /// content and the `new_folder` keybinding, Ctrl+Shift+N by default, are disabled.
+    /// Folders can never be created inside read-only directories.
+    pub const fn allow_create_folder(mut self, allow_create_folder: bool) -> Self {
+        self.config.allow_create_folder = allow_create_folder;
         self
     }
 
-    /// Sets whether the menu with the reload button and other options should be visible
-    /// inside the top panel.
+    /// Sets if missing parent directories should be created when the user saves a file
+    /// in `DialogMode::SaveFile` mode.
     ///
-    /// Has no effect when `FileDialog::show_top_panel` is disabled.
-    pub const fn show_menu_button(mut self, show_menu_button: bool) -> Self {
-        self.config.show_menu_button = show_menu_button;
+    /// If enabled, the user can enter nested file names like `exports/2024/report.pdf`.
+    /// The missing directories are created before the dialog returns the path, and errors
+    /// are displayed in the bottom panel.
+    /// The dialog only creates the directories, never the file itself.
+    ///
+    /// If disabled, the user cannot save a file inside a directory that does not exist.
+    pub const fn create_missing_directories(mut self, create_missing_directories: bool) -> Self {
+        self.config.create_missing_directories = create_missing_directories;
         self
     }
 
-    /// Sets whether the reload button inside the top panel menu should be visible.
+    /// Sets if the directory should be opened when the user enters the name of an existing
+    /// directory and saves in `DialogMode::SaveFile` mode. The entered name is cleared
+    /// afterwards, so that the user can enter the name of the file.
     ///
-    /// Has no effect when `FileDialog::show_top_panel` or
-    /// `FileDialog::show_menu_button` is disabled.
-    pub const fn show_reload_button(mut self, show_reload_button: bool) -> Self {
-        self.config.show_reload_button = show_reload_button;
+    /// If disabled, which is the default, an error is displayed instead.
+    pub const fn open_directory_on_save(mut self, open_directory_on_save: bool) -> Self {
+        self.config.open_directory_on_save = open_directory_on_save;
         self
     }
 
-    /// Sets if the "Open working directory" button should be visible in the hamburger menu.
-    /// The working directory button opens to the currently returned working directory
-    /// from `std::env::current_dir()`.
+    /// Sets if a non-colliding file name should be suggested when the user selects
+    /// an already existing file when the dialog is in `DialogMode::SaveFile` mode.
     ///
-    /// Has no effect when `FileDialog::show_top_panel` or
-    /// `FileDialog::show_menu_button` is disabled.
-    pub const fn show_working_directory_button(
-        mut self,
-        show_working_directory_button: bool,
-    ) -> Self {
-        self.config.show_working_directory_button = show_working_directory_button;
+    /// The suggestion inserts an incrementing counter in front of the file extension,
+    /// for example `report (1).pdf`, and can be accepted with a single click.
+    /// Configured save extensions containing a dot are kept together, so `a.tar.gz`
+    /// becomes `a (1).tar.gz`. No name is suggested if the first 100 counters are taken.
+    pub const fn suggest_copy_name(mut self, suggest_copy_name: bool) -> Self {
+        self.config.suggest_copy_name = suggest_copy_name;
         self
     }
 
-    /// Sets whether the show hidden files and folders option inside the top panel
-    /// menu should be visible.
+    /// Sets the extensions the file name must end with when the dialog is in
+    /// `DialogMode::SaveFile` mode, for example `&["txt", "md"]`.
+    /// The extensions are compared case-insensitively and may contain a leading dot.
+    /// Extensions consisting of multiple parts like `tar.gz` are supported.
     ///
-    /// Has no effect when `FileDialog::show_top_panel` or
-    /// `FileDialog::show_menu_button` is disabled.
-    pub const fn show_hidden_option(mut self, show_hidden_option: bool) -> Self {
-        self.config.show_hidden_option = show_hidden_option;
+    /// In contrast to `FileDialog::add_save_extension`, this does not affect which files
+    /// are displayed. If the list is empty, any extension is allowed.
+    pub fn allowed_save_extensions(mut self, extensions: &[&str]) -> Self {
+        self.config.allowed_save_extensions = extensions
+            .iter()
+            .map(|e| e.trim_start_matches('.').to_string())
+            .collect();
         self
     }
 
-    /// Sets whether the show system files option inside the top panel
-    /// menu should be visible.
+    /// Sets if the path edit is allowed to select the path as the file to save
+    /// if it does not have an extension.
     ///
-    /// Has no effect when `FileDialog::show_top_panel` or
-    /// `FileDialog::show_menu_button` is disabled.
-    pub const fn show_system_files_option(mut self, show_system_files_option: bool) -> Self {
-        self.config.show_system_files_option = show_system_files_option;
+    /// This can lead to confusion if the user wants to open a directory with the path edit,
+    /// types it incorrectly and the dialog tries to select the incorrectly typed folder as
+    /// the file to be saved.
+    ///
+    /// This only affects the `DialogMode::SaveFile` mode.
+    pub const fn allow_path_edit_to_save_file_without_extension(mut self, allow: bool) -> Self {
+        self.config.allow_path_edit_to_save_file_without_extension = allow;
         self
     }
 
-    /// Sets whether the search input should be visible in the top panel.
+    /// Sets if the user is allowed to pick a file that does not exist yet by entering its
+    /// path in the path edit. Only the parent directory of the file must exist.
     ///
-    /// Has no effect when `FileDialog::show_top_panel` is disabled.
-    pub const fn show_search(mut self, show_search: bool) -> Self {
-        self.config.show_search = show_search;
+    /// This only affects the `DialogMode::PickFile` mode.
+    pub const fn allow_nonexistent_file(mut self, allow: bool) -> Self {
+        self.config.allow_nonexistent_file = allow;
         self
     }
 
-    /// Sets if the sidebar with the shortcut directories such as
-    /// “Home”, “Documents” etc. should be visible.
-    pub const fn show_left_panel(mut self, show_left_panel: bool) -> Self {
-        self.config.show_left_panel = show_left_panel;
+    /// Sets the separator of the directories when displaying a path.
+    /// Currently only used when the current path is displayed in the top panel.
+    pub fn directory_separator(mut self, separator: &str) -> Self {
+        self.config.directory_separator = separator.to_string();
         self
     }
 
-    /// Sets if pinned folders should be listed in the left sidebar.
-    /// Disabling this will also disable the functionality to pin a folder.
-    pub const fn show_pinned_folders(mut self, show_pinned_folders: bool) -> Self {
-        self.config.show_pinned_folders = show_pinned_folders;
+    /// Sets if the paths in the file dialog should be canonicalized before use.
+    ///
+    /// By default, all paths are canonicalized. This has the advantage that the paths are
+    /// all brought to a standard and are therefore compatible with each other.
+    ///
+    /// On Windows, however, this results in the namespace prefix `\\?\` being set in
+    /// front of the path, which may not be compatible with other applications.
+    /// In addition, canonicalizing converts all relative paths to absolute ones.
+    ///
+    /// See: [Rust docs](https://doc.rust-lang.org/std/fs/fn.canonicalize.html)
+    /// for more information.
+    ///
+    /// In general, it is only recommended to disable canonicalization if
+    /// you know what you are doing and have a reason for it, for example if a virtual or
+    /// FUSE file system does not support canonicalization or rewrites the paths.
+    /// Disabling canonicalization can lead to unexpected behavior, for example if an
+    /// already canonicalized path is then set as the initial directory.
+    ///
+    /// If disabled, the paths are used exactly as given, also when comparing them.
+    /// A relative initial directory therefore stays relative, so the path displayed in the
+    /// top panel only contains the relative segments and the parent directories of the
+    /// relative path cannot be opened using the parent button.
+    pub const fn canonicalize_paths(mut self, canonicalize: bool) -> Self {
+        self.config.canonicalize_paths = canonicalize;
         self
     }
 
-    /// Sets if the "Places" section should be visible in the left sidebar.
-    /// The Places section contains the user directories such as Home or Documents.
-    ///
-    /// Has no effect when `FileDialog::show_left_panel` is disabled.
-    pub const fn show_places(mut self, show_places: bool) -> Self {
-        self.config.show_places = show_places;
+    /// If the directory content should be loaded via a separate thread.
+    /// This prevents the application from blocking when loading large directories
+    /// or from slow hard drives.
+    pub const fn load_via_thread(mut self, load_via_thread: bool) -> Self {
+        self.config.load_via_thread = load_via_thread;
         self
     }
 
-    /// Sets if the "Devices" section should be visible in the left sidebar.
-    /// The Devices section contains the non removable system disks.
+    /// Sets the time after which a location is considered not responding.
     ///
-    /// Has no effect when `FileDialog::show_left_panel` is disabled.
-    pub const fn show_devices(mut self, show_devices: bool) -> Self {
-        self.config.show_devices = show_devices;
+    /// Calls to a network mount whose server is no longer reachable can block for minutes.
+    /// If no entry of a directory was read within the timeout, the user can retry loading
+    /// it or go back to the previous directory. The directory is canonicalized while its
+    /// content is loading and the devices are loaded on a separate thread as well, so that
+    /// the dialog stays responsive.
+    ///
+    /// Only used if `FileDialog::load_via_thread` is enabled. Defaults to 10 seconds.
+    pub const fn file_system_timeout(mut self, timeout: Duration) -> Self {
+        self.config.file_system_timeout = timeout;
         self
     }
 
-    /// Sets if the "Removable Devices" section should be visible in the left sidebar.
-    /// The Removable Devices section contains the removable disks like USB disks.
+    /// Sets if long filenames should be truncated in the middle.
+    /// The extension, if available, will be preserved.
     ///
-    /// Has no effect when `FileDialog::show_left_panel` is disabled.
-    pub const fn show_removable_devices(mut self, show_removable_devices: bool) -> Self {
-        self.config.show_removable_devices = show_removable_devices;
+    /// Warning! If this is disabled, the scroll-to-selection might not work correctly and have
+    /// an offset for large directories.
+    pub const fn truncate_filenames(mut self, truncate_filenames: bool) -> Self {
+        self.config.truncate_filenames = truncate_filenames;
         self
     }
 
-    // -------------------------------------------------
-    // Getter:
-
-    /// Returns the directory or file that the user picked, or the target file
-    /// if the dialog is in `DialogMode::SaveFile` mode.
+    /// Sets if directories should be listed before files.
     ///
-    /// None is returned when the user has not yet selected an item.
-    pub fn picked(&self) -> Option<&Path> {
-        match &self.state {
-            DialogState::Picked(path) => Some(path),
-            _ => None,
-        }
+    /// If disabled, directories and files are sorted together by name.
+    pub const fn folders_first(mut self, folders_first: bool) -> Self {
+        self.config.folders_first = folders_first;
+        self
     }
 
-    /// Returns the directory or file that the user picked, or the target file
-    /// if the dialog is in `DialogMode::SaveFile` mode.
-    /// Unlike `FileDialog::picked`, this method returns the picked path only once and
-    /// sets the dialog's state to `DialogState::Closed`.
+    /// Sets if a ".." entry should be displayed at the top of the directory content.
+    /// Double-clicking the entry, or selecting it and pressing the `submit` keybinding,
+    /// opens the parent directory. The entry is selected by clicking it or by moving the
+    /// selection up from the first item.
     ///
-    /// None is returned when the user has not yet picked an item.
-    pub fn take_picked(&mut self) -> Option<PathBuf> {
-        match &mut self.state {
-            DialogState::Picked(path) => {
-                let path = std::mem::take(path);
-                self.state = DialogState::Closed;
-                Some(path)
-            }
-            _ => None,
-        }
+    /// The entry is not affected by the search and is never part of the picked paths.
+    /// It is not displayed if the current directory has no parent.
+    pub const fn show_parent_entry(mut self, show_parent_entry: bool) -> Self {
+        self.config.show_parent_entry = show_parent_entry;
+        self
     }
 
-    /// Returns a list of the files and folders the user picked, when the dialog is in
-    /// `DialogMode::PickMultiple` mode.
+    /// Sets if the folders and files inside the directory content should be displayed
+    /// below separate "Folders" and "Files" headers. Clicking a header collapses the group.
     ///
-    /// None is returned when the user has not yet picked an item.
-    pub fn picked_multiple(&self) -> Option<Vec<&Path>> {
-        match &self.state {
-            DialogState::PickedMultiple(items) => {
-                Some(items.iter().map(std::path::PathBuf::as_path).collect())
-            }
-            _ => None,
-        }
+    /// The headers are hidden while searching, while a glob or regex filter is set
+    /// or if one of the groups is empty.
+    /// Requires `FileDialog::folders_first` to be enabled.
+    pub const fn show_group_headers(mut self, show_group_headers: bool) -> Self {
+        self.config.show_group_headers = show_group_headers;
+        self
     }
 
-    /// Returns a list of the files and folders the user picked, when the dialog is in
-    /// `DialogMode::PickMultiple` mode.
-    /// Unlike `FileDialog::picked_multiple`, this method returns the picked paths only once
-    /// and sets the dialog's state to `DialogState::Closed`.
+    /// Sets the maximum number of entries that are loaded when opening a directory.
     ///
-    /// None is returned when the user has not yet picked an item.
-    pub fn take_picked_multiple(&mut self) -> Option<Vec<PathBuf>> {
-        match &mut self.state {
-            DialogState::PickedMultiple(items) => {
-                let items = std::mem::take(items);
-                self.state = DialogState::Closed;
-                Some(items)
-            }
-            _ => None,
-        }
+    /// Loading and sorting directories with tens of thousands of entries can take a while.
+    /// If a directory contains more entries, only the given number of entries is loaded,
+    /// taken in the order of their names, and a button to load all entries is displayed
+    /// at the end of the list. The search only applies to the loaded entries until all
+    /// entries are loaded.
+    ///
+    /// By default, all entries are loaded.
+    pub const fn max_entries(mut self, max_entries: usize) -> Self {
+        self.config.max_entries = Some(max_entries);
+        self
     }
 
-    /// Returns the currently active directory entry.
+    /// Sets how the entries inside the directory content are displayed.
     ///
-    /// This is either the currently highlighted entry, or the currently active directory
-    /// if nothing is being highlighted.
+    /// `ViewMode::Details` displays the size and the last modification date of the entries
+    /// in columns below a header. Clicking a column header sorts the entries by that column,
+    /// clicking it again reverses the sort order.
     ///
-    /// For the [`DialogMode::SelectMultiple`] counterpart,
-    /// see [`FileDialog::active_selected_entries`].
-    pub const fn selected_entry(&self) -> Option<&DirectoryEntry> {
-        self.selected_item.as_ref()
+    /// The dates are displayed in the local time zone if the `information_view` feature is
+    /// enabled, otherwise in UTC.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use egui_file_dialog::{FileDialog, FileSorting, ViewMode};
+    ///
+    /// FileDialog::new()
+    ///     .view_mode(ViewMode::Details)
+    ///     .file_sorting(FileSorting::LastModified, true);
+    /// ```
+    pub const fn view_mode(mut self, view_mode: ViewMode) -> Self {
+        self.config.view_mode = view_mode;
+        self
     }
 
-    /// Returns an iterator over the currently selected entries in [`SelectMultiple`] mode.
-    ///
-    /// For the counterpart in single selection modes, see [`FileDialog::active_entry`].
+    /// Sets the property by which the entries inside the directory content are sorted
+    /// and if they are sorted in descending order.
     ///
-    /// [`SelectMultiple`]: DialogMode::SelectMultiple
-    pub fn selected_entries(&self) -> impl Iterator<Item = &DirectoryEntry> {
-        self.get_dir_content_filtered_iter().filter(|p| p.selected)
+    /// The sort order is part of the `FileDialogStorage`, so it is overwritten when
+    /// the storage is loaded using `FileDialog::storage` afterwards.
+    /// The property is ignored if a custom comparator is set using
+    /// `FileDialog::set_sort_comparator`.
+    pub const fn file_sorting(mut self, sorting: FileSorting, descending: bool) -> Self {
+        self.config.file_sorting = sorting;
+        self.config.storage.sort_descending = descending;
+        self
     }
 
-    /// Returns the ID of the operation for which the dialog is currently being used.
+    /// Sets if double-clicking a directory in `DialogMode::PickDirectory` should pick
+    /// the directory and finish the dialog instead of opening it.
     ///
-    /// See `FileDialog::open` for more information.
-    pub fn operation_id(&self) -> Option<&str> {
-        self.operation_id.as_deref()
+    /// The directory can still be opened by selecting it and pressing the `submit` keybinding.
+    pub const fn pick_directory_on_double_click(mut self, pick: bool) -> Self {
+        self.config.pick_directory_on_double_click = pick;
+        self
+    }
+
+    /// Sets if directory entries should display a `>` chevron at the end of their row.
+    ///
+    /// Clicking the chevron opens the directory with a single click, while clicking the name
+    /// only selects it. This is especially useful in `DialogMode::PickDirectory`, where a
+    /// directory is both a valid selection and something to navigate into.
+    pub const fn show_directory_chevrons(mut self, show_directory_chevrons: bool) -> Self {
+        self.config.show_directory_chevrons = show_directory_chevrons;
+        self
+    }
+
+    /// Sets if the entries of the directory content can be dragged out of the dialog, for
+    /// example onto a canvas of the application. The `PathBuf` of the dragged entry is set as
+    /// the payload of egui's drag-and-drop API and can be received using
+    /// `egui::Response::dnd_release_payload::<PathBuf>`.
+    ///
+    /// Clicking and double-clicking entries is not affected. Note that the directory content
+    /// can then no longer be scrolled by dragging the entries, for example on touch screens.
+    pub const fn draggable_entries(mut self, draggable_entries: bool) -> Self {
+        self.config.draggable_entries = draggable_entries;
+        self
+    }
+
+    /// Sets if the number of items inside a directory should be displayed next to its name,
+    /// for example `Photos (128)`.
+    ///
+    /// The counts are only loaded for the directories that are currently visible and are
+    /// cached until a different directory is opened or the dialog is refreshed.
+    /// While a count is loading, `…` is displayed.
+    ///
+    /// Only the items that would be listed when opening the directory are counted, so hidden
+    /// and ignored items are skipped unless they are displayed.
+    pub const fn show_directory_item_count(mut self, show_directory_item_count: bool) -> Self {
+        self.config.show_directory_item_count = show_directory_item_count;
+        self
+    }
+
+    /// Sets if the total size of the directories should be displayed in the size column
+    /// of `ViewMode::Details`.
+    ///
+    /// The sizes are computed one after another on a background thread after the directory
+    /// is loaded, and the computation is cancelled when another directory is opened.
+    /// While a size is computing, `…` is displayed. Use `FileDialog::directory_size_budget`
+    /// to limit the time spent on large directories.
+    /// When sorting by size, directories are sorted by their total size once it is computed.
+    pub const fn show_directory_sizes(mut self, show_directory_sizes: bool) -> Self {
+        self.config.show_directory_sizes = show_directory_sizes;
+        self
+    }
+
+    /// Sets the budget for computing the size of a single directory.
+    ///
+    /// The computation stops if a subdirectory deeper than `max_depth` is found or it takes
+    /// longer than `timeout`. The size found until then is displayed with a leading `>`.
+    /// This prevents scanning an entire file system, for example when the root
+    /// directory is open.
+    ///
+    /// If `FileDialog::load_via_thread` is disabled, the timeout is shared by all
+    /// directories, so that the dialog blocks for at most the timeout. The sizes of the
+    /// directories remaining after the timeout are not displayed.
+    ///
+    /// Defaults to a depth of 16 and a timeout of 5 seconds.
+    pub const fn directory_size_budget(mut self, max_depth: usize, timeout: Duration) -> Self {
+        self.config.directory_size_max_depth = max_depth;
+        self.config.directory_size_timeout = timeout;
+        self
+    }
+
+    /// Sets if entries matched by the `.gitignore` files of the current directory and its
+    /// parents, up to the root of the git repository, should be hidden. This is useful to hide
+    /// build output like `target/` or `node_modules/` when opening files of a project.
+    ///
+    /// If enabled, the menu contains an option to display the ignored entries dimmed instead.
+    /// Only a subset of the `.gitignore` syntax is supported: blank lines, comments, negation
+    /// using `!`, directory-only patterns with a trailing `/`, anchored patterns
+    /// containing a `/`, `**` and the wildcards supported by the glob filter.
+    pub const fn respect_ignore_files(mut self, respect_ignore_files: bool) -> Self {
+        self.config.respect_ignore_files = respect_ignore_files;
+        self
+    }
+
+    /// Sets if the Unix permissions of the entries should be displayed in a column on the
+    /// right side of the directory content, formatted like `-rw-r--r--`.
+    /// Entries the current user cannot write to are additionally marked with a padlock.
+    ///
+    /// The permissions are read together with the other metadata when loading the directory.
+    /// Only available on Unix platforms.
+    #[cfg(unix)]
+    pub const fn show_permissions(mut self, show_permissions: bool) -> Self {
+        self.config.show_permissions = show_permissions;
+        self
+    }
+
+    /// Sets if the name of the user owning the entries should be displayed in a column
+    /// on the right side of the directory content.
+    ///
+    /// The names are resolved when loading the directory and cached per user ID.
+    /// If a name cannot be resolved, the numeric user ID is displayed instead.
+    /// Only available on Unix platforms.
+    #[cfg(unix)]
+    pub const fn show_owner(mut self, show_owner: bool) -> Self {
+        self.config.show_owner = show_owner;
+        self
+    }
+
+    /// Sets if the name of the group owning the entries should be displayed in a column
+    /// on the right side of the directory content.
+    ///
+    /// The names are resolved when loading the directory and cached per group ID.
+    /// If a name cannot be resolved, the numeric group ID is displayed instead.
+    /// Only available on Unix platforms.
+    #[cfg(unix)]
+    pub const fn show_group(mut self, show_group: bool) -> Self {
+        self.config.show_group = show_group;
+        self
+    }
+
+    /// Sets the unit system used to display file sizes.
+    /// `SizeUnits::Binary` displays sizes like `1.50 MiB`, `SizeUnits::Decimal` like `1.57 MB`.
+    ///
+    /// The sizes are displayed in the information panel and in the summary of the
+    /// selected files at the bottom of the dialog.
+    pub const fn size_units(mut self, units: SizeUnits) -> Self {
+        self.config.size_units = units;
+        self
+    }
+
+    /// Sets a duration to only display files that were modified within that duration,
+    /// for example in the last 7 days. Pass `None` to display all files.
+    ///
+    /// Directories are always displayed so that the user can still navigate.
+    /// Files without a known modification time are also displayed.
+    pub const fn modified_within(mut self, duration: Option<Duration>) -> Self {
+        self.config.modified_within = duration;
+        self
+    }
+
+    /// Sets the minimum size in bytes of the files that are displayed.
+    ///
+    /// The number of files hidden by the size and date filters is displayed
+    /// below the directory content. Files without a known size are always displayed.
+    ///
+    /// The filter only applies the next time the dialog is opened and is reset when the
+    /// dialog is opened again, unless it is specified again.
+    pub const fn min_file_size(mut self, size: u64) -> Self {
+        self.config.min_file_size = Some(size);
+        self.entry_filters_specified = true;
+        self
+    }
+
+    /// Sets the maximum size in bytes of the files that are displayed.
+    ///
+    /// The number of files hidden by the size and date filters is displayed
+    /// below the directory content. Files without a known size are always displayed.
+    ///
+    /// The filter only applies the next time the dialog is opened and is reset when the
+    /// dialog is opened again, unless it is specified again.
+    pub const fn max_file_size(mut self, size: u64) -> Self {
+        self.config.max_file_size = Some(size);
+        self.entry_filters_specified = true;
+        self
+    }
+
+    /// Sets a point in time to only display files that were modified after it.
+    ///
+    /// The number of files hidden by the size and date filters is displayed
+    /// below the directory content. Files without a known modification time
+    /// are always displayed.
+    ///
+    /// The filter only applies the next time the dialog is opened and is reset when the
+    /// dialog is opened again, unless it is specified again.
+    pub const fn modified_after(mut self, time: SystemTime) -> Self {
+        self.config.modified_after = Some(time);
+        self.entry_filters_specified = true;
+        self
+    }
+
+    /// Sets a predicate that decides which entries of a directory are loaded at all.
+    ///
+    /// The predicate is called with the path of every entry, including directories, while
+    /// the directory is loaded. Rejected entries never enter the directory content and their
+    /// metadata is not read, which reduces the loading time and memory usage of very large
+    /// directories. Because of that, rejected entries are also invisible to the search and
+    /// the user cannot navigate into rejected directories using the directory content.
+    ///
+    /// Use `FileDialog::set_filter` to only filter files while keeping all directories
+    /// navigable, and `FileDialog::set_entry_predicate` to change the predicate while the
+    /// dialog is open.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    /// use egui_file_dialog::FileDialog;
+    ///
+    /// // Skip build artifacts
+    /// FileDialog::new().entry_predicate(Arc::new(|path| !path.ends_with("target")));
+    /// ```
+    pub fn entry_predicate(mut self, predicate: Filter<Path>) -> Self {
+        self.config.entry_predicate = Some(PathFilter { filter: predicate });
+        self
+    }
+
+    /// Sets if the user is allowed to open hidden directories.
+    ///
+    /// If disabled, hidden directories and their subdirectories cannot be opened, even if
+    /// hidden files and folders are displayed or the path is entered using the path edit.
+    /// The current directory stays open and an error message is displayed above its content.
+    pub const fn allow_hidden_navigation(mut self, allow_hidden_navigation: bool) -> Self {
+        self.config.allow_hidden_navigation = allow_hidden_navigation;
+        self
+    }
+
+    /// Sets the icon that is used to display errors.
+    pub fn err_icon(mut self, icon: &str) -> Self {
+        self.config.err_icon = icon.to_string();
+        self
+    }
+
+    /// Sets the default icon that is used to display files.
+    pub fn default_file_icon(mut self, icon: &str) -> Self {
+        self.config.default_file_icon = icon.to_string();
+        self
+    }
+
+    /// Sets the default icon that is used to display folders.
+    pub fn default_folder_icon(mut self, icon: &str) -> Self {
+        self.config.default_folder_icon = icon.to_string();
+        self
+    }
+
+    /// Sets the icon that is used to display executable files.
+    /// Icons set using `FileDialog::set_file_icon` take precedence over this icon.
+    pub fn executable_file_icon(mut self, icon: &str) -> Self {
+        self.config.executable_file_icon = icon.to_string();
+        self
+    }
+
+    /// Sets the icon that is used to display devices in the left panel.
+    pub fn device_icon(mut self, icon: &str) -> Self {
+        self.config.device_icon = icon.to_string();
+        self
+    }
+
+    /// Sets the icon that is used to display removable devices in the left panel.
+    pub fn removable_device_icon(mut self, icon: &str) -> Self {
+        self.config.removable_device_icon = icon.to_string();
+        self
+    }
+
+    /// Sets the background color of the selected entries in the central panel.
+    ///
+    /// By default, the selection color of the current egui theme is used.
+    pub const fn selection_fill(mut self, color: egui::Color32) -> Self {
+        self.config.selection_fill = Some(color);
+        self
+    }
+
+    /// Sets the text color of the selected entries in the central panel.
+    ///
+    /// By default, the selection color of the current egui theme is used.
+    pub const fn selection_text_color(mut self, color: egui::Color32) -> Self {
+        self.config.selection_text_color = Some(color);
+        self
+    }
+
+    /// Adds a new file filter the user can select from a dropdown widget.
+    ///
+    /// NOTE: The name must be unique. If a filter with the same name already exists,
+    ///       it will be overwritten.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Display name of the filter
+    /// * `filter` - Sets a filter function that checks whether a given
+    ///   Path matches the criteria for this filter.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    /// use egui_file_dialog::FileDialog;
+    ///
+    /// FileDialog::new()
+    ///     .add_file_filter(
+    ///         "PNG files",
+    ///         Arc::new(|path| path.extension().unwrap_or_default() == "png"))
+    ///     .add_file_filter(
+    ///         "JPG files",
+    ///         Arc::new(|path| path.extension().unwrap_or_default() == "jpg"));
+    /// ```
+    pub fn add_file_filter(mut self, name: &str, filter: Filter<Path>) -> Self {
+        self.config = self.config.add_file_filter(name, filter);
+        self
+    }
+
+    /// Adds multiple file filters that match files by their extension.
+    /// Extensions are compared case-insensitively and without the leading dot.
+    ///
+    /// NOTE: If a filter with the same name already exists, it will be overwritten.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use egui_file_dialog::FileDialog;
+    ///
+    /// FileDialog::new()
+    ///     .file_filters(vec![
+    ///         ("Images".to_string(), vec!["png".to_string(), "jpg".to_string()]),
+    ///         ("Text files".to_string(), vec!["txt".to_string()]),
+    ///     ])
+    ///     .default_file_filter("Images");
+    /// ```
+    pub fn file_filters(mut self, filters: Vec<(String, Vec<String>)>) -> Self {
+        for (name, extensions) in filters {
+            self.config = self.config.add_file_filter(
+                &name,
+                Arc::new(move |path: &Path| {
+                    path.extension().is_some_and(|ext| {
+                        extensions
+                            .iter()
+                            .any(|e| ext.eq_ignore_ascii_case(e.as_str()))
+                    })
+                }),
+            );
+        }
+
+        self
+    }
+
+    /// Sets a function that validates the items selected by the user before the dialog
+    /// can be submitted. If the function returns an error, the submit button is disabled
+    /// and the error message is displayed in the bottom panel.
+    ///
+    /// The result is cached per path, so the function can access the file system
+    /// without being executed in every frame.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    /// use egui_file_dialog::FileDialog;
+    ///
+    /// FileDialog::new()
+    ///     .selection_validator(Arc::new(|path, _mode| {
+    ///         if path.join("Cargo.toml").exists() {
+    ///             Ok(())
+    ///         } else {
+    ///             Err("The folder must contain a Cargo.toml".to_string())
+    ///         }
+    ///     }));
+    /// ```
+    pub fn selection_validator(mut self, validator: PathValidator) -> Self {
+        self.config.selection_validator = Some(SelectionValidator { validator });
+        self
+    }
+
+    /// Sets a function that validates the picked items when the user confirms the selection,
+    /// right before the dialog is finished. If the function returns an error, the dialog stays
+    /// open and the error message is displayed in the bottom panel.
+    ///
+    /// In contrast to `FileDialog::selection_validator`, the function is executed every time
+    /// the user tries to submit the dialog and never while the user is still selecting.
+    /// This makes it suitable for expensive checks, like verifying that a file is a valid image.
+    /// The function is called for every picked path, including the path of a file to be saved.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    /// use egui_file_dialog::FileDialog;
+    ///
+    /// FileDialog::new()
+    ///     .confirm_validator(Arc::new(|path, _mode| {
+    ///         match std::fs::metadata(path) {
+    ///             Ok(metadata) if metadata.len() > 0 => Ok(()),
+    ///             _ => Err("The file must not be empty".to_string()),
+    ///         }
+    ///     }));
+    /// ```
+    pub fn confirm_validator(mut self, validator: PathValidator) -> Self {
+        self.config.confirm_validator = Some(SelectionValidator { validator });
+        self
+    }
+
+    /// Sets a function to render custom widgets inside the bottom panel, to the left of
+    /// the action buttons. The function is called every frame while the dialog is open.
+    ///
+    /// The state of the widgets belongs to your application, the `CustomUiContext`
+    /// gives read-only access to the current selection and mode of the dialog.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::atomic::{AtomicBool, Ordering};
+    /// use std::sync::Arc;
+    /// use egui_file_dialog::FileDialog;
+    ///
+    /// let read_only = Arc::new(AtomicBool::new(false));
+    /// let read_only_ui = read_only.clone();
+    ///
+    /// FileDialog::new().custom_bottom_ui(Box::new(move |ui, _context| {
+    ///     let mut value = read_only_ui.load(Ordering::Relaxed);
+    ///
+    ///     if ui.checkbox(&mut value, "Open as read-only").changed() {
+    ///         read_only_ui.store(value, Ordering::Relaxed);
+    ///     }
+    /// }));
+    /// ```
+    pub fn custom_bottom_ui(mut self, f: CustomBottomUi) -> Self {
+        self.custom_bottom_ui = Some(Callback(f));
+        self
+    }
+
+    /// Sets a function to render a custom panel on the right side of the dialog.
+    /// The function receives the path of the currently selected item and is called
+    /// every frame while the dialog is open.
+    ///
+    /// Use `FileDialog::right_panel_width` and `FileDialog::right_panel_width_range`
+    /// to configure the width of the panel.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use egui_file_dialog::FileDialog;
+    ///
+    /// FileDialog::new()
+    ///     .right_panel_width(200.0)
+    ///     .custom_right_panel(Box::new(|ui, path| {
+    ///         ui.heading("Details");
+    ///
+    ///         if let Some(path) = path {
+    ///             ui.label(path.display().to_string());
+    ///         }
+    ///     }));
+    /// ```
+    pub fn custom_right_panel(mut self, f: CustomRightPanelUi) -> Self {
+        self.custom_right_panel = Some(Callback(f));
+        self
+    }
+
+    /// Sets a function that is called with the new directory every time the user navigates
+    /// to a different directory. This includes navigating using the path, the left panel,
+    /// the back, forward and parent buttons and the path edit.
+    ///
+    /// The function is called once the content of the new directory finished loading.
+    /// It is not called if the directory could not be loaded or loading was cancelled.
+    ///
+    /// The function is not called when the current directory is reloaded,
+    /// unless enabled with `FileDialog::notify_directory_reload`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use egui_file_dialog::FileDialog;
+    ///
+    /// FileDialog::new().on_directory_changed(Box::new(|path| {
+    ///     println!("Browsing {}", path.display());
+    /// }));
+    /// ```
+    pub fn on_directory_changed(mut self, f: DirectoryChangedCallback) -> Self {
+        self.on_directory_changed = Some(Callback(f));
+        self
+    }
+
+    /// Sets a function that is called with the path of the new directory every time the user
+    /// created a directory using the dialog. The function is called exactly once per
+    /// created directory.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use egui_file_dialog::FileDialog;
+    ///
+    /// FileDialog::new().on_directory_created(Box::new(|path| {
+    ///     println!("Created {}", path.display());
+    /// }));
+    /// ```
+    pub fn on_directory_created(mut self, f: DirectoryCreatedCallback) -> Self {
+        self.on_directory_created = Some(Callback(f));
+        self
+    }
+
+    /// Sets if the function set using `FileDialog::on_directory_changed` should also be
+    /// called when the currently open directory is reloaded.
+    pub const fn notify_directory_reload(mut self, notify_directory_reload: bool) -> Self {
+        self.config.notify_directory_reload = notify_directory_reload;
+        self
+    }
+
+    /// Sets if the history of opened directories, used by the back and forward buttons,
+    /// should be kept when the dialog is opened again.
+    ///
+    /// This is required if the history is restored using `FileDialog::set_history`
+    /// before the dialog is opened.
+    pub const fn preserve_history(mut self, preserve_history: bool) -> Self {
+        self.config.preserve_history = preserve_history;
+        self
+    }
+
+    /// Sets the initial width of the custom right panel.
+    /// Use `FileDialog::set_right_panel_width` to change the width while the dialog is open.
+    pub const fn right_panel_width(mut self, width: f32) -> Self {
+        self.config.right_panel_width = Some(width);
+        self
+    }
+
+    /// Sets the range in which the user can resize the custom right panel.
+    pub const fn right_panel_width_range(mut self, range: RangeInclusive<f32>) -> Self {
+        self.config.right_panel_width_range = Some(range);
+        self
+    }
+
+    /// Name of the file filter to be selected by default.
+    ///
+    /// No file filter is selected if there is no file filter with that name.
+    /// In this case, all files are displayed.
+    pub fn default_file_filter(mut self, name: &str) -> Self {
+        self.config.default_file_filter = Some(name.to_string());
+        self
+    }
+
+    /// Sets if file filters that match none of the files inside the current directory
+    /// should be hidden from the dropdown.
+    ///
+    /// The option to display all files and the currently selected filter are always visible.
+    /// The available filters are updated every time a directory is loaded.
+    pub const fn hide_empty_filters(mut self, hide_empty_filters: bool) -> Self {
+        self.config.hide_empty_filters = hide_empty_filters;
+        self
+    }
+
+    /// Adds a new file extension that the user can select in a dropdown widget when
+    /// saving a file.
+    ///
+    /// NOTE: The name must be unique. If an extension with the same name already exists,
+    ///       it will be overwritten.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Display name of the save extension.
+    /// * `file_extension` - The file extension to use.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    /// use egui_file_dialog::FileDialog;
+    ///
+    /// let config = FileDialog::default()
+    ///     .add_save_extension("PNG files", "png")
+    ///     .add_save_extension("JPG files", "jpg");
+    /// ```
+    pub fn add_save_extension(mut self, name: &str, file_extension: &str) -> Self {
+        self.config = self.config.add_save_extension(name, file_extension);
+        self
+    }
+
+    /// Name of the file extension to be selected by default when saving a file.
+    ///
+    /// No file extension is selected if there is no extension with that name.
+    pub fn default_save_extension(mut self, name: &str) -> Self {
+        self.config.default_save_extension = Some(name.to_string());
+        self
+    }
+
+    /// Sets a new icon for specific files or folders.
+    ///
+    /// # Arguments
+    ///
+    /// * `icon` - The icon that should be used.
+    /// * `filter` - Sets a filter function that checks whether a given
+    ///   Path matches the criteria for this icon.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    /// use egui_file_dialog::FileDialog;
+    ///
+    /// FileDialog::new()
+    ///     // .png files should use the "document with picture (U+1F5BB)" icon.
+    ///     .set_file_icon("🖻", Arc::new(|path| path.extension().unwrap_or_default() == "png"))
+    ///     // .git directories should use the "web-github (U+E624)" icon.
+    ///     .set_file_icon("", Arc::new(|path| path.file_name().unwrap_or_default() == ".git"));
+    /// ```
+    pub fn set_file_icon(mut self, icon: &str, filter: Filter<std::path::Path>) -> Self {
+        self.config = self.config.set_file_icon(icon, filter);
+        self
+    }
+
+    /// Adds a new custom quick access section to the left panel.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use egui_file_dialog::FileDialog;
+    ///
+    /// FileDialog::new()
+    ///     .add_quick_access("My App", |s| {
+    ///         s.add_path("Config", "/app/config");
+    ///         s.add_path("Themes", "/app/themes");
+    ///         s.add_path("Languages", "/app/languages");
+    ///     });
+    /// ```
+    // pub fn add_quick_access(mut self, heading: &str, builder: &fn(&mut QuickAccess)) -> Self {
+    pub fn add_quick_access(
+        mut self,
+        heading: &str,
+        builder: impl FnOnce(&mut QuickAccess),
+    ) -> Self {
+        self.config = self.config.add_quick_access(heading, builder);
+        self
+    }
+
+    /// Overwrites the window title.
+    ///
+    /// By default, the title is set dynamically, based on the `DialogMode`
+    /// the dialog is currently in.
+    pub fn title(mut self, title: &str) -> Self {
+        self.config.title = Some(title.to_string());
+        self
+    }
+
+    /// Sets the ID of the window.
+    pub fn id(mut self, id: impl Into<egui::Id>) -> Self {
+        self.config.id = Some(id.into());
+        self
+    }
+
+    /// Sets the default position of the window.
+    pub fn default_pos(mut self, default_pos: impl Into<egui::Pos2>) -> Self {
+        self.config.default_pos = Some(default_pos.into());
+        self
+    }
+
+    /// Sets the window position and prevents it from being dragged around.
+    pub fn fixed_pos(mut self, pos: impl Into<egui::Pos2>) -> Self {
+        self.config.fixed_pos = Some(pos.into());
+        self
+    }
+
+    /// Sets the default size of the window.
+    pub fn default_size(mut self, size: impl Into<egui::Vec2>) -> Self {
+        self.config.default_size = size.into();
+        self
+    }
+
+    /// Sets the maximum size of the window.
+    pub fn max_size(mut self, max_size: impl Into<egui::Vec2>) -> Self {
+        self.config.max_size = Some(max_size.into());
+        self
+    }
+
+    /// Sets the minimum size of the window.
+    ///
+    /// The window is never smaller than required to display the visible side panels
+    /// next to the directory content, regardless of the minimum size.
+    pub fn min_size(mut self, min_size: impl Into<egui::Vec2>) -> Self {
+        self.config.min_size = min_size.into();
+        self
+    }
+
+    /// Sets the anchor of the window.
+    pub fn anchor(mut self, align: egui::Align2, offset: impl Into<egui::Vec2>) -> Self {
+        self.config.anchor = Some((align, offset.into()));
+        self
+    }
+
+    /// Sets if the window is resizable.
+    ///
+    /// A window that is not resizable always has the size set using
+    /// `FileDialog::default_size`, the minimum and maximum size are ignored.
+    pub const fn resizable(mut self, resizable: bool) -> Self {
+        self.config.resizable = resizable;
+        self
+    }
+
+    /// Sets if the window should take the size of its content instead of using the
+    /// default size. An auto sized window cannot be resized by the user.
+    ///
+    /// The window never becomes smaller than the size set using `FileDialog::min_size`.
+    pub const fn auto_sized(mut self, auto_sized: bool) -> Self {
+        self.config.auto_sized = auto_sized;
+        self
+    }
+
+    /// Sets if the window is movable.
+    ///
+    /// Has no effect if an anchor is set.
+    pub const fn movable(mut self, movable: bool) -> Self {
+        self.config.movable = movable;
+        self
+    }
+
+    /// Sets if the title bar of the window is shown.
+    pub const fn title_bar(mut self, title_bar: bool) -> Self {
+        self.config.title_bar = title_bar;
+        self
+    }
+
+    /// Sets if the window can be collapsed by clicking the button in its title bar.
+    ///
+    /// Has no effect if the title bar is hidden using `FileDialog::title_bar`.
+    /// Disabled by default.
+    pub const fn collapsible(mut self, collapsible: bool) -> Self {
+        self.config.collapsible = collapsible;
+        self
+    }
+
+    /// Sets if the top panel with the navigation buttons, current path display
+    /// and search input should be visible.
+    pub const fn show_top_panel(mut self, show_top_panel: bool) -> Self {
+        self.config.show_top_panel = show_top_panel;
+        self
+    }
+
+    /// Sets whether the parent folder button should be visible in the top panel.
+    ///
+    /// Has no effect when `FileDialog::show_top_panel` is disabled.
+    pub const fn show_parent_button(mut self, show_parent_button: bool) -> Self {
+        self.config.show_parent_button = show_parent_button;
+        self
+    }
+
+    /// Sets whether the back button should be visible in the top panel.
+    ///
+    /// Has no effect when `FileDialog::show_top_panel` is disabled.
+    pub const fn show_back_button(mut self, show_back_button: bool) -> Self {
+        self.config.show_back_button = show_back_button;
+        self
+    }
+
+    /// Sets whether the forward button should be visible in the top panel.
+    ///
+    /// Has no effect when `FileDialog::show_top_panel` is disabled.
+    pub const fn show_forward_button(mut self, show_forward_button: bool) -> Self {
+        self.config.show_forward_button = show_forward_button;
+        self
+    }
+
+    /// Sets whether the button to create a new folder should be visible in the top panel.
+    /// The `new_folder` keybinding is still available if the button is hidden,
+    /// use `FileDialog::allow_create_folder` to disable creating folders entirely.
+    ///
+    /// Has no effect when `FileDialog::show_top_panel` is disabled.
+    pub const fn show_new_folder_button(mut self, show_new_folder_button: bool) -> Self {
+        self.config.show_new_folder_button = show_new_folder_button;
+        self
+    }
+
+    /// Sets whether the current path should be visible in the top panel.
+    ///
+    /// Has no effect when `FileDialog::show_top_panel` is disabled.
+    pub const fn show_current_path(mut self, show_current_path: bool) -> Self {
+        self.config.show_current_path = show_current_path;
+        self
+    }
+
+    /// Sets whether the button to text edit the current path should be visible in the top panel.
+    ///
+    /// has no effect when `FileDialog::show_top_panel` is disabled.
+    pub const fn show_path_edit_button(mut self, show_path_edit_button: bool) -> Self {
+        self.config.show_path_edit_button = show_path_edit_button;
+        self
+    }
+
+    /// Sets whether the menu with the reload button and other options should be visible
+    /// inside the top panel.
+    ///
+    /// Has no effect when `FileDialog::show_top_panel` is disabled.
+    pub const fn show_menu_button(mut self, show_menu_button: bool) -> Self {
+        self.config.show_menu_button = show_menu_button;
+        self
+    }
+
+    /// Sets whether the button to reverse the sort order should be visible in the top panel.
+    /// The button is hidden by default.
+    ///
+    /// Has no effect when `FileDialog::show_top_panel` is disabled.
+    pub const fn show_sort_order_button(mut self, show_sort_order_button: bool) -> Self {
+        self.config.show_sort_order_button = show_sort_order_button;
+        self
+    }
+
+    /// Sets whether the button to pin or unpin the current directory should be visible
+    /// in the top panel. The button is hidden by default.
+    ///
+    /// Has no effect when `FileDialog::show_top_panel` or
+    /// `FileDialog::show_pinned_folders` is disabled.
+    pub const fn show_pin_button(mut self, show_pin_button: bool) -> Self {
+        self.config.show_pin_button = show_pin_button;
+        self
+    }
+
+    /// Sets whether the reload button inside the top panel menu should be visible.
+    ///
+    /// Has no effect when `FileDialog::show_top_panel` or
+    /// `FileDialog::show_menu_button` is disabled.
+    pub const fn show_reload_button(mut self, show_reload_button: bool) -> Self {
+        self.config.show_reload_button = show_reload_button;
+        self
+    }
+
+    /// Sets if the "Open working directory" button should be visible in the hamburger menu.
+    /// The working directory button opens to the currently returned working directory
+    /// from `std::env::current_dir()`.
+    ///
+    /// Has no effect when `FileDialog::show_top_panel` or
+    /// `FileDialog::show_menu_button` is disabled.
+    pub const fn show_working_directory_button(
+        mut self,
+        show_working_directory_button: bool,
+    ) -> Self {
+        self.config.show_working_directory_button = show_working_directory_button;
+        self
+    }
+
+    /// Sets whether the show hidden files and folders option inside the top panel
+    /// menu should be visible.
+    ///
+    /// Has no effect when `FileDialog::show_top_panel` or
+    /// `FileDialog::show_menu_button` is disabled.
+    pub const fn show_hidden_option(mut self, show_hidden_option: bool) -> Self {
+        self.config.show_hidden_option = show_hidden_option;
+        self
+    }
+
+    /// Sets whether the show system files option inside the top panel
+    /// menu should be visible.
+    ///
+    /// Has no effect when `FileDialog::show_top_panel` or
+    /// `FileDialog::show_menu_button` is disabled.
+    pub const fn show_system_files_option(mut self, show_system_files_option: bool) -> Self {
+        self.config.show_system_files_option = show_system_files_option;
+        self
+    }
+
+    /// Sets whether the search input should be visible in the top panel.
+    ///
+    /// Has no effect when `FileDialog::show_top_panel` is disabled.
+    pub const fn show_search(mut self, show_search: bool) -> Self {
+        self.config.show_search = show_search;
+        self
+    }
+
+    /// Sets if a panel showing a preview of the selected file should be visible.
+    /// Currently only small UTF-8 text files can be previewed.
+    ///
+    /// The preview is only shown when the dialog is in `DialogMode::PickFile` mode.
+    pub const fn enable_preview(mut self, enable_preview: bool) -> Self {
+        self.config.show_preview = enable_preview;
+        self
+    }
+
+    /// Sets if the sidebar with the shortcut directories such as
+    /// “Home”, “Documents” etc. should be visible.
+    pub const fn show_left_panel(mut self, show_left_panel: bool) -> Self {
+        self.config.show_left_panel = show_left_panel;
+        self
+    }
+
+    /// Sets the initial width of the left sidebar. Defaults to `150.0`.
+    ///
+    /// The width is clamped to the range set using `FileDialog::left_panel_width_range`.
+    pub const fn left_panel_default_width(mut self, width: f32) -> Self {
+        self.config.left_panel_width = width;
+        self
+    }
+
+    /// Sets the range in which the user can resize the left sidebar.
+    /// Defaults to `90.0..=250.0`.
+    ///
+    /// Negative values are treated as zero and the bounds are swapped if the start of the
+    /// range is greater than its end.
+    pub const fn left_panel_width_range(mut self, range: RangeInclusive<f32>) -> Self {
+        self.config.left_panel_width_range = range;
+        self
+    }
+
+    /// Sets if pinned folders should be listed in the left sidebar.
+    /// Disabling this will also disable the functionality to pin a folder.
+    pub const fn show_pinned_folders(mut self, show_pinned_folders: bool) -> Self {
+        self.config.show_pinned_folders = show_pinned_folders;
+        self
+    }
+
+    /// Sets if entries in the left sidebar whose path no longer exists should be marked
+    /// as unavailable. Clicking an unavailable pinned folder offers to unpin it.
+    ///
+    /// The availability of the paths is checked once and cached until the dialog is refreshed.
+    pub const fn mark_unavailable_locations(mut self, mark_unavailable_locations: bool) -> Self {
+        self.config.mark_unavailable_locations = mark_unavailable_locations;
+        self
+    }
+
+    /// Sets if the "Places" section should be visible in the left sidebar.
+    /// The Places section contains the user directories such as Home or Documents.
+    ///
+    /// Has no effect when `FileDialog::show_left_panel` is disabled.
+    pub const fn show_places(mut self, show_places: bool) -> Self {
+        self.config.show_places = show_places;
+        self
+    }
+
+    /// Sets if a single entry of the "Places" section should be visible in the left sidebar,
+    /// for example to hide the Pictures and Videos directories in an audio application.
+    /// All entries are visible by default.
+    ///
+    /// If all entries are hidden, the Places section is hidden entirely.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use egui_file_dialog::{FileDialog, Place};
+    ///
+    /// let dialog = FileDialog::new()
+    ///     .show_places_entry(Place::Pictures, false)
+    ///     .show_places_entry(Place::Videos, false);
+    /// ```
+    pub fn show_places_entry(mut self, place: Place, show: bool) -> Self {
+        self.config.hidden_places.retain(|p| *p != place);
+
+        if !show {
+            self.config.hidden_places.push(place);
+        }
+
+        self
+    }
+
+    /// Sets if the "Devices" section should be visible in the left sidebar.
+    /// The Devices section contains the non removable system disks.
+    ///
+    /// Has no effect when `FileDialog::show_left_panel` is disabled.
+    pub const fn show_devices(mut self, show_devices: bool) -> Self {
+        self.config.show_devices = show_devices;
+        self
+    }
+
+    /// Sets if the "Removable Devices" section should be visible in the left sidebar.
+    /// The Removable Devices section contains the removable disks like USB disks.
+    ///
+    /// Has no effect when `FileDialog::show_left_panel` is disabled.
+    pub const fn show_removable_devices(mut self, show_removable_devices: bool) -> Self {
+        self.config.show_removable_devices = show_removable_devices;
+        self
+    }
+
+    /// Sets if removable devices can be ejected using the context menu of their entry in the
+    /// "Removable Devices" section. If the open directory is located on the device, the
+    /// directory containing its mount point is opened first. The device is ejected in the
+    /// background and, on success, the list of devices is reloaded.
+    /// Otherwise, the error is displayed below the device.
+    ///
+    /// Ejecting is best-effort and uses the command line tools of the platform, like `gio` on
+    /// Linux, `diskutil` on macOS and the Windows shell. Enabled by default.
+    ///
+    /// Requires the `eject` feature.
+    #[cfg(feature = "eject")]
+    pub const fn show_eject_button(mut self, show_eject_button: bool) -> Self {
+        self.config.show_eject_button = show_eject_button;
+        self
+    }
+
+    /// Sets if files can be opened with the default application of the system using the
+    /// context menu of their entry in the directory content.
+    /// Unlike picking the file, this does not close the dialog.
+    ///
+    /// The application is started using the tools of the platform, like `xdg-open` on
+    /// Linux, `open` on macOS and the Windows Explorer. Enabled by default.
+    ///
+    /// Requires the `open_externally` feature.
+    #[cfg(feature = "open_externally")]
+    pub const fn show_open_externally(mut self, show_open_externally: bool) -> Self {
+        self.config.show_open_externally = show_open_externally;
+        self
+    }
+
+    // -------------------------------------------------
+    // Getter:
+
+    /// Returns the directory or file that the user picked, or the target file
+    /// if the dialog is in `DialogMode::SaveFile` mode.
+    ///
+    /// None is returned when the user has not yet selected an item.
+    pub fn picked(&self) -> Option<&Path> {
+        match &self.state {
+            DialogState::Picked(path) => Some(path),
+            _ => None,
+        }
+    }
+
+    /// Returns the directory or file that the user picked, or the target file
+    /// if the dialog is in `DialogMode::SaveFile` mode.
+    /// Unlike `FileDialog::picked`, this method returns the picked path only once and
+    /// sets the dialog's state to `DialogState::Closed`.
+    ///
+    /// None is returned when the user has not yet picked an item.
+    pub fn take_picked(&mut self) -> Option<PathBuf> {
+        match &mut self.state {
+            DialogState::Picked(path) => {
+                let path = std::mem::take(path);
+                self.state = DialogState::Closed;
+                Some(path)
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns a list of the files and folders the user picked, when the dialog is in
+    /// `DialogMode::PickMultiple` mode.
+    ///
+    /// None is returned when the user has not yet picked an item.
+    pub fn picked_multiple(&self) -> Option<Vec<&Path>> {
+        match &self.state {
+            DialogState::PickedMultiple(items) => {
+                Some(items.iter().map(std::path::PathBuf::as_path).collect())
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns a list of the files and folders the user picked, when the dialog is in
+    /// `DialogMode::PickMultiple` mode.
+    /// Unlike `FileDialog::picked_multiple`, this method returns the picked paths only once
+    /// and sets the dialog's state to `DialogState::Closed`.
+    ///
+    /// None is returned when the user has not yet picked an item.
+    pub fn take_picked_multiple(&mut self) -> Option<Vec<PathBuf>> {
+        match &mut self.state {
+            DialogState::PickedMultiple(items) => {
+                let items = std::mem::take(items);
+                self.state = DialogState::Closed;
+                Some(items)
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the result of the dialog only once and sets the dialog's state to
+    /// `DialogState::Closed`.
+    ///
+    /// The result is returned in the first call after the user finished the dialog.
+    /// Every later call returns None until the dialog is opened and finished again,
+    /// so calling this every frame handles every result exactly once.
+    ///
+    /// None is also returned while the dialog is open or closed.
+    /// Use `FileDialog::state` to check the state without taking the result.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use egui_file_dialog::{DialogResult, FileDialog};
+    ///
+    /// fn update(file_dialog: &mut FileDialog) {
+    ///     match file_dialog.take_result() {
+    ///         Some(DialogResult::Selected(path)) => println!("Selected: {path:?}"),
+    ///         Some(DialogResult::SelectedMultiple(paths)) => println!("Selected: {paths:?}"),
+    ///         Some(DialogResult::Cancelled) => println!("Cancelled"),
+    ///         None => {}
+    ///     }
+    /// }
+    /// ```
+    pub fn take_result(&mut self) -> Option<DialogResult> {
+        let result = match std::mem::replace(&mut self.state, DialogState::Closed) {
+            DialogState::Picked(path) => DialogResult::Selected(path),
+            DialogState::PickedMultiple(paths) => DialogResult::SelectedMultiple(paths),
+            DialogState::Cancelled => DialogResult::Cancelled,
+            state @ (DialogState::Open | DialogState::Closed) => {
+                self.state = state;
+                return None;
+            }
+        };
+
+        Some(result)
+    }
+
+    /// Returns the currently active directory entry.
+    ///
+    /// This is either the currently highlighted entry, or the currently active directory
+    /// if nothing is being highlighted.
+    ///
+    /// For the [`DialogMode::SelectMultiple`] counterpart,
+    /// see [`FileDialog::active_selected_entries`].
+    pub const fn selected_entry(&self) -> Option<&DirectoryEntry> {
+        self.selected_item.as_ref()
+    }
+
+    /// Returns an iterator over the currently selected entries in [`SelectMultiple`] mode.
+    ///
+    /// For the counterpart in single selection modes, see [`FileDialog::active_entry`].
+    ///
+    /// [`SelectMultiple`]: DialogMode::SelectMultiple
+    pub fn selected_entries(&self) -> impl Iterator<Item = &DirectoryEntry> {
+        self.get_dir_content_filtered_iter().filter(|p| p.selected)
+    }
+
+    /// Returns the ID of the operation for which the dialog is currently being used.
+    ///
+    /// See `FileDialog::open` for more information.
+    pub fn operation_id(&self) -> Option<&str> {
+        self.operation_id.as_deref()
+    }
+
+    /// Attaches data of any type to the operation for which the dialog is currently
+    /// being used. The data is kept until the dialog is opened again, so this must be called
+    /// after the dialog has been opened.
+    ///
+    /// This is an alternative to the `operation_id` if the different operations the dialog is
+    /// used for are already described by a type in your application.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use egui_file_dialog::FileDialog;
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// enum Action {
+    ///     LoadProject,
+    ///     ImportImage,
+    /// }
+    ///
+    /// let mut file_dialog = FileDialog::new();
+    ///
+    /// file_dialog.pick_file();
+    /// file_dialog.set_user_data(Action::ImportImage);
+    ///
+    /// assert_eq!(file_dialog.user_data::<Action>(), Some(&Action::ImportImage));
+    /// ```
+    pub fn set_user_data<T: Any + Send + Sync>(&mut self, data: T) {
+        self.user_data = Some(Box::new(data));
+    }
+
+    /// Returns the data attached to the current operation using `FileDialog::set_user_data`.
+    /// Returns None if no data is attached or if the data is not of type `T`.
+    pub fn user_data<T: Any>(&self) -> Option<&T> {
+        self.user_data.as_ref()?.downcast_ref()
+    }
+
+    /// Returns the history of opened directories used by the back and forward buttons.
+    /// The oldest directory comes first.
+    pub fn history(&self) -> &[PathBuf] {
+        &self.directory_stack
+    }
+
+    /// Returns the index of the currently open directory inside `FileDialog::history`.
+    /// Returns None if no directory has been opened yet.
+    pub const fn history_position(&self) -> Option<usize> {
+        self.directory_stack
+            .len()
+            .checked_sub(self.directory_offset + 1)
+    }
+
+    /// Returns the number of directories inside `FileDialog::history` that were
+    /// opened after the currently open directory.
+    /// This is the number of times the user can go forward.
+    pub const fn history_offset(&self) -> usize {
+        self.directory_offset
+    }
+
+    /// Returns true if there is a previous directory the user can go back to.
+    /// This is the condition under which the back button is enabled.
+    pub const fn can_go_back(&self) -> bool {
+        self.directory_offset + 1 < self.directory_stack.len()
+    }
+
+    /// Returns true if there is a next directory the user can go forward to.
+    /// This is the condition under which the forward button is enabled.
+    pub const fn can_go_forward(&self) -> bool {
+        self.directory_offset != 0
+    }
+
+    /// Returns true if the given section of the left panel is currently collapsed.
+    /// All sections are expanded by default.
+    pub fn is_section_collapsed(&self, section: LeftPanelSection) -> bool {
+        self.collapsed_sections.contains(&section)
+    }
+
+    /// Collapses or expands the given section of the left panel.
+    /// This can be used to restore the state previously queried using
+    /// `FileDialog::is_section_collapsed`.
+    pub fn set_section_collapsed(&mut self, section: LeftPanelSection, collapsed: bool) {
+        self.collapsed_sections.retain(|s| *s != section);
+
+        if collapsed {
+            self.collapsed_sections.push(section);
+        }
+    }
+
+    /// Returns true if the entries inside the directory view are sorted in descending order.
+    pub const fn sort_descending(&self) -> bool {
+        self.config.storage.sort_descending
+    }
+
+    /// Returns the current value of the search input.
+    pub fn search_value(&self) -> &str {
+        &self.search_value
+    }
+
+    /// Returns the file name exactly as entered by the user when the dialog is in
+    /// `DialogMode::SaveFile` mode.
+    ///
+    /// Unlike the picked path, the name is not joined with the current directory or
+    /// canonicalized. It remains available after the user picked a file until the dialog
+    /// is opened again.
+    pub fn raw_file_name_input(&self) -> &str {
+        &self.file_name_input
+    }
+
+    /// Returns the directory that is currently open in the dialog.
+    ///
+    /// This is only meaningful while the dialog is open. After the dialog is closed,
+    /// the last open directory is returned until the dialog is opened again.
+    /// Returns None if no directory has been opened yet.
+    pub fn current_directory(&self) -> Option<&Path> {
+        if let Some(x) = self.directory_stack.iter().nth_back(self.directory_offset) {
+            return Some(x.as_path());
+        }
+
+        None
+    }
+
+    /// Checks if the given path would currently be displayed inside the directory content,
+    /// if it was located in the open directory.
+    ///
+    /// Applies the same filters the dialog uses to decide which entries are listed,
+    /// including the search value, the selected file filter, the options to show hidden and
+    /// system files, and the configured filters like `FileDialog::glob_filter`.
+    /// This is useful to mirror the filtering of the dialog in the application, for example
+    /// to decide if a file dropped onto the dialog should be accepted.
+    ///
+    /// The metadata of the path is read using the configured file system.
+    /// It is not checked if the path is actually located in the open directory.
+    pub fn would_show(&self, path: &Path) -> bool {
+        let (file_filter, save_extension) = self.get_active_filters();
+
+        load_filtered_entry(
+            &self.config,
+            path,
+            self.show_files,
+            file_filter,
+            save_extension,
+            &*self.config.file_system,
+        )
+        .is_some_and(|entry| {
+            self.directory_content
+                .matches_filters(&entry, &self.search_value)
+        })
     }
 
     /// Returns the mode the dialog is currently in.
@@ -1034,2243 +2993,7642 @@ impl FileDialog {
         self.mode
     }
 
-    /// Returns the state the dialog is currently in.
-    pub fn state(&self) -> DialogState {
-        self.state.clone()
+    /// Returns the state the dialog is currently in.
+    pub fn state(&self) -> DialogState {
+        self.state.clone()
+    }
+
+    /// Get the window Id
+    pub const fn get_window_id(&self) -> egui::Id {
+        self.window_id
+    }
+}
+
+/// UI methods
+impl FileDialog {
+    /// Main update method of the UI
+    ///
+    /// Takes an optional callback to show a custom right panel.
+    fn update_ui(
+        &mut self,
+        right_panel_fn: Option<&mut FileDialogUiCallback>,
+        ctx: &egui::Context,
+        ui: &mut egui::Ui,
+    ) -> bool {
+        if !self.modals.is_empty() {
+            self.ui_update_modals(ui);
+            return false;
+        }
+
+        self.update_selection_validation();
+
+        // The IDs are collected again while rendering the UI.
+        self.search_input_id = None;
+        self.file_name_input_id = None;
+        self.submit_button_id = None;
+        self.cancel_button_id = None;
+
+        if self.config.show_top_panel {
+            egui::TopBottomPanel::top(self.window_id.with("top_panel"))
+                .resizable(false)
+                .show_inside(ui, |ui| {
+                    self.ui_update_top_panel(ui);
+                });
+        }
+
+        // The side panels never take the space the other panels need at least,
+        // so that they keep working when the window is very small
+        let [_, right_min_width, preview_min_width] =
+            self.side_panel_min_widths(right_panel_fn.is_some());
+
+        if self.config.show_left_panel {
+            let (width, width_range) = Self::left_panel_widths(
+                self.config.left_panel_width,
+                &self.config.left_panel_width_range,
+            );
+            let width_range = Self::limit_panel_width(
+                &width_range,
+                ui.available_width()
+                    - MIN_CENTRAL_PANEL_WIDTH
+                    - right_min_width
+                    - preview_min_width,
+            );
+
+            egui::SidePanel::left(self.window_id.with("left_panel"))
+                .resizable(true)
+                .default_width(width)
+                .width_range(width_range)
+                .show_inside(ui, |ui| {
+                    self.ui_update_left_panel(ui);
+                });
+        }
+
+        // Optionally, show a custom right panel (see `FileDialog::custom_right_panel`)
+        if let Some(f) = right_panel_fn {
+            // Unlike the left panel, we have no control over the contents, so we don't
+            // restrict the width by default. It's up to the user to make the UI presentable.
+            let width_range = Self::limit_panel_width(
+                &self.right_panel_widths(),
+                ui.available_width() - MIN_CENTRAL_PANEL_WIDTH - preview_min_width,
+            );
+            let mut right_panel = egui::SidePanel::right(self.window_id.with("right_panel"))
+                .resizable(true)
+                .width_range(width_range);
+
+            if let Some(width) = self.config.right_panel_width {
+                right_panel = right_panel.default_width(width);
+            }
+
+            right_panel.show_inside(ui, |ui| {
+                f(ui, self);
+            });
+        }
+
+        if self.config.show_preview && self.mode == DialogMode::PickFile {
+            egui::SidePanel::right(self.window_id.with("preview_panel"))
+                .resizable(true)
+                .default_width(200.0)
+                .width_range(Self::limit_panel_width(
+                    &(MIN_SIDE_PANEL_WIDTH..=f32::INFINITY),
+                    ui.available_width() - MIN_CENTRAL_PANEL_WIDTH,
+                ))
+                .show_inside(ui, |ui| {
+                    self.file_preview
+                        .update(ui, &self.config, self.selected_item.as_ref());
+                });
+        }
+
+        egui::TopBottomPanel::bottom(self.window_id.with("bottom_panel"))
+            .resizable(false)
+            .show_inside(ui, |ui| {
+                self.ui_update_bottom_panel(ui);
+            });
+
+        egui::CentralPanel::default().show_inside(ui, |ui| {
+            self.ui_update_central_panel(ui);
+        });
+
+        self.ui_update_go_to_dialog(ctx, ui.max_rect());
+
+        self.update_dropped_files(ctx, ui.max_rect())
+    }
+
+    /// Updates the go to dialog and opens the folder entered by the user.
+    /// If the entered path is not an existing folder, the error is displayed inside
+    /// the go to dialog and the dialog stays open.
+    fn ui_update_go_to_dialog(&mut self, ctx: &egui::Context, rect: egui::Rect) {
+        let Some(path) =
+            self.go_to_dialog
+                .update(ctx, self.window_id.with("go_to_dialog"), rect, &self.config)
+        else {
+            return;
+        };
+
+        if self.config.file_system.is_dir(&path) {
+            self.go_to_dialog.close();
+            self.load_directory(&path);
+        } else {
+            self.go_to_dialog
+                .set_error(self.config.labels.err_folder_not_found.clone());
+        }
+    }
+
+    /// Opens the first path that was dropped onto the dialog by the user.
+    /// If a directory was dropped, the directory is opened. Otherwise the parent directory
+    /// is opened and the dropped file is selected.
+    ///
+    /// Only drops within the given rect of the dialog are handled and consumed.
+    /// Dropped files without a path, like drops from a browser on the web, are ignored.
+    ///
+    /// Returns true if the UI should be repainted.
+    fn update_dropped_files(&mut self, ctx: &egui::Context, rect: egui::Rect) -> bool {
+        let path = ctx.input_mut(|i| {
+            // The pointer position is not always known while dropping files,
+            // in which case the drop is assumed to be meant for the dialog.
+            let inside = i.pointer.latest_pos().is_none_or(|pos| rect.contains(pos));
+
+            if !inside || !i.raw.dropped_files.iter().any(|f| f.path.is_some()) {
+                return None;
+            }
+
+            std::mem::take(&mut i.raw.dropped_files)
+                .into_iter()
+                .find_map(|f| f.path)
+        });
+
+        let Some(path) = path else {
+            return false;
+        };
+
+        if self.config.file_system.is_dir(&path) {
+            // If we dropped a directory, go there
+            self.load_directory(&path);
+        } else if let Some(parent) = path.parent() {
+            // Else, go to the parent directory
+            self.load_directory(parent);
+            self.select_item(&mut DirectoryEntry::from_path(
+                &self.config,
+                &path,
+                &*self.config.file_system,
+            ));
+            self.scroll_to_selection = true;
+        } else {
+            return false;
+        }
+
+        true
+    }
+
+    fn ui_update_modals(&mut self, ui: &mut egui::Ui) {
+        // Currently, a rendering error occurs when only a single central panel is rendered
+        // inside a window. Therefore, when rendering a modal, we render an invisible bottom panel,
+        // which prevents the error.
+        // This is currently a bit hacky and should be adjusted again in the future.
+        egui::TopBottomPanel::bottom(self.window_id.with("modal_bottom_panel"))
+            .resizable(false)
+            .show_separator_line(false)
+            .show_inside(ui, |_| {});
+
+        // We need to use a central panel for the modals so that the
+        // window doesn't resize to the size of the modal.
+        egui::CentralPanel::default().show_inside(ui, |ui| {
+            if let Some(modal) = self.modals.last_mut() {
+                #[allow(clippy::single_match)]
+                match modal.update(&self.config, ui) {
+                    ModalState::Close(action) => {
+                        self.exec_modal_action(action);
+                        self.modals.pop();
+                    }
+                    ModalState::Pending => {}
+                }
+            }
+        });
+    }
+
+    /// Gets the window title to use.
+    /// This is either one of the default window titles or the configured window title.
+    const fn get_window_title(&self) -> &String {
+        match &self.config.title {
+            Some(title) => title,
+            None => match &self.mode {
+                DialogMode::PickDirectory => &self.config.labels.title_select_directory,
+                DialogMode::PickFile => &self.config.labels.title_select_file,
+                DialogMode::PickMultiple => &self.config.labels.title_select_multiple,
+                DialogMode::SaveFile => &self.config.labels.title_save_file,
+            },
+        }
+    }
+
+    /// Displays an overlay behind the layer of the dialog that prevents the user from
+    /// interacting with the rest of the application.
+    fn ui_update_modal_overlay(&mut self, ctx: &egui::Context, dialog_layer: egui::LayerId) {
+        let screen_rect = ctx.screen_rect();
+
+        // Nothing can be displayed behind the background layer, for example behind an
+        // `egui::CentralPanel`. Instead, the overlay is displayed on top of the rest of the
+        // application, around the area the dialog took in the last frame.
+        let in_background = dialog_layer.order == egui::Order::Background;
+
+        let rects = if in_background {
+            let Some(dialog_rect) = self.rect_last_frame else {
+                return;
+            };
+
+            Self::rects_around(screen_rect, dialog_rect).to_vec()
+        } else {
+            vec![screen_rect]
+        };
+
+        let order = if in_background {
+            egui::Order::Middle
+        } else {
+            dialog_layer.order
+        };
+
+        let mut clicked = false;
+
+        for (i, rect) in rects.into_iter().enumerate() {
+            if rect.width() <= 0.0 || rect.height() <= 0.0 {
+                continue;
+            }
+
+            let response = egui::Area::new(self.window_id.with("modal_overlay").with(i))
+                .order(order)
+                .fixed_pos(rect.min)
+                .constrain(false)
+                .interactable(true)
+                .show(ctx, |ui| {
+                    ui.painter()
+                        .rect_filled(rect, 0.0, self.config.modal_overlay_color);
+                    ui.allocate_rect(rect, egui::Sense::click())
+                })
+                .inner;
+
+            // Make sure the overlay stays on top of the windows of the application
+            if in_background {
+                ctx.move_to_top(response.layer_id);
+            }
+
+            clicked |= response.clicked();
+        }
+
+        // Make sure the dialog stays on top of the overlay
+        if !in_background {
+            ctx.move_to_top(dialog_layer);
+        }
+
+        if clicked && self.config.cancel_on_overlay_click {
+            self.cancel();
+        }
+    }
+
+    /// Splits the area of the outer rect that is not covered by the inner rect into the
+    /// rects above, below, left and right of the inner rect. The rects can be empty.
+    fn rects_around(outer: egui::Rect, inner: egui::Rect) -> [egui::Rect; 4] {
+        let inner = inner.intersect(outer);
+
+        [
+            egui::Rect::from_x_y_ranges(outer.x_range(), outer.min.y..=inner.min.y),
+            egui::Rect::from_x_y_ranges(outer.x_range(), inner.max.y..=outer.max.y),
+            egui::Rect::from_x_y_ranges(outer.min.x..=inner.min.x, inner.y_range()),
+            egui::Rect::from_x_y_ranges(inner.max.x..=outer.max.x, inner.y_range()),
+        ]
+    }
+
+    /// Gets the minimum widths of the left panel, the custom right panel and the preview
+    /// panel. The width of a panel that is not displayed is zero.
+    fn side_panel_min_widths(&self, right_panel: bool) -> [f32; 3] {
+        let left = if self.config.show_left_panel {
+            *Self::left_panel_widths(
+                self.config.left_panel_width,
+                &self.config.left_panel_width_range,
+            )
+            .1
+            .start()
+        } else {
+            0.0
+        };
+
+        let right = if right_panel {
+            *self.right_panel_widths().start()
+        } else {
+            0.0
+        };
+
+        let preview = if self.config.show_preview && self.mode == DialogMode::PickFile {
+            MIN_SIDE_PANEL_WIDTH
+        } else {
+            0.0
+        };
+
+        [left, right, preview]
+    }
+
+    /// Gets the range in which the user can resize the custom right panel,
+    /// with non-negative and ordered bounds.
+    fn right_panel_widths(&self) -> RangeInclusive<f32> {
+        self.config
+            .right_panel_width_range
+            .as_ref()
+            .map_or(MIN_SIDE_PANEL_WIDTH..=f32::INFINITY, |range| {
+                Self::left_panel_widths(*range.start(), range).1
+            })
+    }
+
+    /// Lowers the maximum of the width range of a side panel to the given width,
+    /// but never below the minimum of the range.
+    const fn limit_panel_width(range: &RangeInclusive<f32>, max: f32) -> RangeInclusive<f32> {
+        *range.start()..=range.end().min(max).max(*range.start())
+    }
+
+    /// Validates the configured width of the left panel.
+    /// Returns the initial width clamped to the width range, and the range with
+    /// non-negative and ordered bounds.
+    const fn left_panel_widths(
+        width: f32,
+        range: &RangeInclusive<f32>,
+    ) -> (f32, RangeInclusive<f32>) {
+        // `f32::min` and `f32::max` ignore NaN values, so the bounds are always valid
+        let min = range.start().min(*range.end()).max(0.0);
+        let max = range.start().max(*range.end()).max(min);
+
+        (width.max(min).min(max), min..=max)
+    }
+
+    /// Updates the top panel of the dialog. Including the navigation buttons,
+    /// the current path display, the reload button and the search field.
+    fn ui_update_top_panel(&mut self, ui: &mut egui::Ui) {
+        const BUTTON_SIZE: egui::Vec2 = egui::Vec2::new(25.0, 25.0);
+
+        ui.horizontal(|ui| {
+            self.ui_update_nav_buttons(ui, BUTTON_SIZE);
+
+            let mut path_display_width = ui.available_width();
+
+            // Leave some area for the menu button and search input
+            if self.config.show_reload_button {
+                path_display_width -= ui
+                    .style()
+                    .spacing
+                    .item_spacing
+                    .x
+                    .mul_add(2.5, BUTTON_SIZE.x);
+            }
+
+            if self.config.show_sort_order_button {
+                path_display_width -= ui.style().spacing.item_spacing.x + BUTTON_SIZE.x;
+            }
+
+            let show_pin_button = self.config.show_pin_button && self.config.show_pinned_folders;
+
+            if show_pin_button {
+                path_display_width -= ui.style().spacing.item_spacing.x + BUTTON_SIZE.x;
+            }
+
+            if self.config.show_search {
+                path_display_width -= 140.0;
+            }
+
+            if self.config.show_current_path {
+                self.ui_update_current_path(ui, path_display_width);
+            }
+
+            if self.config.show_sort_order_button {
+                self.ui_update_sort_order_button(ui, BUTTON_SIZE);
+            }
+
+            if show_pin_button {
+                self.ui_update_pin_button(ui, BUTTON_SIZE);
+            }
+
+            // Hamburger menu containing different options
+            if self.config.show_menu_button
+                && (self.config.show_reload_button
+                    || self.config.show_working_directory_button
+                    || self.config.show_hidden_option
+                    || self.config.show_system_files_option
+                    || self.config.respect_ignore_files)
+            {
+                ui.allocate_ui_with_layout(
+                    BUTTON_SIZE,
+                    egui::Layout::centered_and_justified(egui::Direction::LeftToRight),
+                    |ui| {
+                        let response = ui
+                            .menu_button("☰", |ui| {
+                                self.ui_update_hamburger_menu(ui);
+                            })
+                            .response;
+
+                        Self::set_accessible_name(&response, &self.config.labels.tooltip_menu);
+                        response.on_hover_text(&self.config.labels.tooltip_menu);
+                    },
+                );
+            }
+
+            if self.config.show_search {
+                self.ui_update_search(ui);
+            }
+        });
+
+        ui.add_space(ui.ctx().style().spacing.item_spacing.y);
+    }
+
+    /// Updates the button to reverse the sort order of the directory content.
+    fn ui_update_sort_order_button(&mut self, ui: &mut egui::Ui, button_size: egui::Vec2) {
+        let icon = if self.config.storage.sort_descending {
+            "⏷"
+        } else {
+            "⏶"
+        };
+
+        let response = ui.add_sized(button_size, egui::Button::new(icon));
+        Self::set_accessible_name(&response, &self.config.labels.tooltip_sort_order);
+
+        if response
+            .on_hover_text(&self.config.labels.tooltip_sort_order)
+            .clicked()
+        {
+            self.set_sort_descending(!self.config.storage.sort_descending);
+        }
+    }
+
+    /// Updates the button to pin or unpin the currently open directory.
+    /// The button is displayed as selected while the directory is pinned.
+    fn ui_update_pin_button(&mut self, ui: &mut egui::Ui, button_size: egui::Vec2) {
+        let Some(path) = self.current_directory().map(Path::to_path_buf) else {
+            return;
+        };
+
+        let pinned = self.is_pinned(&path);
+        let tooltip = if pinned {
+            &self.config.labels.tooltip_unpin_directory
+        } else {
+            &self.config.labels.tooltip_pin_directory
+        };
+
+        let response = ui.add_sized(
+            button_size,
+            egui::Button::new(&self.config.pinned_icon).selected(pinned),
+        );
+        Self::set_accessible_name(&response, tooltip);
+
+        if response.on_hover_text(tooltip).clicked() {
+            self.toggle_pinned(path);
+        }
+    }
+
+    /// Updates the navigation buttons like parent or previous directory
+    fn ui_update_nav_buttons(&mut self, ui: &mut egui::Ui, button_size: egui::Vec2) {
+        if self.config.show_parent_button
+            && Self::ui_nav_button(
+                ui,
+                self.current_directory().and_then(Path::parent).is_some(),
+                button_size,
+                "⏶",
+                &self.config.labels.tooltip_parent_directory,
+                &self.config.labels.tooltip_no_parent_directory,
+            )
+        {
+            self.load_parent_directory();
+        }
+
+        if self.config.show_back_button
+            && Self::ui_nav_button(
+                ui,
+                self.can_go_back(),
+                button_size,
+                "⏴",
+                &self.config.labels.tooltip_back,
+                &self.config.labels.tooltip_no_previous_directory,
+            )
+        {
+            self.load_previous_directory();
+        }
+
+        if self.config.show_forward_button
+            && Self::ui_nav_button(
+                ui,
+                self.can_go_forward(),
+                button_size,
+                "⏵",
+                &self.config.labels.tooltip_forward,
+                &self.config.labels.tooltip_no_next_directory,
+            )
+        {
+            self.load_next_directory();
+        }
+
+        if self.config.show_new_folder_button
+            && self.can_create_folder()
+            && Self::ui_nav_button(
+                ui,
+                !self.create_directory_dialog.is_open(),
+                button_size,
+                "+",
+                &self.config.labels.tooltip_new_folder,
+                &self.config.labels.tooltip_new_folder,
+            )
+        {
+            self.open_new_folder_dialog();
+        }
+    }
+
+    /// Updates the view to display the current path.
+    /// This could be the view for displaying the current path and the individual sections,
+    /// as well as the view for text editing of the current path.
+    fn ui_update_current_path(&mut self, ui: &mut egui::Ui, width: f32) {
+        egui::Frame::default()
+            .stroke(egui::Stroke::new(
+                1.0,
+                ui.ctx().style().visuals.window_stroke.color,
+            ))
+            .inner_margin(egui::Margin::from(4))
+            .corner_radius(egui::CornerRadius::from(4))
+            .show(ui, |ui| {
+                const EDIT_BUTTON_SIZE: egui::Vec2 = egui::Vec2::new(22.0, 20.0);
+
+                if self.path_edit_visible {
+                    self.ui_update_path_edit(ui, width, EDIT_BUTTON_SIZE);
+                } else {
+                    self.ui_update_path_display(ui, width, EDIT_BUTTON_SIZE);
+                }
+            });
+    }
+
+    /// Updates the view when the currently open path with the individual sections is displayed.
+    fn ui_update_path_display(
+        &mut self,
+        ui: &mut egui::Ui,
+        width: f32,
+        edit_button_size: egui::Vec2,
+    ) {
+        ui.style_mut().always_scroll_the_only_direction = true;
+        ui.style_mut().spacing.scroll.bar_width = 8.0;
+
+        let max_width = if self.config.show_path_edit_button {
+            ui.style()
+                .spacing
+                .item_spacing
+                .x
+                .mul_add(-2.0, width - edit_button_size.x)
+        } else {
+            width
+        };
+
+        egui::ScrollArea::horizontal()
+            .auto_shrink([false, false])
+            .stick_to_right(true)
+            .max_width(max_width)
+            .show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    ui.style_mut().spacing.item_spacing.x /= 2.5;
+                    ui.style_mut().spacing.button_padding = egui::Vec2::new(5.0, 3.0);
+
+                    if let Some(data) = self.current_directory().map(Path::to_path_buf) {
+                        for (i, (mut segment_str, path)) in
+                            Self::get_path_segments(&data).into_iter().enumerate()
+                        {
+                            if self.is_pinned(&path) {
+                                segment_str =
+                                    format!("{} {}", &self.config.pinned_icon, segment_str);
+                            };
+
+                            if i != 0 {
+                                ui.label(self.config.directory_separator.as_str());
+                            }
+
+                            let re = ui.button(segment_str);
+
+                            if re.clicked() {
+                                self.load_directory(path.as_path());
+                                return;
+                            }
+
+                            self.ui_update_path_context_menu(&re, &path);
+                        }
+                    }
+                });
+            });
+
+        if !self.config.show_path_edit_button {
+            return;
+        }
+
+        let response = ui.add_sized(
+            edit_button_size,
+            egui::Button::new("🖊").fill(egui::Color32::TRANSPARENT),
+        );
+
+        Self::set_accessible_name(&response, &self.config.labels.tooltip_edit_path);
+
+        if response
+            .on_hover_text(&self.config.labels.tooltip_edit_path)
+            .clicked()
+        {
+            self.open_path_edit();
+        }
+    }
+
+    /// Splits the given path into the segments displayed in the top panel.
+    /// Each segment contains the display name and the path to open when the segment is clicked.
+    ///
+    /// The root directory is displayed as a single segment, for example `/` on Unix
+    /// or `C:\` on Windows.
+    fn get_path_segments(path: &Path) -> Vec<(String, PathBuf)> {
+        let mut segments: Vec<(String, PathBuf)> = Vec::new();
+        let mut current = PathBuf::new();
+
+        for component in path.components() {
+            current.push(component);
+            let name = component.as_os_str().to_string_lossy().into_owned();
+
+            // The root directory follows the prefix on Windows, e.g. `C:` and `\`
+            if let (Component::RootDir, Some(last)) = (component, segments.last_mut()) {
+                last.0.push_str(&name);
+                last.1.clone_from(&current);
+                continue;
+            }
+
+            segments.push((name, current.clone()));
+        }
+
+        segments
+    }
+
+    /// Updates the view when the user currently wants to text edit the current path.
+    fn ui_update_path_edit(&mut self, ui: &mut egui::Ui, width: f32, edit_button_size: egui::Vec2) {
+        let desired_width: f32 = ui
+            .style()
+            .spacing
+            .item_spacing
+            .x
+            .mul_add(-3.0, width - edit_button_size.x);
+
+        let response = egui::TextEdit::singleline(&mut self.path_edit_value)
+            .desired_width(desired_width)
+            .show(ui)
+            .response;
+
+        if self.path_edit_activate {
+            response.request_focus();
+            Self::set_cursor_to_end(&response, &self.path_edit_value);
+            self.path_edit_activate = false;
+        }
+
+        if self.path_edit_request_focus {
+            response.request_focus();
+            self.path_edit_request_focus = false;
+        }
+
+        let btn_response = ui.add_sized(edit_button_size, egui::Button::new("✔"));
+        Self::set_accessible_name(&btn_response, &self.config.labels.confirm);
+        let btn_response = btn_response.on_hover_text(&self.config.labels.confirm);
+
+        if btn_response.clicked() {
+            self.submit_path_edit();
+        }
+
+        if !response.has_focus() && !btn_response.contains_pointer() {
+            self.path_edit_visible = false;
+        }
+    }
+
+    /// Updates the hamburger menu containing different options.
+    fn ui_update_hamburger_menu(&mut self, ui: &mut egui::Ui) {
+        const SEPARATOR_SPACING: f32 = 2.0;
+
+        if self.config.show_reload_button && ui.button(&self.config.labels.reload).clicked() {
+            self.refresh();
+            ui.close_kind(UiKind::Menu);
+        }
+
+        let working_dir = self.config.file_system.current_dir();
+
+        if self.config.show_working_directory_button
+            && working_dir.is_ok()
+            && ui.button(&self.config.labels.working_directory).clicked()
+        {
+            self.load_directory(&working_dir.unwrap_or_default());
+            ui.close_kind(UiKind::Menu);
+        }
+
+        if (self.config.show_reload_button || self.config.show_working_directory_button)
+            && (self.config.show_hidden_option
+                || self.config.show_system_files_option
+                || self.config.respect_ignore_files)
+        {
+            ui.add_space(SEPARATOR_SPACING);
+            ui.separator();
+            ui.add_space(SEPARATOR_SPACING);
+        }
+
+        if self.config.show_hidden_option
+            && ui
+                .checkbox(
+                    &mut self.config.storage.show_hidden,
+                    &self.config.labels.show_hidden,
+                )
+                .clicked()
+        {
+            self.refresh();
+            ui.close_kind(UiKind::Menu);
+        }
+
+        if self.config.show_system_files_option
+            && ui
+                .checkbox(
+                    &mut self.config.storage.show_system_files,
+                    &self.config.labels.show_system_files,
+                )
+                .clicked()
+        {
+            self.refresh();
+            ui.close_kind(UiKind::Menu);
+        }
+
+        if self.config.respect_ignore_files
+            && ui
+                .checkbox(
+                    &mut self.config.storage.show_ignored,
+                    &self.config.labels.show_ignored,
+                )
+                .clicked()
+        {
+            self.refresh();
+            ui.close_kind(UiKind::Menu);
+        }
+    }
+
+    /// Updates the search input
+    fn ui_update_search(&mut self, ui: &mut egui::Ui) {
+        egui::Frame::default()
+            .stroke(egui::Stroke::new(
+                1.0,
+                ui.ctx().style().visuals.window_stroke.color,
+            ))
+            .inner_margin(egui::Margin::symmetric(4, 4))
+            .corner_radius(egui::CornerRadius::from(4))
+            .show(ui, |ui| {
+                ui.with_layout(egui::Layout::left_to_right(egui::Align::Min), |ui| {
+                    ui.add_space(ui.ctx().style().spacing.item_spacing.y);
+
+                    ui.label(egui::RichText::from("🔍").size(15.0));
+
+                    let re = ui.add_sized(
+                        egui::Vec2::new(ui.available_width(), 0.0),
+                        egui::TextEdit::singleline(&mut self.search_value),
+                    );
+
+                    self.search_input_id = Some(re.id);
+                    self.edit_search_on_text_input(ui);
+
+                    if re.changed() || self.init_search {
+                        self.selected_item = None;
+                        self.select_first_visible_item();
+                    }
+
+                    if self.init_search {
+                        re.request_focus();
+                        Self::set_cursor_to_end(&re, &self.search_value);
+                        self.directory_content.reset_multi_selection();
+
+                        self.init_search = false;
+                    }
+                });
+            });
+    }
+
+    /// Focuses and types into the search input, if text input without
+    /// shortcut modifiers is detected, and no other inputs are focused.
+    ///
+    /// # Arguments
+    ///
+    /// - `re`: The [`egui::Response`] returned by the filter text edit widget
+    fn edit_search_on_text_input(&mut self, ui: &egui::Ui) {
+        if ui.memory(|mem| mem.focused().is_some()) {
+            return;
+        }
+
+        ui.input(|inp| {
+            // We stop if any modifier is active besides only shift
+            if inp.modifiers.any() && !inp.modifiers.shift_only() {
+                return;
+            }
+
+            // If we find any text input event, we append it to the filter string
+            // and allow proceeding to activating the filter input widget.
+            for text in inp.events.iter().filter_map(|ev| match ev {
+                egui::Event::Text(t) => Some(t),
+                _ => None,
+            }) {
+                self.search_value.push_str(text);
+                self.init_search = true;
+            }
+        });
+    }
+
+    /// Updates the left panel of the dialog. Including the list of the user directories (Places)
+    /// and system disks (Devices, Removable Devices).
+    fn ui_update_left_panel(&mut self, ui: &mut egui::Ui) {
+        ui.with_layout(egui::Layout::top_down_justified(egui::Align::LEFT), |ui| {
+            // Spacing multiplier used between sections in the left sidebar
+            const SPACING_MULTIPLIER: f32 = 4.0;
+
+            egui::containers::ScrollArea::vertical()
+                .auto_shrink([false, false])
+                .show(ui, |ui| {
+                    // Spacing for the first section in the left sidebar
+                    let mut spacing = ui.ctx().style().spacing.item_spacing.y * 2.0;
+
+                    // Update paths pinned to the left sidebar by the user
+                    if self.config.show_pinned_folders && self.ui_update_pinned_paths(ui, spacing) {
+                        spacing = ui.ctx().style().spacing.item_spacing.y * SPACING_MULTIPLIER;
+                    }
+
+                    // Update custom quick access sections
+                    let quick_accesses = std::mem::take(&mut self.config.quick_accesses);
+
+                    for quick_access in &quick_accesses {
+                        ui.add_space(spacing);
+                        self.ui_update_quick_access(ui, quick_access);
+                        spacing = ui.ctx().style().spacing.item_spacing.y * SPACING_MULTIPLIER;
+                    }
+
+                    self.config.quick_accesses = quick_accesses;
+
+                    // Update native quick access sections
+                    if self.config.show_places && self.ui_update_user_directories(ui, spacing) {
+                        spacing = ui.ctx().style().spacing.item_spacing.y * SPACING_MULTIPLIER;
+                    }
+
+                    let disks = std::mem::take(&mut self.system_disks);
+
+                    if self.config.show_devices && self.ui_update_devices(ui, spacing, &disks) {
+                        spacing = ui.ctx().style().spacing.item_spacing.y * SPACING_MULTIPLIER;
+                    }
+
+                    if self.config.show_removable_devices
+                        && self.ui_update_removable_devices(ui, spacing, &disks)
+                    {
+                        // Add this when we add a new section after removable devices
+                        // spacing = ui.ctx().style().spacing.item_spacing.y * SPACING_MULTIPLIER;
+                    }
+
+                    self.system_disks = disks;
+
+                    #[cfg(feature = "eject")]
+                    if let Some(mount_point) = self.eject_request.take() {
+                        self.eject_device(&mount_point);
+                    }
+                });
+        });
+    }
+
+    /// Updates a path entry in the left panel.
+    ///
+    /// Returns the response of the selectable label.
+    fn ui_update_left_panel_entry(
+        &mut self,
+        ui: &mut egui::Ui,
+        display_name: &str,
+        path: &Path,
+    ) -> egui::Response {
+        if !self.is_location_available(path) {
+            return ui
+                .selectable_label(
+                    false,
+                    egui::RichText::new(format!("{display_name}  {}", self.config.warn_icon))
+                        .weak(),
+                )
+                .on_hover_text(&self.config.labels.location_unavailable);
+        }
+
+        let is_current = self
+            .current_directory()
+            .is_some_and(|dir| paths_equal(dir, path));
+        let response = ui.selectable_label(is_current, display_name);
+
+        if response.clicked() {
+            self.load_directory(path);
+        }
+
+        response
+    }
+
+    /// Updates a custom quick access section added to the left panel.
+    fn ui_update_quick_access(&mut self, ui: &mut egui::Ui, quick_access: &QuickAccess) {
+        ui.label(&quick_access.heading);
+
+        for entry in &quick_access.paths {
+            self.ui_update_left_panel_entry(ui, &entry.display_name, &entry.path);
+        }
+    }
+
+    /// Updates the list of pinned folders.
+    ///
+    /// Returns true if at least one directory item was included in the list and the
+    /// heading is visible. If no item was listed, false is returned.
+    fn ui_update_pinned_paths(&mut self, ui: &mut egui::Ui, spacing: f32) -> bool {
+        let mut visible = false;
+
+        for (i, path) in self
+            .config
+            .storage
+            .pinned_folders
+            .clone()
+            .iter()
+            .enumerate()
+        {
+            if i == 0 {
+                ui.add_space(spacing);
+                ui.label(self.config.labels.heading_pinned.as_str());
+
+                visible = true;
+            }
+
+            let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+
+            let response = self.ui_update_left_panel_entry(
+                ui,
+                &format!("{}  {}", self.config.pinned_icon, file_name),
+                path.as_path(),
+            );
+
+            self.ui_update_path_context_menu(&response, path);
+
+            if !self.is_location_available(path) {
+                egui::Popup::menu(&response).show(|ui| {
+                    if ui.button(&self.config.labels.unpin_folder).clicked() {
+                        self.unpin_path(path);
+                        ui.close_kind(UiKind::Menu);
+                    }
+                });
+            }
+        }
+
+        visible
+    }
+
+    /// Updates the list of user directories (Places).
+    ///
+    /// Returns true if at least one directory was included in the list and the
+    /// heading is visible. If no directory was listed, false is returned.
+    fn ui_update_user_directories(&mut self, ui: &mut egui::Ui, spacing: f32) -> bool {
+        // Take temporary ownership of the user directories and configuration.
+        // This is done so that we don't have to clone the user directories and
+        // configured display names.
+        let user_directories = std::mem::take(&mut self.user_directories);
+        let mut labels = std::mem::take(&mut self.config.labels);
+
+        // The tooltip of unavailable locations is still displayed by the entries
+        std::mem::swap(
+            &mut self.config.labels.location_unavailable,
+            &mut labels.location_unavailable,
+        );
+
+        let mut visible = false;
+
+        if let Some(dirs) = &user_directories {
+            // The labels contain the icon of the directory
+            let places = [
+                (Place::Home, &labels.home_dir, dirs.home_dir()),
+                (Place::Desktop, &labels.desktop_dir, dirs.desktop_dir()),
+                (Place::Documents, &labels.documents_dir, dirs.document_dir()),
+                (Place::Downloads, &labels.downloads_dir, dirs.download_dir()),
+                (Place::Audio, &labels.audio_dir, dirs.audio_dir()),
+                (Place::Pictures, &labels.pictures_dir, dirs.picture_dir()),
+                (Place::Videos, &labels.videos_dir, dirs.video_dir()),
+                (Place::Public, &labels.public_dir, dirs.public_dir()),
+                (Place::Templates, &labels.templates_dir, dirs.template_dir()),
+            ];
+
+            let entries: Vec<(&String, &Path)> = places
+                .into_iter()
+                .filter(|(place, _, _)| !self.config.hidden_places.contains(place))
+                .filter_map(|(_, label, path)| path.map(|path| (label, path)))
+                .collect();
+
+            if !entries.is_empty() {
+                ui.add_space(spacing);
+                self.ui_update_left_panel_section(
+                    ui,
+                    LeftPanelSection::Places,
+                    &labels.heading_places,
+                    |this, ui| {
+                        for (label, path) in entries {
+                            this.ui_update_left_panel_entry(ui, label, path);
+                        }
+                    },
+                );
+
+                visible = true;
+            }
+        }
+
+        std::mem::swap(
+            &mut self.config.labels.location_unavailable,
+            &mut labels.location_unavailable,
+        );
+        self.user_directories = user_directories;
+        self.config.labels = labels;
+
+        visible
+    }
+
+    /// Updates the list of devices like system disks.
+    ///
+    /// Returns true if at least one device was included in the list and the
+    /// heading is visible. If no device was listed, false is returned.
+    fn ui_update_devices(&mut self, ui: &mut egui::Ui, spacing: f32, disks: &Disks) -> bool {
+        let heading = self.config.labels.heading_devices.clone();
+
+        self.ui_update_device_section(
+            ui,
+            spacing,
+            LeftPanelSection::Devices,
+            &heading,
+            disks.iter().filter(|x| !x.is_removable()).collect(),
+        )
+    }
+
+    /// Updates the list of removable devices like USB drives.
+    ///
+    /// Returns true if at least one device was included in the list and the
+    /// heading is visible. If no device was listed, false is returned.
+    fn ui_update_removable_devices(
+        &mut self,
+        ui: &mut egui::Ui,
+        spacing: f32,
+        disks: &Disks,
+    ) -> bool {
+        let heading = self.config.labels.heading_removable_devices.clone();
+
+        self.ui_update_device_section(
+            ui,
+            spacing,
+            LeftPanelSection::RemovableDevices,
+            &heading,
+            disks.iter().filter(|x| x.is_removable()).collect(),
+        )
+    }
+
+    /// Updates a collapsible section of the left panel listing the given devices.
+    ///
+    /// Returns true if at least one device was included in the list and the
+    /// heading is visible. If no device was listed, false is returned.
+    fn ui_update_device_section(
+        &mut self,
+        ui: &mut egui::Ui,
+        spacing: f32,
+        section: LeftPanelSection,
+        heading: &str,
+        devices: Vec<&Disk>,
+    ) -> bool {
+        if devices.is_empty() {
+            return false;
+        }
+
+        ui.add_space(spacing);
+        self.ui_update_left_panel_section(ui, section, heading, |this, ui| {
+            for device in devices {
+                this.ui_update_device_entry(ui, device);
+            }
+        });
+
+        true
+    }
+
+    /// Updates a section of the left panel that can be collapsed by clicking its heading.
+    /// The collapse state is stored inside the dialog, so that it can be queried using
+    /// `FileDialog::is_section_collapsed`.
+    fn ui_update_left_panel_section(
+        &mut self,
+        ui: &mut egui::Ui,
+        section: LeftPanelSection,
+        heading: &str,
+        add_contents: impl FnOnce(&mut Self, &mut egui::Ui),
+    ) {
+        let open = !self.is_section_collapsed(section);
+
+        let response = egui::CollapsingHeader::new(heading)
+            .id_salt(self.window_id.with(section))
+            .open(Some(open))
+            .show(ui, |ui| add_contents(self, ui));
+
+        if response.header_response.clicked() {
+            self.set_section_collapsed(section, open);
+        }
+    }
+
+    /// Updates a device entry of a device list like "Devices" or "Removable Devices".
+    fn ui_update_device_entry(&mut self, ui: &mut egui::Ui, device: &Disk) {
+        let label = if device.is_removable() {
+            format!(
+                "{}  {}",
+                self.config.removable_device_icon,
+                device.display_name()
+            )
+        } else {
+            format!("{}  {}", self.config.device_icon, device.display_name())
+        };
+
+        let response = self.ui_update_left_panel_entry(ui, &label, device.mount_point());
+
+        #[cfg(feature = "eject")]
+        self.ui_update_eject_device(ui, &response, device);
+        #[cfg(not(feature = "eject"))]
+        let _ = response;
+    }
+
+    /// Updates the context menu to eject the given removable device and displays
+    /// the error if the device could not be ejected.
+    #[cfg(feature = "eject")]
+    fn ui_update_eject_device(
+        &mut self,
+        ui: &mut egui::Ui,
+        item_response: &egui::Response,
+        device: &Disk,
+    ) {
+        if !self.config.show_eject_button || !device.is_removable() {
+            return;
+        }
+
+        item_response.context_menu(|ui| {
+            let button = egui::Button::new(&self.config.labels.eject_device);
+            if ui
+                .add_enabled(self.ejecting_device.is_none(), button)
+                .clicked()
+            {
+                self.eject_request = Some(device.mount_point().to_path_buf());
+                ui.close_kind(UiKind::Menu);
+            }
+        });
+
+        if let Some((_, err)) = self
+            .eject_error
+            .as_ref()
+            .filter(|(path, _)| paths_equal(path, device.mount_point()))
+        {
+            ui_error_label(ui, &self.config.err_icon, err);
+        }
+    }
+
+    /// Ejects the device mounted at the given path.
+    ///
+    /// If the open directory is located on the device, the directory containing the mount
+    /// point is opened first, so that the dialog no longer reads from the device.
+    /// The device is ejected via a separate thread if `load_via_thread` is enabled,
+    /// because the platform tools block until all data was written to the device.
+    #[cfg(feature = "eject")]
+    fn eject_device(&mut self, mount_point: &Path) {
+        if self
+            .current_directory()
+            .is_some_and(|dir| dir.starts_with(mount_point))
+        {
+            let outside = mount_point.parent().map(Path::to_path_buf).or_else(|| {
+                self.user_directories
+                    .as_ref()
+                    .and_then(|dirs| dirs.home_dir())
+                    .map(Path::to_path_buf)
+            });
+
+            if let Some(path) = outside {
+                self.load_directory(&path);
+            }
+        }
+
+        self.eject_error = None;
+
+        if !self.config.load_via_thread {
+            self.finish_eject(mount_point.to_path_buf(), crate::eject::eject(mount_point));
+            return;
+        }
+
+        let (tx, rx) = mpsc::channel();
+        let p = mount_point.to_path_buf();
+        std::thread::spawn(move || {
+            let _ = tx.send(crate::eject::eject(&p));
+        });
+
+        self.ejecting_device = Some((mount_point.to_path_buf(), Mutex::new(rx)));
+    }
+
+    /// Checks if the device that is ejected on the other thread finished ejecting.
+    #[cfg(feature = "eject")]
+    fn update_ejecting_device(&mut self, ctx: &egui::Context) {
+        let Some((_, recv)) = &mut self.ejecting_device else {
+            return;
+        };
+
+        let result = match recv.get_mut().map(|recv| recv.try_recv()) {
+            Ok(Err(mpsc::TryRecvError::Empty)) => {
+                ctx.request_repaint();
+                return;
+            }
+            Ok(Ok(result)) => result,
+            Ok(Err(mpsc::TryRecvError::Disconnected)) | Err(_) => {
+                Err(std::io::Error::other("thread ended unexpectedly"))
+            }
+        };
+
+        if let Some((mount_point, _)) = self.ejecting_device.take() {
+            self.finish_eject(mount_point, result);
+        }
+    }
+
+    /// Reloads the list of devices if the device was ejected.
+    /// Stores the error to display it below the device if it could not be ejected.
+    #[cfg(feature = "eject")]
+    fn finish_eject(&mut self, mount_point: PathBuf, result: std::io::Result<()>) {
+        match result {
+            Ok(()) => {
+                self.eject_error = None;
+                self.refresh();
+            }
+            Err(err) => {
+                self.eject_error = Some((
+                    mount_point,
+                    format!("{} {err}", self.config.labels.err_eject_device),
+                ));
+            }
+        }
+    }
+
+    /// Updates the bottom panel showing the selected item and main action buttons.
+    fn ui_update_bottom_panel(&mut self, ui: &mut egui::Ui) {
+        const BUTTON_HEIGHT: f32 = 20.0;
+        ui.add_space(5.0);
+
+        // Calculate the width of the action buttons
+        let label_submit_width = match self.mode {
+            DialogMode::PickDirectory | DialogMode::PickFile | DialogMode::PickMultiple => {
+                Self::calc_text_width(ui, &self.config.labels.open_button)
+            }
+            DialogMode::SaveFile => Self::calc_text_width(ui, &self.config.labels.save_button),
+        };
+
+        let mut btn_width = Self::calc_text_width(ui, &self.config.labels.cancel_button);
+        if label_submit_width > btn_width {
+            btn_width = label_submit_width;
+        }
+
+        btn_width += ui.spacing().button_padding.x * 4.0;
+
+        // The size of the action buttons "cancel" and "open"/"save"
+        let button_size: egui::Vec2 = egui::Vec2::new(btn_width, BUTTON_HEIGHT);
+
+        self.ui_update_selection_preview(ui, button_size);
+
+        if self.mode == DialogMode::SaveFile && self.config.save_extensions.is_empty() {
+            ui.add_space(ui.style().spacing.item_spacing.y);
+        }
+
+        self.ui_update_action_buttons(ui, button_size);
+    }
+
+    /// Updates the selection preview like "Selected directory: X"
+    fn ui_update_selection_preview(&mut self, ui: &mut egui::Ui, button_size: egui::Vec2) {
+        const SELECTION_PREVIEW_MIN_WIDTH: f32 = 50.0;
+        let item_spacing = ui.style().spacing.item_spacing;
+
+        let render_filter_selection = (!self.config.file_filters.is_empty()
+            && (self.mode == DialogMode::PickFile || self.mode == DialogMode::PickMultiple))
+            || (!self.config.save_extensions.is_empty() && self.mode == DialogMode::SaveFile);
+
+        let filter_selection_width = button_size.x.mul_add(2.0, item_spacing.x);
+        let mut filter_selection_separate_line = false;
+
+        ui.horizontal(|ui| {
+            match &self.mode {
+                DialogMode::PickDirectory => ui.label(&self.config.labels.selected_directory),
+                DialogMode::PickFile => ui.label(&self.config.labels.selected_file),
+                DialogMode::PickMultiple => ui.label(&self.config.labels.selected_items),
+                DialogMode::SaveFile => ui.label(&self.config.labels.file_name),
+            };
+
+            // Make sure there is enough width for the selection preview. If the available
+            // width is not enough, render the drop-down menu to select a file filter or
+            // save extension on a separate line and give the selection preview
+            // the entire available width.
+            let mut scroll_bar_width: f32 =
+                ui.available_width() - filter_selection_width - item_spacing.x;
+
+            if scroll_bar_width < SELECTION_PREVIEW_MIN_WIDTH || !render_filter_selection {
+                filter_selection_separate_line = true;
+                scroll_bar_width = ui.available_width();
+            }
+
+            match &self.mode {
+                DialogMode::PickDirectory | DialogMode::PickFile | DialogMode::PickMultiple => {
+                    self.ui_update_selection_preview_text(ui, scroll_bar_width);
+                }
+                DialogMode::SaveFile => {
+                    let mut output = egui::TextEdit::singleline(&mut self.file_name_input)
+                        .cursor_at_end(false)
+                        .margin(egui::Margin::symmetric(4, 3))
+                        .desired_width(scroll_bar_width - item_spacing.x)
+                        .show(ui);
+
+                    self.file_name_input_id = Some(output.response.id);
+
+                    if self.file_name_input_request_focus {
+                        self.highlight_file_name_input(&mut output);
+                        output.state.store(ui.ctx(), output.response.id);
+
+                        output.response.request_focus();
+                        self.file_name_input_request_focus = false;
+                    }
+
+                    if output.response.changed() {
+                        self.file_name_input_error = self.validate_file_name_input();
+                        self.update_file_name_suggestion();
+                    }
+
+                    if output.response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter))
+                    {
+                        self.submit();
+                    }
+                }
+            };
+
+            if !filter_selection_separate_line && render_filter_selection {
+                if self.mode == DialogMode::SaveFile {
+                    self.ui_update_save_extension_selection(ui, filter_selection_width);
+                } else {
+                    self.ui_update_file_filter_selection(ui, filter_selection_width);
+                }
+            }
+        });
+
+        if filter_selection_separate_line && render_filter_selection {
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Min), |ui| {
+                if self.mode == DialogMode::SaveFile {
+                    self.ui_update_save_extension_selection(ui, filter_selection_width);
+                } else {
+                    self.ui_update_file_filter_selection(ui, filter_selection_width);
+                }
+            });
+        }
+
+        if self.mode == DialogMode::SaveFile {
+            self.ui_update_file_name_suggestion(ui);
+        }
+    }
+
+    /// Updates the button to accept the suggested non-colliding file name.
+    fn ui_update_file_name_suggestion(&mut self, ui: &mut egui::Ui) {
+        let Some(suggestion) = self.file_name_suggestion.clone() else {
+            return;
+        };
+
+        ui.horizontal(|ui| {
+            ui.label(&self.config.labels.file_name_suggestion);
+
+            if ui.button(&suggestion).clicked() {
+                self.file_name_input = suggestion;
+                self.file_name_input_error = self.validate_file_name_input();
+                self.update_file_name_suggestion();
+            }
+        });
+    }
+
+    /// Highlights the characters inside the file name input until the file extension.
+    /// Do not forget to store these changes after calling this function:
+    /// `output.state.store(ui.ctx(), output.response.id);`
+    fn highlight_file_name_input(&self, output: &mut egui::text_edit::TextEditOutput) {
+        if let Some(pos) = self.file_name_input.rfind('.') {
+            let range = if pos == 0 {
+                CCursorRange::two(CCursor::new(0), CCursor::new(0))
+            } else {
+                CCursorRange::two(CCursor::new(0), CCursor::new(pos))
+            };
+
+            output.state.cursor.set_char_range(Some(range));
+        }
+    }
+
+    /// Updates the text displaying the names of the selected items.
+    /// Hovering the text displays the total size of the selected files.
+    fn ui_update_selection_preview_text(&self, ui: &mut egui::Ui, width: f32) {
+        use egui::containers::scroll_area::ScrollBarVisibility;
+
+        let text = self.get_selection_preview_text();
+        let size_summary = self.get_selection_size_summary();
+
+        egui::containers::ScrollArea::horizontal()
+            .auto_shrink([false, false])
+            .max_width(width)
+            .stick_to_right(true)
+            .scroll_bar_visibility(ScrollBarVisibility::AlwaysHidden)
+            .show(ui, |ui| {
+                let response = ui.colored_label(ui.style().visuals.selection.bg_fill, text);
+
+                if let Some(summary) = size_summary {
+                    response.on_hover_text(summary);
+                }
+            });
+    }
+
+    fn get_selection_preview_text(&self) -> String {
+        if self.is_selection_valid() {
+            match &self.mode {
+                DialogMode::PickDirectory | DialogMode::PickFile => self
+                    .selected_item
+                    .as_ref()
+                    .map_or_else(String::new, |item| item.file_name().to_string()),
+                DialogMode::PickMultiple => {
+                    let mut result = String::new();
+
+                    for (i, item) in self
+                        .get_dir_content_filtered_iter()
+                        .filter(|p| p.selected)
+                        .enumerate()
+                    {
+                        if i == 0 {
+                            result += item.file_name();
+                            continue;
+                        }
+
+                        result += format!(", {}", item.file_name()).as_str();
+                    }
+
+                    result
+                }
+                DialogMode::SaveFile => String::new(),
+            }
+        } else {
+            String::new()
+        }
+    }
+
+    /// Gets the total size of the selected files, formatted using the configured size units.
+    /// Returns None if no files are selected or if none of their sizes are known.
+    fn get_selection_size_summary(&self) -> Option<String> {
+        if !self.is_selection_valid() {
+            return None;
+        }
+
+        let sizes: Vec<u64> = match &self.mode {
+            DialogMode::PickFile => self
+                .selected_item
+                .iter()
+                .filter_map(|item| item.metadata().size)
+                .collect(),
+            DialogMode::PickMultiple => self
+                .get_dir_content_filtered_iter()
+                .filter(|item| item.selected && item.is_file())
+                .filter_map(|item| item.metadata().size)
+                .collect(),
+            DialogMode::PickDirectory | DialogMode::SaveFile => Vec::new(),
+        };
+
+        if sizes.is_empty() {
+            return None;
+        }
+
+        Some(format_size(sizes.iter().sum(), self.config.size_units))
+    }
+
+    fn ui_update_file_filter_selection(&mut self, ui: &mut egui::Ui, width: f32) {
+        let selected_filter = self.get_selected_file_filter();
+        let selected_text = match selected_filter {
+            Some(f) => &f.name,
+            None => &self.config.labels.file_filter_all_files,
+        };
+
+        // The item that the user selected inside the drop down.
+        // If none, the user did not change the selected item this frame.
+        let mut select_filter: Option<Option<FileFilter>> = None;
+
+        egui::containers::ComboBox::from_id_salt(self.window_id.with("file_filter_selection"))
+            .width(width)
+            .selected_text(selected_text)
+            .wrap_mode(egui::TextWrapMode::Truncate)
+            .show_ui(ui, |ui| {
+                for filter in &self.config.file_filters {
+                    let selected = selected_filter.is_some_and(|f| f.id == filter.id);
+
+                    if !selected && !self.directory_content.is_file_filter_available(filter.id) {
+                        continue;
+                    }
+
+                    if ui.selectable_label(selected, &filter.name).clicked() {
+                        select_filter = Some(Some(filter.clone()));
+                    }
+                }
+
+                if ui
+                    .selectable_label(
+                        selected_filter.is_none(),
+                        &self.config.labels.file_filter_all_files,
+                    )
+                    .clicked()
+                {
+                    select_filter = Some(None);
+                }
+            });
+
+        if let Some(i) = select_filter {
+            self.select_file_filter(i);
+        }
+    }
+
+    fn ui_update_save_extension_selection(&mut self, ui: &mut egui::Ui, width: f32) {
+        let selected_extension = self.get_selected_save_extension();
+        let selected_text = match selected_extension {
+            Some(e) => &e.to_string(),
+            None => &self.config.labels.save_extension_any,
+        };
+
+        // The item that the user selected inside the drop down.
+        // If none, the user did not change the selected item this frame.
+        let mut select_extension: Option<Option<SaveExtension>> = None;
+
+        egui::containers::ComboBox::from_id_salt(self.window_id.with("save_extension_selection"))
+            .width(width)
+            .selected_text(selected_text)
+            .wrap_mode(egui::TextWrapMode::Truncate)
+            .show_ui(ui, |ui| {
+                for extension in &self.config.save_extensions {
+                    let selected = selected_extension.is_some_and(|s| s.id == extension.id);
+
+                    if ui
+                        .selectable_label(selected, extension.to_string())
+                        .clicked()
+                    {
+                        select_extension = Some(Some(extension.clone()));
+                    }
+                }
+            });
+
+        if let Some(i) = select_extension {
+            self.file_name_input_request_focus = true;
+            self.select_save_extension(i);
+        }
+    }
+
+    /// Updates the action buttons like save, open and cancel
+    fn ui_update_action_buttons(&mut self, ui: &mut egui::Ui, button_size: egui::Vec2) {
+        ui.with_layout(egui::Layout::right_to_left(egui::Align::Min), |ui| {
+            let label = match &self.mode {
+                DialogMode::PickDirectory | DialogMode::PickFile | DialogMode::PickMultiple => {
+                    self.config.labels.open_button.as_str()
+                }
+                DialogMode::SaveFile => self.config.labels.save_button.as_str(),
+            };
+
+            let validation_error = self.selection_validation_error();
+            let submit_directory = self.get_submit_directory();
+
+            let submit_response = self.ui_button_sized(
+                ui,
+                submit_directory.is_some() || self.is_selection_valid(),
+                button_size,
+                label,
+                self.file_name_input_error.as_deref().or(validation_error),
+            );
+
+            self.submit_button_id = Some(submit_response.id);
+
+            if submit_response.clicked() {
+                if let Some(dir) = submit_directory {
+                    self.load_directory(&dir);
+                } else {
+                    self.submit();
+                }
+            }
+
+            let cancel_response = ui.add_sized(
+                button_size,
+                egui::Button::new(self.config.labels.cancel_button.as_str()),
+            );
+
+            self.cancel_button_id = Some(cancel_response.id);
+
+            if cancel_response.clicked() {
+                self.cancel();
+            }
+
+            if let Some(err) = self
+                .selection_validation_error()
+                .or(self.confirm_validation_error.as_deref())
+            {
+                ui_error_label(ui, &self.config.err_icon, err);
+            }
+
+            if let Some(mut custom_ui) = self.custom_bottom_ui.take() {
+                let context = self.get_custom_ui_context();
+
+                ui.with_layout(egui::Layout::left_to_right(egui::Align::Center), |ui| {
+                    (custom_ui.0)(ui, &context);
+                });
+
+                self.custom_bottom_ui = Some(custom_ui);
+            }
+        });
+    }
+
+    /// Updates the central panel. This is either the contents of the directory
+    /// or the error message when there was an error loading the current directory.
+    fn ui_update_central_panel(&mut self, ui: &mut egui::Ui) {
+        if self.device_overview {
+            self.ui_update_device_overview(ui);
+            return;
+        }
+
+        if let Some(notice) = &self.directory_notice {
+            egui::TopBottomPanel::top(self.window_id.with("directory_notice"))
+                .resizable(false)
+                .show_separator_line(false)
+                .show_inside(ui, |ui| {
+                    ui.horizontal_wrapped(|ui| {
+                        ui.label(
+                            egui::RichText::new(&self.config.warn_icon)
+                                .color(ui.visuals().warn_fg_color),
+                        );
+                        ui.label(notice);
+                    });
+                });
+        }
+
+        if self.update_directory_content(ui) {
+            return;
+        }
+
+        let filtered_count = self.directory_content.filtered_count();
+        let unreadable_count = self.directory_content.unreadable_count();
+        if filtered_count != 0 || unreadable_count != 0 {
+            egui::TopBottomPanel::bottom(self.window_id.with("filtered_count"))
+                .resizable(false)
+                .show_separator_line(false)
+                .show_inside(ui, |ui| {
+                    if filtered_count != 0 {
+                        ui.weak(format!(
+                            "{} {filtered_count}",
+                            self.config.labels.files_hidden_by_filters
+                        ));
+                    }
+
+                    if unreadable_count != 0 {
+                        ui.horizontal(|ui| {
+                            ui.label(
+                                egui::RichText::new(&self.config.warn_icon)
+                                    .color(ui.visuals().warn_fg_color),
+                            );
+                            ui.weak(format!(
+                                "{} {unreadable_count}",
+                                self.config.labels.entries_unreadable
+                            ));
+                        });
+                    }
+                });
+        }
+
+        self.ui_update_central_panel_content(ui);
+
+        // Make it visible that the keybindings to move the selection apply to the list
+        if self.list_focused {
+            ui.painter().rect_stroke(
+                ui.max_rect(),
+                egui::CornerRadius::from(2),
+                ui.visuals().selection.stroke,
+                egui::StrokeKind::Outside,
+            );
+        }
+    }
+
+    /// Updates the overview of all devices, displayed in place of the directory content
+    /// if the dialog was opened with `start_at_devices` enabled.
+    fn ui_update_device_overview(&mut self, ui: &mut egui::Ui) {
+        const TILE_SIZE: egui::Vec2 = egui::vec2(180.0, 48.0);
+
+        let mut open_device = None;
+
+        egui::containers::ScrollArea::vertical()
+            .id_salt(self.window_id.with("device_overview"))
+            .auto_shrink([false, false])
+            .show(ui, |ui| {
+                ui.label(&self.config.labels.heading_devices);
+
+                ui.horizontal_wrapped(|ui| {
+                    for disk in &self.system_disks {
+                        let icon = if disk.is_removable() {
+                            &self.config.removable_device_icon
+                        } else {
+                            &self.config.device_icon
+                        };
+
+                        let text = egui::RichText::new(format!("{icon}  {}", disk.display_name()))
+                            .size(16.0);
+
+                        if ui
+                            .add_sized(TILE_SIZE, egui::Button::new(text).truncate())
+                            .on_hover_text(disk.mount_point().display().to_string())
+                            .clicked()
+                        {
+                            open_device = Some(disk.mount_point().to_path_buf());
+                        }
+                    }
+                });
+            });
+
+        if let Some(path) = open_device {
+            self.load_directory(&path);
+        }
+    }
+
+    /// Updates the directory content (Not the UI!).
+    /// This is required because the contents of the directory might be loaded on a
+    /// separate thread. This function checks the status of the directory content
+    /// and updates the UI accordingly.
+    fn update_directory_content(&mut self, ui: &mut egui::Ui) -> bool {
+        const SHOW_SPINNER_AFTER: f32 = 0.2;
+
+        match self.directory_content.update() {
+            DirectoryContentState::Pending(timestamp) => {
+                let now = std::time::SystemTime::now();
+                let elapsed = now.duration_since(*timestamp).unwrap_or_default();
+
+                if self
+                    .directory_content
+                    .is_unresponsive(self.config.file_system_timeout)
+                {
+                    self.ui_update_not_responding(ui);
+                } else if elapsed.as_secs_f32() > SHOW_SPINNER_AFTER {
+                    self.ui_update_loading_progress(ui);
+                }
+
+                // Prevent egui from not updating the UI when there is no user input
+                ui.ctx().request_repaint();
+
+                true
+            }
+            DirectoryContentState::Errored(err) => {
+                ui.centered_and_justified(|ui| ui_error_label(ui, &self.config.err_icon, err));
+                true
+            }
+            DirectoryContentState::Finished => {
+                self.update_loaded_directory();
+                self.update_reload_scroll();
+
+                if self.mode == DialogMode::PickDirectory {
+                    if let Some(dir) = self.current_directory() {
+                        let mut dir_entry =
+                            DirectoryEntry::from_path(&self.config, dir, &*self.config.file_system);
+                        self.select_item(&mut dir_entry);
+                    }
+                }
+
+                false
+            }
+            DirectoryContentState::Success => {
+                self.update_reload_scroll();
+                false
+            }
+        }
+    }
+
+    /// Updates the spinner displayed while the directory content is loading,
+    /// including the number of entries read so far and a button to cancel loading.
+    fn ui_update_loading_progress(&mut self, ui: &mut egui::Ui) {
+        let cancel = ui
+            .vertical_centered(|ui| {
+                ui.add_space(ui.available_height() / 3.0);
+                ui.spinner();
+                ui.label(format!(
+                    "{} {}",
+                    self.directory_content.loaded_entries(),
+                    self.config.labels.loading_entries
+                ));
+                ui.button(&self.config.labels.cancel).clicked()
+            })
+            .inner;
+
+        if cancel {
+            self.cancel_directory_load();
+        }
+    }
+
+    /// Updates the view displayed if the directory content did not finish loading within
+    /// the configured timeout, with buttons to load the directory again or to return to
+    /// the previous directory.
+    fn ui_update_not_responding(&mut self, ui: &mut egui::Ui) {
+        let (retry, go_back) = ui
+            .vertical_centered(|ui| {
+                ui.add_space(ui.available_height() / 3.0);
+                ui_error_label(
+                    ui,
+                    &self.config.err_icon,
+                    &self.config.labels.err_not_responding,
+                );
+
+                let retry = ui.button(&self.config.labels.retry).clicked();
+                let go_back = ui
+                    .add_enabled(
+                        self.previous_directory.is_some(),
+                        egui::Button::new(&self.config.labels.go_back),
+                    )
+                    .clicked();
+
+                (retry, go_back)
+            })
+            .inner;
+
+        if retry {
+            self.reload_directory();
+        } else if go_back {
+            self.cancel_directory_load();
+        }
+    }
+
+    /// Scrolls to the selected item after reloading the directory, if the item
+    /// still exists and moved to a different position.
+    fn update_reload_scroll(&mut self) {
+        if let Some(index) = self.reload_selection_index.take() {
+            self.scroll_to_selection = self
+                .get_selected_item_index()
+                .is_some_and(|new_index| new_index != index);
+        }
+    }
+
+    /// Updates the contents of the currently open directory.
+    /// TODO: Refactor
+    fn ui_update_central_panel_content(&mut self, ui: &mut egui::Ui) {
+        // Temporarily take ownership of the directory content.
+        let mut data = std::mem::take(&mut self.directory_content);
+
+        // If the multi selection should be reset, excluding the currently
+        // selected primary item.
+        let mut reset_multi_selection = false;
+
+        // The item the user wants to make a batch selection from.
+        // The primary selected item is used for item a.
+        let mut batch_select_item_b: Option<DirectoryEntry> = None;
+
+        // If we should return after updating the directory entries.
+        let mut should_return = false;
+
+        // Prevent egui from not updating the UI while item counts are loading
+        if self.config.show_directory_item_count && self.directory_item_counts.update() {
+            ui.ctx().request_repaint();
+        }
+
+        // Updated before the segments are created, because directories are sorted again
+        // once their sizes are computed
+        if self.update_directory_sizes(&mut data) {
+            ui.ctx().request_repaint();
+        }
+
+        let segments = self.get_content_segments(&data);
+        let total_rows = segments.iter().map(ContentSegment::len).sum();
+
+        ui.with_layout(egui::Layout::top_down_justified(egui::Align::LEFT), |ui| {
+            if self.config.view_mode == ViewMode::Details {
+                self.ui_update_details_header(ui);
+            }
+
+            // Dragging the entries would otherwise scroll the list
+            let scroll_area = egui::containers::ScrollArea::vertical()
+                .auto_shrink([false, false])
+                .scroll_source(egui::scroll_area::ScrollSource {
+                    drag: !self.config.draggable_entries,
+                    ..Default::default()
+                });
+            let row_height = ui.spacing().interact_size.y;
+
+            // Used to move the selection by one page
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            let rows_per_page = (ui.available_height() / (row_height + ui.spacing().item_spacing.y))
+                .floor()
+                .max(1.0) as usize;
+            self.rows_per_page = rows_per_page;
+
+            if self.search_value.is_empty()
+                && !self.is_name_filter_set()
+                && !self.create_directory_dialog.is_open()
+                && !self.scroll_to_selection
+            {
+                // Only update visible items when the search value and name filters are empty,
+                // the create directory dialog is closed and we are currently not scrolling
+                // to the current item.
+                scroll_area.show_rows(ui, row_height, total_rows, |ui, range| {
+                    self.ui_update_content_background(ui);
+
+                    should_return = self.ui_update_content_segments(
+                        ui,
+                        &mut data,
+                        &segments,
+                        range,
+                        &mut reset_multi_selection,
+                        &mut batch_select_item_b,
+                    );
+                });
+            } else {
+                // Update each element if the search value is not empty as we apply the
+                // search value in every frame. We can't use `egui::ScrollArea::show_rows`
+                // because we don't know how many files the search value applies to.
+                // We also have to update every item when the create directory dialog is open as
+                // it's displayed as the last element.
+                scroll_area.show(ui, |ui| {
+                    self.ui_update_content_background(ui);
+
+                    should_return = self.ui_update_content_segments(
+                        ui,
+                        &mut data,
+                        &segments,
+                        0..total_rows,
+                        &mut reset_multi_selection,
+                        &mut batch_select_item_b,
+                    );
+
+                    if let Some(entry) = self.ui_update_create_directory_dialog(ui) {
+                        data.push(entry);
+                    }
+                });
+            }
+        });
+
+        if should_return {
+            return;
+        }
+
+        // Reset the multi selection except the currently selected primary item
+        if reset_multi_selection {
+            for item in data.filtered_iter_mut(&self.search_value) {
+                if let Some(selected_item) = &self.selected_item {
+                    if selected_item.path_eq(item) {
+                        continue;
+                    }
+                }
+
+                item.selected = false;
+            }
+        }
+
+        // Check if we should perform a batch selection
+        if let Some(item_b) = batch_select_item_b {
+            if let Some(item_a) = &self.selected_item {
+                self.batch_select_between(&mut data, item_a, &item_b);
+            }
+        }
+
+        self.directory_content = data;
+        self.scroll_to_selection = false;
+    }
+
+    /// Updates the empty background of the directory content list.
+    /// Clicking the background clears the selection in `DialogMode::PickFile` and
+    /// `DialogMode::PickDirectory`.
+    ///
+    /// Must be called before the rows are added so that the rows are above the background.
+    fn ui_update_content_background(&mut self, ui: &egui::Ui) {
+        let response = ui.interact(
+            ui.clip_rect(),
+            ui.id().with("content_background"),
+            egui::Sense::click(),
+        );
+
+        response.context_menu(|ui| self.ui_update_content_context_menu(ui));
+
+        if !response.clicked() {
+            return;
+        }
+
+        self.list_focused = true;
+        self.parent_entry_selected = false;
+
+        if matches!(self.mode, DialogMode::PickFile | DialogMode::PickDirectory) {
+            self.selected_item = None;
+        }
+    }
+
+    /// Updates the context menu opened by right-clicking the empty space
+    /// of the directory content.
+    fn ui_update_content_context_menu(&mut self, ui: &mut egui::Ui) {
+        if self.can_create_folder() && ui.button(&self.config.labels.new_folder).clicked() {
+            self.open_new_folder_dialog();
+            ui.close_kind(UiKind::Menu);
+        }
+
+        if self.config.show_reload_button && ui.button(&self.config.labels.reload).clicked() {
+            self.refresh();
+            ui.close_kind(UiKind::Menu);
+        }
+
+        if self.config.show_path_edit_button && ui.button(&self.config.labels.paste_path).clicked()
+        {
+            // The clipboard can only be read by the backend, which sends
+            // the text as a paste event in one of the next frames.
+            self.paste_path_requested = true;
+            ui.ctx()
+                .send_viewport_cmd(egui::ViewportCommand::RequestPaste);
+            ui.close_kind(UiKind::Menu);
+        }
+
+        if self.config.show_hidden_option
+            && ui
+                .checkbox(
+                    &mut self.config.storage.show_hidden,
+                    &self.config.labels.show_hidden,
+                )
+                .clicked()
+        {
+            self.refresh();
+            ui.close_kind(UiKind::Menu);
+        }
+    }
+
+    /// Checks if a glob or regex filter hides files from the directory content list.
+    const fn is_name_filter_set(&self) -> bool {
+        #[cfg(feature = "regex")]
+        if self.config.regex_filter.is_some() {
+            return true;
+        }
+
+        self.config.glob_filter.is_some()
+    }
+
+    /// Updates the header of the details view. Clicking a column sorts the entries by
+    /// that column, clicking the column the entries are already sorted by reverses the order.
+    fn ui_update_details_header(&mut self, ui: &mut egui::Ui) {
+        let (rect, _) = ui.allocate_exact_size(
+            egui::vec2(ui.available_width(), ui.spacing().interact_size.y),
+            egui::Sense::hover(),
+        );
+
+        let mut right = rect.right() - ui.spacing().button_padding.x - self.chevron_width();
+        let mut clicked = None;
+
+        // Changes made using the context menu are applied in the next frame
+        let columns = self.config.storage.details_columns.clone();
+
+        // The columns are aligned with the columns drawn by `ui_update_detail_columns`
+        for (index, state) in columns.iter().enumerate().rev() {
+            if !state.visible {
+                continue;
+            }
+
+            let column = egui::Rect::from_x_y_ranges(right - state.width..=right, rect.y_range());
+            let sorting = state.column.sorting();
+
+            let response = self.ui_update_details_header_column(
+                ui,
+                column,
+                egui::Align::Max,
+                sorting,
+                Self::details_column_label(&self.config.labels, state.column),
+            );
+
+            if response.clicked() {
+                clicked = Some(sorting);
+            }
+
+            response.context_menu(|ui| self.ui_update_details_columns_menu(ui, Some(index)));
+            self.ui_update_details_column_resize_handle(ui, index, column);
+
+            right -= state.width + 15.0;
+        }
+
+        let column = egui::Rect::from_x_y_ranges(
+            rect.left() + ui.spacing().button_padding.x..=right,
+            rect.y_range(),
+        );
+
+        let response = self.ui_update_details_header_column(
+            ui,
+            column,
+            egui::Align::Min,
+            FileSorting::Name,
+            &self.config.labels.column_name,
+        );
+
+        if response.clicked() {
+            clicked = Some(FileSorting::Name);
+        }
+
+        response.context_menu(|ui| self.ui_update_details_columns_menu(ui, None));
+
+        ui.separator();
+
+        if let Some(sorting) = clicked {
+            if sorting == self.config.file_sorting {
+                self.set_sort_descending(!self.config.storage.sort_descending);
+            } else {
+                self.set_sort_descending(false);
+                self.set_file_sorting(sorting);
+            }
+        }
+    }
+
+    /// Updates a single column of the details header inside the given rect.
+    /// The column the entries are sorted by displays an arrow indicating the sort order.
+    fn ui_update_details_header_column(
+        &self,
+        ui: &mut egui::Ui,
+        rect: egui::Rect,
+        align: egui::Align,
+        sorting: FileSorting,
+        label: &str,
+    ) -> egui::Response {
+        let text = if self.config.sort_comparator.is_some() || sorting != self.config.file_sorting {
+            label.to_string()
+        } else if self.config.storage.sort_descending {
+            format!("{label} ⏷")
+        } else {
+            format!("{label} ⏶")
+        };
+
+        let layout = egui::Layout::left_to_right(egui::Align::Center).with_main_align(align);
+
+        ui.scope_builder(egui::UiBuilder::new().max_rect(rect).layout(layout), |ui| {
+            ui.add(egui::Button::new(egui::RichText::new(text).strong()).frame(false))
+        })
+        .inner
+    }
+
+    /// Updates the separator on the left side of the given column of the details header,
+    /// which the user can drag to resize the column.
+    fn ui_update_details_column_resize_handle(
+        &mut self,
+        ui: &egui::Ui,
+        index: usize,
+        column: egui::Rect,
+    ) {
+        // Centered inside the spacing between the columns
+        let x = column.left() - 7.5;
+        let rect = egui::Rect::from_x_y_ranges(x - 3.0..=x + 3.0, column.y_range());
+
+        let response = ui
+            .interact(
+                rect,
+                self.window_id.with(("details_column_resize", index)),
+                egui::Sense::drag(),
+            )
+            .on_hover_and_drag_cursor(egui::CursorIcon::ResizeHorizontal);
+
+        let stroke = if response.hovered() || response.dragged() {
+            ui.visuals().widgets.hovered.fg_stroke
+        } else {
+            ui.visuals().widgets.noninteractive.bg_stroke
+        };
+        ui.painter().vline(x, column.y_range(), stroke);
+
+        if response.dragged() {
+            if let Some(state) = self.config.storage.details_columns.get_mut(index) {
+                // The columns are aligned to the right, so dragging to the left widens them
+                state.width = (state.width - response.drag_delta().x).clamp(
+                    *DETAILS_COLUMN_WIDTH_RANGE.start(),
+                    *DETAILS_COLUMN_WIDTH_RANGE.end(),
+                );
+            }
+        }
+    }
+
+    /// Updates the context menu of the details header, used to show and hide the columns.
+    /// If the menu was opened on a column, given by `index`, the column can also be moved.
+    fn ui_update_details_columns_menu(&mut self, ui: &mut egui::Ui, index: Option<usize>) {
+        let labels = &self.config.labels;
+        let columns = &mut self.config.storage.details_columns;
+
+        for state in columns.iter_mut() {
+            ui.checkbox(
+                &mut state.visible,
+                Self::details_column_label(labels, state.column),
+            );
+        }
+
+        let Some(index) = index else {
+            return;
+        };
+
+        // Hidden columns are skipped, so that the column visibly moves
+        let previous = (0..index).rev().find(|i| columns[*i].visible);
+        let next = (index + 1..columns.len()).find(|i| columns[*i].visible);
+
+        ui.separator();
+
+        for (target, label) in [
+            (previous, &labels.column_move_left),
+            (next, &labels.column_move_right),
+        ] {
+            if ui
+                .add_enabled(target.is_some(), egui::Button::new(label))
+                .clicked()
+            {
+                if let Some(target) = target {
+                    columns.swap(index, target);
+                }
+
+                ui.close_kind(UiKind::Menu);
+            }
+        }
+    }
+
+    /// Gets the header text of the given column of the details view.
+    const fn details_column_label(labels: &FileDialogLabels, column: DetailsColumn) -> &str {
+        match column {
+            DetailsColumn::Size => labels.column_size.as_str(),
+            DetailsColumn::LastModified => labels.column_modified.as_str(),
+        }
+    }
+
+    /// Returns the segments of rows displayed in the directory content list.
+    fn get_content_segments(&self, data: &DirectoryContent) -> Vec<ContentSegment> {
+        let mut segments = Vec::new();
+
+        if self.is_parent_entry_visible() {
+            segments.push(ContentSegment::ParentEntry);
+        }
+
+        let folders = data.leading_dir_count();
+        let show_headers = self.config.show_group_headers
+            && self.config.folders_first
+            && self.search_value.is_empty()
+            && !self.is_name_filter_set()
+            && folders != 0
+            && folders != data.len();
+
+        if show_headers {
+            for (group, range) in [
+                (EntryGroup::Folders, 0..folders),
+                (EntryGroup::Files, folders..data.len()),
+            ] {
+                segments.push(ContentSegment::Header(group, range.len()));
+
+                if !self.collapsed_groups.contains(&group) {
+                    segments.push(ContentSegment::Entries(range));
+                }
+            }
+        } else {
+            segments.push(ContentSegment::Entries(0..data.len()));
+        }
+
+        if let Some(total) = data.truncated_total() {
+            segments.push(ContentSegment::Truncated(total));
+        }
+
+        segments
+    }
+
+    /// Updates the rows of the given segments that are inside the given range of rows.
+    /// Returns true if the directory content was changed and the remaining UI should
+    /// not be updated.
+    fn ui_update_content_segments(
+        &mut self,
+        ui: &mut egui::Ui,
+        data: &mut DirectoryContent,
+        segments: &[ContentSegment],
+        rows: Range<usize>,
+        reset_multi_selection: &mut bool,
+        batch_select_item_b: &mut Option<DirectoryEntry>,
+    ) -> bool {
+        let search_value = self.search_value.clone();
+        let mut should_return = false;
+        let mut offset = 0;
+
+        for segment in segments {
+            let start = rows.start.max(offset);
+            let end = rows.end.min(offset + segment.len());
+
+            if start < end {
+                match segment {
+                    ContentSegment::ParentEntry => {
+                        should_return |= self.ui_update_parent_entry(ui);
+                    }
+                    ContentSegment::Header(group, count) => {
+                        self.ui_update_group_header(ui, *group, *count);
+                    }
+                    ContentSegment::Truncated(total) => {
+                        should_return |= self.ui_update_truncated_entry(ui, data.len(), *total);
+                    }
+                    ContentSegment::Entries(range) => {
+                        let range = range.start + start - offset..range.start + end - offset;
+
+                        for item in data.filtered_iter_range_mut(range, &search_value) {
+                            should_return |= self.ui_update_central_panel_entry(
+                                ui,
+                                item,
+                                reset_multi_selection,
+                                batch_select_item_b,
+                            );
+                        }
+                    }
+                }
+            }
+
+            offset += segment.len();
+        }
+
+        should_return
+    }
+
+    /// Updates the header of a group of entries.
+    /// Clicking the header collapses or expands the group.
+    fn ui_update_group_header(&mut self, ui: &mut egui::Ui, group: EntryGroup, count: usize) {
+        let collapsed = self.collapsed_groups.contains(&group);
+
+        let label = match group {
+            EntryGroup::Folders => &self.config.labels.heading_folders,
+            EntryGroup::Files => &self.config.labels.heading_files,
+        };
+
+        let icon = if collapsed { "⏵" } else { "⏷" };
+        let text = egui::RichText::new(format!("{icon} {label} ({count})")).strong();
+
+        if ui.add(egui::Button::new(text).frame(false)).clicked() {
+            if collapsed {
+                self.collapsed_groups.retain(|g| *g != group);
+            } else {
+                self.collapsed_groups.push(group);
+            }
+        }
+    }
+
+    /// Updates the ".." entry displayed at the top of the directory content.
+    /// Returns true if the user opened the parent directory.
+    fn ui_update_parent_entry(&mut self, ui: &mut egui::Ui) -> bool {
+        let re = ui.selectable_label(
+            self.parent_entry_selected,
+            format!("{} ..", self.config.default_folder_icon),
+        );
+
+        if self.parent_entry_selected && self.scroll_to_selection {
+            re.scroll_to_me(Some(egui::Align::Center));
+            self.scroll_to_selection = false;
+        }
+
+        if re.clicked() {
+            self.list_focused = true;
+            self.select_parent_entry();
+        }
+
+        if re.double_clicked() {
+            self.load_parent_directory();
+            return true;
+        }
+
+        false
+    }
+
+    /// Updates the row at the end of the list, displayed if not all entries of the directory
+    /// were loaded. Returns true if the user requested to load all entries.
+    fn ui_update_truncated_entry(
+        &mut self,
+        ui: &mut egui::Ui,
+        loaded: usize,
+        total: usize,
+    ) -> bool {
+        let button = if self.search_value.is_empty() {
+            &self.config.labels.show_all_entries
+        } else {
+            &self.config.labels.search_all_entries
+        };
+
+        let clicked = ui
+            .horizontal(|ui| {
+                ui.weak(format!(
+                    "{} {loaded} / {total}",
+                    self.config.labels.entries_truncated
+                ));
+                ui.link(button).clicked()
+            })
+            .inner;
+
+        if clicked {
+            self.load_all_entries();
+        }
+
+        clicked
+    }
+
+    /// Overwrites the selection visuals of the given UI with the colors
+    /// set in the configuration.
+    fn apply_selection_visuals(&self, ui: &mut egui::Ui) {
+        let selection = &mut ui.visuals_mut().selection;
+
+        if let Some(fill) = self.config.selection_fill {
+            selection.bg_fill = fill;
+        }
+
+        if let Some(color) = self.config.selection_text_color {
+            selection.stroke.color = color;
+        }
+    }
+
+    /// Updates a single directory content entry.
+    /// TODO: Refactor
+    fn ui_update_central_panel_entry(
+        &mut self,
+        ui: &mut egui::Ui,
+        item: &mut DirectoryEntry,
+        reset_multi_selection: &mut bool,
+        batch_select_item_b: &mut Option<DirectoryEntry>,
+    ) -> bool {
+        let file_name = item.file_name();
+        let primary_selected = self.is_primary_selected(item);
+        let (icons, label, truncate) = self.entry_label(ui, item);
+
+        let selected = primary_selected || item.selected;
+
+        // Entries that could not be accessed are displayed disabled
+        let mut re = ui
+            .add_enabled_ui(item.error().is_none(), |ui| {
+                if selected {
+                    self.apply_selection_visuals(ui);
+                }
+
+                ui.selectable_label(selected, label)
+            })
+            .inner;
+
+        self.request_directory_item_count(ui, item, re.rect);
+        self.ui_update_detail_columns(ui, item, re.rect);
+
+        if self.config.show_directory_chevrons
+            && item.is_dir()
+            && item.error().is_none()
+            && self.ui_update_directory_chevron(ui, item, re.rect)
+        {
+            self.load_directory(&item.to_path_buf());
+            return true;
+        }
+
+        if self.config.draggable_entries && item.error().is_none() {
+            re = re.interact(egui::Sense::drag());
+            re.dnd_set_drag_payload(item.to_path_buf());
+
+            if re.dragged() {
+                Self::ui_update_drag_overlay(ui, &format!("{icons}{file_name}"));
+            }
+        }
+
+        if truncate {
+            re = re.on_hover_text(file_name);
+        }
+
+        if let Some(err) = item.error() {
+            re = re.on_disabled_hover_ui(|ui| {
+                ui_error_label(ui, &self.config.err_icon, err);
+            });
+        }
+
+        if item.is_dir() {
+            self.ui_update_path_context_menu(&re, item.as_path());
+        } else {
+            #[cfg(feature = "open_externally")]
+            self.ui_update_open_externally(&re, item.as_path());
+        }
+
+        if re.context_menu_opened() {
+            self.select_item(item);
+        }
+
+        if primary_selected && self.scroll_to_selection {
+            re.scroll_to_me(Some(egui::Align::Center));
+            self.scroll_to_selection = false;
+        }
+
+        if re.clicked() {
+            self.list_focused = true;
+        }
+
+        // The user wants to select the item as the primary selected item
+        if re.clicked()
+            && !ui.input(|i| i.modifiers.command)
+            && !ui.input(|i| i.modifiers.shift_only())
+        {
+            self.select_item(item);
+
+            // Reset the multi selection except the now primary selected item
+            if self.mode == DialogMode::PickMultiple {
+                *reset_multi_selection = true;
+            }
+        }
+
+        // The user wants to select or unselect the item as part of a
+        // multi selection
+        if self.mode == DialogMode::PickMultiple
+            && re.clicked()
+            && ui.input(|i| i.modifiers.command)
+        {
+            if primary_selected {
+                // If the clicked item is the primary selected item,
+                // deselect it and remove it from the multi selection
+                item.selected = false;
+                self.selected_item = None;
+            } else {
+                item.selected = !item.selected;
+
+                // If the item was selected, make it the primary selected item
+                if item.selected {
+                    self.select_item(item);
+                }
+            }
+        }
+
+        // The user wants to select every item between the last selected item
+        // and the current item
+        if self.mode == DialogMode::PickMultiple
+            && re.clicked()
+            && ui.input(|i| i.modifiers.shift_only())
+        {
+            if let Some(selected_item) = self.selected_item.clone() {
+                // We perform a batch selection from the item that was
+                // primarily selected before the user clicked on this item.
+                *batch_select_item_b = Some(selected_item);
+
+                // And now make this item the primary selected item
+                item.selected = true;
+                self.select_item(item);
+            }
+        }
+
+        // The user double clicked on the directory entry.
+        // Either open the directory or submit the dialog.
+        if re.double_clicked() && !ui.input(|i| i.modifiers.command) {
+            let pick_directory = self.mode == DialogMode::PickDirectory
+                && self.config.pick_directory_on_double_click;
+
+            if item.is_dir() && !pick_directory {
+                self.load_directory(&item.to_path_buf());
+                return true;
+            }
+
+            self.select_item(item);
+
+            self.submit();
+        }
+
+        false
+    }
+
+    /// Displays the name of the entry that is currently dragged next to the pointer.
+    fn ui_update_drag_overlay(ui: &egui::Ui, text: &str) {
+        let Some(pos) = ui.ctx().pointer_interact_pos() else {
+            return;
+        };
+
+        egui::Area::new(ui.id().with("drag_overlay"))
+            .order(egui::Order::Tooltip)
+            .fixed_pos(pos + egui::vec2(12.0, 12.0))
+            .interactable(false)
+            .show(ui.ctx(), |ui| {
+                egui::Frame::popup(ui.style()).show(ui, |ui| {
+                    ui.label(text);
+                });
+            });
+    }
+
+    fn ui_update_create_directory_dialog(&mut self, ui: &mut egui::Ui) -> Option<DirectoryEntry> {
+        self.create_directory_dialog
+            .update(ui, &self.config)
+            .directory()
+            .map(|path| self.process_new_folder(&path))
+    }
+
+    /// Selects every item inside the `directory_content` between `item_a` and `item_b`,
+    /// excluding both given items.
+    fn batch_select_between(
+        &self,
+        directory_content: &mut DirectoryContent,
+        item_a: &DirectoryEntry,
+        item_b: &DirectoryEntry,
+    ) {
+        // Get the position of item a and item b
+        let pos_a = directory_content
+            .filtered_iter(&self.search_value)
+            .position(|p| p.path_eq(item_a));
+        let pos_b = directory_content
+            .filtered_iter(&self.search_value)
+            .position(|p| p.path_eq(item_b));
+
+        // If both items where found inside the directory entry, mark every item between
+        // them as selected
+        if let Some(pos_a) = pos_a {
+            if let Some(pos_b) = pos_b {
+                if pos_a == pos_b {
+                    return;
+                }
+
+                // Get the min and max of both positions.
+                // We will iterate from min to max.
+                let mut min = pos_a;
+                let mut max = pos_b;
+
+                if min > max {
+                    min = pos_b;
+                    max = pos_a;
+                }
+
+                for item in directory_content
+                    .filtered_iter_mut(&self.search_value)
+                    .enumerate()
+                    .filter(|(i, _)| i > &min && i < &max)
+                    .map(|(_, p)| p)
+                {
+                    // Entries that could not be accessed can not be picked
+                    item.selected = item.error().is_none();
+                }
+            }
+        }
+    }
+
+    /// Helper function to add a sized button that can be enabled or disabled
+    fn ui_button_sized(
+        &self,
+        ui: &mut egui::Ui,
+        enabled: bool,
+        size: egui::Vec2,
+        label: &str,
+        err_tooltip: Option<&str>,
+    ) -> egui::Response {
+        ui.add_enabled_ui(enabled, |ui| {
+            let response = ui.add_sized(size, egui::Button::new(label));
+
+            if let Some(err) = err_tooltip {
+                return response.on_disabled_hover_ui(|ui| {
+                    ui_error_label(ui, &self.config.err_icon, err);
+                });
+            }
+
+            response
+        })
+        .inner
+    }
+
+    /// Helper function to add a sized navigation button that shows the given hover text.
+    /// `disabled_hover_text` is shown instead if the button is disabled.
+    fn ui_nav_button(
+        ui: &mut egui::Ui,
+        enabled: bool,
+        size: egui::Vec2,
+        label: &str,
+        hover_text: &str,
+        disabled_hover_text: &str,
+    ) -> bool {
+        ui.add_enabled_ui(enabled, |ui| {
+            let response = ui.add_sized(size, egui::Button::new(label));
+            Self::set_accessible_name(&response, hover_text);
+
+            response
+                .on_hover_text(hover_text)
+                .on_disabled_hover_text(disabled_hover_text)
+                .clicked()
+        })
+        .inner
+    }
+
+    /// Replaces the accessible name of an icon-only widget so that screen readers
+    /// announce the given text instead of the icon.
+    fn set_accessible_name(response: &egui::Response, name: &str) {
+        response.widget_info(|| {
+            egui::WidgetInfo::labeled(egui::WidgetType::Button, response.enabled(), name)
+        });
+    }
+
+    /// Updates the context menu of a path.
+    ///
+    /// # Arguments
+    ///
+    /// * `item_response` - The response of the egui item for which the context menu should
+    ///                     be opened.
+    /// * `path` - The path for which the context menu should be opened.
+    fn ui_update_path_context_menu(&mut self, item_response: &egui::Response, path: &Path) {
+        // Path context menus are currently only used for pinned folders.
+        if !self.config.show_pinned_folders {
+            return;
+        }
+
+        item_response.context_menu(|ui| {
+            let pinned = self.is_pinned(path);
+
+            if pinned {
+                if ui.button(&self.config.labels.unpin_folder).clicked() {
+                    self.unpin_path(path);
+                    ui.close_kind(UiKind::Menu);
+                }
+            } else if ui.button(&self.config.labels.pin_folder).clicked() {
+                self.pin_path(path.to_path_buf());
+                ui.close_kind(UiKind::Menu);
+            }
+        });
+    }
+
+    /// Updates the context menu to open the given file with the default application of
+    /// the system. If the file could not be opened, the error is displayed inside the menu.
+    #[cfg(feature = "open_externally")]
+    fn ui_update_open_externally(&mut self, item_response: &egui::Response, path: &Path) {
+        if !self.config.show_open_externally {
+            return;
+        }
+
+        item_response.context_menu(|ui| {
+            if ui.button(&self.config.labels.open_externally).clicked() {
+                match crate::open_externally::open(path) {
+                    Ok(()) => {
+                        self.open_externally_error = None;
+                        ui.close_kind(UiKind::Menu);
+                    }
+                    Err(err) => {
+                        self.open_externally_error = Some((
+                            path.to_path_buf(),
+                            format!("{} {err}", self.config.labels.err_open_externally),
+                        ));
+                    }
+                }
+            }
+
+            if let Some((_, err)) = self
+                .open_externally_error
+                .as_ref()
+                .filter(|(p, _)| paths_equal(p, path))
+            {
+                ui_error_label(ui, &self.config.err_icon, err);
+            }
+        });
+    }
+
+    /// Sets the cursor position to the end of a text input field.
+    ///
+    /// # Arguments
+    ///
+    /// * `re` - response of the text input widget
+    /// * `data` - buffer holding the text of the input widget
+    fn set_cursor_to_end(re: &egui::Response, data: &str) {
+        // Set the cursor to the end of the filter input string
+        if let Some(mut state) = egui::TextEdit::load_state(&re.ctx, re.id) {
+            state
+                .cursor
+                .set_char_range(Some(CCursorRange::one(CCursor::new(data.len()))));
+            state.store(&re.ctx, re.id);
+        }
+    }
+
+    /// Calculates the width of a single char.
+    fn calc_char_width(ui: &egui::Ui, char: char) -> f32 {
+        ui.fonts(|f| f.glyph_width(&egui::TextStyle::Body.resolve(ui.style()), char))
+    }
+
+    /// Calculates the width of the specified text using the current font configuration.
+    /// Does not take new lines or text breaks into account!
+    fn calc_text_width(ui: &egui::Ui, text: &str) -> f32 {
+        let mut width = 0.0;
+
+        for char in text.chars() {
+            width += Self::calc_char_width(ui, char);
+        }
+
+        width
+    }
+
+    fn truncate_filename(ui: &egui::Ui, item: &DirectoryEntry, max_length: f32) -> String {
+        const TRUNCATE_STR: &str = "...";
+
+        let path = item.as_path();
+
+        let file_stem = if item.is_file() {
+            path.file_stem().and_then(|f| f.to_str()).unwrap_or("")
+        } else {
+            item.file_name()
+        };
+
+        let extension = if item.is_file() {
+            path.extension().map_or(String::new(), |ext| {
+                format!(".{}", ext.to_str().unwrap_or(""))
+            })
+        } else {
+            String::new()
+        };
+
+        let extension_width = Self::calc_text_width(ui, &extension);
+        let reserved = extension_width + Self::calc_text_width(ui, TRUNCATE_STR);
+
+        if max_length <= reserved {
+            return format!("{TRUNCATE_STR}{extension}");
+        }
+
+        let mut width = reserved;
+        let mut front = String::new();
+        let mut back = String::new();
+
+        for (i, char) in file_stem.chars().enumerate() {
+            let w = Self::calc_char_width(ui, char);
+
+            if width + w > max_length {
+                break;
+            }
+
+            front.push(char);
+            width += w;
+
+            let back_index = file_stem.len() - i - 1;
+
+            if back_index <= i {
+                break;
+            }
+
+            if let Some(char) = file_stem.chars().nth(back_index) {
+                let w = Self::calc_char_width(ui, char);
+
+                if width + w > max_length {
+                    break;
+                }
+
+                back.push(char);
+                width += w;
+            }
+        }
+
+        format!(
+            "{front}{TRUNCATE_STR}{}{extension}",
+            back.chars().rev().collect::<String>()
+        )
+    }
+}
+
+/// Keybindings
+impl FileDialog {
+    /// Checks whether any of the given navigation keybindings is pressed.
+    ///
+    /// Mouse buttons, like the back and forward buttons of a mouse, are only considered while
+    /// the pointer is above the dialog, so that they can still be used by the rest of the
+    /// application. Unlike keyboard input, they are not suppressed by a focused text field.
+    fn navigation_pressed(&self, ctx: &egui::Context, keybindings: &[KeyBinding]) -> bool {
+        keybindings.iter().any(|keybinding| match keybinding {
+            KeyBinding::PointerButton(_) => {
+                self.is_pointer_over_dialog(ctx) && keybinding.pressed(ctx, false)
+            }
+            _ => keybinding.pressed(ctx, true),
+        })
+    }
+
+    /// Checks if the pointer is currently above the area the dialog was rendered in.
+    fn is_pointer_over_dialog(&self, ctx: &egui::Context) -> bool {
+        let Some(rect) = self.rect_last_frame else {
+            return false;
+        };
+
+        ctx.input(|i| i.pointer.hover_pos())
+            .is_some_and(|pos| rect.contains(pos))
+    }
+
+    /// Checks whether certain keybindings have been pressed and executes the corresponding actions.
+    fn update_keybindings(&mut self, ctx: &egui::Context) {
+        // We don't want to execute keybindings if a modal is currently open.
+        // The modals implement the keybindings themselves.
+        if let Some(modal) = self.modals.last_mut() {
+            modal.update_keybindings(&self.config, ctx);
+            return;
+        }
+
+        // The go to dialog handles the keyboard input itself
+        if self.go_to_dialog.is_open() {
+            return;
+        }
+
+        let keybindings = std::mem::take(&mut self.config.keybindings);
+
+        if FileDialogKeyBindings::any_pressed(ctx, &keybindings.go_to, false) {
+            self.open_go_to_dialog();
+        }
+
+        if FileDialogKeyBindings::any_pressed(ctx, &keybindings.focus_previous, false) {
+            self.exec_keybinding_focus(ctx, false);
+        } else if FileDialogKeyBindings::any_pressed(ctx, &keybindings.focus_next, false) {
+            self.exec_keybinding_focus(ctx, true);
+        }
+
+        if FileDialogKeyBindings::any_pressed(ctx, &keybindings.submit, false) {
+            self.exec_keybinding_submit();
+        }
+
+        if FileDialogKeyBindings::any_pressed(ctx, &keybindings.cancel, false) {
+            self.exec_keybinding_cancel();
+        }
+
+        if self.navigation_pressed(ctx, &keybindings.parent) {
+            self.load_parent_directory();
+        }
+
+        if self.navigation_pressed(ctx, &keybindings.back) {
+            self.load_previous_directory();
+        }
+
+        if self.navigation_pressed(ctx, &keybindings.forward) {
+            self.load_next_directory();
+        }
+
+        if FileDialogKeyBindings::any_pressed(ctx, &keybindings.reload, true) {
+            self.refresh();
+        }
+
+        if FileDialogKeyBindings::any_pressed(ctx, &keybindings.new_folder, true) {
+            self.open_new_folder_dialog();
+        }
+
+        if FileDialogKeyBindings::any_pressed(ctx, &keybindings.edit_path, true) {
+            self.open_path_edit();
+        }
+
+        if FileDialogKeyBindings::any_pressed(ctx, &keybindings.home_edit_path, true) {
+            if let Some(dirs) = &self.user_directories {
+                if let Some(home) = dirs.home_dir() {
+                    self.load_directory(home.to_path_buf().as_path());
+                    self.open_path_edit();
+                }
+            }
+        }
+
+        if FileDialogKeyBindings::any_pressed(ctx, &keybindings.selection_up, false) {
+            self.exec_keybinding_selection_up();
+            self.list_focused = true;
+
+            // We want to break out of input fields like search when pressing selection keys
+            if let Some(id) = ctx.memory(egui::Memory::focused) {
+                ctx.memory_mut(|w| w.surrender_focus(id));
+            }
+        }
+
+        if FileDialogKeyBindings::any_pressed(ctx, &keybindings.selection_down, false) {
+            self.exec_keybinding_selection_down();
+            self.list_focused = true;
+
+            // We want to break out of input fields like search when pressing selection keys
+            if let Some(id) = ctx.memory(egui::Memory::focused) {
+                ctx.memory_mut(|w| w.surrender_focus(id));
+            }
+        }
+
+        if FileDialogKeyBindings::any_pressed(ctx, &keybindings.selection_first, true) {
+            self.exec_keybinding_selection_first();
+            self.list_focused = true;
+        }
+
+        if FileDialogKeyBindings::any_pressed(ctx, &keybindings.selection_last, true) {
+            self.exec_keybinding_selection_last();
+            self.list_focused = true;
+        }
+
+        if FileDialogKeyBindings::any_pressed(ctx, &keybindings.selection_page_up, true) {
+            self.exec_keybinding_selection_page(false);
+            self.list_focused = true;
+        }
+
+        if FileDialogKeyBindings::any_pressed(ctx, &keybindings.selection_page_down, true) {
+            self.exec_keybinding_selection_page(true);
+            self.list_focused = true;
+        }
+
+        if FileDialogKeyBindings::any_pressed(ctx, &keybindings.select_all, true)
+            && self.mode == DialogMode::PickMultiple
+        {
+            // Entries that could not be accessed can not be picked
+            for item in self.directory_content.filtered_iter_mut(&self.search_value) {
+                item.selected = item.error().is_none();
+            }
+        }
+
+        self.config.keybindings = keybindings;
+    }
+
+    /// Executes the action when the keybinding `submit` is pressed.
+    fn exec_keybinding_submit(&mut self) {
+        if self.path_edit_visible {
+            self.submit_path_edit();
+            return;
+        }
+
+        if self.create_directory_dialog.is_open() {
+            if let Some(dir) = self.create_directory_dialog.submit().directory() {
+                self.process_new_folder(&dir);
+            }
+            return;
+        }
+
+        if self.parent_entry_selected && self.is_parent_entry_visible() {
+            self.load_parent_directory();
+            return;
+        }
+
+        // Check if there is a directory selected we can open
+        if let Some(item) = &self.selected_item {
+            // Make sure the selected item is visible inside the directory view.
+            let is_visible = self
+                .get_dir_content_filtered_iter()
+                .any(|p| p.path_eq(item));
+
+            if is_visible && item.is_dir() {
+                self.load_directory(&item.to_path_buf());
+                return;
+            }
+        }
+
+        self.submit();
+    }
+
+    /// Executes the action when the keybinding `cancel` is pressed.
+    fn exec_keybinding_cancel(&mut self) {
+        // We have to check if the `create_directory_dialog` and `path_edit_visible` is open,
+        // because egui does not consume pressing the escape key inside a text input.
+        // So when pressing the escape key inside a text input, the text input is closed
+        // but the keybindings still register the press on the escape key.
+        // (Although the keybindings are updated before the UI and they check whether another
+        //  widget is currently in focus!)
+        //
+        // This is practical for us because we can close the path edit and
+        // the create directory dialog.
+        // However, this causes problems when the user presses escape in other text
+        // inputs for which we have no status saved. This would then close the entire file dialog.
+        // To fix this, we check if any item was focused in the last frame.
+        //
+        // Note that this only happens with the escape key and not when the enter key is
+        // used to close a text input. This is why we don't have to check for the
+        // dialogs in `exec_keybinding_submit`.
+        //
+        // If the search input contains a value, we clear it first so the user doesn't
+        // lose the current directory by a single press of the escape key.
+
+        if self.create_directory_dialog.is_open() {
+            self.create_directory_dialog.close();
+        } else if self.path_edit_visible {
+            self.close_path_edit();
+        } else if !self.search_value.is_empty() {
+            self.search_value.clear();
+        } else if !self.any_focused_last_frame {
+            self.cancel();
+        }
+    }
+
+    /// Executes the action when the keybinding `focus_next` or `focus_previous` is pressed.
+    /// The focus cycles through the search input, the directory list, the file name
+    /// input (only when saving a file), the submit button and the cancel button.
+    fn exec_keybinding_focus(&mut self, ctx: &egui::Context, next: bool) {
+        let mut order = Vec::with_capacity(5);
+
+        if let Some(id) = self.search_input_id {
+            order.push(FocusTarget::Widget(id));
+        }
+
+        order.push(FocusTarget::List);
+
+        if self.mode == DialogMode::SaveFile {
+            if let Some(id) = self.file_name_input_id {
+                order.push(FocusTarget::Widget(id));
+            }
+        }
+
+        if self.is_selection_valid() {
+            if let Some(id) = self.submit_button_id {
+                order.push(FocusTarget::Widget(id));
+            }
+        }
+
+        if let Some(id) = self.cancel_button_id {
+            order.push(FocusTarget::Widget(id));
+        }
+
+        let current = match ctx.memory(egui::Memory::focused) {
+            Some(id) => order.iter().position(|t| *t == FocusTarget::Widget(id)),
+            None if self.list_focused => order.iter().position(|t| *t == FocusTarget::List),
+            None => None,
+        };
+
+        let index = match current {
+            Some(i) if next => (i + 1) % order.len(),
+            Some(i) => (i + order.len() - 1) % order.len(),
+            None if next => 0,
+            None => order.len() - 1,
+        };
+
+        self.pending_focus = Some(order[index]);
+    }
+
+    /// Applies the focus requested by the focus keybindings.
+    /// This is called after the UI has been rendered, so that the focus changes made by
+    /// egui itself while handling the same key press are overwritten.
+    fn update_focus(&mut self, ctx: &egui::Context) {
+        let focused = ctx.memory(egui::Memory::focused);
+
+        match self.pending_focus.take() {
+            Some(FocusTarget::Widget(id)) => {
+                ctx.memory_mut(|w| w.request_focus(id));
+                self.list_focused = false;
+            }
+            Some(FocusTarget::List) => {
+                if let Some(id) = focused {
+                    ctx.memory_mut(|w| w.surrender_focus(id));
+                }
+                self.list_focused = true;
+            }
+            None => {
+                let tracked = [
+                    self.search_input_id,
+                    self.file_name_input_id,
+                    self.submit_button_id,
+                    self.cancel_button_id,
+                ];
+
+                // The user moved the focus to one of the other areas, e.g. by clicking it
+                if focused.is_some() && tracked.contains(&focused) {
+                    self.list_focused = false;
+                }
+            }
+        }
+    }
+
+    /// Executes the action when the keybinding `selection_up` is pressed.
+    fn exec_keybinding_selection_up(&mut self) {
+        if self.directory_content.len() == 0 {
+            return;
+        }
+
+        self.directory_content.reset_multi_selection();
+
+        if let Some(item) = &self.selected_item {
+            if self.select_next_visible_item_before(&item.clone()) {
+                return;
+            }
+
+            // The first item is selected, move on to the ".." entry above it
+            if self.is_parent_entry_visible() {
+                self.select_parent_entry();
+                return;
+            }
+        }
+
+        // No item is selected or no more items left.
+        // Select the last item from the directory content.
+        self.select_last_visible_item();
+    }
+
+    /// Executes the action when the keybinding `selection_down` is pressed.
+    fn exec_keybinding_selection_down(&mut self) {
+        if self.directory_content.len() == 0 {
+            return;
+        }
+
+        self.directory_content.reset_multi_selection();
+
+        if let Some(item) = &self.selected_item {
+            if self.select_next_visible_item_after(&item.clone()) {
+                return;
+            }
+        }
+
+        // No item is selected or no more items left.
+        // Select the last item from the directory content.
+        self.select_first_visible_item();
+    }
+
+    /// Executes the action when the keybinding `selection_first` is pressed.
+    fn exec_keybinding_selection_first(&mut self) {
+        if self.directory_content.len() == 0 {
+            return;
+        }
+
+        self.select_first_visible_item();
+    }
+
+    /// Executes the action when the keybinding `selection_last` is pressed.
+    fn exec_keybinding_selection_last(&mut self) {
+        if self.directory_content.len() == 0 {
+            return;
+        }
+
+        self.select_last_visible_item();
+    }
+
+    /// Executes the action when the keybinding `selection_page_up` or
+    /// `selection_page_down` is pressed.
+    fn exec_keybinding_selection_page(&mut self, down: bool) {
+        if self.directory_content.len() == 0 {
+            return;
+        }
+
+        self.directory_content.reset_multi_selection();
+
+        let mut directory_content = std::mem::take(&mut self.directory_content);
+        let search_value = std::mem::take(&mut self.search_value);
+
+        let count = directory_content.filtered_iter(&search_value).count();
+        let index = self.selected_item.as_ref().and_then(|item| {
+            directory_content
+                .filtered_iter(&search_value)
+                .position(|p| p.path_eq(item))
+        });
+
+        let target = match index {
+            Some(index) if down => index.saturating_add(self.rows_per_page),
+            Some(index) => index.saturating_sub(self.rows_per_page),
+            // No item is selected, start from the beginning or the end of the list.
+            None if down => 0,
+            None => count.saturating_sub(1),
+        };
+
+        if let Some(item) = directory_content
+            .filtered_iter_mut(&search_value)
+            .nth(target.min(count.saturating_sub(1)))
+        {
+            self.select_item(item);
+            self.scroll_to_selection = true;
+        }
+
+        self.directory_content = directory_content;
+        self.search_value = search_value;
+    }
+}
+
+/// Implementation
+impl FileDialog {
+    /// Gets the file filter and save extension that are applied to the directory content
+    /// in the current mode.
+    fn get_active_filters(&self) -> (Option<&FileFilter>, Option<&str>) {
+        let file_filter = match self.mode {
+            DialogMode::PickFile | DialogMode::PickMultiple => self.get_selected_file_filter(),
+            _ => None,
+        };
+
+        let save_extension = if self.mode == DialogMode::SaveFile {
+            self.get_selected_save_extension()
+                .map(|e| e.file_extension.as_str())
+        } else {
+            None
+        };
+
+        (file_filter, save_extension)
+    }
+
+    /// Get the file filter the user currently selected.
+    fn get_selected_file_filter(&self) -> Option<&FileFilter> {
+        self.selected_file_filter
+            .and_then(|id| self.config.file_filters.iter().find(|p| p.id == id))
+    }
+
+    /// Sets the default file filter to use.
+    fn set_default_file_filter(&mut self) {
+        if let Some(name) = &self.config.default_file_filter {
+            for filter in &self.config.file_filters {
+                if filter.name == name.as_str() {
+                    self.selected_file_filter = Some(filter.id);
+                }
+            }
+        }
+    }
+
+    /// Selects the given file filter and applies the appropriate filters.
+    fn select_file_filter(&mut self, filter: Option<FileFilter>) {
+        self.selected_file_filter = filter.map(|f| f.id);
+        self.selected_item = None;
+        self.refresh();
+    }
+
+    /// Get the save extension the user currently selected.
+    fn get_selected_save_extension(&self) -> Option<&SaveExtension> {
+        self.selected_save_extension
+            .and_then(|id| self.config.save_extensions.iter().find(|p| p.id == id))
+    }
+
+    /// Sets the save extension to use.
+    fn set_default_save_extension(&mut self) {
+        let config = std::mem::take(&mut self.config);
+
+        if let Some(name) = &config.default_save_extension {
+            for extension in &config.save_extensions {
+                if extension.name == name.as_str() {
+                    self.selected_save_extension = Some(extension.id);
+                    self.set_file_name_extension(&extension.file_extension);
+                }
+            }
+        }
+
+        self.config = config;
+    }
+
+    /// Selects the given save extension.
+    fn select_save_extension(&mut self, extension: Option<SaveExtension>) {
+        if let Some(ex) = extension {
+            self.selected_save_extension = Some(ex.id);
+            self.set_file_name_extension(&ex.file_extension);
+        }
+
+        self.selected_item = None;
+        self.refresh();
+    }
+
+    /// Updates the extension of `Self::file_name_input`.
+    fn set_file_name_extension(&mut self, extension: &str) {
+        // Prevent `PathBuf::set_extension` to append the file extension when there is
+        // already one without a file name. For example `.png` would be changed to `.png.txt`
+        // when using `PathBuf::set_extension`.
+        let dot_count = self.file_name_input.chars().filter(|c| *c == '.').count();
+        let use_simple = dot_count == 1 && self.file_name_input.chars().nth(0) == Some('.');
+
+        let mut p = PathBuf::from(&self.file_name_input);
+        if !use_simple && p.set_extension(extension) {
+            self.file_name_input = p.to_string_lossy().into_owned();
+        } else {
+            self.file_name_input = format!(".{extension}");
+        }
+    }
+
+    /// Gets a filtered iterator of the directory content of this object.
+    fn get_dir_content_filtered_iter(&self) -> impl Iterator<Item = &DirectoryEntry> {
+        self.directory_content.filtered_iter(&self.search_value)
+    }
+
+    /// Gets the position of the selected item in the visible directory content.
+    fn get_selected_item_index(&self) -> Option<usize> {
+        let selected = self.selected_item.as_ref()?;
+        self.get_dir_content_filtered_iter()
+            .position(|item| item.path_eq(selected))
+    }
+
+    /// Creates the label of the given entry of the directory content, containing the icons,
+    /// the file name and the item count of directories. Ignored entries are dimmed.
+    /// Returns the icons, the label and if the file name was truncated because it does not
+    /// fit into the available width.
+    fn entry_label(&self, ui: &egui::Ui, item: &DirectoryEntry) -> (String, egui::RichText, bool) {
+        let file_name = item.file_name();
+
+        let icons = if self.is_pinned(item.as_path()) {
+            format!("{} {} ", item.icon(), self.config.pinned_icon)
+        } else {
+            format!("{} ", item.icon())
+        };
+
+        let item_count = self.directory_item_count_text(item);
+        let icons_width = Self::calc_text_width(ui, &format!("{icons}{item_count}"));
+        let columns_width = self.detail_columns_width(ui, item);
+
+        // Calc available width for the file name and include a small margin
+        let available_width = ui.available_width() - icons_width - columns_width - 15.0;
+
+        let truncate = self.config.truncate_filenames
+            && available_width < Self::calc_text_width(ui, file_name);
+
+        let text = if truncate {
+            Self::truncate_filename(ui, item, available_width)
+        } else {
+            file_name.to_owned()
+        };
+
+        let mut label = egui::RichText::new(format!("{icons}{text}{item_count}"));
+        if item.is_ignored() {
+            label = label.weak();
+        }
+
+        (icons, label, truncate)
+    }
+
+    /// Gets the text of the permissions column of the given entry, like `🔒 -r--r--r--`.
+    /// Returns None if the column is disabled or the permissions are unknown.
+    #[cfg(unix)]
+    fn permissions_text(&self, item: &DirectoryEntry) -> Option<String> {
+        if !self.config.show_permissions {
+            return None;
+        }
+
+        let metadata = item.metadata();
+        let permissions = crate::permissions::format_permissions(metadata.permissions?);
+
+        Some(if metadata.read_only {
+            format!("🔒 {permissions}")
+        } else {
+            permissions
+        })
+    }
+
+    /// The permissions column is only available on Unix platforms.
+    #[cfg(not(unix))]
+    const fn permissions_text(&self, _item: &DirectoryEntry) -> Option<String> {
+        None
+    }
+
+    /// Gets the names of the user and group owning the given entry, in the order they are
+    /// displayed. Only contains the columns that are enabled. The names are looked up
+    /// when the directory is loaded.
+    #[cfg(unix)]
+    fn owner_texts<'a>(&self, item: &'a DirectoryEntry) -> Vec<&'a str> {
+        let metadata = item.metadata();
+        let mut texts = Vec::new();
+
+        if self.config.show_owner {
+            texts.push(metadata.owner.as_deref().unwrap_or_default());
+        }
+
+        if self.config.show_group {
+            texts.push(metadata.group.as_deref().unwrap_or_default());
+        }
+
+        texts
+    }
+
+    /// The owner and group columns are only available on Unix platforms.
+    #[cfg(not(unix))]
+    const fn owner_texts<'a>(&self, _item: &'a DirectoryEntry) -> Vec<&'a str> {
+        Vec::new()
+    }
+
+    /// Gets the width required by the columns displayed on the right side of the given entry,
+    /// including the spacing to the file name.
+    fn detail_columns_width(&self, ui: &egui::Ui, item: &DirectoryEntry) -> f32 {
+        let details_width = if self.config.view_mode == ViewMode::Details {
+            self.config
+                .storage
+                .details_columns
+                .iter()
+                .filter(|state| state.visible)
+                .map(|state| state.width + 15.0)
+                .sum()
+        } else {
+            0.0
+        };
+
+        let permissions_width = self
+            .permissions_text(item)
+            .map_or(0.0, |text| Self::calc_text_width(ui, &text) + 15.0);
+
+        #[allow(clippy::cast_precision_loss)]
+        let owner_width = self.owner_texts(item).len() as f32 * (OWNER_COLUMN_WIDTH + 15.0);
+
+        details_width + permissions_width + owner_width + self.chevron_width()
+    }
+
+    /// Gets the width reserved at the end of each row for the chevron used to open
+    /// directories. The space is also reserved for files so that the columns line up.
+    const fn chevron_width(&self) -> f32 {
+        if self.config.show_directory_chevrons {
+            DIRECTORY_CHEVRON_WIDTH
+        } else {
+            0.0
+        }
+    }
+
+    /// Updates the chevron at the end of the row of a directory entry.
+    /// Returns true if the user clicked the chevron to open the directory.
+    fn ui_update_directory_chevron(
+        &self,
+        ui: &egui::Ui,
+        item: &DirectoryEntry,
+        row: egui::Rect,
+    ) -> bool {
+        let rect = egui::Rect::from_x_y_ranges(
+            row.right() - DIRECTORY_CHEVRON_WIDTH..=row.right(),
+            row.y_range(),
+        );
+
+        let response = ui
+            .interact(
+                rect,
+                ui.id().with(("directory_chevron", item.as_path())),
+                egui::Sense::click(),
+            )
+            .on_hover_text(&self.config.labels.open_directory);
+
+        let color = if response.hovered() {
+            ui.visuals().strong_text_color()
+        } else {
+            ui.visuals().weak_text_color()
+        };
+
+        ui.painter().text(
+            rect.center(),
+            egui::Align2::CENTER_CENTER,
+            ">",
+            egui::TextStyle::Body.resolve(ui.style()),
+            color,
+        );
+
+        response.clicked()
+    }
+
+    /// Draws the columns displaying the size, last modification date, owner, group and
+    /// permissions of the given entry right-aligned inside the row of the entry.
+    fn ui_update_detail_columns(&self, ui: &egui::Ui, item: &DirectoryEntry, rect: egui::Rect) {
+        let font_id = egui::TextStyle::Body.resolve(ui.style());
+        let color = ui.visuals().weak_text_color();
+        let mut right = rect.right() - ui.spacing().button_padding.x - self.chevron_width();
+
+        if self.config.view_mode == ViewMode::Details {
+            let metadata = item.metadata();
+
+            for state in self.config.storage.details_columns.iter().rev() {
+                if !state.visible {
+                    continue;
+                }
+
+                let text = match state.column {
+                    DetailsColumn::Size if item.is_dir() => self.directory_size_text(item),
+                    DetailsColumn::Size => metadata
+                        .size
+                        .map(|size| format_size(size, self.config.size_units)),
+                    DetailsColumn::LastModified => metadata.last_modified.map(|date| {
+                        crate::date::format_date(date, &self.config.labels.column_modified_format)
+                    }),
+                };
+
+                if let Some(text) = text {
+                    ui.painter().text(
+                        egui::pos2(right, rect.center().y),
+                        egui::Align2::RIGHT_CENTER,
+                        text,
+                        font_id.clone(),
+                        color,
+                    );
+                }
+
+                right -= state.width + 15.0;
+            }
+        }
+
+        if let Some(text) = self.permissions_text(item) {
+            let text_rect = ui.painter().text(
+                egui::pos2(right, rect.center().y),
+                egui::Align2::RIGHT_CENTER,
+                text,
+                font_id.clone(),
+                color,
+            );
+
+            right -= text_rect.width() + 15.0;
+        }
+
+        // The names are left-aligned inside columns of a fixed width, so that they
+        // line up between the rows. Names that are too long are truncated.
+        for name in self.owner_texts(item).into_iter().rev() {
+            let mut job =
+                egui::text::LayoutJob::simple_singleline(name.to_owned(), font_id.clone(), color);
+            job.wrap = egui::text::TextWrapping::truncate_at_width(OWNER_COLUMN_WIDTH);
+
+            let galley = ui.painter().layout_job(job);
+            let pos = egui::pos2(
+                right - OWNER_COLUMN_WIDTH,
+                rect.center().y - galley.size().y / 2.0,
+            );
+            ui.painter().galley(pos, galley, color);
+
+            right -= OWNER_COLUMN_WIDTH + 15.0;
+        }
+    }
+
+    /// Starts computing the sizes of the directories inside the given directory content
+    /// if they are displayed, and receives the sizes computed in the meantime.
+    /// Returns true if there are still sizes computing.
+    fn update_directory_sizes(&mut self, data: &mut DirectoryContent) -> bool {
+        if !self.config.show_directory_sizes || self.config.view_mode != ViewMode::Details {
+            return false;
+        }
+
+        if let Some(directory) = self
+            .current_directory()
+            .filter(|dir| !self.directory_sizes.is_requested(dir))
+        {
+            let subdirectories = data
+                .iter()
+                .filter(|item| item.is_dir())
+                .map(DirectoryEntry::to_path_buf)
+                .collect();
+
+            let directory = directory.to_path_buf();
+            self.directory_sizes
+                .request(&self.config, &directory, subdirectories);
+        }
+
+        let computing = self.directory_sizes.update();
+
+        if self.directory_sizes.take_changed() {
+            data.set_directory_sizes(&self.config, |path| {
+                self.directory_sizes
+                    .get(path)
+                    .and_then(DirectorySize::bytes)
+            });
+        }
+
+        computing
+    }
+
+    /// Gets the text displayed in the size column of the given directory.
+    /// Returns None if directory sizes are not displayed or the size is not available.
+    fn directory_size_text(&self, item: &DirectoryEntry) -> Option<String> {
+        if !self.config.show_directory_sizes {
+            return None;
+        }
+
+        match self.directory_sizes.get(item.as_path())? {
+            DirectorySize::Loaded(size) => Some(format_size(size, self.config.size_units)),
+            DirectorySize::Partial(size) => {
+                Some(format!("> {}", format_size(size, self.config.size_units)))
+            }
+            DirectorySize::Pending => Some("…".to_string()),
+            DirectorySize::Unavailable => None,
+        }
+    }
+
+    /// Gets the text displaying the number of items inside the given directory,
+    /// like ` (128)`. Returns an empty string for files or if the count is not displayed.
+    fn directory_item_count_text(&self, item: &DirectoryEntry) -> String {
+        if !self.config.show_directory_item_count || !item.is_dir() {
+            return String::new();
+        }
+
+        match self.directory_item_counts.get(item.as_path()) {
+            Some(ItemCount::Loaded(count)) => format!(" ({count})"),
+            Some(ItemCount::Unavailable) => String::new(),
+            Some(ItemCount::Pending) | None => " (…)".to_string(),
+        }
+    }
+
+    /// Starts loading the number of items inside the given directory, if the count is
+    /// displayed and not loaded yet. Counts are only loaded for directories whose row,
+    /// given by `rect`, is actually visible to the user.
+    fn request_directory_item_count(
+        &mut self,
+        ui: &egui::Ui,
+        item: &DirectoryEntry,
+        rect: egui::Rect,
+    ) {
+        if self.config.show_directory_item_count && item.is_dir() && ui.is_rect_visible(rect) {
+            self.directory_item_counts
+                .request(&self.config, item.as_path());
+        }
+    }
+
+    /// Checks if the user is allowed to create a folder inside the current directory.
+    fn can_create_folder(&self) -> bool {
+        self.config.allow_create_folder
+            && self
+                .current_directory()
+                .is_some_and(|dir| !self.config.file_system.is_read_only(dir))
+    }
+
+    /// Opens the dialog to create a new folder.
+    /// Does nothing if the user is not allowed to create folders or if the dialog is
+    /// already open, so that the name entered by the user is not reset.
+    fn open_new_folder_dialog(&mut self) {
+        if !self.can_create_folder() || self.create_directory_dialog.is_open() {
+            return;
+        }
+
+        if let Some(x) = self.current_directory() {
+            self.create_directory_dialog.open(x.to_path_buf());
+        }
+    }
+
+    /// Opens the popup to enter the path of a folder to open.
+    /// The input is initialized with the path of the current directory.
+    fn open_go_to_dialog(&mut self) {
+        let directory = self.current_directory().map(Path::to_path_buf);
+        self.go_to_dialog.open(directory.as_deref());
+    }
+
+    /// Function that processes a newly created folder.
+    fn process_new_folder(&mut self, created_dir: &Path) -> DirectoryEntry {
+        if let Some(callback) = &mut self.on_directory_created {
+            (callback.0)(created_dir);
+        }
+
+        let mut entry =
+            DirectoryEntry::from_path(&self.config, created_dir, &*self.config.file_system);
+        entry.resolve_owner_names(&self.config);
+
+        self.directory_content.push(entry.clone());
+
+        self.select_item(&mut entry);
+
+        entry
+    }
+
+    /// Opens a new modal window.
+    fn open_modal(&mut self, modal: Box<dyn FileDialogModal + Send + Sync>) {
+        self.modals.push(modal);
+    }
+
+    /// Executes the given modal action.
+    fn exec_modal_action(&mut self, action: ModalAction) {
+        match action {
+            ModalAction::None => {}
+            // The path was already validated before the modal was opened
+            ModalAction::SaveFile(path) => self.state = DialogState::Picked(path),
+        };
+    }
+
+    /// Canonicalizes the specified path if canonicalization is enabled.
+    /// Returns the input path if an error occurs or canonicalization is disabled.
+    ///
+    /// Directories are canonicalized while their content is loading instead, so that the UI
+    /// does not block if a directory is not responding.
+    fn canonicalize_path(&self, path: &Path) -> PathBuf {
+        if self.config.canonicalize_paths {
+            self.config
+                .file_system
+                .canonicalize(path)
+                .unwrap_or_else(|_| path.to_path_buf())
+        } else {
+            path.to_path_buf()
+        }
+    }
+
+    /// Pins a path to the left sidebar.
+    fn pin_path(&mut self, path: PathBuf) {
+        self.config.storage.pinned_folders.push(path);
+    }
+
+    /// Unpins a path from the left sidebar.
+    fn unpin_path(&mut self, path: &Path) {
+        self.config
+            .storage
+            .pinned_folders
+            .retain(|p| !paths_equal(p, path));
+    }
+
+    /// Checks if the path of an entry in the left sidebar exists.
+    /// The result is cached until the dialog is refreshed, so that the file system is
+    /// not queried every frame. Paths are considered available while they are checked.
+    /// Always returns true if `mark_unavailable_locations` is disabled.
+    fn is_location_available(&mut self, path: &Path) -> bool {
+        if !self.config.mark_unavailable_locations {
+            return true;
+        }
+
+        self.location_availability.request(&self.config, path);
+        self.location_availability.is_available(path)
+    }
+
+    /// Pins the path to the left sidebar, or unpins it if it is already pinned.
+    fn toggle_pinned(&mut self, path: PathBuf) {
+        if self.is_pinned(&path) {
+            self.unpin_path(&path);
+        } else {
+            self.pin_path(path);
+        }
+    }
+
+    /// Checks if the path is pinned to the left sidebar.
+    fn is_pinned(&self, path: &Path) -> bool {
+        self.config
+            .storage
+            .pinned_folders
+            .iter()
+            .any(|p| paths_equal(p, path))
+    }
+
+    fn is_primary_selected(&self, item: &DirectoryEntry) -> bool {
+        self.selected_item.as_ref().is_some_and(|x| x.path_eq(item))
+    }
+
+    /// Resets the dialog to use default values.
+    /// Configuration variables are retained.
+    fn reset(&mut self) {
+        let config = self.config.clone();
+        let custom_bottom_ui = self.custom_bottom_ui.take();
+        let custom_right_panel = self.custom_right_panel.take();
+        let on_directory_changed = self.on_directory_changed.take();
+        let on_directory_created = self.on_directory_created.take();
+        let inline_picker_path = self.inline_picker_path.take();
+        let collapsed_sections = std::mem::take(&mut self.collapsed_sections);
+        let preserve_history = config.preserve_history;
+        let directory_stack = std::mem::take(&mut self.directory_stack);
+        let directory_offset = self.directory_offset;
+
+        *self = Self::with_config(config);
+        self.custom_bottom_ui = custom_bottom_ui;
+        self.custom_right_panel = custom_right_panel;
+        self.on_directory_changed = on_directory_changed;
+        self.on_directory_created = on_directory_created;
+        self.inline_picker_path = inline_picker_path;
+        self.collapsed_sections = collapsed_sections;
+
+        if preserve_history {
+            self.directory_stack = directory_stack;
+            self.directory_offset = directory_offset;
+        }
+    }
+
+    /// Refreshes the dialog.
+    /// Including the user directories, system disks and currently open directory.
+    fn refresh(&mut self) {
+        self.load_devices();
+        self.location_availability.clear();
+
+        self.reload_directory();
+    }
+
+    /// Loads the user directories and system disks.
+    ///
+    /// The devices are loaded via a separate thread if `load_via_thread` is enabled, because
+    /// listing the disks blocks as long as a network mount is not responding.
+    /// The previous devices stay visible until the new ones are loaded.
+    fn load_devices(&mut self) {
+        let canonicalize = self.config.canonicalize_paths;
+        let file_system = self.config.file_system.clone();
+
+        if !self.config.load_via_thread {
+            self.user_directories = file_system.user_dirs(canonicalize);
+            self.system_disks = file_system.get_disks(canonicalize);
+            return;
+        }
+
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let _ = tx.send((
+                file_system.user_dirs(canonicalize),
+                file_system.get_disks(canonicalize),
+            ));
+        });
+
+        self.devices_recv = Some(Mutex::new(rx));
+    }
+
+    /// Updates the user directories and system disks if they finished loading
+    /// on the other thread.
+    fn update_devices(&mut self, ctx: &egui::Context) {
+        let Some(recv) = &mut self.devices_recv else {
+            return;
+        };
+
+        let value = recv
+            .get_mut()
+            .map_or(Err(mpsc::TryRecvError::Disconnected), |recv| {
+                recv.try_recv()
+            });
+        match value {
+            Ok((user_directories, disks)) => {
+                self.user_directories = user_directories;
+                self.system_disks = disks;
+                self.devices_recv = None;
+            }
+            Err(mpsc::TryRecvError::Empty) => ctx.request_repaint(),
+            Err(mpsc::TryRecvError::Disconnected) => self.devices_recv = None,
+        }
+    }
+
+    /// Submits the current selection and tries to finish the dialog, if the selection is valid.
+    fn submit(&mut self) {
+        self.update_selection_validation();
+
+        // Make sure the selected item or entered file name is valid.
+        if !self.is_selection_valid() {
+            return;
+        }
+
+        self.config.storage.last_picked_dir = self.current_directory().map(PathBuf::from);
+
+        match &self.mode {
+            DialogMode::PickDirectory | DialogMode::PickFile => {
+                // Should always contain a value since `is_selection_valid` is used to
+                // validate the selection.
+                if let Some(item) = self.selected_item.clone() {
+                    self.finish(DialogState::Picked(item.to_path_buf()));
+                }
+            }
+            DialogMode::PickMultiple => {
+                let result: Vec<PathBuf> = self
+                    .selected_entries()
+                    .map(crate::DirectoryEntry::to_path_buf)
+                    .collect();
+
+                self.finish(DialogState::PickedMultiple(result));
+            }
+            DialogMode::SaveFile => {
+                // Should always contain a value since `is_selection_valid` is used to
+                // validate the selection.
+                if let Some(path) = self.current_directory() {
+                    let full_path = path.join(&self.file_name_input);
+
+                    if self.config.open_directory_on_save
+                        && self.config.file_system.is_dir(&full_path)
+                    {
+                        self.file_name_input.clear();
+                        self.load_directory(&full_path);
+                        self.file_name_input_error = self.validate_file_name_input();
+                        return;
+                    }
+
+                    self.submit_save_file(full_path);
+                }
+            }
+        }
+    }
+
+    /// Submits the file dialog with the specified path and opens the `OverwriteFileModal`
+    /// if the path already exists.
+    fn submit_save_file(&mut self, path: PathBuf) {
+        if !self.validate_confirm(std::slice::from_ref(&path)) {
+            return;
+        }
+
+        if self.config.file_system.exists(&path) {
+            self.open_modal(Box::new(OverwriteFileModal::new(path)));
+
+            return;
+        }
+
+        if self.config.create_missing_directories {
+            if let Err(err) = self.create_parent_directories(&path) {
+                self.file_name_input_error = Some(format!(
+                    "{} {err}",
+                    self.config.labels.err_create_directories
+                ));
+                return;
+            }
+        }
+
+        self.state = DialogState::Picked(path);
+    }
+
+    /// Finishes the dialog with the given result if the picked paths pass the
+    /// confirm validator. Otherwise the dialog stays open.
+    fn finish(&mut self, state: DialogState) {
+        let valid = match &state {
+            DialogState::Picked(path) => self.validate_confirm(std::slice::from_ref(path)),
+            DialogState::PickedMultiple(paths) => self.validate_confirm(paths),
+            _ => true,
+        };
+
+        if valid {
+            self.state = state;
+        }
+    }
+
+    /// Executes the confirm validator for the given paths.
+    /// Returns false and stores the first error if one of the paths is invalid.
+    fn validate_confirm(&mut self, paths: &[PathBuf]) -> bool {
+        let Some(validator) = self.config.confirm_validator.clone() else {
+            return true;
+        };
+
+        self.confirm_validation_error = paths
+            .iter()
+            .find_map(|path| (validator.validator)(path, self.mode).err());
+
+        self.confirm_validation_error.is_none()
+    }
+
+    /// Creates all parent directories of the given path that do not exist yet.
+    fn create_parent_directories(&self, path: &Path) -> std::io::Result<()> {
+        let file_system = &self.config.file_system;
+
+        let missing: Vec<&Path> = path
+            .ancestors()
+            .skip(1)
+            .take_while(|p| !p.as_os_str().is_empty() && !file_system.is_dir(p))
+            .collect();
+
+        for dir in missing.iter().rev() {
+            file_system.create_dir(dir)?;
+        }
+
+        Ok(())
+    }
+
+    /// Cancels the dialog.
+    fn cancel(&mut self) {
+        self.state = DialogState::Cancelled;
+    }
+
+    /// This function generates the initial directory based on the configuration.
+    /// The function does the following things:
+    ///   - Get the path to open based on the opening mode
+    ///   - Attempts to use the parent directory if the path is a file
+    ///
+    /// The path is canonicalized while the directory content is loading.
+    fn get_initial_directory(&self) -> PathBuf {
+        let path = match self.config.opening_mode {
+            OpeningMode::AlwaysInitialDir => &self.config.initial_directory,
+            OpeningMode::LastVisitedDir => self
+                .config
+                .storage
+                .last_visited_dir
+                .as_deref()
+                .unwrap_or(&self.config.initial_directory),
+            OpeningMode::LastPickedDir => self
+                .config
+                .storage
+                .last_picked_dir
+                .as_deref()
+                .unwrap_or(&self.config.initial_directory),
+        };
+
+        let mut path = path.to_path_buf();
+
+        if self.config.file_system.is_file(&path) {
+            if let Some(parent) = path.parent() {
+                // The parent of a relative file name is empty
+                path = if parent.as_os_str().is_empty() {
+                    PathBuf::from(".")
+                } else {
+                    parent.to_path_buf()
+                };
+            }
+        }
+
+        path
+    }
+
+    /// Creates the read-only context passed to custom UI callbacks.
+    fn get_custom_ui_context(&self) -> CustomUiContext<'_> {
+        CustomUiContext {
+            mode: self.mode,
+            current_directory: self.current_directory(),
+            selected_entry: self.selected_item.as_ref(),
+            selected_entries: if self.mode == DialogMode::PickMultiple {
+                self.selected_entries().collect()
+            } else {
+                Vec::new()
+            },
+        }
+    }
+
+    /// Checks whether the selection or the file name entered is valid.
+    /// What is checked depends on the mode the dialog is currently in.
+    fn is_selection_valid(&self) -> bool {
+        let valid = match &self.mode {
+            DialogMode::PickDirectory => self
+                .selected_item
+                .as_ref()
+                .is_some_and(|item| item.is_dir() && item.error().is_none()),
+            DialogMode::PickFile => self
+                .selected_item
+                .as_ref()
+                .is_some_and(|item| item.is_file() && item.error().is_none()),
+            DialogMode::PickMultiple => self.get_dir_content_filtered_iter().any(|p| p.selected),
+            DialogMode::SaveFile => self.file_name_input_error.is_none(),
+        };
+
+        valid && self.selection_validation_error().is_none()
+    }
+
+    /// Returns the selected directory that is opened by the submit button instead
+    /// of finishing the dialog.
+    /// This is the case if a visible directory is selected in `DialogMode::PickFile`.
+    fn get_submit_directory(&self) -> Option<PathBuf> {
+        if self.mode != DialogMode::PickFile {
+            return None;
+        }
+
+        self.selected_item
+            .as_ref()
+            .filter(|item| {
+                item.is_dir()
+                    && self
+                        .get_dir_content_filtered_iter()
+                        .any(|p| p.path_eq(item))
+            })
+            .map(DirectoryEntry::to_path_buf)
+    }
+
+    /// Returns the paths that would be returned if the user submitted the dialog now.
+    fn get_selection_paths(&self) -> Vec<PathBuf> {
+        match &self.mode {
+            DialogMode::PickDirectory => self
+                .selected_item
+                .iter()
+                .map(DirectoryEntry::to_path_buf)
+                .collect(),
+            DialogMode::PickFile => self
+                .selected_item
+                .iter()
+                .filter(|item| item.is_file())
+                .map(DirectoryEntry::to_path_buf)
+                .collect(),
+            DialogMode::PickMultiple => self
+                .selected_entries()
+                .map(DirectoryEntry::to_path_buf)
+                .collect(),
+            DialogMode::SaveFile => self
+                .current_directory()
+                .filter(|_| self.file_name_input_error.is_none())
+                .map(|dir| dir.join(&self.file_name_input))
+                .into_iter()
+                .collect(),
+        }
+    }
+
+    /// Executes the selection validator for every selected path that has not yet
+    /// been validated.
+    fn update_selection_validation(&mut self) {
+        let Some(validator) = self.config.selection_validator.clone() else {
+            return;
+        };
+
+        for path in self.get_selection_paths() {
+            if !self.selection_validation_cache.contains_key(&path) {
+                let result = (validator.validator)(&path, self.mode);
+                self.selection_validation_cache.insert(path, result);
+            }
+        }
     }
 
-    /// Get the window Id
-    pub const fn get_window_id(&self) -> egui::Id {
-        self.window_id
+    /// Returns the error message of the selection validator for the current selection.
+    /// Returns None if the selection is valid or has not been validated yet.
+    fn selection_validation_error(&self) -> Option<&str> {
+        self.config.selection_validator.as_ref()?;
+
+        self.get_selection_paths().iter().find_map(|path| {
+            self.selection_validation_cache
+                .get(path)
+                .and_then(|r| r.as_ref().err())
+                .map(String::as_str)
+        })
     }
-}
 
-/// UI methods
-impl FileDialog {
-    /// Main update method of the UI
+    /// Validates the file name entered by the user.
     ///
-    /// Takes an optional callback to show a custom right panel.
-    fn update_ui(
-        &mut self,
-        right_panel_fn: Option<&mut FileDialogUiCallback>,
-        ctx: &egui::Context,
-        ui: &mut egui::Ui,
-    ) -> bool {
-        if !self.modals.is_empty() {
-            self.ui_update_modals(ui);
-            return false;
+    /// Returns None if the file name is valid. Otherwise returns an error message.
+    fn validate_file_name_input(&self) -> Option<String> {
+        if self.file_name_input.is_empty() {
+            return Some(self.config.labels.err_empty_file_name.clone());
+        }
+
+        if !self.is_save_extension_allowed() {
+            let labels = &self.config.labels;
+            let mut extensions: Vec<String> = self
+                .config
+                .allowed_save_extensions
+                .iter()
+                .map(|e| format!(".{e}"))
+                .collect();
+
+            // Join the extensions like ".txt, .md or .rst"
+            let last = extensions.pop().unwrap_or_default();
+            let extensions = if extensions.is_empty() {
+                last
+            } else {
+                format!(
+                    "{} {} {last}",
+                    extensions.join(", "),
+                    labels.err_invalid_save_extension_or
+                )
+            };
+
+            return Some(format!(
+                "{} {extensions}",
+                labels.err_invalid_save_extension
+            ));
+        }
+
+        if let Some(x) = self.current_directory() {
+            let mut full_path = x.to_path_buf();
+            full_path.push(self.file_name_input.as_str());
+
+            if self.config.file_system.is_dir(&full_path) {
+                if self.config.open_directory_on_save {
+                    return None;
+                }
+
+                return Some(self.config.labels.err_directory_exists.clone());
+            }
+
+            if !self.config.allow_file_overwrite && self.config.file_system.exists(&full_path) {
+                return Some(self.config.labels.err_file_exists.clone());
+            }
+
+            if !self.config.create_missing_directories
+                && full_path
+                    .parent()
+                    .is_some_and(|parent| !self.config.file_system.is_dir(parent))
+            {
+                return Some(self.config.labels.err_parent_directory_missing.clone());
+            }
+        } else {
+            // There is most likely a bug in the code if we get this error message!
+            return Some("Currently not in a directory".to_string());
+        }
+
+        None
+    }
+
+    /// Checks if the extension of the file name entered by the user is one of the
+    /// allowed save extensions.
+    fn is_save_extension_allowed(&self) -> bool {
+        if self.config.allowed_save_extensions.is_empty() {
+            return true;
+        }
+
+        // The file name is compared using its end instead of `Path::extension`,
+        // so that extensions containing a dot like "tar.gz" are supported
+        let file_name = self.file_name_input.to_ascii_lowercase();
+
+        self.config.allowed_save_extensions.iter().any(|e| {
+            let suffix = format!(".{}", e.to_ascii_lowercase());
+            file_name.len() > suffix.len() && file_name.ends_with(&suffix)
+        })
+    }
+
+    /// Marks the given item as the selected directory item.
+    /// Also updates the `file_name_input` to the name of the selected item.
+    fn select_item(&mut self, item: &mut DirectoryEntry) {
+        self.confirm_validation_error = None;
+        self.parent_entry_selected = false;
+
+        if self.mode == DialogMode::PickMultiple {
+            item.selected = true;
+        }
+        self.selected_item = Some(item.clone());
+
+        if self.mode == DialogMode::SaveFile && item.is_file() {
+            self.file_name_input = item.file_name().to_string();
+            self.file_name_input_error = self.validate_file_name_input();
+            self.update_file_name_suggestion();
+        }
+    }
+
+    /// Updates the suggested file name if a file with the name entered by the user
+    /// already exists in the current directory.
+    fn update_file_name_suggestion(&mut self) {
+        self.file_name_suggestion = None;
+
+        if !self.config.suggest_copy_name {
+            return;
+        }
+
+        let Some(directory) = self.current_directory() else {
+            return;
+        };
+
+        let file_system = &self.config.file_system;
+
+        if !file_system.is_file(&directory.join(&self.file_name_input)) {
+            return;
+        }
+
+        let (stem, extension) = self.split_file_extension(&self.file_name_input);
+
+        let suggestion = (1..=MAX_COPY_NAME_PROBES)
+            .map(|counter| Self::increment_file_name(stem, extension, counter))
+            .find(|name| !file_system.exists(&directory.join(name)));
+
+        self.file_name_suggestion = suggestion;
+    }
+
+    /// Splits the given file name into its stem and its extension including the dot.
+    /// The longest configured save extension the file name ends with is used as the
+    /// extension, so that extensions containing a dot like "tar.gz" are kept together.
+    /// Otherwise, the file name is split at its last dot.
+    fn split_file_extension<'a>(&self, file_name: &'a str) -> (&'a str, &'a str) {
+        let lowercase = file_name.to_ascii_lowercase();
+
+        let configured = self
+            .config
+            .save_extensions
+            .iter()
+            .map(|e| e.file_extension.as_str())
+            .chain(
+                self.config
+                    .allowed_save_extensions
+                    .iter()
+                    .map(String::as_str),
+            )
+            .map(|e| format!(".{}", e.to_ascii_lowercase()))
+            .filter(|suffix| lowercase.len() > suffix.len() && lowercase.ends_with(suffix))
+            .map(|suffix| suffix.len())
+            .max();
+
+        // The suffix starts with an ASCII dot, so the position is always a char boundary
+        if let Some(len) = configured {
+            return file_name.split_at(file_name.len() - len);
+        }
+
+        match file_name.rfind('.') {
+            Some(pos) if pos > 0 => file_name.split_at(pos),
+            _ => (file_name, ""),
+        }
+    }
+
+    /// Inserts the given counter between the stem and the extension of a file name.
+    /// An existing counter is replaced, so `report (1).pdf` becomes `report (2).pdf`
+    /// instead of `report (1) (2).pdf`.
+    fn increment_file_name(stem: &str, extension: &str, counter: usize) -> String {
+        let stem = stem
+            .strip_suffix(')')
+            .and_then(|s| s.rsplit_once(" ("))
+            .filter(|(_, n)| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()))
+            .map_or(stem, |(base, _)| base);
+
+        format!("{stem} ({counter}){extension}")
+    }
+
+    /// Attempts to select the last visible item in `directory_content` before the specified item.
+    ///
+    /// Returns true if an item is found and selected.
+    /// Returns false if no visible item is found before the specified item.
+    fn select_next_visible_item_before(&mut self, item: &DirectoryEntry) -> bool {
+        let mut return_val = false;
+
+        self.directory_content.reset_multi_selection();
+
+        let mut directory_content = std::mem::take(&mut self.directory_content);
+        let search_value = std::mem::take(&mut self.search_value);
+
+        let index = directory_content
+            .filtered_iter(&search_value)
+            .position(|p| p.path_eq(item));
+
+        if let Some(index) = index {
+            if index != 0 {
+                if let Some(item) = directory_content
+                    .filtered_iter_mut(&search_value)
+                    .nth(index.saturating_sub(1))
+                {
+                    self.select_item(item);
+                    self.scroll_to_selection = true;
+                    return_val = true;
+                }
+            }
+        }
+
+        self.directory_content = directory_content;
+        self.search_value = search_value;
+
+        return_val
+    }
+
+    /// Attempts to select the last visible item in `directory_content` after the specified item.
+    ///
+    /// Returns true if an item is found and selected.
+    /// Returns false if no visible item is found after the specified item.
+    fn select_next_visible_item_after(&mut self, item: &DirectoryEntry) -> bool {
+        let mut return_val = false;
+
+        self.directory_content.reset_multi_selection();
+
+        let mut directory_content = std::mem::take(&mut self.directory_content);
+        let search_value = std::mem::take(&mut self.search_value);
+
+        let index = directory_content
+            .filtered_iter(&search_value)
+            .position(|p| p.path_eq(item));
+
+        if let Some(index) = index {
+            if let Some(item) = directory_content
+                .filtered_iter_mut(&search_value)
+                .nth(index.saturating_add(1))
+            {
+                self.select_item(item);
+                self.scroll_to_selection = true;
+                return_val = true;
+            }
+        }
+
+        self.directory_content = directory_content;
+        self.search_value = search_value;
+
+        return_val
+    }
+
+    /// Checks if the ".." entry is displayed at the top of the directory content.
+    fn is_parent_entry_visible(&self) -> bool {
+        self.config.show_parent_entry && self.current_directory().and_then(Path::parent).is_some()
+    }
+
+    /// Selects the ".." entry, so that the parent directory can be opened
+    /// using the `submit` keybinding.
+    fn select_parent_entry(&mut self) {
+        self.directory_content.reset_multi_selection();
+        self.confirm_validation_error = None;
+        self.selected_item = None;
+        self.parent_entry_selected = true;
+        self.scroll_to_selection = true;
+    }
+
+    /// Tries to select the first visible item inside `directory_content`.
+    fn select_first_visible_item(&mut self) {
+        self.directory_content.reset_multi_selection();
+
+        let mut directory_content = std::mem::take(&mut self.directory_content);
+
+        if let Some(item) = directory_content
+            .filtered_iter_mut(&self.search_value.clone())
+            .next()
+        {
+            self.select_item(item);
+            self.scroll_to_selection = true;
+        }
+
+        self.directory_content = directory_content;
+    }
+
+    /// Tries to select the last visible item inside `directory_content`.
+    fn select_last_visible_item(&mut self) {
+        self.directory_content.reset_multi_selection();
+
+        let mut directory_content = std::mem::take(&mut self.directory_content);
+
+        if let Some(item) = directory_content
+            .filtered_iter_mut(&self.search_value.clone())
+            .last()
+        {
+            self.select_item(item);
+            self.scroll_to_selection = true;
+        }
+
+        self.directory_content = directory_content;
+    }
+
+    /// Opens the text field in the top panel to text edit the current path.
+    fn open_path_edit(&mut self) {
+        let path = self.current_directory().map_or_else(String::new, |path| {
+            path.to_str().unwrap_or_default().to_string()
+        });
+
+        self.path_edit_value = path;
+        self.path_edit_activate = true;
+        self.path_edit_visible = true;
+    }
+
+    /// Opens the path pasted by the backend after the user requested
+    /// to paste a path using the context menu of the directory content.
+    ///
+    /// The backend may take several frames to read the clipboard, so the request stays
+    /// pending until the text is pasted. The request is dropped once the user does
+    /// something else, because no text is pasted at all if the clipboard is empty.
+    fn update_paste_path(&mut self, ctx: &egui::Context) {
+        if !self.paste_path_requested {
+            return;
+        }
+
+        let (pasted, interacted) = ctx.input(|i| {
+            let pasted = i.events.iter().find_map(|event| match event {
+                egui::Event::Paste(text) => Some(text.trim().to_string()),
+                _ => None,
+            });
+            let interacted = i.events.iter().any(|event| {
+                matches!(
+                    event,
+                    egui::Event::PointerButton { pressed: true, .. }
+                        | egui::Event::Key { pressed: true, .. }
+                        | egui::Event::Text(_)
+                )
+            });
+
+            (pasted, interacted)
+        });
+
+        if pasted.is_none() && !interacted {
+            return;
+        }
+
+        self.paste_path_requested = false;
+
+        if let Some(path) = pasted.filter(|p| !p.is_empty()) {
+            self.path_edit_value = path;
+            self.submit_path_edit();
         }
+    }
+
+    /// Loads the directory from the path text edit.
+    fn submit_path_edit(&mut self) {
+        self.close_path_edit();
 
-        if self.config.show_top_panel {
-            egui::TopBottomPanel::top(self.window_id.with("top_panel"))
-                .resizable(false)
-                .show_inside(ui, |ui| {
-                    self.ui_update_top_panel(ui);
-                });
+        let path = PathBuf::from(&self.path_edit_value);
+
+        if self.mode == DialogMode::PickFile && self.config.file_system.is_file(&path) {
+            self.submit_path_edit_selection(&path);
+            return;
         }
 
-        if self.config.show_left_panel {
-            egui::SidePanel::left(self.window_id.with("left_panel"))
-                .resizable(true)
-                .default_width(150.0)
-                .width_range(90.0..=250.0)
-                .show_inside(ui, |ui| {
-                    self.ui_update_left_panel(ui);
-                });
+        // Assume the user wants to pick a file that does not exist yet when
+        //   - picking nonexistent files is allowed,
+        //   - the path does not exist,
+        //   - and the parent directory exists
+        if self.mode == DialogMode::PickFile
+            && self.config.allow_nonexistent_file
+            && !self.config.file_system.is_dir(&path)
+            && path
+                .parent()
+                .is_some_and(|p| self.config.file_system.is_dir(p))
+        {
+            self.submit_path_edit_selection(&path);
+            return;
         }
 
-        // Optionally, show a custom right panel (see `update_with_custom_right_panel`)
-        if let Some(f) = right_panel_fn {
-            let mut right_panel = egui::SidePanel::right(self.window_id.with("right_panel"))
-                // Unlike the left panel, we have no control over the contents, so
-                // we don't restrict the width. It's up to the user to make the UI presentable.
-                .resizable(true);
-            if let Some(width) = self.config.right_panel_width {
-                right_panel = right_panel.default_width(width);
+        // Assume the user wants to save the given path when
+        //   - an extension to the file name is given or the path
+        //     edit is allowed to save a file without extension,
+        //   - the path is not an existing directory,
+        //   - and the parent directory exists or missing directories are created
+        // Otherwise we will assume the user wants to open the path as a directory.
+        if self.mode == DialogMode::SaveFile
+            && (path.extension().is_some()
+                || self.config.allow_path_edit_to_save_file_without_extension)
+            && !self.config.file_system.is_dir(&path)
+            && (self.config.create_missing_directories
+                || path
+                    .parent()
+                    .is_some_and(|p| self.config.file_system.is_dir(p)))
+        {
+            // Keep the name the user typed accessible through `raw_file_name_input`
+            if let Some(file_name) = path.file_name() {
+                self.file_name_input = file_name.to_string_lossy().into_owned();
             }
-            right_panel.show_inside(ui, |ui| {
-                f(ui, self);
-            });
+
+            self.submit_path_edit_selection(&path);
+            return;
         }
 
-        egui::TopBottomPanel::bottom(self.window_id.with("bottom_panel"))
-            .resizable(false)
-            .show_inside(ui, |ui| {
-                self.ui_update_bottom_panel(ui);
-            });
+        self.load_directory(&path);
+    }
 
-        egui::CentralPanel::default().show_inside(ui, |ui| {
-            self.ui_update_central_panel(ui);
-        });
+    /// Submits the path entered in the path edit as the selection of the dialog,
+    /// if it passes the selection validator.
+    fn submit_path_edit_selection(&mut self, path: &Path) {
+        let path = self.canonicalize_path(path);
 
-        let mut repaint = false;
-
-        // Check if files were dropped
-        ctx.input(|i| {
-            if let Some(dropped_file) = i.raw.dropped_files.last() {
-                if let Some(path) = &dropped_file.path {
-                    if self.config.file_system.is_dir(path) {
-                        // If we dropped a directory, go there
-                        self.load_directory(path.as_path());
-                        repaint = true;
-                    } else if let Some(parent) = path.parent() {
-                        // Else, go to the parent directory
-                        self.load_directory(parent);
-                        self.select_item(&mut DirectoryEntry::from_path(
-                            &self.config,
-                            path,
-                            &*self.config.file_system,
-                        ));
-                        self.scroll_to_selection = true;
-                        repaint = true;
-                    }
-                }
+        if let Some(validator) = self.config.selection_validator.clone() {
+            let result = self
+                .selection_validation_cache
+                .entry(path.clone())
+                .or_insert_with(|| (validator.validator)(&path, self.mode));
+
+            if let Err(err) = result {
+                self.confirm_validation_error = Some(err.clone());
+                return;
             }
-        });
+        }
 
-        repaint
-    }
+        self.config.storage.last_picked_dir = path.parent().map(PathBuf::from);
 
-    fn ui_update_modals(&mut self, ui: &mut egui::Ui) {
-        // Currently, a rendering error occurs when only a single central panel is rendered
-        // inside a window. Therefore, when rendering a modal, we render an invisible bottom panel,
-        // which prevents the error.
-        // This is currently a bit hacky and should be adjusted again in the future.
-        egui::TopBottomPanel::bottom(self.window_id.with("modal_bottom_panel"))
-            .resizable(false)
-            .show_separator_line(false)
-            .show_inside(ui, |_| {});
+        if self.mode == DialogMode::SaveFile {
+            self.submit_save_file(path);
+        } else {
+            self.finish(DialogState::Picked(path));
+        }
+    }
 
-        // We need to use a central panel for the modals so that the
-        // window doesn't resize to the size of the modal.
-        egui::CentralPanel::default().show_inside(ui, |ui| {
-            if let Some(modal) = self.modals.last_mut() {
-                #[allow(clippy::single_match)]
-                match modal.update(&self.config, ui) {
-                    ModalState::Close(action) => {
-                        self.exec_modal_action(action);
-                        self.modals.pop();
-                    }
-                    ModalState::Pending => {}
-                }
-            }
-        });
+    /// Closes the text field at the top to edit the current path without loading
+    /// the entered directory.
+    fn close_path_edit(&mut self) {
+        self.path_edit_visible = false;
     }
 
-    /// Gets the window title to use.
-    /// This is either one of the default window titles or the configured window title.
-    const fn get_window_title(&self) -> &String {
-        match &self.config.title {
-            Some(title) => title,
-            None => match &self.mode {
-                DialogMode::PickDirectory => &self.config.labels.title_select_directory,
-                DialogMode::PickFile => &self.config.labels.title_select_file,
-                DialogMode::PickMultiple => &self.config.labels.title_select_multiple,
-                DialogMode::SaveFile => &self.config.labels.title_save_file,
-            },
+    /// Loads the next directory in the `directory_stack`.
+    /// If `directory_offset` is 0 and there is no other directory to load, `Ok()` is returned and
+    /// nothing changes.
+    /// Otherwise, the result of the directory loading operation is returned.
+    fn load_next_directory(&mut self) {
+        if !self.can_go_forward() {
+            // There is no next directory that can be loaded
+            return;
+        }
+
+        self.directory_offset -= 1;
+
+        // Copy path and load directory
+        if let Some(path) = self.current_directory() {
+            let path = path.to_path_buf();
+            self.load_directory_content(&path);
+            self.notify_directory_changed(&path, false);
         }
     }
 
-    /// Updates the top panel of the dialog. Including the navigation buttons,
-    /// the current path display, the reload button and the search field.
-    fn ui_update_top_panel(&mut self, ui: &mut egui::Ui) {
-        const BUTTON_SIZE: egui::Vec2 = egui::Vec2::new(25.0, 25.0);
+    /// Loads the previous directory the user opened.
+    /// If there is no previous directory left, `Ok()` is returned and nothing changes.
+    /// Otherwise, the result of the directory loading operation is returned.
+    fn load_previous_directory(&mut self) {
+        if !self.can_go_back() {
+            // There is no previous directory that can be loaded
+            return;
+        }
 
-        ui.horizontal(|ui| {
-            self.ui_update_nav_buttons(ui, BUTTON_SIZE);
+        self.directory_offset += 1;
 
-            let mut path_display_width = ui.available_width();
+        // Copy path and load directory
+        if let Some(path) = self.current_directory() {
+            let path = path.to_path_buf();
+            self.load_directory_content(&path);
+            self.notify_directory_changed(&path, false);
+        }
+    }
 
-            // Leave some area for the menu button and search input
-            if self.config.show_reload_button {
-                path_display_width -= ui
-                    .style()
-                    .spacing
-                    .item_spacing
-                    .x
-                    .mul_add(2.5, BUTTON_SIZE.x);
+    /// Loads the parent directory of the currently open directory.
+    /// If the directory doesn't have a parent, `Ok()` is returned and nothing changes.
+    /// Otherwise, the result of the directory loading operation is returned.
+    fn load_parent_directory(&mut self) {
+        if let Some(x) = self.current_directory() {
+            if let Some(x) = x.to_path_buf().parent() {
+                self.load_directory(x);
             }
+        }
+    }
 
-            if self.config.show_search {
-                path_display_width -= 140.0;
-            }
+    /// Reloads the currently open directory.
+    /// If no directory is currently open, `Ok()` will be returned.
+    /// Otherwise, the result of the directory loading operation is returned.
+    ///
+    /// In most cases, this function should not be called directly.
+    /// Instead, `refresh` should be used to reload all other data like system disks too.
+    fn reload_directory(&mut self) {
+        if let Some(x) = self.current_directory() {
+            let path = x.to_path_buf();
+            let selected_index = self.get_selected_item_index();
 
-            if self.config.show_current_path {
-                self.ui_update_current_path(ui, path_display_width);
-            }
+            self.load_directory_content(&path);
+            self.reloading_directory = true;
 
-            // Hamburger menu containing different options
-            if self.config.show_menu_button
-                && (self.config.show_reload_button
-                    || self.config.show_working_directory_button
-                    || self.config.show_hidden_option
-                    || self.config.show_system_files_option)
-            {
-                ui.allocate_ui_with_layout(
-                    BUTTON_SIZE,
-                    egui::Layout::centered_and_justified(egui::Direction::LeftToRight),
-                    |ui| {
-                        ui.menu_button("☰", |ui| {
-                            self.ui_update_hamburger_menu(ui);
-                        });
-                    },
-                );
-            }
+            // Keep the scroll position instead of jumping to the selection,
+            // unless the selected item moved while reloading.
+            self.scroll_to_selection = false;
+            self.reload_selection_index = selected_index;
 
-            if self.config.show_search {
-                self.ui_update_search(ui);
-            }
-        });
+            self.notify_directory_changed(&path, true);
+        }
+    }
 
-        ui.add_space(ui.ctx().style().spacing.item_spacing.y);
+    /// Reloads the currently open directory including all of its entries,
+    /// ignoring the configured maximum number of entries.
+    fn load_all_entries(&mut self) {
+        self.load_all_entries_in = self.current_directory().map(Path::to_path_buf);
+        self.reload_directory();
     }
 
-    /// Updates the navigation buttons like parent or previous directory
-    fn ui_update_nav_buttons(&mut self, ui: &mut egui::Ui, button_size: egui::Vec2) {
-        if self.config.show_parent_button {
-            if let Some(x) = self.current_directory() {
-                if self.ui_button_sized(ui, x.parent().is_some(), button_size, "⏶", None) {
-                    self.load_parent_directory();
-                }
-            } else {
-                let _ = self.ui_button_sized(ui, false, button_size, "⏶", None);
-            }
-        }
+    /// Cancels loading the current directory content and returns to the directory that was
+    /// open before, including its position in the navigation history.
+    fn cancel_directory_load(&mut self) {
+        self.directory_content.cancel();
 
-        if self.config.show_back_button
-            && self.ui_button_sized(
-                ui,
-                self.directory_offset + 1 < self.directory_stack.len(),
-                button_size,
-                "⏴",
-                None,
-            )
-        {
-            self.load_previous_directory();
-        }
+        let Some(previous) = self.previous_directory.take() else {
+            // There is no directory to return to, for example when the dialog was just opened
+            self.directory_content =
+                DirectoryContent::from_error(self.config.labels.err_loading_cancelled.clone());
+            return;
+        };
 
-        if self.config.show_forward_button
-            && self.ui_button_sized(ui, self.directory_offset != 0, button_size, "⏵", None)
-        {
-            self.load_next_directory();
-        }
+        self.directory_content = previous.content;
+        self.directory_stack = previous.directory_stack;
+        self.directory_offset = previous.directory_offset;
+        self.content_navigation = (self.directory_stack.clone(), self.directory_offset);
 
-        if self.config.show_new_folder_button
-            && self.ui_button_sized(
-                ui,
-                !self.create_directory_dialog.is_open(),
-                button_size,
-                "+",
-                None,
-            )
-        {
-            self.open_new_folder_dialog();
+        if let Some(path) = self.current_directory().map(Path::to_path_buf) {
+            self.notify_directory_changed(&path, false);
         }
     }
 
-    /// Updates the view to display the current path.
-    /// This could be the view for displaying the current path and the individual sections,
-    /// as well as the view for text editing of the current path.
-    fn ui_update_current_path(&mut self, ui: &mut egui::Ui, width: f32) {
-        egui::Frame::default()
-            .stroke(egui::Stroke::new(
-                1.0,
-                ui.ctx().style().visuals.window_stroke.color,
-            ))
-            .inner_margin(egui::Margin::from(4))
-            .corner_radius(egui::CornerRadius::from(4))
-            .show(ui, |ui| {
-                const EDIT_BUTTON_SIZE: egui::Vec2 = egui::Vec2::new(22.0, 20.0);
-
-                if self.path_edit_visible {
-                    self.ui_update_path_edit(ui, width, EDIT_BUTTON_SIZE);
-                } else {
-                    self.ui_update_path_display(ui, width, EDIT_BUTTON_SIZE);
-                }
-            });
+    /// Gets the nearest parent directory of the given path that still exists.
+    fn nearest_existing_ancestor(&self, path: &Path) -> Option<PathBuf> {
+        path.ancestors()
+            .skip(1)
+            .find(|p| self.config.file_system.is_dir(p))
+            .map(Path::to_path_buf)
     }
 
-    /// Updates the view when the currently open path with the individual sections is displayed.
-    fn ui_update_path_display(
-        &mut self,
-        ui: &mut egui::Ui,
-        width: f32,
-        edit_button_size: egui::Vec2,
-    ) {
-        ui.style_mut().always_scroll_the_only_direction = true;
-        ui.style_mut().spacing.scroll.bar_width = 8.0;
+    /// Loads the given directory and updates the `directory_stack`.
+    /// The function deletes all directories from the `directory_stack` that are currently
+    /// stored in the vector before the `directory_offset`.
+    ///
+    /// The function also sets the loaded directory as the selected item.
+    fn load_directory(&mut self, path: &Path) {
+        // Do not load the same directory again.
+        // Use reload_directory if the content of the directory should be updated.
+        // Paths pointing to the same directory in a different way, like symbolic links,
+        // are only detected once the directory is loaded and its path is canonicalized
+        if let Some(x) = self.current_directory() {
+            if paths_equal(x, path) {
+                return;
+            }
+        }
+
+        // Rejected directories are not opened, so that the current directory,
+        // the navigation history and the last visited directory stay untouched
+        if !self.is_navigation_allowed(path) {
+            let err = self.config.labels.err_hidden_navigation.clone();
+
+            if self.current_directory().is_some() {
+                self.directory_notice = Some(err);
+            } else {
+                self.directory_content = DirectoryContent::from_error(err);
+            }
 
-        let max_width = if self.config.show_path_edit_button {
-            ui.style()
-                .spacing
-                .item_spacing
-                .x
-                .mul_add(-2.0, width - edit_button_size.x)
-        } else {
-            width
-        };
+            return;
+        }
 
-        egui::ScrollArea::horizontal()
-            .auto_shrink([false, false])
-            .stick_to_right(true)
-            .max_width(max_width)
-            .show(ui, |ui| {
-                ui.horizontal(|ui| {
-                    ui.style_mut().spacing.item_spacing.x /= 2.5;
-                    ui.style_mut().spacing.button_padding = egui::Vec2::new(5.0, 3.0);
+        // Do not keep a directory in the history whose content never loaded
+        if self.directory_content.is_pending() {
+            if let Some(previous) = &self.previous_directory {
+                self.directory_stack.clone_from(&previous.directory_stack);
+                self.directory_offset = previous.directory_offset;
+            }
+        }
 
-                    let mut path = PathBuf::new();
+        if self.directory_offset != 0 && self.directory_stack.len() > self.directory_offset {
+            self.directory_stack
+                .drain(self.directory_stack.len() - self.directory_offset..);
+        }
 
-                    if let Some(data) = self.current_directory().map(Path::to_path_buf) {
-                        for (i, segment) in data.iter().enumerate() {
-                            path.push(segment);
+        self.directory_stack.push(path.to_path_buf());
+        self.directory_offset = 0;
 
-                            let mut segment_str = segment.to_str().unwrap_or_default().to_string();
+        self.load_directory_content(path);
+        self.notify_directory_changed(path, false);
 
-                            if self.is_pinned(&path) {
-                                segment_str =
-                                    format!("{} {}", &self.config.pinned_icon, segment_str);
-                            };
+        // Clear the entry filter buffer.
+        // It's unlikely the user wants to keep the current filter when entering a new directory.
+        self.search_value.clear();
+    }
 
-                            if i != 0 {
-                                ui.label(self.config.directory_separator.as_str());
-                            }
+    /// Calls the function set using `FileDialog::on_directory_changed` once the content of
+    /// the given directory finished loading.
+    /// `reload` should be true if the currently open directory was only reloaded.
+    fn notify_directory_changed(&mut self, path: &Path, reload: bool) {
+        if !reload || self.config.notify_directory_reload {
+            self.pending_directory_change = Some(path.to_path_buf());
+        }
 
-                            let re = ui.button(segment_str);
+        self.update_loaded_directory();
+    }
 
-                            if re.clicked() {
-                                self.load_directory(path.as_path());
-                                return;
-                            }
+    /// Updates the dialog once the directory content that was requested last finished
+    /// loading. The open directory is replaced by the canonicalized path of the loaded
+    /// directory, which is also stored as the last visited directory.
+    ///
+    /// If the directory is the same as the directory that was open before, like when
+    /// opening a symbolic link to it, the navigation history is restored instead.
+    fn update_loaded_directory(&mut self) {
+        if !self.directory_load_pending || self.directory_content.is_pending() {
+            return;
+        }
 
-                            self.ui_update_path_context_menu(&re, &path.clone());
-                        }
-                    }
-                });
-            });
+        self.directory_load_pending = false;
+        let previous = self.previous_directory.take();
+        let reload = std::mem::take(&mut self.reloading_directory);
 
-        if !self.config.show_path_edit_button {
+        let Some(path) = self.directory_content.path().map(Path::to_path_buf) else {
+            // The directory was removed or unmounted while the dialog was open
+            if reload && self.directory_content.is_not_found() {
+                self.open_nearest_existing_ancestor();
+            }
+
+            self.update_pending_directory_change();
+            return;
+        };
+
+        if let Some(previous) = previous.filter(|previous| {
+            !reload
+                && previous
+                    .content
+                    .path()
+                    .is_some_and(|p| paths_equal(p, &path))
+        }) {
+            self.directory_stack = previous.directory_stack;
+            self.directory_offset = previous.directory_offset;
+            self.content_navigation = (self.directory_stack.clone(), self.directory_offset);
+            self.pending_directory_change = None;
             return;
         }
 
-        if ui
-            .add_sized(
-                edit_button_size,
-                egui::Button::new("🖊").fill(egui::Color32::TRANSPARENT),
-            )
-            .clicked()
-        {
-            self.open_path_edit();
+        let index = self
+            .directory_stack
+            .len()
+            .checked_sub(self.directory_offset + 1);
+        if let Some(current) = index.and_then(|i| self.directory_stack.get_mut(i)) {
+            current.clone_from(&path);
+            self.content_navigation = (self.directory_stack.clone(), self.directory_offset);
         }
-    }
 
-    /// Updates the view when the user currently wants to text edit the current path.
-    fn ui_update_path_edit(&mut self, ui: &mut egui::Ui, width: f32, edit_button_size: egui::Vec2) {
-        let desired_width: f32 = ui
-            .style()
-            .spacing
-            .item_spacing
-            .x
-            .mul_add(-3.0, width - edit_button_size.x);
+        if self.pending_directory_change.is_some() {
+            self.pending_directory_change = Some(path.clone());
+        }
 
-        let response = egui::TextEdit::singleline(&mut self.path_edit_value)
-            .desired_width(desired_width)
-            .show(ui)
-            .response;
+        self.config.storage.last_visited_dir = Some(path);
+        self.update_pending_directory_change();
+    }
 
-        if self.path_edit_activate {
-            response.request_focus();
-            Self::set_cursor_to_end(&response, &self.path_edit_value);
-            self.path_edit_activate = false;
+    /// Opens the nearest parent of the current directory that still exists and displays
+    /// a notice that the directory was removed.
+    fn open_nearest_existing_ancestor(&mut self) {
+        let Some(path) = self.current_directory().map(Path::to_path_buf) else {
+            return;
+        };
+
+        if let Some(ancestor) = self.nearest_existing_ancestor(&path) {
+            self.load_directory(&ancestor);
+            self.directory_notice = Some(format!(
+                "{} {}",
+                self.config.labels.directory_removed,
+                ancestor.display()
+            ));
         }
+    }
 
-        if self.path_edit_request_focus {
-            response.request_focus();
-            self.path_edit_request_focus = false;
+    /// Calls the function set using `FileDialog::on_directory_changed` if the content of the
+    /// directory the user changed to finished loading. The call is dropped if loading failed,
+    /// for example because the directory was rejected.
+    fn update_pending_directory_change(&mut self) {
+        if self.directory_content.is_pending() {
+            return;
         }
 
-        let btn_response = ui.add_sized(edit_button_size, egui::Button::new("✔"));
+        let Some(path) = self.pending_directory_change.take() else {
+            return;
+        };
 
-        if btn_response.clicked() {
-            self.submit_path_edit();
+        if !self.directory_content.is_loaded() {
+            return;
         }
 
-        if !response.has_focus() && !btn_response.contains_pointer() {
-            self.path_edit_visible = false;
+        if let Some(callback) = &mut self.on_directory_changed {
+            (callback.0)(&path);
         }
     }
 
-    /// Updates the hamburger menu containing different options.
-    fn ui_update_hamburger_menu(&mut self, ui: &mut egui::Ui) {
-        const SEPARATOR_SPACING: f32 = 2.0;
+    /// Checks if the user is allowed to open the given directory.
+    /// The directory is rejected if hidden navigation is disabled and the directory
+    /// or one of its parents is hidden.
+    fn is_navigation_allowed(&self, path: &Path) -> bool {
+        self.config.allow_hidden_navigation
+            || !path
+                .ancestors()
+                .any(|p| self.config.file_system.is_path_hidden(p))
+    }
 
-        if self.config.show_reload_button && ui.button(&self.config.labels.reload).clicked() {
-            self.refresh();
-            ui.close_kind(UiKind::Menu);
+    /// Loads the directory content of the given path.
+    fn load_directory_content(&mut self, path: &Path) {
+        // The counts belong to the directories of the previous content
+        self.directory_item_counts.clear();
+
+        let (selected_file_filter, selected_save_extension) = self.get_active_filters();
+
+        let max_entries = self.config.max_entries.filter(|_| {
+            self.load_all_entries_in
+                .as_deref()
+                .is_none_or(|dir| !paths_equal(dir, path))
+        });
+
+        let content = if self.is_navigation_allowed(path) {
+            DirectoryContent::from_path(
+                &self.config,
+                path,
+                self.show_files,
+                selected_file_filter,
+                selected_save_extension,
+                max_entries,
+                self.config.file_system.clone(),
+            )
+        } else {
+            DirectoryContent::from_error(self.config.labels.err_hidden_navigation.clone())
+        };
+
+        let mut previous = std::mem::replace(&mut self.directory_content, content);
+        let (directory_stack, directory_offset) = std::mem::replace(
+            &mut self.content_navigation,
+            (self.directory_stack.clone(), self.directory_offset),
+        );
+
+        // If the previous directory did not finish loading either, the directory
+        // that was open before it is kept
+        if previous.is_pending() {
+            previous.cancel();
+        } else {
+            self.previous_directory = Some(PreviousDirectory {
+                content: previous,
+                directory_stack,
+                directory_offset,
+            });
         }
 
-        let working_dir = self.config.file_system.current_dir();
+        self.directory_load_pending = true;
+        self.reloading_directory = false;
+        self.create_directory_dialog.close();
+        self.parent_entry_selected = false;
+        self.device_overview = false;
+        self.directory_notice = None;
+        self.directory_sizes.clear();
+        self.scroll_to_selection = true;
+        self.reload_selection_index = None;
+        self.selection_validation_cache.clear();
 
-        if self.config.show_working_directory_button
-            && working_dir.is_ok()
-            && ui.button(&self.config.labels.working_directory).clicked()
-        {
-            self.load_directory(&working_dir.unwrap_or_default());
-            ui.close_kind(UiKind::Menu);
+        if self.mode == DialogMode::SaveFile {
+            self.file_name_input_error = self.validate_file_name_input();
+            self.update_file_name_suggestion();
         }
+    }
+}
 
-        if (self.config.show_reload_button || self.config.show_working_directory_button)
-            && (self.config.show_hidden_option || self.config.show_system_files_option)
-        {
-            ui.add_space(SEPARATOR_SPACING);
-            ui.separator();
-            ui.add_space(SEPARATOR_SPACING);
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{MemoryFileSystem, Metadata};
+    use std::ops::ControlFlow;
+
+    /// File system used to test the dialog without touching the disk.
+    /// Stores its content in a `MemoryFileSystem` and adds the behaviour of slow or
+    /// partially broken file systems.
+    #[derive(Default)]
+    struct TestFileSystem {
+        inner: MemoryFileSystem,
+        /// Directories that take a while to read, like on a slow network mount.
+        slow_directories: Vec<PathBuf>,
+        /// Directories whose entries are listed slowly one after another.
+        slow_listings: Vec<PathBuf>,
+        /// Symbolic links to directories, mapped to their target.
+        links: Vec<(PathBuf, PathBuf)>,
+        /// Entries that are listed as an error when reading their directory.
+        unreadable_entries: Vec<PathBuf>,
+        /// Entries whose metadata can not be queried, like without permissions.
+        inaccessible_entries: Vec<PathBuf>,
+    }
+
+    impl TestFileSystem {
+        /// Creates a file system containing the given directories and empty files.
+        fn new(directories: &[&str], files: &[&str]) -> Self {
+            let inner = directories
+                .iter()
+                .fold(MemoryFileSystem::new(), MemoryFileSystem::with_dir);
+            let inner = files.iter().fold(inner, |fs, file| fs.with_file(file, ""));
+
+            Self {
+                inner,
+                ..Self::default()
+            }
         }
 
-        if self.config.show_hidden_option
-            && ui
-                .checkbox(
-                    &mut self.config.storage.show_hidden,
-                    &self.config.labels.show_hidden,
-                )
-                .clicked()
-        {
-            self.refresh();
-            ui.close_kind(UiKind::Menu);
+        fn with_file_size(mut self, path: &str, size: usize) -> Self {
+            self.inner = self.inner.with_file(path, vec![0; size]);
+            self
         }
 
-        if self.config.show_system_files_option
-            && ui
-                .checkbox(
-                    &mut self.config.storage.show_system_files,
-                    &self.config.labels.show_system_files,
-                )
-                .clicked()
-        {
-            self.refresh();
-            ui.close_kind(UiKind::Menu);
+        fn with_slow_directory(mut self, path: &str) -> Self {
+            self.slow_directories.push(PathBuf::from(path));
+            self
         }
-    }
 
-    /// Updates the search input
-    fn ui_update_search(&mut self, ui: &mut egui::Ui) {
-        egui::Frame::default()
-            .stroke(egui::Stroke::new(
-                1.0,
-                ui.ctx().style().visuals.window_stroke.color,
-            ))
-            .inner_margin(egui::Margin::symmetric(4, 4))
-            .corner_radius(egui::CornerRadius::from(4))
-            .show(ui, |ui| {
-                ui.with_layout(egui::Layout::left_to_right(egui::Align::Min), |ui| {
-                    ui.add_space(ui.ctx().style().spacing.item_spacing.y);
+        fn with_slow_listing(mut self, path: &str) -> Self {
+            self.slow_listings.push(PathBuf::from(path));
+            self
+        }
 
-                    ui.label(egui::RichText::from("🔍").size(15.0));
+        fn with_unreadable_entry(mut self, path: &str) -> Self {
+            self.unreadable_entries.push(PathBuf::from(path));
+            self
+        }
 
-                    let re = ui.add_sized(
-                        egui::Vec2::new(ui.available_width(), 0.0),
-                        egui::TextEdit::singleline(&mut self.search_value),
-                    );
+        fn with_inaccessible_entry(mut self, path: &str) -> Self {
+            self.inaccessible_entries.push(PathBuf::from(path));
+            self
+        }
 
-                    self.edit_search_on_text_input(ui);
+        fn with_link(mut self, link: &str, target: &str) -> Self {
+            self.links
+                .push((PathBuf::from(link), PathBuf::from(target)));
+            self
+        }
+    }
 
-                    if re.changed() || self.init_search {
-                        self.selected_item = None;
-                        self.select_first_visible_item();
-                    }
+    impl FileSystem for TestFileSystem {
+        fn metadata(&self, path: &Path) -> std::io::Result<Metadata> {
+            if self.inaccessible_entries.iter().any(|p| p == path) {
+                return Err(std::io::ErrorKind::PermissionDenied.into());
+            }
 
-                    if self.init_search {
-                        re.request_focus();
-                        Self::set_cursor_to_end(&re, &self.search_value);
-                        self.directory_content.reset_multi_selection();
+            self.inner.metadata(path)
+        }
 
-                        self.init_search = false;
-                    }
-                });
-            });
-    }
+        fn is_dir(&self, path: &Path) -> bool {
+            self.inner.is_dir(path)
+        }
 
-    /// Focuses and types into the search input, if text input without
-    /// shortcut modifiers is detected, and no other inputs are focused.
-    ///
-    /// # Arguments
-    ///
-    /// - `re`: The [`egui::Response`] returned by the filter text edit widget
-    fn edit_search_on_text_input(&mut self, ui: &egui::Ui) {
-        if ui.memory(|mem| mem.focused().is_some()) {
-            return;
+        fn is_file(&self, path: &Path) -> bool {
+            self.inner.is_file(path)
         }
 
-        ui.input(|inp| {
-            // We stop if any modifier is active besides only shift
-            if inp.modifiers.any() && !inp.modifiers.shift_only() {
-                return;
-            }
+        fn exists(&self, path: &Path) -> bool {
+            self.inner.exists(path)
+        }
 
-            // If we find any text input event, we append it to the filter string
-            // and allow proceeding to activating the filter input widget.
-            for text in inp.events.iter().filter_map(|ev| match ev {
-                egui::Event::Text(t) => Some(t),
-                _ => None,
-            }) {
-                self.search_value.push_str(text);
-                self.init_search = true;
-            }
-        });
-    }
+        fn read_dir(&self, path: &Path) -> std::io::Result<Vec<PathBuf>> {
+            let mut children = Vec::new();
+            self.read_dir_incremental(path, &mut |entry| {
+                children.extend(entry);
+                ControlFlow::Continue(())
+            })?;
 
-    /// Updates the left panel of the dialog. Including the list of the user directories (Places)
-    /// and system disks (Devices, Removable Devices).
-    fn ui_update_left_panel(&mut self, ui: &mut egui::Ui) {
-        ui.with_layout(egui::Layout::top_down_justified(egui::Align::LEFT), |ui| {
-            // Spacing multiplier used between sections in the left sidebar
-            const SPACING_MULTIPLIER: f32 = 4.0;
+            Ok(children)
+        }
 
-            egui::containers::ScrollArea::vertical()
-                .auto_shrink([false, false])
-                .show(ui, |ui| {
-                    // Spacing for the first section in the left sidebar
-                    let mut spacing = ui.ctx().style().spacing.item_spacing.y * 2.0;
+        fn read_dir_incremental(
+            &self,
+            path: &Path,
+            on_entry: &mut dyn FnMut(std::io::Result<PathBuf>) -> ControlFlow<()>,
+        ) -> std::io::Result<()> {
+            let children = self.inner.read_dir(path)?;
 
-                    // Update paths pinned to the left sidebar by the user
-                    if self.config.show_pinned_folders && self.ui_update_pinned_paths(ui, spacing) {
-                        spacing = ui.ctx().style().spacing.item_spacing.y * SPACING_MULTIPLIER;
-                    }
+            if self.slow_directories.iter().any(|p| p == path) {
+                std::thread::sleep(Duration::from_millis(500));
+            }
 
-                    // Update custom quick access sections
-                    let quick_accesses = std::mem::take(&mut self.config.quick_accesses);
+            for entry in children {
+                if self.slow_listings.iter().any(|p| p == path) {
+                    std::thread::sleep(Duration::from_millis(50));
+                }
 
-                    for quick_access in &quick_accesses {
-                        ui.add_space(spacing);
-                        self.ui_update_quick_access(ui, quick_access);
-                        spacing = ui.ctx().style().spacing.item_spacing.y * SPACING_MULTIPLIER;
-                    }
+                let entry = if self.unreadable_entries.contains(&entry) {
+                    Err(std::io::ErrorKind::PermissionDenied.into())
+                } else {
+                    Ok(entry)
+                };
 
-                    self.config.quick_accesses = quick_accesses;
+                if on_entry(entry).is_break() {
+                    break;
+                }
+            }
 
-                    // Update native quick access sections
-                    if self.config.show_places && self.ui_update_user_directories(ui, spacing) {
-                        spacing = ui.ctx().style().spacing.item_spacing.y * SPACING_MULTIPLIER;
-                    }
+            Ok(())
+        }
 
-                    let disks = std::mem::take(&mut self.system_disks);
+        fn get_disks(&self, canonicalize_paths: bool) -> Disks {
+            self.inner.get_disks(canonicalize_paths)
+        }
 
-                    if self.config.show_devices && self.ui_update_devices(ui, spacing, &disks) {
-                        spacing = ui.ctx().style().spacing.item_spacing.y * SPACING_MULTIPLIER;
-                    }
+        fn is_path_hidden(&self, path: &Path) -> bool {
+            self.inner.is_path_hidden(path)
+        }
 
-                    if self.config.show_removable_devices
-                        && self.ui_update_removable_devices(ui, spacing, &disks)
-                    {
-                        // Add this when we add a new section after removable devices
-                        // spacing = ui.ctx().style().spacing.item_spacing.y * SPACING_MULTIPLIER;
-                    }
+        fn create_dir(&self, path: &Path) -> std::io::Result<()> {
+            self.inner.create_dir(path)
+        }
 
-                    self.system_disks = disks;
-                });
-        });
-    }
+        fn user_dirs(&self, canonicalize_paths: bool) -> Option<UserDirectories> {
+            self.inner.user_dirs(canonicalize_paths)
+        }
 
-    /// Updates a path entry in the left panel.
-    ///
-    /// Returns the response of the selectable label.
-    fn ui_update_left_panel_entry(
-        &mut self,
-        ui: &mut egui::Ui,
-        display_name: &str,
-        path: &Path,
-    ) -> egui::Response {
-        let response = ui.selectable_label(self.current_directory() == Some(path), display_name);
+        fn current_dir(&self) -> std::io::Result<PathBuf> {
+            self.inner.current_dir()
+        }
 
-        if response.clicked() {
-            self.load_directory(path);
+        fn read_to_string(&self, path: &Path) -> std::io::Result<String> {
+            self.inner.read_to_string(path)
         }
 
-        response
+        fn canonicalize(&self, path: &Path) -> std::io::Result<PathBuf> {
+            let resolved = self.links.iter().find_map(|(link, target)| {
+                let rest = path.strip_prefix(link).ok()?;
+                Some(target.join(rest))
+            });
+
+            self.inner.canonicalize(resolved.as_deref().unwrap_or(path))
+        }
     }
 
-    /// Updates a custom quick access section added to the left panel.
-    fn ui_update_quick_access(&mut self, ui: &mut egui::Ui, quick_access: &QuickAccess) {
-        ui.label(&quick_access.heading);
+    /// Creates a dialog browsing the given file system, which opens the given directory.
+    /// The directories are loaded without a thread, so that they are available immediately.
+    fn test_dialog(
+        file_system: impl FileSystem + Send + Sync + 'static,
+        directory: &str,
+    ) -> FileDialog {
+        FileDialog::with_file_system(Arc::new(file_system))
+            .initial_directory(PathBuf::from(directory))
+            .load_via_thread(false)
+    }
+
+    /// Headless egui context that renders the dialog inside a `CentralPanel` filling a screen
+    /// of a fixed size, and simulates the input of the user.
+    struct TestUi {
+        ctx: egui::Context,
+        /// The shapes painted in the last frame, used to find widgets by their text.
+        shapes: Vec<egui::epaint::ClippedShape>,
+        /// The time of the next frame in seconds.
+        time: f64,
+    }
+
+    impl TestUi {
+        /// The size of the screen the dialog is rendered on.
+        const SCREEN_SIZE: egui::Vec2 = egui::vec2(800.0, 600.0);
+        /// The time that passes between two frames.
+        const FRAME_TIME: f64 = 1.0 / 60.0;
+
+        fn new() -> Self {
+            Self {
+                ctx: egui::Context::default(),
+                shapes: Vec::new(),
+                time: 0.0,
+            }
+        }
 
-        for entry in &quick_access.paths {
-            self.ui_update_left_panel_entry(ui, &entry.display_name, &entry.path);
+        fn screen_rect() -> egui::Rect {
+            egui::Rect::from_min_size(egui::Pos2::ZERO, Self::SCREEN_SIZE)
         }
-    }
 
-    /// Updates the list of pinned folders.
-    ///
-    /// Returns true if at least one directory item was included in the list and the
-    /// heading is visible. If no item was listed, false is returned.
-    fn ui_update_pinned_paths(&mut self, ui: &mut egui::Ui, spacing: f32) -> bool {
-        let mut visible = false;
+        /// Runs a single frame with the given input.
+        fn run_ctx(&mut self, mut input: egui::RawInput, run_ui: impl FnMut(&egui::Context)) {
+            input.screen_rect = Some(Self::screen_rect());
+            input.time = Some(self.time);
+            self.time += Self::FRAME_TIME;
+            self.shapes = self.ctx.run(input, run_ui).shapes;
+        }
 
-        for (i, path) in self
-            .config
-            .storage
-            .pinned_folders
-            .clone()
-            .iter()
-            .enumerate()
-        {
-            if i == 0 {
-                ui.add_space(spacing);
-                ui.label(self.config.labels.heading_pinned.as_str());
+        /// Runs a single frame with the given input and calls `add_contents` inside
+        /// a `CentralPanel`.
+        fn run_ui(&mut self, input: egui::RawInput, mut add_contents: impl FnMut(&mut egui::Ui)) {
+            self.run_ctx(input, |ctx| {
+                egui::CentralPanel::default().show(ctx, |ui| add_contents(ui));
+            });
+        }
 
-                visible = true;
-            }
+        /// Runs a single frame that updates the dialog using `FileDialog::update`.
+        fn run(&mut self, dialog: &mut FileDialog, events: Vec<egui::Event>) -> FileDialogResponse {
+            self.run_with_modifiers(dialog, egui::Modifiers::NONE, events)
+        }
 
-            let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+        /// Runs a single frame that updates the dialog while the given modifiers are held.
+        fn run_with_modifiers(
+            &mut self,
+            dialog: &mut FileDialog,
+            modifiers: egui::Modifiers,
+            events: Vec<egui::Event>,
+        ) -> FileDialogResponse {
+            let input = egui::RawInput {
+                modifiers,
+                events,
+                ..Default::default()
+            };
 
-            let response = self.ui_update_left_panel_entry(
-                ui,
-                &format!("{}  {}", self.config.pinned_icon, file_name),
-                path.as_path(),
-            );
+            let mut response = FileDialogResponse::default();
+            self.run_ui(input, |ui| {
+                let ctx = ui.ctx().clone();
+                response = dialog.update(&ctx, ui);
+            });
+            response
+        }
 
-            self.ui_update_path_context_menu(&response, path);
+        /// Presses the key while the given modifiers are held.
+        fn press_key(
+            &mut self,
+            dialog: &mut FileDialog,
+            key: egui::Key,
+            modifiers: egui::Modifiers,
+        ) {
+            let event = egui::Event::Key {
+                key,
+                physical_key: None,
+                pressed: true,
+                repeat: false,
+                modifiers,
+            };
+            self.run_with_modifiers(dialog, modifiers, vec![event]);
         }
 
-        visible
-    }
+        /// Moves the pointer to the position and clicks the given button there.
+        /// Each step is done in a separate frame, like a real user would.
+        fn click_button(
+            &mut self,
+            dialog: &mut FileDialog,
+            pos: egui::Pos2,
+            button: egui::PointerButton,
+        ) {
+            let event = |pressed| egui::Event::PointerButton {
+                pos,
+                button,
+                pressed,
+                modifiers: egui::Modifiers::NONE,
+            };
 
-    /// Updates the list of user directories (Places).
-    ///
-    /// Returns true if at least one directory was included in the list and the
-    /// heading is visible. If no directory was listed, false is returned.
-    fn ui_update_user_directories(&mut self, ui: &mut egui::Ui, spacing: f32) -> bool {
-        // Take temporary ownership of the user directories and configuration.
-        // This is done so that we don't have to clone the user directories and
-        // configured display names.
-        let user_directories = std::mem::take(&mut self.user_directories);
-        let labels = std::mem::take(&mut self.config.labels);
+            self.wait_for_click_timeout();
+            self.run(dialog, vec![egui::Event::PointerMoved(pos)]);
+            self.run(dialog, vec![event(true)]);
+            self.run(dialog, vec![event(false)]);
+        }
 
-        let mut visible = false;
+        /// Lets enough time pass so that the next click is not counted as
+        /// a double click together with the previous one.
+        fn wait_for_click_timeout(&mut self) {
+            self.time += 1.0;
+        }
 
-        if let Some(dirs) = &user_directories {
-            ui.add_space(spacing);
-            ui.label(labels.heading_places.as_str());
+        /// Clicks the primary pointer button at the given position.
+        fn click(&mut self, dialog: &mut FileDialog, pos: egui::Pos2) {
+            self.click_button(dialog, pos, egui::PointerButton::Primary);
+        }
 
-            if let Some(path) = dirs.home_dir() {
-                self.ui_update_left_panel_entry(ui, &labels.home_dir, path);
-            }
-            if let Some(path) = dirs.desktop_dir() {
-                self.ui_update_left_panel_entry(ui, &labels.desktop_dir, path);
-            }
-            if let Some(path) = dirs.document_dir() {
-                self.ui_update_left_panel_entry(ui, &labels.documents_dir, path);
-            }
-            if let Some(path) = dirs.download_dir() {
-                self.ui_update_left_panel_entry(ui, &labels.downloads_dir, path);
-            }
-            if let Some(path) = dirs.audio_dir() {
-                self.ui_update_left_panel_entry(ui, &labels.audio_dir, path);
+        /// Double clicks the primary pointer button at the given position.
+        fn double_click(&mut self, dialog: &mut FileDialog, pos: egui::Pos2) {
+            let event = |pressed| egui::Event::PointerButton {
+                pos,
+                button: egui::PointerButton::Primary,
+                pressed,
+                modifiers: egui::Modifiers::NONE,
+            };
+
+            self.wait_for_click_timeout();
+            self.run(dialog, vec![egui::Event::PointerMoved(pos)]);
+            for pressed in [true, false, true, false] {
+                self.run(dialog, vec![event(pressed)]);
             }
-            if let Some(path) = dirs.picture_dir() {
-                self.ui_update_left_panel_entry(ui, &labels.pictures_dir, path);
+        }
+
+        /// Gets the rects of the texts painted in the last frame that match the predicate.
+        fn text_rects(&self, matches: impl Fn(&str) -> bool) -> Vec<egui::Rect> {
+            fn collect(
+                shape: &egui::Shape,
+                matches: &dyn Fn(&str) -> bool,
+                rects: &mut Vec<egui::Rect>,
+            ) {
+                match shape {
+                    egui::Shape::Text(text) if matches(text.galley.text()) => {
+                        rects.push(text.galley.rect.translate(text.pos.to_vec2()));
+                    }
+                    egui::Shape::Vec(shapes) => {
+                        for shape in shapes {
+                            collect(shape, matches, rects);
+                        }
+                    }
+                    _ => {}
+                }
             }
-            if let Some(path) = dirs.video_dir() {
-                self.ui_update_left_panel_entry(ui, &labels.videos_dir, path);
+
+            let mut rects = Vec::new();
+            for clipped in &self.shapes {
+                collect(&clipped.shape, &matches, &mut rects);
             }
+            rects
+        }
 
-            visible = true;
+        /// Gets the rect of the first text painted in the last frame that matches the
+        /// predicate, for example the label of a directory entry.
+        fn text_rect(&self, matches: impl Fn(&str) -> bool) -> egui::Rect {
+            let Some(rect) = self.text_rects(matches).first().copied() else {
+                panic!("no matching text was painted in the last frame");
+            };
+            rect
         }
 
-        self.user_directories = user_directories;
-        self.config.labels = labels;
+        /// Gets the rect of the label of the directory entry with the given file name.
+        /// The labels start with the icon of the entry, separated by a space.
+        fn entry_rect(&self, file_name: &str) -> egui::Rect {
+            let suffix = format!(" {file_name}");
+            self.text_rect(|text| text.ends_with(&suffix))
+        }
+    }
 
-        visible
+    /// Creates a file dialog that is currently open in an empty directory.
+    fn open_dialog() -> FileDialog {
+        let mut dialog = test_dialog(MemoryFileSystem::new(), "/");
+        dialog.pick_file();
+        dialog
     }
 
-    /// Updates the list of devices like system disks.
-    ///
-    /// Returns true if at least one device was included in the list and the
-    /// heading is visible. If no device was listed, false is returned.
-    fn ui_update_devices(&mut self, ui: &mut egui::Ui, spacing: f32, disks: &Disks) -> bool {
-        let mut visible = false;
+    #[test]
+    fn cancel_closes_create_directory_dialog_first() {
+        let mut dialog = open_dialog();
+        dialog.create_directory_dialog.open(PathBuf::from("/"));
+        dialog.search_value = String::from("search");
 
-        for (i, disk) in disks.iter().filter(|x| !x.is_removable()).enumerate() {
-            if i == 0 {
-                ui.add_space(spacing);
-                ui.label(self.config.labels.heading_devices.as_str());
+        dialog.exec_keybinding_cancel();
 
-                visible = true;
-            }
+        assert!(!dialog.create_directory_dialog.is_open());
+        assert_eq!(dialog.search_value, "search");
+        assert_eq!(dialog.state, DialogState::Open);
+    }
 
-            self.ui_update_device_entry(ui, disk);
-        }
+    #[test]
+    fn cancel_clears_search_before_cancelling() {
+        let mut dialog = open_dialog();
+        dialog.search_value = String::from("search");
 
-        visible
+        dialog.exec_keybinding_cancel();
+
+        assert!(dialog.search_value.is_empty());
+        assert_eq!(dialog.state, DialogState::Open);
+
+        dialog.exec_keybinding_cancel();
+
+        assert_eq!(dialog.state, DialogState::Cancelled);
     }
 
-    /// Updates the list of removable devices like USB drives.
-    ///
-    /// Returns true if at least one device was included in the list and the
-    /// heading is visible. If no device was listed, false is returned.
-    fn ui_update_removable_devices(
-        &mut self,
-        ui: &mut egui::Ui,
-        spacing: f32,
-        disks: &Disks,
-    ) -> bool {
-        let mut visible = false;
+    #[test]
+    fn navigate_mock_file_system() {
+        let file_system = TestFileSystem::new(
+            &["/home", "/home/user", "/home/user/docs"],
+            &["/home/user/notes.txt"],
+        );
 
-        for (i, disk) in disks.iter().filter(|x| x.is_removable()).enumerate() {
-            if i == 0 {
-                ui.add_space(spacing);
-                ui.label(self.config.labels.heading_removable_devices.as_str());
+        let mut dialog = test_dialog(file_system, "/home/user");
 
-                visible = true;
-            }
+        dialog.pick_file();
 
-            self.ui_update_device_entry(ui, disk);
-        }
+        assert_eq!(dialog.current_directory(), Some(Path::new("/home/user")));
+        let names: Vec<&str> = dialog
+            .get_dir_content_filtered_iter()
+            .map(DirectoryEntry::file_name)
+            .collect();
+        assert_eq!(names, ["docs", "notes.txt"]);
 
-        visible
+        dialog.load_parent_directory();
+        assert_eq!(dialog.current_directory(), Some(Path::new("/home")));
+
+        dialog.load_previous_directory();
+        assert_eq!(dialog.current_directory(), Some(Path::new("/home/user")));
     }
 
-    /// Updates a device entry of a device list like "Devices" or "Removable Devices".
-    fn ui_update_device_entry(&mut self, ui: &mut egui::Ui, device: &Disk) {
-        let label = if device.is_removable() {
-            format!(
-                "{}  {}",
-                self.config.removable_device_icon,
-                device.display_name()
-            )
-        } else {
-            format!("{}  {}", self.config.device_icon, device.display_name())
-        };
+    #[test]
+    fn selection_first_last_and_page() {
+        let files: Vec<String> = (0..100).map(|i| format!("/dir/file{i:02}.txt")).collect();
+        let files: Vec<&str> = files.iter().map(String::as_str).collect();
+        let file_system = TestFileSystem::new(&["/dir"], &files);
 
-        self.ui_update_left_panel_entry(ui, &label, device.mount_point());
-    }
+        let mut dialog = test_dialog(file_system, "/dir").as_modal(false);
 
-    /// Updates the bottom panel showing the selected item and main action buttons.
-    fn ui_update_bottom_panel(&mut self, ui: &mut egui::Ui) {
-        const BUTTON_HEIGHT: f32 = 20.0;
-        ui.add_space(5.0);
+        dialog.pick_file();
 
-        // Calculate the width of the action buttons
-        let label_submit_width = match self.mode {
-            DialogMode::PickDirectory | DialogMode::PickFile | DialogMode::PickMultiple => {
-                Self::calc_text_width(ui, &self.config.labels.open_button)
-            }
-            DialogMode::SaveFile => Self::calc_text_width(ui, &self.config.labels.save_button),
+        // The number of rows per page depends on the height of the rendered list
+        TestUi::new().run(&mut dialog, Vec::new());
+        let page = dialog.rows_per_page;
+        assert!(page > 1 && page < 50, "{page}");
+
+        let selected = |dialog: &FileDialog| {
+            dialog
+                .selected_entry()
+                .map(|e| e.file_name().to_string())
+                .unwrap_or_default()
         };
 
-        let mut btn_width = Self::calc_text_width(ui, &self.config.labels.cancel_button);
-        if label_submit_width > btn_width {
-            btn_width = label_submit_width;
+        dialog.exec_keybinding_selection_last();
+        assert_eq!(selected(&dialog), "file99.txt");
+
+        dialog.exec_keybinding_selection_first();
+        assert_eq!(selected(&dialog), "file00.txt");
+
+        dialog.exec_keybinding_selection_page(true);
+        assert_eq!(selected(&dialog), format!("file{page:02}.txt"));
+
+        for _ in 0..100 {
+            dialog.exec_keybinding_selection_page(true);
         }
+        assert_eq!(selected(&dialog), "file99.txt");
 
-        btn_width += ui.spacing().button_padding.x * 4.0;
+        dialog.exec_keybinding_selection_page(false);
+        assert_eq!(selected(&dialog), format!("file{:02}.txt", 99 - page));
+    }
 
-        // The size of the action buttons "cancel" and "open"/"save"
-        let button_size: egui::Vec2 = egui::Vec2::new(btn_width, BUTTON_HEIGHT);
+    #[test]
+    fn reject_hidden_navigation() {
+        let file_system = TestFileSystem::new(
+            &["/home", "/home/.config", "/home/.config/app"],
+            &["/home/.config/app/settings.toml"],
+        );
 
-        self.ui_update_selection_preview(ui, button_size);
+        let mut dialog = test_dialog(file_system, "/home").allow_hidden_navigation(false);
 
-        if self.mode == DialogMode::SaveFile && self.config.save_extensions.is_empty() {
-            ui.add_space(ui.style().spacing.item_spacing.y);
+        dialog.pick_file();
+        assert_eq!(
+            dialog.directory_content.update(),
+            &DirectoryContentState::Success
+        );
+
+        let err = dialog.config.labels.err_hidden_navigation.clone();
+
+        // The current directory, the history and the last visited directory stay the same
+        for path in ["/home/.config", "/home/.config/app"] {
+            dialog.load_directory(Path::new(path));
+            assert_eq!(dialog.current_directory(), Some(Path::new("/home")));
+            assert_eq!(dialog.directory_stack, [PathBuf::from("/home")]);
+            assert_eq!(
+                dialog.config.storage.last_visited_dir,
+                Some(PathBuf::from("/home"))
+            );
+            assert_eq!(
+                dialog.directory_content.update(),
+                &DirectoryContentState::Success
+            );
+            assert_eq!(dialog.directory_notice.as_ref(), Some(&err));
         }
 
-        self.ui_update_action_buttons(ui, button_size);
+        // A rejected initial directory displays the error instead of the content
+        let mut dialog = FileDialog::with_file_system(dialog.config.file_system.clone())
+            .initial_directory(PathBuf::from("/home/.config"))
+            .load_via_thread(false)
+            .allow_hidden_navigation(false);
+
+        dialog.pick_file();
+        assert_eq!(dialog.current_directory(), None);
+        assert_eq!(
+            dialog.directory_content.update(),
+            &DirectoryContentState::Errored(err)
+        );
     }
 
-    /// Updates the selection preview like "Selected directory: X"
-    fn ui_update_selection_preview(&mut self, ui: &mut egui::Ui, button_size: egui::Vec2) {
-        const SELECTION_PREVIEW_MIN_WIDTH: f32 = 50.0;
-        let item_spacing = ui.style().spacing.item_spacing;
-
-        let render_filter_selection = (!self.config.file_filters.is_empty()
-            && (self.mode == DialogMode::PickFile || self.mode == DialogMode::PickMultiple))
-            || (!self.config.save_extensions.is_empty() && self.mode == DialogMode::SaveFile);
+    #[test]
+    fn directory_changed_callback() {
+        let file_system = TestFileSystem::new(&["/home", "/home/user"], &[]);
+        let changes = Arc::new(egui::mutex::Mutex::new(Vec::new()));
+        let changes_callback = changes.clone();
 
-        let filter_selection_width = button_size.x.mul_add(2.0, item_spacing.x);
-        let mut filter_selection_separate_line = false;
+        let mut dialog =
+            test_dialog(file_system, "/home/user").on_directory_changed(Box::new(move |path| {
+                changes_callback.lock().push(path.to_path_buf());
+            }));
 
-        ui.horizontal(|ui| {
-            match &self.mode {
-                DialogMode::PickDirectory => ui.label(&self.config.labels.selected_directory),
-                DialogMode::PickFile => ui.label(&self.config.labels.selected_file),
-                DialogMode::PickMultiple => ui.label(&self.config.labels.selected_items),
-                DialogMode::SaveFile => ui.label(&self.config.labels.file_name),
-            };
+        dialog.pick_directory();
+        dialog.load_parent_directory();
+        dialog.load_previous_directory();
+        dialog.load_next_directory();
+        dialog.refresh();
 
-            // Make sure there is enough width for the selection preview. If the available
-            // width is not enough, render the drop-down menu to select a file filter or
-            // save extension on a separate line and give the selection preview
-            // the entire available width.
-            let mut scroll_bar_width: f32 =
-                ui.available_width() - filter_selection_width - item_spacing.x;
+        assert_eq!(
+            *changes.lock(),
+            ["/home/user", "/home", "/home/user", "/home"].map(PathBuf::from)
+        );
+    }
 
-            if scroll_bar_width < SELECTION_PREVIEW_MIN_WIDTH || !render_filter_selection {
-                filter_selection_separate_line = true;
-                scroll_bar_width = ui.available_width();
+    #[test]
+    fn directory_changed_after_loading() {
+        let file_system = TestFileSystem::new(&["/dir", "/dir/slow"], &["/dir/a.txt"])
+            .with_slow_directory("/dir/slow");
+        let changes = Arc::new(egui::mutex::Mutex::new(Vec::new()));
+        let changes_callback = changes.clone();
+
+        let mut dialog = test_dialog(file_system, "/dir")
+            .load_via_thread(true)
+            .as_modal(false)
+            .on_directory_changed(Box::new(move |path| {
+                changes_callback.lock().push(path.to_path_buf());
+            }));
+        dialog.pick_file();
+
+        let mut ui = TestUi::new();
+        let mut run_until_loaded = |dialog: &mut FileDialog| {
+            ui.run(dialog, Vec::new());
+            while dialog.directory_content.is_pending() {
+                std::thread::sleep(Duration::from_millis(1));
+                ui.run(dialog, Vec::new());
             }
+        };
 
-            match &self.mode {
-                DialogMode::PickDirectory | DialogMode::PickFile | DialogMode::PickMultiple => {
-                    use egui::containers::scroll_area::ScrollBarVisibility;
+        run_until_loaded(&mut dialog);
+        assert_eq!(*changes.lock(), [PathBuf::from("/dir")]);
+
+        // The function is not called while the directory is loading
+        dialog.load_directory(Path::new("/dir/slow"));
+        assert!(dialog.directory_content.is_pending());
+        assert_eq!(changes.lock().len(), 1);
+
+        run_until_loaded(&mut dialog);
+        assert_eq!(*changes.lock(), ["/dir", "/dir/slow"].map(PathBuf::from));
+
+        // Directories whose loading was cancelled are never reported
+        dialog.load_parent_directory();
+        run_until_loaded(&mut dialog);
+        dialog.load_directory(Path::new("/dir/slow"));
+        dialog.cancel_directory_load();
+        run_until_loaded(&mut dialog);
+        assert_eq!(
+            *changes.lock(),
+            ["/dir", "/dir/slow", "/dir", "/dir"].map(PathBuf::from)
+        );
+    }
 
-                    let text = self.get_selection_preview_text();
+    #[test]
+    fn directory_created_callback() {
+        let file_system = TestFileSystem::new(&["/dir", "/dir/new"], &[]);
+        let created = Arc::new(egui::mutex::Mutex::new(Vec::new()));
+        let created_callback = created.clone();
 
-                    egui::containers::ScrollArea::horizontal()
-                        .auto_shrink([false, false])
-                        .max_width(scroll_bar_width)
-                        .stick_to_right(true)
-                        .scroll_bar_visibility(ScrollBarVisibility::AlwaysHidden)
-                        .show(ui, |ui| {
-                            ui.colored_label(ui.style().visuals.selection.bg_fill, text);
-                        });
-                }
-                DialogMode::SaveFile => {
-                    let mut output = egui::TextEdit::singleline(&mut self.file_name_input)
-                        .cursor_at_end(false)
-                        .margin(egui::Margin::symmetric(4, 3))
-                        .desired_width(scroll_bar_width - item_spacing.x)
-                        .show(ui);
+        let mut dialog =
+            test_dialog(file_system, "/dir").on_directory_created(Box::new(move |path| {
+                created_callback.lock().push(path.to_path_buf());
+            }));
 
-                    if self.file_name_input_request_focus {
-                        self.highlight_file_name_input(&mut output);
-                        output.state.store(ui.ctx(), output.response.id);
+        // The callback is kept when opening the dialog
+        dialog.pick_directory();
+        dialog.process_new_folder(Path::new("/dir/new"));
+
+        assert_eq!(*created.lock(), [PathBuf::from("/dir/new")]);
+    }
+
+    #[test]
+    fn scroll_to_path() {
+        let file_system = TestFileSystem::new(&["/dir"], &["/dir/a.txt", "/dir/b.txt"]);
+
+        let mut dialog = test_dialog(file_system, "/dir").as_modal(false);
+
+        // Rendering the dialog scrolls to the initial selection
+        dialog.pick_file();
+        TestUi::new().run(&mut dialog, Vec::new());
+        assert!(!dialog.scroll_to_selection);
+
+        assert!(dialog.scroll_to(Path::new("/dir/b.txt")));
+        assert!(dialog.scroll_to_selection);
+        assert_eq!(
+            dialog.selected_entry().map(DirectoryEntry::as_path),
+            Some(Path::new("/dir/b.txt"))
+        );
+
+        assert!(!dialog.scroll_to(Path::new("/dir/c.txt")));
+    }
+
+    #[test]
+    fn refresh_keeps_scroll_position() {
+        let file_system = TestFileSystem::new(&["/dir"], &["/dir/a.txt", "/dir/b.txt"]);
+
+        let mut dialog = test_dialog(file_system, "/dir").as_modal(false);
 
-                        output.response.request_focus();
-                        self.file_name_input_request_focus = false;
-                    }
+        let mut ui = TestUi::new();
 
-                    if output.response.changed() {
-                        self.file_name_input_error = self.validate_file_name_input();
-                    }
+        dialog.pick_file();
+        assert!(dialog.scroll_to(Path::new("/dir/b.txt")));
+        ui.run(&mut dialog, Vec::new());
+        assert!(!dialog.scroll_to_selection);
 
-                    if output.response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter))
-                    {
-                        self.submit();
-                    }
-                }
-            };
+        dialog.refresh();
+        assert!(!dialog.scroll_to_selection);
 
-            if !filter_selection_separate_line && render_filter_selection {
-                if self.mode == DialogMode::SaveFile {
-                    self.ui_update_save_extension_selection(ui, filter_selection_width);
-                } else {
-                    self.ui_update_file_filter_selection(ui, filter_selection_width);
-                }
-            }
-        });
+        ui.run(&mut dialog, Vec::new());
 
-        if filter_selection_separate_line && render_filter_selection {
-            ui.with_layout(egui::Layout::right_to_left(egui::Align::Min), |ui| {
-                if self.mode == DialogMode::SaveFile {
-                    self.ui_update_save_extension_selection(ui, filter_selection_width);
-                } else {
-                    self.ui_update_file_filter_selection(ui, filter_selection_width);
-                }
-            });
-        }
+        // The selected item did not move, so the dialog should not scroll to it
+        assert!(dialog.reload_selection_index.is_none());
+        assert!(!dialog.scroll_to_selection);
+        assert_eq!(
+            dialog.selected_entry().map(DirectoryEntry::as_path),
+            Some(Path::new("/dir/b.txt"))
+        );
     }
 
-    /// Highlights the characters inside the file name input until the file extension.
-    /// Do not forget to store these changes after calling this function:
-    /// `output.state.store(ui.ctx(), output.response.id);`
-    fn highlight_file_name_input(&self, output: &mut egui::text_edit::TextEditOutput) {
-        if let Some(pos) = self.file_name_input.rfind('.') {
-            let range = if pos == 0 {
-                CCursorRange::two(CCursor::new(0), CCursor::new(0))
-            } else {
-                CCursorRange::two(CCursor::new(0), CCursor::new(pos))
-            };
+    #[test]
+    fn mouse_back_button() {
+        let file_system = TestFileSystem::new(&["/dir", "/dir/sub"], &[]);
 
-            output.state.cursor.set_char_range(Some(range));
-        }
-    }
+        let mut dialog = test_dialog(file_system, "/dir").as_modal(false);
 
-    fn get_selection_preview_text(&self) -> String {
-        if self.is_selection_valid() {
-            match &self.mode {
-                DialogMode::PickDirectory | DialogMode::PickFile => self
-                    .selected_item
-                    .as_ref()
-                    .map_or_else(String::new, |item| item.file_name().to_string()),
-                DialogMode::PickMultiple => {
-                    let mut result = String::new();
+        dialog.pick_file();
+        dialog.load_directory(Path::new("/dir/sub"));
 
-                    for (i, item) in self
-                        .get_dir_content_filtered_iter()
-                        .filter(|p| p.selected)
-                        .enumerate()
-                    {
-                        if i == 0 {
-                            result += item.file_name();
-                            continue;
-                        }
+        let mut ui = TestUi::new();
+        ui.run(&mut dialog, Vec::new());
 
-                        result += format!(", {}", item.file_name()).as_str();
-                    }
+        // The button is ignored while the pointer is outside of the dialog
+        let outside = egui::pos2(-10.0, -10.0);
+        ui.click_button(&mut dialog, outside, egui::PointerButton::Extra1);
+        assert_eq!(dialog.current_directory(), Some(Path::new("/dir/sub")));
 
-                    result
-                }
-                DialogMode::SaveFile => String::new(),
-            }
-        } else {
-            String::new()
-        }
+        let inside = TestUi::screen_rect().center();
+        ui.click_button(&mut dialog, inside, egui::PointerButton::Extra1);
+        assert_eq!(dialog.current_directory(), Some(Path::new("/dir")));
     }
 
-    fn ui_update_file_filter_selection(&mut self, ui: &mut egui::Ui, width: f32) {
-        let selected_filter = self.get_selected_file_filter();
-        let selected_text = match selected_filter {
-            Some(f) => &f.name,
-            None => &self.config.labels.file_filter_all_files,
-        };
-
-        // The item that the user selected inside the drop down.
-        // If none, the user did not change the selected item this frame.
-        let mut select_filter: Option<Option<FileFilter>> = None;
+    #[test]
+    fn collapsed_sections() {
+        let mut dialog = test_dialog(MemoryFileSystem::new(), "/");
 
-        egui::containers::ComboBox::from_id_salt(self.window_id.with("file_filter_selection"))
-            .width(width)
-            .selected_text(selected_text)
-            .wrap_mode(egui::TextWrapMode::Truncate)
-            .show_ui(ui, |ui| {
-                for filter in &self.config.file_filters {
-                    let selected = selected_filter.is_some_and(|f| f.id == filter.id);
+        assert!(!dialog.is_section_collapsed(LeftPanelSection::Places));
 
-                    if ui.selectable_label(selected, &filter.name).clicked() {
-                        select_filter = Some(Some(filter.clone()));
-                    }
-                }
+        dialog.set_section_collapsed(LeftPanelSection::Places, true);
+        dialog.set_section_collapsed(LeftPanelSection::Places, true);
+        dialog.set_section_collapsed(LeftPanelSection::Devices, true);
+        assert!(dialog.is_section_collapsed(LeftPanelSection::Places));
+        assert!(dialog.is_section_collapsed(LeftPanelSection::Devices));
+        assert!(!dialog.is_section_collapsed(LeftPanelSection::RemovableDevices));
 
-                if ui
-                    .selectable_label(
-                        selected_filter.is_none(),
-                        &self.config.labels.file_filter_all_files,
-                    )
-                    .clicked()
-                {
-                    select_filter = Some(None);
-                }
-            });
+        dialog.set_section_collapsed(LeftPanelSection::Places, false);
+        assert!(!dialog.is_section_collapsed(LeftPanelSection::Places));
+        assert!(dialog.is_section_collapsed(LeftPanelSection::Devices));
 
-        if let Some(i) = select_filter {
-            self.select_file_filter(i);
-        }
+        // The sections stay collapsed when the dialog is opened
+        dialog.pick_file();
+        assert!(!dialog.is_section_collapsed(LeftPanelSection::Places));
+        assert!(dialog.is_section_collapsed(LeftPanelSection::Devices));
     }
 
-    fn ui_update_save_extension_selection(&mut self, ui: &mut egui::Ui, width: f32) {
-        let selected_extension = self.get_selected_save_extension();
-        let selected_text = match selected_extension {
-            Some(e) => &e.to_string(),
-            None => &self.config.labels.save_extension_any,
-        };
+    #[test]
+    fn reload_removed_directory() {
+        let file_system = TestFileSystem::new(&["/dir", "/dir/sub", "/dir/sub/deep"], &[]);
 
-        // The item that the user selected inside the drop down.
-        // If none, the user did not change the selected item this frame.
-        let mut select_extension: Option<Option<SaveExtension>> = None;
+        let mut dialog = test_dialog(file_system, "/dir/sub/deep");
 
-        egui::containers::ComboBox::from_id_salt(self.window_id.with("save_extension_selection"))
-            .width(width)
-            .selected_text(selected_text)
-            .wrap_mode(egui::TextWrapMode::Truncate)
-            .show_ui(ui, |ui| {
-                for extension in &self.config.save_extensions {
-                    let selected = selected_extension.is_some_and(|s| s.id == extension.id);
+        dialog.pick_file();
+        assert_eq!(dialog.current_directory(), Some(Path::new("/dir/sub/deep")));
 
-                    if ui
-                        .selectable_label(selected, extension.to_string())
-                        .clicked()
-                    {
-                        select_extension = Some(Some(extension.clone()));
-                    }
-                }
-            });
+        // Simulate another process removing the open directory and its parent
+        dialog.config.file_system = Arc::new(TestFileSystem::new(&["/dir"], &[]));
+        dialog.refresh();
 
-        if let Some(i) = select_extension {
-            self.file_name_input_request_focus = true;
-            self.select_save_extension(i);
-        }
+        assert_eq!(dialog.current_directory(), Some(Path::new("/dir")));
+        assert!(dialog
+            .directory_notice
+            .as_deref()
+            .is_some_and(|notice| notice.ends_with("/dir")));
+
+        // The notice is removed when the directory is loaded again
+        dialog.reload_directory();
+        assert!(dialog.directory_notice.is_none());
     }
 
-    /// Updates the action buttons like save, open and cancel
-    fn ui_update_action_buttons(&mut self, ui: &mut egui::Ui, button_size: egui::Vec2) {
-        ui.with_layout(egui::Layout::right_to_left(egui::Align::Min), |ui| {
-            let label = match &self.mode {
-                DialogMode::PickDirectory | DialogMode::PickFile | DialogMode::PickMultiple => {
-                    self.config.labels.open_button.as_str()
-                }
-                DialogMode::SaveFile => self.config.labels.save_button.as_str(),
-            };
+    #[test]
+    fn max_entries() {
+        // The file system returns the entries in an arbitrary order
+        let files = ["/dir/d", "/dir/B", "/dir/e", "/dir/a", "/dir/c"];
+        let file_system = TestFileSystem::new(&["/dir"], &files);
 
-            if self.ui_button_sized(
-                ui,
-                self.is_selection_valid(),
-                button_size,
-                label,
-                self.file_name_input_error.as_deref(),
-            ) {
-                self.submit();
-            }
+        let mut dialog = test_dialog(file_system, "/dir").max_entries(2);
 
-            if ui
-                .add_sized(
-                    button_size,
-                    egui::Button::new(self.config.labels.cancel_button.as_str()),
-                )
-                .clicked()
-            {
-                self.cancel();
-            }
-        });
+        dialog.pick_file();
+        assert_eq!(dialog.directory_content.len(), 2);
+        assert_eq!(dialog.directory_content.truncated_total(), Some(5));
+
+        let names: Vec<_> = dialog
+            .directory_content
+            .iter()
+            .map(DirectoryEntry::file_name)
+            .collect();
+        assert_eq!(names, ["a", "B"]);
+
+        dialog.load_all_entries();
+        assert_eq!(dialog.directory_content.len(), 5);
+        assert_eq!(dialog.directory_content.truncated_total(), None);
+
+        // All entries stay loaded when the directory is refreshed
+        dialog.refresh();
+        assert_eq!(dialog.directory_content.len(), 5);
     }
 
-    /// Updates the central panel. This is either the contents of the directory
-    /// or the error message when there was an error loading the current directory.
-    fn ui_update_central_panel(&mut self, ui: &mut egui::Ui) {
-        if self.update_directory_content(ui) {
-            return;
-        }
+    #[test]
+    fn toggle_pinned_directory() {
+        let file_system = TestFileSystem::new(&["/dir"], &[]);
+        let mut dialog = test_dialog(file_system, "/dir");
 
-        self.ui_update_central_panel_content(ui);
+        dialog.pick_directory();
+        let path = PathBuf::from("/dir");
+
+        dialog.toggle_pinned(path.clone());
+        assert!(dialog.is_pinned(&path));
+        assert_eq!(dialog.config.storage.pinned_folders, vec![path.clone()]);
+
+        dialog.toggle_pinned(path.clone());
+        assert!(!dialog.is_pinned(&path));
+        assert!(dialog.config.storage.pinned_folders.is_empty());
     }
 
-    /// Updates the directory content (Not the UI!).
-    /// This is required because the contents of the directory might be loaded on a
-    /// separate thread. This function checks the status of the directory content
-    /// and updates the UI accordingly.
-    fn update_directory_content(&mut self, ui: &mut egui::Ui) -> bool {
-        const SHOW_SPINNER_AFTER: f32 = 0.2;
+    #[test]
+    fn center_window_on_open() {
+        let file_system = TestFileSystem::new(&["/dir"], &[]);
+        let mut dialog = test_dialog(file_system, "/dir")
+            .as_modal(false)
+            .default_size([400.0, 300.0]);
 
-        match self.directory_content.update() {
-            DirectoryContentState::Pending(timestamp) => {
-                let now = std::time::SystemTime::now();
+        let mut ui = TestUi::new();
+        let mut run = |dialog: &mut FileDialog| {
+            ui.run_ui(egui::RawInput::default(), |ui| {
+                dialog.show(ui);
+            });
+            ui.ctx.memory(|mem| mem.area_rect(dialog.get_window_id()))
+        };
 
-                if now
-                    .duration_since(*timestamp)
-                    .unwrap_or_default()
-                    .as_secs_f32()
-                    > SHOW_SPINNER_AFTER
-                {
-                    ui.centered_and_justified(egui::Ui::spinner);
-                }
+        dialog.pick_file();
+        let rect = run(&mut dialog);
+        let screen_center = TestUi::screen_rect().center();
+        assert!(rect.is_some_and(|rect| rect.center().distance(screen_center) < 50.0));
+        assert!(!dialog.center_window);
 
-                // Prevent egui from not updating the UI when there is no user input
-                ui.ctx().request_repaint();
+        // The window is placed again when it is opened the next time
+        dialog.pick_file();
+        assert!(dialog.center_window);
+    }
 
-                true
-            }
-            DirectoryContentState::Errored(err) => {
-                ui.centered_and_justified(|ui| ui.colored_label(ui.visuals().error_fg_color, err));
-                true
-            }
-            DirectoryContentState::Finished => {
-                if self.mode == DialogMode::PickDirectory {
-                    if let Some(dir) = self.current_directory() {
-                        let mut dir_entry =
-                            DirectoryEntry::from_path(&self.config, dir, &*self.config.file_system);
-                        self.select_item(&mut dir_entry);
-                    }
-                }
+    #[test]
+    fn small_window() {
+        let file_system = TestFileSystem::new(&["/dir"], &["/dir/a.txt"]);
 
-                false
-            }
-            DirectoryContentState::Success => false,
+        let mut dialog = test_dialog(file_system, "/dir")
+            .as_modal(false)
+            .resizable(false)
+            .default_size([150.0, 300.0])
+            .min_size([10.0, 10.0])
+            .right_panel_width_range(60.0..=200.0)
+            .custom_right_panel(Box::new(|ui, _| {
+                ui.label("Details");
+            }));
+        dialog.pick_file();
+
+        let mut ui = TestUi::new();
+        let mut window_rect = egui::Rect::NOTHING;
+        for _ in 0..3 {
+            ui.run_ctx(egui::RawInput::default(), |ctx| {
+                if let Some(re) = dialog.window().show(ctx, |ui| dialog.update(ctx, ui)) {
+                    window_rect = re.response.rect;
+                }
+            });
         }
+
+        // The window is enlarged so that the side panels fit next to the directory content
+        let left_panel_width = *dialog.config.left_panel_width_range.start();
+        assert!(window_rect.width() >= left_panel_width + 60.0 + MIN_CENTRAL_PANEL_WIDTH);
+
+        let entry = ui.entry_rect("a.txt");
+        let details = ui.text_rect(|text| text == "Details");
+        assert!(entry.min.x >= window_rect.min.x + left_panel_width);
+        assert!(entry.max.x <= details.min.x);
+        assert!(details.max.x <= window_rect.max.x);
     }
 
-    /// Updates the contents of the currently open directory.
-    /// TODO: Refactor
-    fn ui_update_central_panel_content(&mut self, ui: &mut egui::Ui) {
-        // Temporarily take ownership of the directory content.
-        let mut data = std::mem::take(&mut self.directory_content);
+    #[test]
+    fn window_title_per_mode() {
+        let file_system = TestFileSystem::new(&["/dir"], &[]);
+        let mut dialog = test_dialog(file_system, "/dir");
+        let labels = FileDialogLabels::default();
 
-        // If the multi selection should be reset, excluding the currently
-        // selected primary item.
-        let mut reset_multi_selection = false;
+        // The same instance is reused for different modes
+        dialog.pick_file();
+        assert_eq!(dialog.get_window_title(), &labels.title_select_file);
 
-        // The item the user wants to make a batch selection from.
-        // The primary selected item is used for item a.
-        let mut batch_select_item_b: Option<DirectoryEntry> = None;
+        dialog.save_file();
+        assert_eq!(dialog.get_window_title(), &labels.title_save_file);
 
-        // If we should return after updating the directory entries.
-        let mut should_return = false;
+        dialog.pick_directory();
+        assert_eq!(dialog.get_window_title(), &labels.title_select_directory);
 
-        ui.with_layout(egui::Layout::top_down_justified(egui::Align::LEFT), |ui| {
-            let scroll_area = egui::containers::ScrollArea::vertical().auto_shrink([false, false]);
+        dialog.pick_multiple();
+        assert_eq!(dialog.get_window_title(), &labels.title_select_multiple);
+    }
 
-            if self.search_value.is_empty()
-                && !self.create_directory_dialog.is_open()
-                && !self.scroll_to_selection
-            {
-                // Only update visible items when the search value is empty,
-                // the create directory dialog is closed and we are currently not scrolling
-                // to the current item.
-                scroll_area.show_rows(ui, ui.spacing().interact_size.y, data.len(), |ui, range| {
-                    for item in data.iter_range_mut(range) {
-                        if self.ui_update_central_panel_entry(
-                            ui,
-                            item,
-                            &mut reset_multi_selection,
-                            &mut batch_select_item_b,
-                        ) {
-                            should_return = true;
-                        }
-                    }
-                });
-            } else {
-                // Update each element if the search value is not empty as we apply the
-                // search value in every frame. We can't use `egui::ScrollArea::show_rows`
-                // because we don't know how many files the search value applies to.
-                // We also have to update every item when the create directory dialog is open as
-                // it's displayed as the last element.
-                scroll_area.show(ui, |ui| {
-                    for item in data.filtered_iter_mut(&self.search_value.clone()) {
-                        if self.ui_update_central_panel_entry(
-                            ui,
-                            item,
-                            &mut reset_multi_selection,
-                            &mut batch_select_item_b,
-                        ) {
-                            should_return = true;
-                        }
-                    }
+    #[test]
+    fn left_panel_widths() {
+        assert_eq!(
+            FileDialog::left_panel_widths(150.0, &(90.0..=250.0)),
+            (150.0, 90.0..=250.0)
+        );
+        assert_eq!(
+            FileDialog::left_panel_widths(500.0, &(90.0..=250.0)),
+            (250.0, 90.0..=250.0)
+        );
+        assert_eq!(
+            FileDialog::left_panel_widths(150.0, &(300.0..=-20.0)),
+            (150.0, 0.0..=300.0)
+        );
+        assert_eq!(
+            FileDialog::left_panel_widths(f32::NAN, &(f32::NAN..=200.0)),
+            (200.0, 200.0..=200.0)
+        );
+    }
 
-                    if let Some(entry) = self.ui_update_create_directory_dialog(ui) {
-                        data.push(entry);
-                    }
-                });
-            }
-        });
+    #[test]
+    #[cfg(unix)]
+    fn path_segments_root() {
+        let segments = |path: &str| {
+            FileDialog::get_path_segments(Path::new(path))
+                .into_iter()
+                .map(|(name, path)| (name, path.to_string_lossy().into_owned()))
+                .collect::<Vec<_>>()
+        };
 
-        if should_return {
-            return;
-        }
+        assert_eq!(segments("/"), [("/".into(), "/".into())]);
+        assert_eq!(
+            segments("/home/user"),
+            [
+                ("/".into(), "/".into()),
+                ("home".into(), "/home".into()),
+                ("user".into(), "/home/user".into())
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg(windows)]
+    fn path_segments_root() {
+        let segments: Vec<_> = FileDialog::get_path_segments(Path::new("C:\\Users"))
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect();
+
+        assert_eq!(segments, ["C:\\", "Users"]);
+    }
 
-        // Reset the multi selection except the currently selected primary item
-        if reset_multi_selection {
-            for item in data.filtered_iter_mut(&self.search_value) {
-                if let Some(selected_item) = &self.selected_item {
-                    if selected_item.path_eq(item) {
-                        continue;
-                    }
-                }
+    #[test]
+    fn pick_nonexistent_file() {
+        let file_system = TestFileSystem::new(&["/dir"], &[]);
 
-                item.selected = false;
-            }
-        }
+        let mut dialog = test_dialog(file_system, "/dir").allow_nonexistent_file(true);
 
-        // Check if we should perform a batch selection
-        if let Some(item_b) = batch_select_item_b {
-            if let Some(item_a) = &self.selected_item {
-                self.batch_select_between(&mut data, item_a, &item_b);
-            }
-        }
+        dialog.pick_file();
 
-        self.directory_content = data;
-        self.scroll_to_selection = false;
+        dialog.path_edit_value = String::from("/missing/new.txt");
+        dialog.submit_path_edit();
+        assert_eq!(dialog.state, DialogState::Open);
+
+        dialog.path_edit_value = String::from("/dir/new.txt");
+        dialog.submit_path_edit();
+        assert_eq!(
+            dialog.state,
+            DialogState::Picked(PathBuf::from("/dir/new.txt"))
+        );
     }
 
-    /// Updates a single directory content entry.
-    /// TODO: Refactor
-    fn ui_update_central_panel_entry(
-        &mut self,
-        ui: &mut egui::Ui,
-        item: &mut DirectoryEntry,
-        reset_multi_selection: &mut bool,
-        batch_select_item_b: &mut Option<DirectoryEntry>,
-    ) -> bool {
-        let file_name = item.file_name();
-        let primary_selected = self.is_primary_selected(item);
-        let pinned = self.is_pinned(item.as_path());
+    #[test]
+    fn set_history() {
+        let file_system = TestFileSystem::new(&["/a", "/b", "/c"], &[]);
 
-        let icons = if pinned {
-            format!("{} {} ", item.icon(), self.config.pinned_icon)
-        } else {
-            format!("{} ", item.icon())
-        };
+        let mut dialog = test_dialog(file_system, "/c").preserve_history(true);
 
-        let icons_width = Self::calc_text_width(ui, &icons);
+        let history = ["/a", "/missing", "/b", "/c"].map(PathBuf::from).to_vec();
+        dialog.set_history(history, 2);
 
-        // Calc available width for the file name and include a small margin
-        let available_width = ui.available_width() - icons_width - 15.0;
+        assert_eq!(dialog.history(), ["/a", "/b", "/c"].map(PathBuf::from));
+        assert_eq!(dialog.history_position(), Some(1));
+        assert_eq!(dialog.current_directory(), Some(Path::new("/b")));
+        assert_eq!(dialog.history_offset(), 1);
+        assert!(dialog.can_go_back());
+        assert!(dialog.can_go_forward());
 
-        let truncate = self.config.truncate_filenames
-            && available_width < Self::calc_text_width(ui, file_name);
+        // The initial directory is opened and the history is kept
+        dialog.pick_directory();
+        assert_eq!(dialog.history(), ["/a", "/b", "/c"].map(PathBuf::from));
+        assert_eq!(dialog.history_position(), Some(2));
+        assert!(!dialog.can_go_forward());
 
-        let text = if truncate {
-            Self::truncate_filename(ui, item, available_width)
-        } else {
-            file_name.to_owned()
-        };
+        dialog.load_previous_directory();
+        assert_eq!(dialog.current_directory(), Some(Path::new("/b")));
+    }
 
-        let mut re =
-            ui.selectable_label(primary_selected || item.selected, format!("{icons}{text}"));
+    #[test]
+    fn update_returns_result_once() {
+        let file_system = TestFileSystem::new(&["/dir"], &["/dir/a.txt"]);
 
-        if truncate {
-            re = re.on_hover_text(file_name);
-        }
+        let mut dialog = test_dialog(file_system, "/dir");
+        dialog.pick_file();
 
-        if item.is_dir() {
-            self.ui_update_path_context_menu(&re, item.as_path());
+        let mut ui = TestUi::new();
+        let mut run_frame = |events: Vec<egui::Event>| ui.run(&mut dialog, events);
 
-            if re.context_menu_opened() {
-                self.select_item(item);
-            }
-        }
+        let response = run_frame(Vec::new());
+        assert!(response.is_open());
+        assert!(response.rect().is_some());
+        assert!(!response.cancelled());
 
-        if primary_selected && self.scroll_to_selection {
-            re.scroll_to_me(Some(egui::Align::Center));
-            self.scroll_to_selection = false;
-        }
+        let escape = egui::Event::Key {
+            key: egui::Key::Escape,
+            physical_key: None,
+            pressed: true,
+            repeat: false,
+            modifiers: egui::Modifiers::NONE,
+        };
 
-        // The user wants to select the item as the primary selected item
-        if re.clicked()
-            && !ui.input(|i| i.modifiers.command)
-            && !ui.input(|i| i.modifiers.shift_only())
-        {
-            self.select_item(item);
+        // The result is only contained in the response of the frame the dialog was finished in
+        let response = run_frame(vec![escape]);
+        assert!(!response.is_open());
+        assert!(response.cancelled());
+        assert_eq!(response.selected(), None);
 
-            // Reset the multi selection except the now primary selected item
-            if self.mode == DialogMode::PickMultiple {
-                *reset_multi_selection = true;
-            }
-        }
+        let response = run_frame(Vec::new());
+        assert!(!response.is_open());
+        assert!(!response.cancelled());
+    }
 
-        // The user wants to select or unselect the item as part of a
-        // multi selection
-        if self.mode == DialogMode::PickMultiple
-            && re.clicked()
-            && ui.input(|i| i.modifiers.command)
-        {
-            if primary_selected {
-                // If the clicked item is the primary selected item,
-                // deselect it and remove it from the multi selection
-                item.selected = false;
-                self.selected_item = None;
-            } else {
-                item.selected = !item.selected;
+    #[test]
+    fn submit_directory_in_pick_file_mode() {
+        let file_system = TestFileSystem::new(&["/dir", "/dir/sub"], &["/dir/a.txt"]);
 
-                // If the item was selected, make it the primary selected item
-                if item.selected {
-                    self.select_item(item);
-                }
-            }
-        }
+        let mut dialog = test_dialog(file_system, "/dir");
+        dialog.pick_file();
 
-        // The user wants to select every item between the last selected item
-        // and the current item
-        if self.mode == DialogMode::PickMultiple
-            && re.clicked()
-            && ui.input(|i| i.modifiers.shift_only())
-        {
-            if let Some(selected_item) = self.selected_item.clone() {
-                // We perform a batch selection from the item that was
-                // primarily selected before the user clicked on this item.
-                *batch_select_item_b = Some(selected_item);
+        assert!(dialog.scroll_to(Path::new("/dir/sub")));
+        assert_eq!(
+            dialog.get_submit_directory(),
+            Some(PathBuf::from("/dir/sub"))
+        );
 
-                // And now make this item the primary selected item
-                item.selected = true;
-                self.select_item(item);
-            }
-        }
+        assert!(dialog.scroll_to(Path::new("/dir/a.txt")));
+        assert_eq!(dialog.get_submit_directory(), None);
 
-        // The user double clicked on the directory entry.
-        // Either open the directory or submit the dialog.
-        if re.double_clicked() && !ui.input(|i| i.modifiers.command) {
-            if item.is_dir() {
-                self.load_directory(&item.to_path_buf());
-                return true;
-            }
+        dialog.pick_directory();
 
-            self.select_item(item);
+        assert!(dialog.scroll_to(Path::new("/dir/sub")));
+        assert_eq!(dialog.get_submit_directory(), None);
+    }
 
-            self.submit();
+    #[test]
+    fn load_same_directory() {
+        let file_system = TestFileSystem::new(&["/foo", "/foo/bar"], &[]);
+
+        let mut dialog = test_dialog(file_system, "/foo");
+        dialog.pick_directory();
+
+        for path in ["/foo", "/foo/", "/foo/."] {
+            dialog.load_directory(Path::new(path));
+            assert_eq!(dialog.history(), [PathBuf::from("/foo")]);
         }
 
-        false
+        dialog.load_directory(Path::new("/foo/bar"));
+        assert_eq!(dialog.history().len(), 2);
     }
 
-    fn ui_update_create_directory_dialog(&mut self, ui: &mut egui::Ui) -> Option<DirectoryEntry> {
-        self.create_directory_dialog
-            .update(ui, &self.config)
-            .directory()
-            .map(|path| self.process_new_folder(&path))
+    #[test]
+    fn group_header_segments() {
+        let file_system = TestFileSystem::new(&["/dir", "/dir/a", "/dir/b"], &["/dir/c.txt"]);
+
+        let mut dialog = test_dialog(file_system, "/dir").show_group_headers(true);
+        dialog.pick_file();
+
+        assert_eq!(
+            dialog.get_content_segments(&dialog.directory_content),
+            [
+                ContentSegment::Header(EntryGroup::Folders, 2),
+                ContentSegment::Entries(0..2),
+                ContentSegment::Header(EntryGroup::Files, 1),
+                ContentSegment::Entries(2..3),
+            ]
+        );
+
+        dialog.collapsed_groups.push(EntryGroup::Folders);
+        assert_eq!(
+            dialog.get_content_segments(&dialog.directory_content),
+            [
+                ContentSegment::Header(EntryGroup::Folders, 2),
+                ContentSegment::Header(EntryGroup::Files, 1),
+                ContentSegment::Entries(2..3),
+            ]
+        );
+
+        // The headers are hidden while searching
+        dialog.search_value = String::from("a");
+        assert_eq!(
+            dialog.get_content_segments(&dialog.directory_content),
+            [ContentSegment::Entries(0..3)]
+        );
     }
 
-    /// Selects every item inside the `directory_content` between `item_a` and `item_b`,
-    /// excluding both given items.
-    fn batch_select_between(
-        &self,
-        directory_content: &mut DirectoryContent,
-        item_a: &DirectoryEntry,
-        item_b: &DirectoryEntry,
-    ) {
-        // Get the position of item a and item b
-        let pos_a = directory_content
-            .filtered_iter(&self.search_value)
-            .position(|p| p.path_eq(item_a));
-        let pos_b = directory_content
-            .filtered_iter(&self.search_value)
-            .position(|p| p.path_eq(item_b));
+    #[test]
+    fn inline_picker_returns_path_once() {
+        let file_system = TestFileSystem::new(&["/dir"], &["/dir/a.txt"]);
 
-        // If both items where found inside the directory entry, mark every item between
-        // them as selected
-        if let Some(pos_a) = pos_a {
-            if let Some(pos_b) = pos_b {
-                if pos_a == pos_b {
-                    return;
-                }
+        let mut dialog = test_dialog(file_system, "/dir").as_modal(false);
+        dialog.open(
+            DialogMode::PickDirectory,
+            false,
+            Some(INLINE_PICKER_OPERATION_ID),
+        );
 
-                // Get the min and max of both positions.
-                // We will iterate from min to max.
-                let mut min = pos_a;
-                let mut max = pos_b;
+        let mut ui = TestUi::new();
+        let mut run_frame = |dialog: &mut FileDialog| {
+            let mut picked = None;
+            ui.run_ui(egui::RawInput::default(), |ui| {
+                picked = dialog.inline_picker(ui, DialogMode::PickDirectory);
+            });
+            picked
+        };
 
-                if min > max {
-                    min = pos_b;
-                    max = pos_a;
-                }
+        assert_eq!(run_frame(&mut dialog), None);
 
-                for item in directory_content
-                    .filtered_iter_mut(&self.search_value)
-                    .enumerate()
-                    .filter(|(i, _)| i > &min && i < &max)
-                    .map(|(_, p)| p)
-                {
-                    item.selected = true;
-                }
-            }
-        }
+        dialog.finish(DialogState::Picked(PathBuf::from("/dir/a.txt")));
+        assert_eq!(run_frame(&mut dialog), Some(PathBuf::from("/dir/a.txt")));
+        assert_eq!(run_frame(&mut dialog), None);
+
+        // The picked path is still displayed after opening the dialog again
+        dialog.pick_file();
+        assert_eq!(dialog.inline_picker_path, Some(PathBuf::from("/dir/a.txt")));
+
+        // The results of dialogs opened for other operations are not consumed
+        dialog.finish(DialogState::Picked(PathBuf::from("/dir")));
+        assert_eq!(run_frame(&mut dialog), None);
+        assert_eq!(
+            dialog.take_result(),
+            Some(DialogResult::Selected(PathBuf::from("/dir")))
+        );
     }
 
-    /// Helper function to add a sized button that can be enabled or disabled
-    fn ui_button_sized(
-        &self,
-        ui: &mut egui::Ui,
-        enabled: bool,
-        size: egui::Vec2,
-        label: &str,
-        err_tooltip: Option<&str>,
-    ) -> bool {
-        let mut clicked = false;
+    #[test]
+    fn click_background_clears_selection() {
+        let file_system = TestFileSystem::new(&["/dir"], &["/dir/a.txt"]);
 
-        ui.add_enabled_ui(enabled, |ui| {
-            let response = ui.add_sized(size, egui::Button::new(label));
-            clicked = response.clicked();
+        let mut dialog = test_dialog(file_system, "/dir").as_modal(false);
+        dialog.pick_file();
 
-            if let Some(err) = err_tooltip {
-                response.on_disabled_hover_ui(|ui| {
-                    ui.horizontal_wrapped(|ui| {
-                        ui.spacing_mut().item_spacing.x = 0.0;
+        let mut ui = TestUi::new();
+        ui.run(&mut dialog, Vec::new());
+        assert!(dialog.scroll_to(Path::new("/dir/a.txt")));
+        ui.run(&mut dialog, Vec::new());
+        assert!(dialog.selected_item.is_some());
 
-                        ui.colored_label(
-                            ui.ctx().style().visuals.error_fg_color,
-                            format!("{} ", self.config.err_icon),
-                        );
+        let entry = ui.entry_rect("a.txt");
 
-                        ui.label(err);
-                    });
-                });
-            }
-        });
+        // Click into the empty area below the only entry
+        ui.click(&mut dialog, entry.center() + egui::vec2(0.0, 100.0));
+        assert!(dialog.selected_item.is_none());
 
-        clicked
+        // Clicking the entry still selects it
+        ui.click(&mut dialog, entry.center());
+        assert!(dialog.selected_item.is_some());
     }
 
-    /// Updates the context menu of a path.
-    ///
-    /// # Arguments
-    ///
-    /// * `item_response` - The response of the egui item for which the context menu should
-    ///                     be opened.
-    /// * `path` - The path for which the context menu should be opened.
-    fn ui_update_path_context_menu(&mut self, item_response: &egui::Response, path: &Path) {
-        // Path context menus are currently only used for pinned folders.
-        if !self.config.show_pinned_folders {
-            return;
-        }
+    #[test]
+    fn parent_entry() {
+        let file_system = TestFileSystem::new(&["/", "/dir", "/dir/sub"], &["/dir/sub/a.txt"]);
 
-        item_response.context_menu(|ui| {
-            let pinned = self.is_pinned(path);
+        let mut dialog = test_dialog(file_system, "/dir/sub")
+            .as_modal(false)
+            .show_parent_entry(true);
+        dialog.pick_file();
 
-            if pinned {
-                if ui.button(&self.config.labels.unpin_folder).clicked() {
-                    self.unpin_path(path);
-                    ui.close_kind(UiKind::Menu);
-                }
-            } else if ui.button(&self.config.labels.pin_folder).clicked() {
-                self.pin_path(path.to_path_buf());
-                ui.close_kind(UiKind::Menu);
-            }
-        });
-    }
+        let mut ui = TestUi::new();
+        ui.run(&mut dialog, Vec::new());
 
-    /// Sets the cursor position to the end of a text input field.
-    ///
-    /// # Arguments
-    ///
-    /// * `re` - response of the text input widget
-    /// * `data` - buffer holding the text of the input widget
-    fn set_cursor_to_end(re: &egui::Response, data: &str) {
-        // Set the cursor to the end of the filter input string
-        if let Some(mut state) = egui::TextEdit::load_state(&re.ctx, re.id) {
-            state
-                .cursor
-                .set_char_range(Some(CCursorRange::one(CCursor::new(data.len()))));
-            state.store(&re.ctx, re.id);
-        }
-    }
+        // A single click only selects the entry, submitting it opens the parent directory
+        let parent = ui.entry_rect("..");
+        ui.click(&mut dialog, parent.center());
+        assert!(dialog.parent_entry_selected);
+        assert_eq!(dialog.current_directory(), Some(Path::new("/dir/sub")));
 
-    /// Calculates the width of a single char.
-    fn calc_char_width(ui: &egui::Ui, char: char) -> f32 {
-        ui.fonts(|f| f.glyph_width(&egui::TextStyle::Body.resolve(ui.style()), char))
-    }
+        ui.press_key(&mut dialog, egui::Key::Enter, egui::Modifiers::NONE);
+        assert_eq!(dialog.current_directory(), Some(Path::new("/dir")));
+        assert!(!dialog.parent_entry_selected);
 
-    /// Calculates the width of the specified text using the current font configuration.
-    /// Does not take new lines or text breaks into account!
-    fn calc_text_width(ui: &egui::Ui, text: &str) -> f32 {
-        let mut width = 0.0;
+        // The entry is reachable by moving the selection up from the first item
+        ui.run(&mut dialog, Vec::new());
+        ui.double_click(&mut dialog, ui.entry_rect("sub").center());
+        assert_eq!(dialog.current_directory(), Some(Path::new("/dir/sub")));
 
-        for char in text.chars() {
-            width += Self::calc_char_width(ui, char);
-        }
+        ui.press_key(&mut dialog, egui::Key::ArrowDown, egui::Modifiers::NONE);
+        assert!(dialog.selected_item.is_some());
+        ui.press_key(&mut dialog, egui::Key::ArrowUp, egui::Modifiers::NONE);
+        assert!(dialog.parent_entry_selected);
+        assert!(dialog.selected_item.is_none());
 
-        width
-    }
+        ui.press_key(&mut dialog, egui::Key::ArrowDown, egui::Modifiers::NONE);
+        assert!(!dialog.parent_entry_selected);
+        ui.press_key(&mut dialog, egui::Key::ArrowUp, egui::Modifiers::NONE);
+        ui.press_key(&mut dialog, egui::Key::Enter, egui::Modifiers::NONE);
+        assert_eq!(dialog.current_directory(), Some(Path::new("/dir")));
 
-    fn truncate_filename(ui: &egui::Ui, item: &DirectoryEntry, max_length: f32) -> String {
-        const TRUNCATE_STR: &str = "...";
+        // Double clicking the entry opens the parent directory right away
+        ui.run(&mut dialog, Vec::new());
+        ui.double_click(&mut dialog, ui.entry_rect("..").center());
+        assert_eq!(dialog.current_directory(), Some(Path::new("/")));
+    }
 
-        let path = item.as_path();
+    #[test]
+    fn directory_chevron() {
+        let file_system = TestFileSystem::new(&["/dir", "/dir/sub"], &[]);
 
-        let file_stem = if item.is_file() {
-            path.file_stem().and_then(|f| f.to_str()).unwrap_or("")
-        } else {
-            item.file_name()
-        };
+        let mut dialog = test_dialog(file_system, "/dir")
+            .as_modal(false)
+            .show_directory_chevrons(true);
+        dialog.pick_directory();
 
-        let extension = if item.is_file() {
-            path.extension().map_or(String::new(), |ext| {
-                format!(".{}", ext.to_str().unwrap_or(""))
-            })
-        } else {
-            String::new()
+        let mut ui = TestUi::new();
+        ui.run(&mut dialog, Vec::new());
+
+        let entry = ui.entry_rect("sub");
+        let Some(chevron) = ui
+            .text_rects(|text| text == ">")
+            .into_iter()
+            .find(|rect| rect.y_range().contains(entry.center().y))
+        else {
+            panic!("no chevron was painted in the row of the directory");
         };
 
-        let extension_width = Self::calc_text_width(ui, &extension);
-        let reserved = extension_width + Self::calc_text_width(ui, TRUNCATE_STR);
+        // Clicking the name only selects the directory
+        ui.click(&mut dialog, entry.center());
+        assert_eq!(dialog.current_directory(), Some(Path::new("/dir")));
+        assert!(dialog.selected_item.is_some());
+
+        // A single click on the chevron opens the directory
+        ui.click(&mut dialog, chevron.center());
+        assert_eq!(dialog.current_directory(), Some(Path::new("/dir/sub")));
+    }
+
+    #[test]
+    fn custom_right_panel() {
+        let file_system = TestFileSystem::new(&["/dir"], &["/dir/a.txt"]);
+
+        let mut dialog = test_dialog(file_system, "/dir")
+            .as_modal(false)
+            .right_panel_width(150.0)
+            .custom_right_panel(Box::new(|ui, path| {
+                ui.label(format!(
+                    "Details: {}",
+                    path.map_or_else(String::new, |p| p.display().to_string())
+                ));
+            }));
+        dialog.pick_file();
+
+        let mut ui = TestUi::new();
+        ui.run(&mut dialog, Vec::new());
+        assert_eq!(ui.text_rects(|text| text == "Details: ").len(), 1);
+
+        // The panel receives the selected item and is kept when the dialog is opened again
+        ui.click(&mut dialog, ui.entry_rect("a.txt").center());
+        ui.run(&mut dialog, Vec::new());
+        assert_eq!(ui.text_rects(|text| text == "Details: /dir/a.txt").len(), 1);
+
+        dialog.pick_file();
+        ui.run(&mut dialog, Vec::new());
+        let panel = ui.text_rect(|text| text == "Details: ");
+        assert!(panel.min.x > TestUi::SCREEN_SIZE.x - 200.0);
+    }
+
+    #[test]
+    fn pick_directory_on_double_click() {
+        let file_system = || TestFileSystem::new(&["/dir", "/dir/sub"], &[]);
+
+        // By default, double clicking a directory opens it
+        let mut dialog = test_dialog(file_system(), "/dir").as_modal(false);
+        dialog.pick_directory();
+
+        let mut ui = TestUi::new();
+        ui.run(&mut dialog, Vec::new());
+
+        let entry = ui.entry_rect("sub");
+        ui.double_click(&mut dialog, entry.center());
+        assert_eq!(dialog.current_directory(), Some(Path::new("/dir/sub")));
+        assert_eq!(dialog.state(), DialogState::Open);
+
+        // With the option enabled, the directory is picked instead
+        let mut dialog = test_dialog(file_system(), "/dir")
+            .as_modal(false)
+            .pick_directory_on_double_click(true);
+        dialog.pick_directory();
+
+        let mut ui = TestUi::new();
+        ui.run(&mut dialog, Vec::new());
+
+        let entry = ui.entry_rect("sub");
+        ui.double_click(&mut dialog, entry.center());
+        assert_eq!(dialog.current_directory(), Some(Path::new("/dir")));
+        assert_eq!(
+            dialog.state(),
+            DialogState::Picked(PathBuf::from("/dir/sub"))
+        );
+    }
 
-        if max_length <= reserved {
-            return format!("{TRUNCATE_STR}{extension}");
-        }
+    #[test]
+    fn paste_path() {
+        let file_system = TestFileSystem::new(&["/dir", "/dir/sub"], &[]);
 
-        let mut width = reserved;
-        let mut front = String::new();
-        let mut back = String::new();
+        let mut dialog = test_dialog(file_system, "/dir").as_modal(false);
+        dialog.pick_directory();
 
-        for (i, char) in file_stem.chars().enumerate() {
-            let w = Self::calc_char_width(ui, char);
+        let mut ui = TestUi::new();
+        let mut run_frame =
+            |dialog: &mut FileDialog, events: Vec<egui::Event>| ui.run(dialog, events);
 
-            if width + w > max_length {
-                break;
-            }
+        // Paste events are ignored unless the user requested to paste a path
+        run_frame(
+            &mut dialog,
+            vec![egui::Event::Paste("/dir/sub".to_string())],
+        );
+        assert_eq!(dialog.current_directory(), Some(Path::new("/dir")));
+
+        // The backend may take a few frames to send the pasted text
+        dialog.paste_path_requested = true;
+        run_frame(&mut dialog, Vec::new());
+        run_frame(&mut dialog, Vec::new());
+        run_frame(
+            &mut dialog,
+            vec![egui::Event::Paste("/dir/sub\n".to_string())],
+        );
+        assert_eq!(dialog.current_directory(), Some(Path::new("/dir/sub")));
+        assert!(!dialog.paste_path_requested);
+
+        // The request is dropped once the user does something else
+        dialog.paste_path_requested = true;
+        run_frame(
+            &mut dialog,
+            vec![egui::Event::Key {
+                key: egui::Key::F12,
+                physical_key: None,
+                pressed: true,
+                repeat: false,
+                modifiers: egui::Modifiers::NONE,
+            }],
+        );
+        assert!(!dialog.paste_path_requested);
+        run_frame(&mut dialog, vec![egui::Event::Paste("/dir".to_string())]);
+        assert_eq!(dialog.state, DialogState::Open);
+        assert_eq!(dialog.current_directory(), Some(Path::new("/dir/sub")));
+    }
 
-            front.push(char);
-            width += w;
+    #[test]
+    fn modal_inside_central_panel() {
+        let file_system = TestFileSystem::new(&["/dir"], &["/dir/a.txt"]);
 
-            let back_index = file_stem.len() - i - 1;
+        let mut dialog = test_dialog(file_system, "/dir").cancel_on_overlay_click(true);
+        dialog.pick_file();
 
-            if back_index <= i {
-                break;
-            }
+        let mut ui = TestUi::new();
+        let mut app_clicked = false;
+        let mut run_frame = |ui: &mut TestUi, dialog: &mut FileDialog, events| {
+            let input = egui::RawInput {
+                events,
+                ..Default::default()
+            };
+            ui.run_ctx(input, |ctx| {
+                egui::SidePanel::left("app").show(ctx, |ui| {
+                    app_clicked |= ui.button("App").clicked();
+                });
+                egui::CentralPanel::default().show(ctx, |ui| {
+                    dialog.update(ctx, ui);
+                });
+            });
+        };
+        let click_events = |pos| {
+            let event = |pressed| egui::Event::PointerButton {
+                pos,
+                button: egui::PointerButton::Primary,
+                pressed,
+                modifiers: egui::Modifiers::NONE,
+            };
 
-            if let Some(char) = file_stem.chars().nth(back_index) {
-                let w = Self::calc_char_width(ui, char);
+            [
+                vec![egui::Event::PointerMoved(pos)],
+                vec![event(true)],
+                vec![event(false)],
+            ]
+        };
 
-                if width + w > max_length {
-                    break;
-                }
+        run_frame(&mut ui, &mut dialog, Vec::new());
+        run_frame(&mut ui, &mut dialog, Vec::new());
 
-                back.push(char);
-                width += w;
-            }
+        // The dialog can still be used
+        let entry = ui.entry_rect("a.txt");
+        for events in click_events(entry.center()) {
+            run_frame(&mut ui, &mut dialog, events);
+        }
+        assert!(dialog.selected_item.is_some());
+        assert_eq!(dialog.state(), DialogState::Open);
+
+        // The rest of the application is covered by the overlay
+        ui.wait_for_click_timeout();
+        let button = ui.text_rect(|text| text == "App");
+        for events in click_events(button.center()) {
+            run_frame(&mut ui, &mut dialog, events);
         }
+        assert!(!app_clicked);
+        assert_eq!(dialog.state(), DialogState::Cancelled);
+    }
+
+    #[test]
+    fn drop_file() {
+        let file_system = TestFileSystem::new(&["/dir", "/other"], &["/other/a.txt"]);
+
+        let mut dialog = test_dialog(file_system, "/dir").as_modal(false);
+        dialog.pick_file();
+
+        let mut ui = TestUi::new();
+        let mut drop = |dialog: &mut FileDialog, pos: egui::Pos2, paths: &[Option<&str>]| {
+            let input = egui::RawInput {
+                events: vec![egui::Event::PointerMoved(pos)],
+                dropped_files: paths
+                    .iter()
+                    .map(|path| egui::DroppedFile {
+                        path: path.map(PathBuf::from),
+                        ..Default::default()
+                    })
+                    .collect(),
+                ..Default::default()
+            };
+            ui.run_ctx(input, |ctx| {
+                egui::SidePanel::left("app").show(ctx, |_| {});
+                egui::CentralPanel::default().show(ctx, |ui| {
+                    dialog.update(ctx, ui);
+                });
+            });
+        };
 
-        format!(
-            "{front}{TRUNCATE_STR}{}{extension}",
-            back.chars().rev().collect::<String>()
-        )
+        // Drops outside of the dialog and drops without a path are ignored
+        let outside = egui::pos2(1.0, TestUi::screen_rect().center().y);
+        let inside = TestUi::screen_rect().center();
+        drop(&mut dialog, outside, &[Some("/other/a.txt")]);
+        drop(&mut dialog, inside, &[None]);
+        assert_eq!(dialog.current_directory(), Some(Path::new("/dir")));
+
+        // The first path is used
+        drop(
+            &mut dialog,
+            inside,
+            &[None, Some("/other/a.txt"), Some("/dir")],
+        );
+        assert_eq!(dialog.current_directory(), Some(Path::new("/other")));
+        assert_eq!(
+            dialog.selected_item.as_ref().map(DirectoryEntry::as_path),
+            Some(Path::new("/other/a.txt"))
+        );
     }
-}
 
-/// Keybindings
-impl FileDialog {
-    /// Checks whether certain keybindings have been pressed and executes the corresponding actions.
-    fn update_keybindings(&mut self, ctx: &egui::Context) {
-        // We don't want to execute keybindings if a modal is currently open.
-        // The modals implement the keybindings themselves.
-        if let Some(modal) = self.modals.last_mut() {
-            modal.update_keybindings(&self.config, ctx);
-            return;
-        }
+    #[test]
+    fn drag_entry() {
+        let file_system = TestFileSystem::new(&["/dir"], &["/dir/a.txt"]);
 
-        let keybindings = std::mem::take(&mut self.config.keybindings);
+        let mut dialog = test_dialog(file_system, "/dir")
+            .as_modal(false)
+            .draggable_entries(true);
+        dialog.pick_file();
 
-        if FileDialogKeyBindings::any_pressed(ctx, &keybindings.submit, false) {
-            self.exec_keybinding_submit();
-        }
+        let mut ui = TestUi::new();
 
-        if FileDialogKeyBindings::any_pressed(ctx, &keybindings.cancel, false) {
-            self.exec_keybinding_cancel();
-        }
+        // The first frame scrolls to the selection, which lays out the rows differently
+        ui.run(&mut dialog, Vec::new());
+        ui.run(&mut dialog, Vec::new());
 
-        if FileDialogKeyBindings::any_pressed(ctx, &keybindings.parent, true) {
-            self.load_parent_directory();
+        let entry = ui.entry_rect("a.txt").center();
+        ui.run(&mut dialog, vec![egui::Event::PointerMoved(entry)]);
+        ui.run(
+            &mut dialog,
+            vec![egui::Event::PointerButton {
+                pos: entry,
+                button: egui::PointerButton::Primary,
+                pressed: true,
+                modifiers: egui::Modifiers::NONE,
+            }],
+        );
+        for offset in [40.0, 80.0, 120.0] {
+            let pos = entry + egui::vec2(0.0, offset);
+            ui.run(&mut dialog, vec![egui::Event::PointerMoved(pos)]);
         }
 
-        if FileDialogKeyBindings::any_pressed(ctx, &keybindings.back, true) {
-            self.load_previous_directory();
-        }
+        assert_eq!(
+            egui::DragAndDrop::payload::<PathBuf>(&ui.ctx).as_deref(),
+            Some(&PathBuf::from("/dir/a.txt"))
+        );
+    }
 
-        if FileDialogKeyBindings::any_pressed(ctx, &keybindings.forward, true) {
-            self.load_next_directory();
-        }
+    #[test]
+    fn directory_item_count() {
+        let file_system = TestFileSystem::new(
+            &["/dir", "/dir/sub", "/dir/sub/nested"],
+            &["/dir/a.txt", "/dir/sub/b.txt"],
+        );
 
-        if FileDialogKeyBindings::any_pressed(ctx, &keybindings.reload, true) {
-            self.refresh();
-        }
+        let mut dialog = test_dialog(file_system, "/dir")
+            .as_modal(false)
+            .show_directory_item_count(true);
+        dialog.pick_file();
+
+        let item = |dialog: &FileDialog, path: &str| {
+            dialog
+                .directory_content
+                .filtered_iter("")
+                .find(|e| e.as_path() == Path::new(path))
+                .cloned()
+        };
 
-        if FileDialogKeyBindings::any_pressed(ctx, &keybindings.new_folder, true) {
-            self.open_new_folder_dialog();
-        }
+        // Counts are only loaded for directories that were displayed
+        let sub = item(&dialog, "/dir/sub");
+        assert_eq!(
+            sub.map(|e| dialog.directory_item_count_text(&e)).as_deref(),
+            Some(" (…)")
+        );
 
-        if FileDialogKeyBindings::any_pressed(ctx, &keybindings.edit_path, true) {
-            self.open_path_edit();
-        }
+        TestUi::new().run(&mut dialog, Vec::new());
 
-        if FileDialogKeyBindings::any_pressed(ctx, &keybindings.home_edit_path, true) {
-            if let Some(dirs) = &self.user_directories {
-                if let Some(home) = dirs.home_dir() {
-                    self.load_directory(home.to_path_buf().as_path());
-                    self.open_path_edit();
-                }
-            }
-        }
+        assert_eq!(
+            dialog.directory_item_counts.get(Path::new("/dir/sub")),
+            Some(ItemCount::Loaded(2))
+        );
+        assert_eq!(
+            dialog.directory_item_counts.get(Path::new("/dir/a.txt")),
+            None
+        );
 
-        if FileDialogKeyBindings::any_pressed(ctx, &keybindings.selection_up, false) {
-            self.exec_keybinding_selection_up();
+        let sub = item(&dialog, "/dir/sub");
+        assert_eq!(
+            sub.map(|e| dialog.directory_item_count_text(&e)).as_deref(),
+            Some(" (2)")
+        );
+        let file = item(&dialog, "/dir/a.txt");
+        assert_eq!(
+            file.map(|e| dialog.directory_item_count_text(&e))
+                .as_deref(),
+            Some("")
+        );
+    }
 
-            // We want to break out of input fields like search when pressing selection keys
-            if let Some(id) = ctx.memory(egui::Memory::focused) {
-                ctx.memory_mut(|w| w.surrender_focus(id));
+    #[test]
+    fn directory_item_count_visibility() {
+        let file_system = MemoryFileSystem::new()
+            .with_dir("/repo/.git")
+            .with_file("/repo/.gitignore", "*.log")
+            .with_file("/repo/src/main.rs", "")
+            .with_file("/repo/src/.hidden", "")
+            .with_file("/repo/src/debug.log", "");
+
+        let mut dialog = test_dialog(file_system, "/repo")
+            .load_via_thread(true)
+            .respect_ignore_files(true)
+            .show_directory_item_count(true);
+        dialog.pick_file();
+        wait_for_directory(&mut dialog);
+
+        let count = |dialog: &mut FileDialog| {
+            dialog
+                .directory_item_counts
+                .request(&dialog.config, Path::new("/repo/src"));
+
+            while dialog.directory_item_counts.update() {
+                std::thread::sleep(Duration::from_millis(1));
             }
-        }
 
-        if FileDialogKeyBindings::any_pressed(ctx, &keybindings.selection_down, false) {
-            self.exec_keybinding_selection_down();
+            dialog.directory_item_counts.get(Path::new("/repo/src"))
+        };
 
-            // We want to break out of input fields like search when pressing selection keys
-            if let Some(id) = ctx.memory(egui::Memory::focused) {
-                ctx.memory_mut(|w| w.surrender_focus(id));
-            }
-        }
+        // Hidden and ignored items are not counted, like they are not listed
+        assert_eq!(count(&mut dialog), Some(ItemCount::Loaded(1)));
 
-        if FileDialogKeyBindings::any_pressed(ctx, &keybindings.select_all, true)
-            && self.mode == DialogMode::PickMultiple
-        {
-            for item in self.directory_content.filtered_iter_mut(&self.search_value) {
-                item.selected = true;
-            }
-        }
+        dialog.config.storage.show_hidden = true;
+        dialog.refresh();
+        wait_for_directory(&mut dialog);
+        assert_eq!(
+            dialog.directory_item_counts.get(Path::new("/repo/src")),
+            None
+        );
+        assert_eq!(count(&mut dialog), Some(ItemCount::Loaded(2)));
 
-        self.config.keybindings = keybindings;
+        dialog.config.storage.show_ignored = true;
+        dialog.refresh();
+        wait_for_directory(&mut dialog);
+        assert_eq!(count(&mut dialog), Some(ItemCount::Loaded(3)));
+
+        // The counts are removed when opening a different directory
+        dialog.load_directory(Path::new("/repo/src"));
+        assert_eq!(
+            dialog.directory_item_counts.get(Path::new("/repo/src")),
+            None
+        );
     }
 
-    /// Executes the action when the keybinding `submit` is pressed.
-    fn exec_keybinding_submit(&mut self) {
-        if self.path_edit_visible {
-            self.submit_path_edit();
-            return;
-        }
+    #[test]
+    fn unreadable_entries() {
+        let file_system =
+            TestFileSystem::new(&["/dir"], &["/dir/a.txt", "/dir/b.txt", "/dir/c.txt"])
+                .with_inaccessible_entry("/dir/b.txt")
+                .with_unreadable_entry("/dir/c.txt");
+
+        let mut dialog = test_dialog(file_system, "/dir").as_modal(false);
+        dialog.pick_multiple();
+
+        // Entries that could not be listed are only counted, because their path is unknown
+        assert_eq!(dialog.directory_content.unreadable_count(), 1);
+        assert_eq!(dialog.directory_content.len(), 2);
+
+        // Entries that could not be accessed are not selected
+        let mut ui = TestUi::new();
+        ui.press_key(&mut dialog, egui::Key::A, egui::Modifiers::COMMAND);
+        let selected: Vec<&Path> = dialog
+            .directory_content
+            .filtered_iter("")
+            .filter(|e| e.selected)
+            .map(DirectoryEntry::as_path)
+            .collect();
+        assert_eq!(selected, [Path::new("/dir/a.txt")]);
+    }
+
+    #[test]
+    fn directory_sizes() {
+        let file_system = TestFileSystem::new(
+            &[
+                "/dir",
+                "/dir/sub",
+                "/dir/sub/nested",
+                "/dir/sub/nested/deep",
+            ],
+            &["/dir/a.txt", "/dir/sub/b.txt"],
+        );
 
-        if self.create_directory_dialog.is_open() {
-            if let Some(dir) = self.create_directory_dialog.submit().directory() {
-                self.process_new_folder(&dir);
-            }
-            return;
-        }
+        let mut dialog = test_dialog(file_system, "/dir")
+            .as_modal(false)
+            .view_mode(ViewMode::Details)
+            .show_directory_sizes(true)
+            .directory_size_budget(1, Duration::from_mins(1));
+        dialog.pick_file();
 
-        // Check if there is a directory selected we can open
-        if let Some(item) = &self.selected_item {
-            // Make sure the selected item is visible inside the directory view.
-            let is_visible = self
-                .get_dir_content_filtered_iter()
-                .any(|p| p.path_eq(item));
+        let mut ui = TestUi::new();
+        ui.run(&mut dialog, Vec::new());
 
-            if is_visible && item.is_dir() {
-                self.load_directory(&item.to_path_buf());
-                return;
-            }
-        }
+        // The deepest directory exceeds the depth budget
+        assert_eq!(
+            dialog.directory_sizes.get(Path::new("/dir/sub")),
+            Some(DirectorySize::Partial(0))
+        );
+        assert_eq!(dialog.directory_sizes.get(Path::new("/dir/a.txt")), None);
 
-        self.submit();
-    }
+        // The sizes are computed again after navigating to another directory
+        dialog.load_directory(Path::new("/dir/sub"));
+        assert_eq!(dialog.directory_sizes.get(Path::new("/dir/sub")), None);
 
-    /// Executes the action when the keybinding `cancel` is pressed.
-    fn exec_keybinding_cancel(&mut self) {
-        // We have to check if the `create_directory_dialog` and `path_edit_visible` is open,
-        // because egui does not consume pressing the escape key inside a text input.
-        // So when pressing the escape key inside a text input, the text input is closed
-        // but the keybindings still register the press on the escape key.
-        // (Although the keybindings are updated before the UI and they check whether another
-        //  widget is currently in focus!)
-        //
-        // This is practical for us because we can close the path edit and
-        // the create directory dialog.
-        // However, this causes problems when the user presses escape in other text
-        // inputs for which we have no status saved. This would then close the entire file dialog.
-        // To fix this, we check if any item was focused in the last frame.
-        //
-        // Note that this only happens with the escape key and not when the enter key is
-        // used to close a text input. This is why we don't have to check for the
-        // dialogs in `exec_keybinding_submit`.
+        ui.run(&mut dialog, Vec::new());
 
-        if self.create_directory_dialog.is_open() {
-            self.create_directory_dialog.close();
-        } else if self.path_edit_visible {
-            self.close_path_edit();
-        } else if !self.any_focused_last_frame {
-            self.cancel();
-            return;
-        }
+        assert_eq!(
+            dialog.directory_sizes.get(Path::new("/dir/sub/nested")),
+            Some(DirectorySize::Loaded(0))
+        );
     }
 
-    /// Executes the action when the keybinding `selection_up` is pressed.
-    fn exec_keybinding_selection_up(&mut self) {
-        if self.directory_content.len() == 0 {
-            return;
-        }
+    #[test]
+    fn sort_by_directory_sizes() {
+        let file_system = TestFileSystem::new(
+            &["/dir", "/dir/big", "/dir/small"],
+            &["/dir/a.txt", "/dir/big/b.txt", "/dir/small/c.txt"],
+        )
+        .with_file_size("/dir/a.txt", 10)
+        .with_file_size("/dir/big/b.txt", 100)
+        .with_file_size("/dir/small/c.txt", 1);
 
-        self.directory_content.reset_multi_selection();
+        let mut dialog = test_dialog(file_system, "/dir")
+            .as_modal(false)
+            .view_mode(ViewMode::Details)
+            .show_directory_sizes(true)
+            .file_sorting(FileSorting::Size, false);
+        dialog.pick_file();
 
-        if let Some(item) = &self.selected_item {
-            if self.select_next_visible_item_before(&item.clone()) {
-                return;
-            }
-        }
+        TestUi::new().run(&mut dialog, Vec::new());
 
-        // No item is selected or no more items left.
-        // Select the last item from the directory content.
-        self.select_last_visible_item();
+        let names: Vec<&str> = dialog
+            .directory_content
+            .iter()
+            .map(DirectoryEntry::file_name)
+            .collect();
+        assert_eq!(names, ["small", "big", "a.txt"]);
+    }
+
+    #[test]
+    fn directory_size_budget_without_thread() {
+        let file_system = TestFileSystem::new(&["/dir", "/dir/a", "/dir/b"], &[])
+            .with_slow_directory("/dir/a")
+            .with_slow_directory("/dir/b");
+
+        let mut dialog = test_dialog(file_system, "/dir")
+            .as_modal(false)
+            .view_mode(ViewMode::Details)
+            .show_directory_sizes(true)
+            .directory_size_budget(8, Duration::from_millis(100));
+        dialog.pick_file();
+
+        TestUi::new().run(&mut dialog, Vec::new());
+
+        // All directories share the budget, so the size of the second one is skipped
+        assert_eq!(
+            dialog.directory_sizes.get(Path::new("/dir/a")),
+            Some(DirectorySize::Loaded(0))
+        );
+        assert_eq!(dialog.directory_sizes.get(Path::new("/dir/b")), None);
     }
 
-    /// Executes the action when the keybinding `selection_down` is pressed.
-    fn exec_keybinding_selection_down(&mut self) {
-        if self.directory_content.len() == 0 {
-            return;
-        }
+    #[test]
+    fn hide_empty_filters() {
+        let file_system = TestFileSystem::new(&["/dir"], &["/dir/a.png", "/dir/b.txt"]);
+        let mut dialog = test_dialog(file_system, "/dir")
+            .file_filters(vec![
+                ("Images".to_string(), vec!["png".to_string()]),
+                ("Text".to_string(), vec!["txt".to_string()]),
+                ("Videos".to_string(), vec!["mp4".to_string()]),
+            ])
+            .default_file_filter("Text")
+            .hide_empty_filters(true);
 
-        self.directory_content.reset_multi_selection();
+        dialog.pick_file();
 
-        if let Some(item) = &self.selected_item {
-            if self.select_next_visible_item_after(&item.clone()) {
-                return;
-            }
-        }
+        // Filters matching files hidden by the selected filter are still available
+        let available: Vec<&str> = dialog
+            .config
+            .file_filters
+            .iter()
+            .filter(|f| dialog.directory_content.is_file_filter_available(f.id))
+            .map(|f| f.name.as_str())
+            .collect();
+        assert_eq!(available, vec!["Images", "Text"]);
+    }
+
+    #[test]
+    fn details_columns() {
+        let file_system = TestFileSystem::new(&["/dir"], &["/dir/a.txt"]);
+        let mut dialog = test_dialog(file_system, "/dir").view_mode(ViewMode::Details);
+
+        dialog.pick_file();
+        let item = DirectoryEntry::from_path(
+            &dialog.config,
+            Path::new("/dir/a.txt"),
+            &*dialog.config.file_system,
+        );
 
-        // No item is selected or no more items left.
-        // Select the last item from the directory content.
-        self.select_first_visible_item();
-    }
-}
+        let mut widths = Vec::new();
+        TestUi::new().run_ui(egui::RawInput::default(), |ui| {
+            widths.push(dialog.detail_columns_width(ui, &item));
 
-/// Implementation
-impl FileDialog {
-    /// Get the file filter the user currently selected.
-    fn get_selected_file_filter(&self) -> Option<&FileFilter> {
-        self.selected_file_filter
-            .and_then(|id| self.config.file_filters.iter().find(|p| p.id == id))
-    }
+            dialog.storage_mut().details_columns[0].width = 200.0;
+            widths.push(dialog.detail_columns_width(ui, &item));
 
-    /// Sets the default file filter to use.
-    fn set_default_file_filter(&mut self) {
-        if let Some(name) = &self.config.default_file_filter {
-            for filter in &self.config.file_filters {
-                if filter.name == name.as_str() {
-                    self.selected_file_filter = Some(filter.id);
-                }
-            }
-        }
-    }
+            dialog.storage_mut().details_columns[1].visible = false;
+            widths.push(dialog.detail_columns_width(ui, &item));
+        });
 
-    /// Selects the given file filter and applies the appropriate filters.
-    fn select_file_filter(&mut self, filter: Option<FileFilter>) {
-        self.selected_file_filter = filter.map(|f| f.id);
-        self.selected_item = None;
-        self.refresh();
-    }
+        assert_eq!(widths, vec![230.0, 310.0, 215.0]);
 
-    /// Get the save extension the user currently selected.
-    fn get_selected_save_extension(&self) -> Option<&SaveExtension> {
-        self.selected_save_extension
-            .and_then(|id| self.config.save_extensions.iter().find(|p| p.id == id))
+        // The columns are kept when the dialog is opened again
+        dialog.pick_file();
+        assert!(!dialog.storage_mut().details_columns[1].visible);
     }
 
-    /// Sets the save extension to use.
-    fn set_default_save_extension(&mut self) {
-        let config = std::mem::take(&mut self.config);
+    #[test]
+    fn cancel_directory_load() {
+        let file_system = TestFileSystem::new(&["/dir", "/dir/slow"], &["/dir/a.txt"])
+            .with_slow_directory("/dir/slow");
+
+        let mut dialog = test_dialog(file_system, "/dir").load_via_thread(true);
 
-        if let Some(name) = &config.default_save_extension {
-            for extension in &config.save_extensions {
-                if extension.name == name.as_str() {
-                    self.selected_save_extension = Some(extension.id);
-                    self.set_file_name_extension(&extension.file_extension);
-                }
-            }
+        dialog.pick_file();
+        while dialog.directory_content.update() != &DirectoryContentState::Success {
+            std::thread::sleep(Duration::from_millis(1));
         }
 
-        self.config = config;
+        dialog.load_directory(Path::new("/dir/slow"));
+        assert!(dialog.directory_content.is_pending());
+        assert_eq!(dialog.directory_stack.len(), 2);
+
+        // The previous directory and its content are restored without loading them again
+        dialog.cancel_directory_load();
+        assert_eq!(dialog.current_directory(), Some(Path::new("/dir")));
+        assert_eq!(dialog.directory_stack, vec![PathBuf::from("/dir")]);
+        assert!(!dialog.can_go_forward());
+        assert_eq!(dialog.directory_content.len(), 2);
+        assert!(dialog.previous_directory.is_none());
     }
 
-    /// Selects the given save extension.
-    fn select_save_extension(&mut self, extension: Option<SaveExtension>) {
-        if let Some(ex) = extension {
-            self.selected_save_extension = Some(ex.id);
-            self.set_file_name_extension(&ex.file_extension);
-        }
+    #[test]
+    fn would_show() {
+        let file_system = TestFileSystem::new(
+            &["/dir", "/dir/sub"],
+            &["/dir/a.png", "/dir/b.txt", "/dir/.c.png"],
+        );
+        let mut dialog = test_dialog(file_system, "/dir")
+            .file_filters(vec![("Images".to_string(), vec!["png".to_string()])])
+            .default_file_filter("Images");
 
-        self.selected_item = None;
-        self.refresh();
+        dialog.pick_file();
+
+        assert!(dialog.would_show(Path::new("/dir/a.png")));
+        assert!(dialog.would_show(Path::new("/dir/sub")));
+        assert!(!dialog.would_show(Path::new("/dir/b.txt")));
+        assert!(!dialog.would_show(Path::new("/dir/.c.png")));
+
+        dialog.search_value = "sub".to_string();
+        assert!(dialog.would_show(Path::new("/dir/sub")));
+        assert!(!dialog.would_show(Path::new("/dir/a.png")));
     }
 
-    /// Updates the extension of `Self::file_name_input`.
-    fn set_file_name_extension(&mut self, extension: &str) {
-        // Prevent `PathBuf::set_extension` to append the file extension when there is
-        // already one without a file name. For example `.png` would be changed to `.png.txt`
-        // when using `PathBuf::set_extension`.
-        let dot_count = self.file_name_input.chars().filter(|c| *c == '.').count();
-        let use_simple = dot_count == 1 && self.file_name_input.chars().nth(0) == Some('.');
+    #[test]
+    fn unresponsive_directory() {
+        let file_system =
+            TestFileSystem::new(&["/dir", "/dir/slow", "/dir/other"], &["/dir/a.txt"])
+                .with_slow_directory("/dir/slow");
 
-        let mut p = PathBuf::from(&self.file_name_input);
-        if !use_simple && p.set_extension(extension) {
-            self.file_name_input = p.to_string_lossy().into_owned();
-        } else {
-            self.file_name_input = format!(".{extension}");
+        let mut dialog = test_dialog(file_system, "/dir")
+            .load_via_thread(true)
+            .file_system_timeout(Duration::from_millis(100));
+
+        dialog.pick_file();
+        while dialog.directory_content.update() != &DirectoryContentState::Success {
+            std::thread::sleep(Duration::from_millis(1));
         }
-    }
 
-    /// Gets a filtered iterator of the directory content of this object.
-    fn get_dir_content_filtered_iter(&self) -> impl Iterator<Item = &DirectoryEntry> {
-        self.directory_content.filtered_iter(&self.search_value)
+        dialog.load_directory(Path::new("/dir/slow"));
+        assert!(!dialog
+            .directory_content
+            .is_unresponsive(Duration::from_millis(100)));
+
+        std::thread::sleep(Duration::from_millis(150));
+        assert!(dialog
+            .directory_content
+            .is_unresponsive(Duration::from_millis(100)));
+
+        // The directory that never loaded is not kept in the history
+        dialog.load_directory(Path::new("/dir/other"));
+        assert_eq!(
+            dialog.directory_stack,
+            vec![PathBuf::from("/dir"), PathBuf::from("/dir/other")]
+        );
     }
 
-    /// Opens the dialog to create a new folder.
-    fn open_new_folder_dialog(&mut self) {
-        if let Some(x) = self.current_directory() {
-            self.create_directory_dialog.open(x.to_path_buf());
+    /// Waits until the directory content finished loading on the other thread.
+    fn wait_for_directory(dialog: &mut FileDialog) {
+        while dialog.directory_content.is_pending() {
+            dialog.directory_content.update();
+            std::thread::sleep(Duration::from_millis(1));
         }
+
+        dialog.update_loaded_directory();
     }
 
-    /// Function that processes a newly created folder.
-    fn process_new_folder(&mut self, created_dir: &Path) -> DirectoryEntry {
-        let mut entry =
-            DirectoryEntry::from_path(&self.config, created_dir, &*self.config.file_system);
+    #[test]
+    fn canonicalize_while_loading() {
+        let file_system = TestFileSystem::new(&["/dir", "/dir/sub", "/dir/slow"], &[])
+            .with_slow_directory("/dir/slow")
+            .with_link("/link", "/dir");
 
-        self.directory_content.push(entry.clone());
+        let mut dialog = test_dialog(file_system, "/link/sub").load_via_thread(true);
 
-        self.select_item(&mut entry);
+        // The directory is canonicalized by the thread loading its content
+        dialog.pick_file();
+        assert_eq!(dialog.directory_stack, [PathBuf::from("/link/sub")]);
+        assert_eq!(dialog.config.storage.last_visited_dir, None);
 
-        entry
-    }
+        wait_for_directory(&mut dialog);
+        assert_eq!(dialog.directory_stack, [PathBuf::from("/dir/sub")]);
+        assert_eq!(
+            dialog.config.storage.last_visited_dir,
+            Some(PathBuf::from("/dir/sub"))
+        );
 
-    /// Opens a new modal window.
-    fn open_modal(&mut self, modal: Box<dyn FileDialogModal + Send + Sync>) {
-        self.modals.push(modal);
-    }
+        dialog.load_directory(Path::new("/link"));
+        wait_for_directory(&mut dialog);
+        assert_eq!(
+            dialog.directory_stack,
+            [PathBuf::from("/dir/sub"), PathBuf::from("/dir")]
+        );
 
-    /// Executes the given modal action.
-    fn exec_modal_action(&mut self, action: ModalAction) {
-        match action {
-            ModalAction::None => {}
-            ModalAction::SaveFile(path) => self.state = DialogState::Picked(path),
-        };
+        // Opening the same directory using a different path keeps the history
+        dialog.load_directory(Path::new("/link"));
+        wait_for_directory(&mut dialog);
+        assert_eq!(
+            dialog.directory_stack,
+            [PathBuf::from("/dir/sub"), PathBuf::from("/dir")]
+        );
+        assert!(dialog.can_go_back());
+
+        // The last visited directory only changes once the directory finished loading
+        dialog.load_directory(Path::new("/dir/slow"));
+        assert_eq!(
+            dialog.config.storage.last_visited_dir,
+            Some(PathBuf::from("/dir"))
+        );
+
+        wait_for_directory(&mut dialog);
+        assert_eq!(
+            dialog.config.storage.last_visited_dir,
+            Some(PathBuf::from("/dir/slow"))
+        );
     }
 
-    /// Canonicalizes the specified path if canonicalization is enabled.
-    /// Returns the input path if an error occurs or canonicalization is disabled.
-    fn canonicalize_path(&self, path: &Path) -> PathBuf {
-        if self.config.canonicalize_paths {
-            dunce::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
-        } else {
-            path.to_path_buf()
+    #[test]
+    fn slow_listing_is_responsive() {
+        let file_system = TestFileSystem::new(
+            &["/dir", "/dir/slow"],
+            &[
+                "/dir/slow/a.txt",
+                "/dir/slow/b.txt",
+                "/dir/slow/c.txt",
+                "/dir/slow/d.txt",
+                "/dir/slow/e.txt",
+            ],
+        )
+        .with_slow_listing("/dir/slow");
+
+        let mut dialog = test_dialog(file_system, "/dir/slow")
+            .load_via_thread(true)
+            .file_system_timeout(Duration::from_millis(100));
+
+        dialog.pick_file();
+
+        // Every entry takes 50ms to list, so the whole directory takes longer than the
+        // timeout while entries keep arriving
+        let mut max_loaded = 0;
+        while dialog.directory_content.update() != &DirectoryContentState::Success {
+            assert!(!dialog
+                .directory_content
+                .is_unresponsive(Duration::from_millis(100)));
+            max_loaded = max_loaded.max(dialog.directory_content.loaded_entries());
+            std::thread::sleep(Duration::from_millis(10));
         }
-    }
 
-    /// Pins a path to the left sidebar.
-    fn pin_path(&mut self, path: PathBuf) {
-        self.config.storage.pinned_folders.push(path);
+        assert!(max_loaded > 0);
+        assert_eq!(dialog.directory_content.filtered_iter("").count(), 5);
     }
 
-    /// Unpins a path from the left sidebar.
-    fn unpin_path(&mut self, path: &Path) {
-        self.config
-            .storage
-            .pinned_folders
-            .retain(|p| p.as_path() != path);
-    }
+    #[cfg(feature = "eject")]
+    #[test]
+    fn eject_device() {
+        let file_system = TestFileSystem::new(
+            &["/", "/test-media", "/test-media/usb", "/test-media/usb/dir"],
+            &[],
+        );
 
-    /// Checks if the path is pinned to the left sidebar.
-    fn is_pinned(&self, path: &Path) -> bool {
-        self.config
-            .storage
-            .pinned_folders
-            .iter()
-            .any(|p| p.as_path() == path)
-    }
+        let mut dialog = test_dialog(file_system, "/test-media/usb/dir").load_via_thread(true);
 
-    fn is_primary_selected(&self, item: &DirectoryEntry) -> bool {
-        self.selected_item.as_ref().is_some_and(|x| x.path_eq(item))
-    }
+        dialog.pick_directory();
+        wait_for_directory(&mut dialog);
 
-    /// Resets the dialog to use default values.
-    /// Configuration variables are retained.
-    fn reset(&mut self) {
-        let config = self.config.clone();
-        *self = Self::with_config(config);
-    }
+        // The dialog leaves the device before it is ejected
+        dialog.eject_device(Path::new("/test-media/usb"));
+        assert_eq!(dialog.current_directory(), Some(Path::new("/test-media")));
+        assert!(dialog.ejecting_device.is_some());
+        assert!(dialog.eject_error.is_none());
 
-    /// Refreshes the dialog.
-    /// Including the user directories, system disks and currently open directory.
-    fn refresh(&mut self) {
-        self.user_directories = self
-            .config
-            .file_system
-            .user_dirs(self.config.canonicalize_paths);
-        self.system_disks = self
-            .config
-            .file_system
-            .get_disks(self.config.canonicalize_paths);
+        // The path is not a mount point, so ejecting it fails
+        let ctx = egui::Context::default();
+        while dialog.ejecting_device.is_some() {
+            dialog.update_ejecting_device(&ctx);
+            std::thread::sleep(Duration::from_millis(1));
+        }
 
-        self.reload_directory();
+        assert!(dialog
+            .eject_error
+            .as_ref()
+            .is_some_and(|(path, _)| path == Path::new("/test-media/usb")));
     }
 
-    /// Submits the current selection and tries to finish the dialog, if the selection is valid.
-    fn submit(&mut self) {
-        // Make sure the selected item or entered file name is valid.
-        if !self.is_selection_valid() {
-            return;
-        }
+    #[test]
+    fn unavailable_locations() {
+        let file_system = TestFileSystem::new(&["/dir"], &[]);
+        let mut dialog = test_dialog(file_system, "/dir");
 
-        self.config.storage.last_picked_dir = self.current_directory().map(PathBuf::from);
+        dialog.pick_directory();
+        dialog.pin_path(PathBuf::from("/dir"));
+        dialog.pin_path(PathBuf::from("/gone"));
 
-        match &self.mode {
-            DialogMode::PickDirectory | DialogMode::PickFile => {
-                // Should always contain a value since `is_selection_valid` is used to
-                // validate the selection.
-                if let Some(item) = self.selected_item.clone() {
-                    self.state = DialogState::Picked(item.to_path_buf());
-                }
-            }
-            DialogMode::PickMultiple => {
-                let result: Vec<PathBuf> = self
-                    .selected_entries()
-                    .map(crate::DirectoryEntry::to_path_buf)
-                    .collect();
+        TestUi::new().run(&mut dialog, Vec::new());
 
-                self.state = DialogState::PickedMultiple(result);
-            }
-            DialogMode::SaveFile => {
-                // Should always contain a value since `is_selection_valid` is used to
-                // validate the selection.
-                if let Some(path) = self.current_directory() {
-                    let full_path = path.join(&self.file_name_input);
-                    self.submit_save_file(full_path);
-                }
-            }
+        assert!(dialog.location_availability.is_available(Path::new("/dir")));
+        assert!(!dialog
+            .location_availability
+            .is_available(Path::new("/gone")));
+        assert!(!dialog.is_location_available(Path::new("/gone")));
+
+        dialog.refresh();
+        assert!(dialog
+            .location_availability
+            .is_available(Path::new("/gone")));
+
+        dialog.config.mark_unavailable_locations = false;
+        assert!(dialog.is_location_available(Path::new("/gone")));
+
+        // Locations are considered available until they were checked on the other thread
+        dialog.config.mark_unavailable_locations = true;
+        dialog.config.load_via_thread = true;
+        assert!(dialog.is_location_available(Path::new("/gone")));
+
+        while dialog.location_availability.update() {
+            std::thread::sleep(Duration::from_millis(1));
         }
+        assert!(!dialog.is_location_available(Path::new("/gone")));
     }
 
-    /// Submits the file dialog with the specified path and opens the `OverwriteFileModal`
-    /// if the path already exists.
-    fn submit_save_file(&mut self, path: PathBuf) {
-        if path.exists() {
-            self.open_modal(Box::new(OverwriteFileModal::new(path)));
+    #[test]
+    fn new_folder_shortcut() {
+        let file_system = TestFileSystem::new(&["/dir"], &[]);
 
-            return;
-        }
+        let mut dialog = test_dialog(file_system, "/dir")
+            .as_modal(false)
+            .allow_create_folder(false);
+        dialog.pick_file();
 
-        self.state = DialogState::Picked(path);
+        let mut ui = TestUi::new();
+        let mut press_shortcut = |dialog: &mut FileDialog| {
+            let modifiers = egui::Modifiers::COMMAND | egui::Modifiers::SHIFT;
+            ui.press_key(dialog, egui::Key::N, modifiers);
+        };
+
+        // Creating folders is not allowed
+        press_shortcut(&mut dialog);
+        assert!(!dialog.create_directory_dialog.is_open());
+
+        // Hiding the button does not disable the shortcut
+        dialog.config.allow_create_folder = true;
+        dialog.config.show_new_folder_button = false;
+        press_shortcut(&mut dialog);
+        assert!(dialog.create_directory_dialog.is_open());
+    }
+
+    #[test]
+    fn relative_initial_directory_without_canonicalization() {
+        let mut dialog = FileDialog::new()
+            .initial_directory(PathBuf::from("src"))
+            .canonicalize_paths(false)
+            .load_via_thread(false);
+        dialog.pick_file();
+
+        // The path is used as given, so the top panel only displays the relative segment
+        assert_eq!(dialog.current_directory(), Some(Path::new("src")));
+        assert_eq!(
+            FileDialog::get_path_segments(Path::new("src")),
+            [("src".to_string(), PathBuf::from("src"))]
+        );
+        assert!(dialog
+            .get_dir_content_filtered_iter()
+            .any(|e| e.as_path() == Path::new("src/lib.rs")));
+
+        let mut dialog = FileDialog::new()
+            .initial_directory(PathBuf::from("src"))
+            .load_via_thread(false);
+        dialog.pick_file();
+
+        assert!(dialog.current_directory().is_some_and(Path::is_absolute));
     }
 
-    /// Cancels the dialog.
-    fn cancel(&mut self) {
-        self.state = DialogState::Cancelled;
+    #[test]
+    fn start_at_devices() {
+        let file_system = TestFileSystem::new(&["/dir"], &["/dir/a.txt"]);
+
+        let mut dialog = test_dialog(file_system, "/dir").start_at_devices(true);
+        dialog.pick_file();
+
+        assert!(dialog.device_overview);
+        assert_eq!(dialog.current_directory(), None);
+
+        TestUi::new().run(&mut dialog, Vec::new());
+        assert!(dialog.device_overview);
+
+        dialog.load_directory(Path::new("/dir"));
+
+        assert!(!dialog.device_overview);
+        assert_eq!(dialog.current_directory(), Some(Path::new("/dir")));
     }
 
-    /// This function generates the initial directory based on the configuration.
-    /// The function does the following things:
-    ///   - Get the path to open based on the opening mode
-    ///   - Canonicalize the path if enabled
-    ///   - Attempts to use the parent directory if the path is a file
-    fn get_initial_directory(&self) -> PathBuf {
-        let path = match self.config.opening_mode {
-            OpeningMode::AlwaysInitialDir => &self.config.initial_directory,
-            OpeningMode::LastVisitedDir => self
-                .config
-                .storage
-                .last_visited_dir
-                .as_deref()
-                .unwrap_or(&self.config.initial_directory),
-            OpeningMode::LastPickedDir => self
-                .config
-                .storage
-                .last_picked_dir
-                .as_deref()
-                .unwrap_or(&self.config.initial_directory),
+    #[test]
+    fn active_filter() {
+        let file_system = TestFileSystem::new(&["/dir"], &["/dir/a.txt", "/dir/b.png"]);
+
+        let mut dialog = test_dialog(file_system, "/dir").add_file_filter(
+            "Text",
+            Arc::new(|p| p.extension().is_some_and(|e| e == "txt")),
+        );
+        dialog.pick_file();
+
+        let names = |dialog: &FileDialog| -> Vec<String> {
+            dialog
+                .get_dir_content_filtered_iter()
+                .map(|e| e.file_name().to_string())
+                .collect()
         };
 
-        let mut path = self.canonicalize_path(path);
+        assert_eq!(dialog.active_filter(), None);
+        assert_eq!(names(&dialog), ["a.txt", "b.png"]);
 
-        if self.config.file_system.is_file(&path) {
-            if let Some(parent) = path.parent() {
-                path = parent.to_path_buf();
-            }
-        }
+        assert!(dialog.set_active_filter("Text"));
+        assert_eq!(dialog.active_filter(), Some("Text"));
+        assert_eq!(names(&dialog), ["a.txt"]);
 
-        path
+        assert!(!dialog.set_active_filter("Images"));
+        assert_eq!(dialog.active_filter(), Some("Text"));
     }
 
-    /// Gets the currently open directory.
-    fn current_directory(&self) -> Option<&Path> {
-        if let Some(x) = self.directory_stack.iter().nth_back(self.directory_offset) {
-            return Some(x.as_path());
-        }
+    #[test]
+    fn render_modes() {
+        for render_mode in [
+            RenderMode::Window,
+            RenderMode::Viewport,
+            RenderMode::InlineUi,
+        ] {
+            let file_system = TestFileSystem::new(&["/dir"], &["/dir/a.txt"]);
 
-        None
-    }
+            let mut dialog = test_dialog(file_system, "/dir").render_mode(render_mode);
+            dialog.pick_file();
 
-    /// Checks whether the selection or the file name entered is valid.
-    /// What is checked depends on the mode the dialog is currently in.
-    fn is_selection_valid(&self) -> bool {
-        match &self.mode {
-            DialogMode::PickDirectory => self
-                .selected_item
-                .as_ref()
-                .is_some_and(crate::DirectoryEntry::is_dir),
-            DialogMode::PickFile => self
-                .selected_item
-                .as_ref()
-                .is_some_and(DirectoryEntry::is_file),
-            DialogMode::PickMultiple => self.get_dir_content_filtered_iter().any(|p| p.selected),
-            DialogMode::SaveFile => self.file_name_input_error.is_none(),
+            let mut rendered = false;
+            TestUi::new().run_ui(egui::RawInput::default(), |ui| {
+                rendered = dialog.show(ui).response().is_some();
+            });
+
+            // Viewports are embedded into a window if the backend does not support them
+            assert!(rendered, "{render_mode:?}");
+            assert_eq!(dialog.state(), DialogState::Open);
         }
     }
 
-    /// Validates the file name entered by the user.
-    ///
-    /// Returns None if the file name is valid. Otherwise returns an error message.
-    fn validate_file_name_input(&self) -> Option<String> {
-        if self.file_name_input.is_empty() {
-            return Some(self.config.labels.err_empty_file_name.clone());
-        }
+    #[test]
+    fn go_to_dialog() {
+        let file_system = TestFileSystem::new(&["/dir", "/dir/sub"], &[]);
 
-        if let Some(x) = self.current_directory() {
-            let mut full_path = x.to_path_buf();
-            full_path.push(self.file_name_input.as_str());
+        let mut dialog = test_dialog(file_system, "/dir").as_modal(false);
+        dialog.pick_file();
 
-            if self.config.file_system.is_dir(&full_path) {
-                return Some(self.config.labels.err_directory_exists.clone());
-            }
+        let mut ui = TestUi::new();
 
-            if !self.config.allow_file_overwrite && self.config.file_system.is_file(&full_path) {
-                return Some(self.config.labels.err_file_exists.clone());
-            }
-        } else {
-            // There is most likely a bug in the code if we get this error message!
-            return Some("Currently not in a directory".to_string());
+        ui.press_key(&mut dialog, egui::Key::L, egui::Modifiers::COMMAND);
+        assert!(dialog.go_to_dialog.is_open());
+
+        // The input contains the current directory, so the user can append to it
+        let none = egui::Modifiers::NONE;
+        ui.run(&mut dialog, vec![egui::Event::Text("/missing".to_string())]);
+        ui.press_key(&mut dialog, egui::Key::Enter, none);
+        assert!(dialog.go_to_dialog.is_open());
+        assert_eq!(dialog.current_directory(), Some(Path::new("/dir")));
+
+        for _ in 0.."/missing".len() {
+            ui.press_key(&mut dialog, egui::Key::Backspace, none);
         }
+        ui.run(&mut dialog, vec![egui::Event::Text("/sub".to_string())]);
+        ui.press_key(&mut dialog, egui::Key::Enter, none);
+        assert!(!dialog.go_to_dialog.is_open());
+        assert_eq!(dialog.current_directory(), Some(Path::new("/dir/sub")));
+    }
+
+    #[test]
+    fn reverse_sort_order() {
+        let file_system =
+            TestFileSystem::new(&["/dir", "/dir/a", "/dir/b"], &["/dir/c.txt", "/dir/d.txt"]);
+
+        let mut dialog = test_dialog(file_system, "/dir");
+        dialog.pick_file();
+        assert!(dialog.scroll_to(Path::new("/dir/c.txt")));
+
+        dialog.set_sort_descending(true);
+        assert!(dialog.sort_descending());
+
+        // Directories are still listed first
+        let names: Vec<_> = dialog
+            .directory_content
+            .filtered_iter("")
+            .map(DirectoryEntry::file_name)
+            .collect();
+        assert_eq!(names, ["b", "a", "d.txt", "c.txt"]);
+
+        assert_eq!(
+            dialog.selected_item.as_ref().map(DirectoryEntry::as_path),
+            Some(Path::new("/dir/c.txt"))
+        );
+    }
 
-        None
+    #[test]
+    fn custom_sort_comparator() {
+        let file_system = TestFileSystem::new(
+            &["/dir", "/dir/a.x", "/dir/b.y"],
+            &["/dir/a.md", "/dir/b.txt", "/dir/c.rs"],
+        );
+
+        let mut dialog = test_dialog(file_system, "/dir");
+        dialog.pick_file();
+
+        let names = |dialog: &FileDialog| -> Vec<String> {
+            dialog
+                .get_dir_content_filtered_iter()
+                .map(|e| e.file_name().to_string())
+                .collect()
+        };
+
+        // Sort by extension in reverse order, folders are still listed first
+        dialog.set_sort_comparator(Arc::new(|a, b| b.extension().cmp(&a.extension())));
+        assert_eq!(names(&dialog), ["b.y", "a.x", "b.txt", "c.rs", "a.md"]);
+
+        dialog.clear_sort_comparator();
+        assert_eq!(names(&dialog), ["a.x", "b.y", "a.md", "b.txt", "c.rs"]);
     }
 
-    /// Marks the given item as the selected directory item.
-    /// Also updates the `file_name_input` to the name of the selected item.
-    fn select_item(&mut self, item: &mut DirectoryEntry) {
-        if self.mode == DialogMode::PickMultiple {
-            item.selected = true;
-        }
-        self.selected_item = Some(item.clone());
+    #[test]
+    fn glob_filter() {
+        let file_system = TestFileSystem::new(
+            &["/dir", "/dir/sub"],
+            &["/dir/a.tar.gz", "/dir/b.tar.zst", "/dir/c.zip"],
+        );
+
+        let mut dialog = test_dialog(file_system, "/dir");
+        dialog.set_glob_filter("*.tar.{gz,zst}");
+        dialog.pick_file();
+
+        let visible = |dialog: &FileDialog| -> Vec<String> {
+            dialog
+                .get_dir_content_filtered_iter()
+                .map(|e| e.file_name().to_string())
+                .collect()
+        };
+
+        // The pattern is kept when opening the dialog and does not affect directories
+        assert_eq!(visible(&dialog), ["sub", "a.tar.gz", "b.tar.zst"]);
 
-        if self.mode == DialogMode::SaveFile && item.is_file() {
-            self.file_name_input = item.file_name().to_string();
-            self.file_name_input_error = self.validate_file_name_input();
-        }
-    }
+        // The pattern is combined with the search value
+        dialog.set_search_value("b");
+        assert_eq!(visible(&dialog), ["sub", "b.tar.zst"]);
 
-    /// Attempts to select the last visible item in `directory_content` before the specified item.
-    ///
-    /// Returns true if an item is found and selected.
-    /// Returns false if no visible item is found before the specified item.
-    fn select_next_visible_item_before(&mut self, item: &DirectoryEntry) -> bool {
-        let mut return_val = false;
+        dialog.clear_glob_filter();
+        dialog.set_search_value("");
+        assert_eq!(visible(&dialog).len(), 4);
+    }
 
-        self.directory_content.reset_multi_selection();
+    #[test]
+    fn entry_predicate() {
+        let file_system = TestFileSystem::new(
+            &["/dir", "/dir/src", "/dir/target"],
+            &["/dir/a.txt", "/dir/target.txt"],
+        );
 
-        let mut directory_content = std::mem::take(&mut self.directory_content);
-        let search_value = std::mem::take(&mut self.search_value);
+        let mut dialog = test_dialog(file_system, "/dir")
+            .entry_predicate(Arc::new(|path| !path.ends_with("target")));
+        dialog.pick_file();
+
+        // Rejected entries are not part of the content, so the search cannot find them either
+        dialog.set_search_value("target");
+        let names: Vec<_> = dialog
+            .get_dir_content_filtered_iter()
+            .map(DirectoryEntry::file_name)
+            .collect();
+        assert_eq!(names, ["target.txt"]);
+        assert_eq!(dialog.directory_content.len(), 3);
+
+        // The predicate can be changed while the dialog is open
+        dialog.set_entry_predicate(Arc::new(|path| !path.ends_with("src")));
+        dialog.set_search_value("");
+        let names: Vec<_> = dialog
+            .get_dir_content_filtered_iter()
+            .map(DirectoryEntry::file_name)
+            .collect();
+        assert_eq!(names, ["target", "a.txt", "target.txt"]);
+
+        dialog.clear_entry_predicate();
+        assert_eq!(dialog.directory_content.len(), 4);
+    }
+
+    #[test]
+    fn path_filter() {
+        let file_system = TestFileSystem::new(
+            &["/dir", "/dir/sub"],
+            &["/dir/a.txt", "/dir/b.txt", "/dir/sub/c.txt"],
+        );
 
-        let index = directory_content
-            .filtered_iter(&search_value)
-            .position(|p| p.path_eq(item));
+        let mut dialog = test_dialog(file_system, "/dir");
+        dialog.pick_file();
 
-        if let Some(index) = index {
-            if index != 0 {
-                if let Some(item) = directory_content
-                    .filtered_iter_mut(&search_value)
-                    .nth(index.saturating_sub(1))
-                {
-                    self.select_item(item);
-                    self.scroll_to_selection = true;
-                    return_val = true;
-                }
-            }
-        }
+        let visible = |dialog: &FileDialog| -> Vec<String> {
+            dialog
+                .get_dir_content_filtered_iter()
+                .map(|e| e.file_name().to_string())
+                .collect()
+        };
 
-        self.directory_content = directory_content;
-        self.search_value = search_value;
+        // The filter does not affect directories and is evaluated when loading the directory
+        dialog.set_filter(Arc::new(|path| path.ends_with("b.txt")));
+        assert_eq!(visible(&dialog), ["sub", "b.txt"]);
 
-        return_val
+        dialog.clear_filter();
+        assert_eq!(visible(&dialog), ["sub", "a.txt", "b.txt"]);
     }
 
-    /// Attempts to select the last visible item in `directory_content` after the specified item.
-    ///
-    /// Returns true if an item is found and selected.
-    /// Returns false if no visible item is found after the specified item.
-    fn select_next_visible_item_after(&mut self, item: &DirectoryEntry) -> bool {
-        let mut return_val = false;
+    #[test]
+    #[cfg(feature = "regex")]
+    fn regex_filter() -> Result<(), regex::Error> {
+        let file_system = TestFileSystem::new(
+            &["/dir", "/dir/2023"],
+            &[
+                "/dir/report-2023.pdf",
+                "/dir/report-final.pdf",
+                "/dir/2024.txt",
+            ],
+        );
 
-        self.directory_content.reset_multi_selection();
+        let mut dialog = test_dialog(file_system, "/dir");
+        dialog.set_regex_filter(regex::Regex::new(r"\d{4}")?);
+        dialog.pick_file();
 
-        let mut directory_content = std::mem::take(&mut self.directory_content);
-        let search_value = std::mem::take(&mut self.search_value);
+        let visible = |dialog: &FileDialog| -> Vec<String> {
+            dialog
+                .get_dir_content_filtered_iter()
+                .map(|e| e.file_name().to_string())
+                .collect()
+        };
 
-        let index = directory_content
-            .filtered_iter(&search_value)
-            .position(|p| p.path_eq(item));
+        assert_eq!(visible(&dialog), ["2023", "2024.txt", "report-2023.pdf"]);
 
-        if let Some(index) = index {
-            if let Some(item) = directory_content
-                .filtered_iter_mut(&search_value)
-                .nth(index.saturating_add(1))
-            {
-                self.select_item(item);
-                self.scroll_to_selection = true;
-                return_val = true;
-            }
-        }
+        dialog.set_regex_filter(regex::Regex::new(r"^report-\w+\.pdf$")?);
+        assert_eq!(
+            visible(&dialog),
+            ["2023", "report-2023.pdf", "report-final.pdf"]
+        );
 
-        self.directory_content = directory_content;
-        self.search_value = search_value;
+        dialog.clear_regex_filter();
+        assert_eq!(visible(&dialog).len(), 4);
 
-        return_val
+        Ok(())
     }
 
-    /// Tries to select the first visible item inside `directory_content`.
-    fn select_first_visible_item(&mut self) {
-        self.directory_content.reset_multi_selection();
+    #[test]
+    fn reset_entry_filters_on_open() {
+        let file_system = TestFileSystem::new(&["/dir"], &[]);
+        let time = SystemTime::UNIX_EPOCH + Duration::from_secs(1000);
 
-        let mut directory_content = std::mem::take(&mut self.directory_content);
+        let mut dialog = test_dialog(file_system, "/dir")
+            .min_file_size(1024)
+            .max_file_size(4096)
+            .modified_after(time);
 
-        if let Some(item) = directory_content
-            .filtered_iter_mut(&self.search_value.clone())
-            .next()
-        {
-            self.select_item(item);
-            self.scroll_to_selection = true;
-        }
+        // The filters specified before opening the dialog are applied
+        dialog.pick_file();
+        assert_eq!(dialog.config.min_file_size, Some(1024));
+        assert_eq!(dialog.config.max_file_size, Some(4096));
+        assert_eq!(dialog.config.modified_after, Some(time));
 
-        self.directory_content = directory_content;
+        // And reset if the dialog is opened again without specifying them
+        dialog.pick_file();
+        assert_eq!(dialog.config.min_file_size, None);
+        assert_eq!(dialog.config.max_file_size, None);
+        assert_eq!(dialog.config.modified_after, None);
+
+        dialog.set_file_size_range(Some(1), None);
+        dialog.pick_file();
+        assert_eq!(dialog.config.min_file_size, Some(1));
+        assert_eq!(dialog.config.modified_after, None);
+
+        dialog.pick_file();
+        assert_eq!(dialog.config.min_file_size, None);
     }
 
-    /// Tries to select the last visible item inside `directory_content`.
-    fn select_last_visible_item(&mut self) {
-        self.directory_content.reset_multi_selection();
+    #[test]
+    fn allowed_save_extensions() {
+        let file_system = TestFileSystem::new(&["/dir"], &[]);
 
-        let mut directory_content = std::mem::take(&mut self.directory_content);
+        let mut dialog = test_dialog(file_system, "/dir").allowed_save_extensions(&["txt", ".md"]);
+        dialog.save_file();
 
-        if let Some(item) = directory_content
-            .filtered_iter_mut(&self.search_value.clone())
-            .last()
-        {
-            self.select_item(item);
-            self.scroll_to_selection = true;
-        }
+        dialog.file_name_input = String::from("notes.MD");
+        assert_eq!(dialog.validate_file_name_input(), None);
 
-        self.directory_content = directory_content;
-    }
+        dialog.file_name_input = String::from("notes");
+        assert_eq!(
+            dialog.validate_file_name_input().as_deref(),
+            Some("File must end in .txt or .md")
+        );
 
-    /// Opens the text field in the top panel to text edit the current path.
-    fn open_path_edit(&mut self) {
-        let path = self.current_directory().map_or_else(String::new, |path| {
-            path.to_str().unwrap_or_default().to_string()
-        });
+        dialog.file_name_input = String::from("notes.pdf");
+        assert!(dialog.validate_file_name_input().is_some());
 
-        self.path_edit_value = path;
-        self.path_edit_activate = true;
-        self.path_edit_visible = true;
+        dialog.file_name_input = String::from(".txt");
+        assert!(dialog.validate_file_name_input().is_some());
+
+        dialog.config.allowed_save_extensions = vec!["tar.gz".to_string()];
+        dialog.file_name_input = String::from("backup.tar.gz");
+        assert_eq!(dialog.validate_file_name_input(), None);
+
+        dialog.file_name_input = String::from("backup.gz");
+        assert_eq!(
+            dialog.validate_file_name_input().as_deref(),
+            Some("File must end in .tar.gz")
+        );
     }
 
-    /// Loads the directory from the path text edit.
-    fn submit_path_edit(&mut self) {
-        self.close_path_edit();
+    #[test]
+    fn open_directory_on_save() {
+        let file_system = TestFileSystem::new(&["/dir", "/dir/sub"], &[]);
 
-        let path = self.canonicalize_path(&PathBuf::from(&self.path_edit_value));
+        let mut dialog = test_dialog(file_system, "/dir");
+        dialog.save_file();
 
-        if self.mode == DialogMode::PickFile && self.config.file_system.is_file(&path) {
-            self.state = DialogState::Picked(path);
-            return;
-        }
+        dialog.file_name_input = String::from("sub");
+        assert_eq!(
+            dialog.validate_file_name_input().as_deref(),
+            Some("A directory with the name already exists")
+        );
 
-        // Assume the user wants to save the given path when
-        //   - an extension to the file name is given or the path
-        //     edit is allowed to save a file without extension,
-        //   - the path is not an existing directory,
-        //   - and the parent directory exists
-        // Otherwise we will assume the user wants to open the path as a directory.
-        if self.mode == DialogMode::SaveFile
-            && (path.extension().is_some()
-                || self.config.allow_path_edit_to_save_file_without_extension)
-            && !self.config.file_system.is_dir(&path)
-            && path.parent().is_some_and(std::path::Path::exists)
-        {
-            self.submit_save_file(path);
-            return;
-        }
+        dialog.config.open_directory_on_save = true;
+        dialog.file_name_input_error = dialog.validate_file_name_input();
+        dialog.submit();
 
-        self.load_directory(&path);
+        assert_eq!(dialog.state, DialogState::Open);
+        assert_eq!(dialog.current_directory(), Some(Path::new("/dir/sub")));
+        assert!(dialog.file_name_input.is_empty());
     }
 
-    /// Closes the text field at the top to edit the current path without loading
-    /// the entered directory.
-    fn close_path_edit(&mut self) {
-        self.path_edit_visible = false;
-    }
+    #[test]
+    fn overwrite_existing_file() {
+        let file_system = TestFileSystem::new(&["/dir"], &["/dir/a.txt"]);
 
-    /// Loads the next directory in the `directory_stack`.
-    /// If `directory_offset` is 0 and there is no other directory to load, `Ok()` is returned and
-    /// nothing changes.
-    /// Otherwise, the result of the directory loading operation is returned.
-    fn load_next_directory(&mut self) {
-        if self.directory_offset == 0 {
-            // There is no next directory that can be loaded
-            return;
-        }
+        let mut dialog = test_dialog(file_system, "/dir");
+        dialog.save_file();
 
-        self.directory_offset -= 1;
+        dialog.submit_save_file(PathBuf::from("/dir/b.txt"));
+        assert!(dialog.modals.is_empty());
+        assert_eq!(
+            dialog.state,
+            DialogState::Picked(PathBuf::from("/dir/b.txt"))
+        );
 
-        // Copy path and load directory
-        if let Some(path) = self.current_directory() {
-            self.load_directory_content(path.to_path_buf().as_path());
-        }
+        dialog.save_file();
+        dialog.submit_save_file(PathBuf::from("/dir/a.txt"));
+        assert_eq!(dialog.modals.len(), 1);
+        assert_eq!(dialog.state, DialogState::Open);
     }
 
-    /// Loads the previous directory the user opened.
-    /// If there is no previous directory left, `Ok()` is returned and nothing changes.
-    /// Otherwise, the result of the directory loading operation is returned.
-    fn load_previous_directory(&mut self) {
-        if self.directory_offset + 1 >= self.directory_stack.len() {
-            // There is no previous directory that can be loaded
-            return;
-        }
+    #[test]
+    fn create_missing_directories() {
+        // A file is in the way of the directory that has to be created
+        let file_system = TestFileSystem::new(&["/dir"], &["/dir/exports"]);
 
-        self.directory_offset += 1;
+        let mut dialog = test_dialog(file_system, "/dir");
+        dialog.save_file();
 
-        // Copy path and load directory
-        if let Some(path) = self.current_directory() {
-            self.load_directory_content(path.to_path_buf().as_path());
-        }
+        dialog.file_name_input = String::from("exports/report.pdf");
+        assert_eq!(
+            dialog.validate_file_name_input().as_deref(),
+            Some("The directory does not exist")
+        );
+
+        dialog.config.create_missing_directories = true;
+        dialog.file_name_input_error = dialog.validate_file_name_input();
+        assert_eq!(dialog.file_name_input_error, None);
+
+        // The directory cannot be created, so the error is displayed
+        dialog.submit();
+        assert_eq!(dialog.state, DialogState::Open);
+        assert!(dialog
+            .file_name_input_error
+            .as_deref()
+            .is_some_and(|err| err.starts_with("Failed to create the directory:")));
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn owner_names_of_enabled_columns() {
+        let mut dialog = FileDialog::new();
+        let path = std::env::temp_dir();
+        let load = |dialog: &FileDialog| {
+            let Some(item) =
+                load_filtered_entry(&dialog.config, &path, true, None, None, &NativeFileSystem)
+            else {
+                panic!("the temporary directory is not listed");
+            };
+            item
+        };
+
+        // The names are not looked up while the columns are hidden
+        let item = load(&dialog);
+        assert_eq!(item.metadata().owner, None);
+        assert_eq!(item.metadata().group, None);
+        assert!(dialog.owner_texts(&item).is_empty());
+
+        let (Some(uid), Some(gid)) = (item.metadata().owner_id, item.metadata().group_id) else {
+            panic!("the owner of the directory is unknown");
+        };
+        let owner = crate::permissions::user_name(uid);
+        let group = crate::permissions::group_name(gid);
+
+        dialog.config.show_owner = true;
+        let item = load(&dialog);
+        assert_eq!(item.metadata().group, None);
+        assert_eq!(dialog.owner_texts(&item), [owner.as_str()]);
+
+        dialog.config.show_group = true;
+        let item = load(&dialog);
+        assert_eq!(dialog.owner_texts(&item), [owner.as_str(), group.as_str()]);
+    }
+
+    /// Creates a dialog saving a file in `/home/user` of an in-memory file system.
+    fn memory_save_dialog() -> FileDialog {
+        let file_system = MemoryFileSystem::new()
+            .with_dir("/home/user/documents")
+            .with_file("/home/user/notes.txt", "Hello world")
+            .with_current_dir("/home/user");
+
+        let mut dialog = test_dialog(file_system, "/home/user");
+        dialog.save_file();
+        wait_for_directory(&mut dialog);
+
+        dialog
+    }
+
+    #[test]
+    fn memory_file_system_navigation() {
+        let mut dialog = memory_save_dialog();
+        assert_eq!(dialog.current_directory(), Some(Path::new("/home/user")));
+        assert_eq!(dialog.directory_content.len(), 2);
+
+        // Relative components are resolved once the directory is loaded
+        dialog.load_directory(Path::new("/home/user/documents/.."));
+        wait_for_directory(&mut dialog);
+        assert_eq!(dialog.directory_stack, [PathBuf::from("/home/user")]);
+        assert!(!dialog.can_go_back());
+
+        dialog.load_parent_directory();
+        wait_for_directory(&mut dialog);
+        dialog.load_parent_directory();
+        wait_for_directory(&mut dialog);
+        assert_eq!(dialog.current_directory(), Some(Path::new("/")));
+
+        // The root directory has no parent
+        dialog.load_parent_directory();
+        wait_for_directory(&mut dialog);
+        assert_eq!(dialog.current_directory(), Some(Path::new("/")));
+        assert_eq!(dialog.directory_stack.len(), 3);
+
+        // Opening a directory after going back removes the forward history
+        dialog.load_previous_directory();
+        dialog.load_previous_directory();
+        wait_for_directory(&mut dialog);
+        assert!(dialog.can_go_forward());
+        dialog.load_directory(Path::new("/home/user/documents"));
+        wait_for_directory(&mut dialog);
+        assert!(!dialog.can_go_forward());
+        assert_eq!(
+            dialog.directory_stack,
+            [
+                PathBuf::from("/home/user"),
+                PathBuf::from("/home/user/documents")
+            ]
+        );
+
+        // A file can not be opened as a directory
+        dialog.load_directory(Path::new("/home/user/notes.txt"));
+        wait_for_directory(&mut dialog);
+        assert!(matches!(
+            dialog.directory_content.update(),
+            DirectoryContentState::Errored(_)
+        ));
+    }
+
+    #[test]
+    fn memory_file_system_path_edit_save() {
+        let mut dialog = memory_save_dialog();
+
+        // The parent directory only exists in memory
+        dialog.path_edit_value = String::from("/home/user/documents/report.pdf");
+        dialog.submit_path_edit();
+        assert_eq!(
+            dialog.state,
+            DialogState::Picked(PathBuf::from("/home/user/documents/report.pdf"))
+        );
+
+        let mut dialog = memory_save_dialog();
+        dialog.path_edit_value = String::from("/home/user/exports/report.pdf");
+        dialog.submit_path_edit();
+        assert_eq!(dialog.state, DialogState::Open);
+
+        // Missing directories are created when saving through the path edit as well
+        dialog.config.create_missing_directories = true;
+        dialog.path_edit_value = String::from("/home/user/exports/report.pdf");
+        dialog.submit_path_edit();
+        assert_eq!(
+            dialog.state,
+            DialogState::Picked(PathBuf::from("/home/user/exports/report.pdf"))
+        );
+        assert!(dialog
+            .config
+            .file_system
+            .is_dir(Path::new("/home/user/exports")));
     }
 
-    /// Loads the parent directory of the currently open directory.
-    /// If the directory doesn't have a parent, `Ok()` is returned and nothing changes.
-    /// Otherwise, the result of the directory loading operation is returned.
-    fn load_parent_directory(&mut self) {
-        if let Some(x) = self.current_directory() {
-            if let Some(x) = x.to_path_buf().parent() {
-                self.load_directory(x);
-            }
-        }
+    #[test]
+    fn memory_file_system_validation() {
+        let mut dialog = memory_save_dialog();
+
+        dialog.file_name_input = String::new();
+        assert_eq!(
+            dialog.validate_file_name_input(),
+            Some(dialog.config.labels.err_empty_file_name.clone())
+        );
+
+        dialog.file_name_input = String::from("documents");
+        assert_eq!(
+            dialog.validate_file_name_input(),
+            Some(dialog.config.labels.err_directory_exists.clone())
+        );
+
+        dialog.file_name_input = String::from("notes.txt");
+        assert_eq!(dialog.validate_file_name_input(), None);
+        dialog.config.allow_file_overwrite = false;
+        assert_eq!(
+            dialog.validate_file_name_input(),
+            Some(dialog.config.labels.err_file_exists.clone())
+        );
+        dialog.config.allow_file_overwrite = true;
+
+        // Overwriting an existing file has to be confirmed
+        dialog.submit_save_file(PathBuf::from("/home/user/notes.txt"));
+        assert_eq!(dialog.modals.len(), 1);
+        assert_eq!(dialog.state, DialogState::Open);
+        dialog.modals.clear();
+
+        dialog.file_name_input = String::from("exports/2024/report.pdf");
+        assert_eq!(
+            dialog.validate_file_name_input(),
+            Some(dialog.config.labels.err_parent_directory_missing.clone())
+        );
+
+        // The missing directories are created when submitting
+        dialog.config.create_missing_directories = true;
+        dialog.file_name_input_error = dialog.validate_file_name_input();
+        assert_eq!(dialog.file_name_input_error, None);
+        dialog.submit();
+        assert_eq!(
+            dialog.state,
+            DialogState::Picked(PathBuf::from("/home/user/exports/2024/report.pdf"))
+        );
+        assert!(dialog
+            .config
+            .file_system
+            .is_dir(Path::new("/home/user/exports/2024")));
     }
 
-    /// Reloads the currently open directory.
-    /// If no directory is currently open, `Ok()` will be returned.
-    /// Otherwise, the result of the directory loading operation is returned.
-    ///
-    /// In most cases, this function should not be called directly.
-    /// Instead, `refresh` should be used to reload all other data like system disks too.
-    fn reload_directory(&mut self) {
-        if let Some(x) = self.current_directory() {
-            self.load_directory_content(x.to_path_buf().as_path());
-        }
+    #[test]
+    fn confirm_validator() {
+        let file_system = TestFileSystem::new(&["/dir"], &["/dir/a.txt", "/dir/b.txt"]);
+
+        let mut dialog =
+            test_dialog(file_system, "/dir").confirm_validator(Arc::new(|path, _mode| {
+                if path.ends_with("a.txt") {
+                    Err("Not a valid image".to_string())
+                } else {
+                    Ok(())
+                }
+            }));
+
+        dialog.pick_file();
+
+        // The selection itself is valid, only submitting is rejected
+        assert!(dialog.scroll_to(Path::new("/dir/a.txt")));
+        assert!(dialog.is_selection_valid());
+        dialog.submit();
+        assert_eq!(dialog.state, DialogState::Open);
+        assert_eq!(
+            dialog.confirm_validation_error.as_deref(),
+            Some("Not a valid image")
+        );
+
+        assert!(dialog.scroll_to(Path::new("/dir/b.txt")));
+        assert_eq!(dialog.confirm_validation_error, None);
+        dialog.submit();
+        assert_eq!(
+            dialog.state,
+            DialogState::Picked(PathBuf::from("/dir/b.txt"))
+        );
     }
 
-    /// Loads the given directory and updates the `directory_stack`.
-    /// The function deletes all directories from the `directory_stack` that are currently
-    /// stored in the vector before the `directory_offset`.
-    ///
-    /// The function also sets the loaded directory as the selected item.
-    fn load_directory(&mut self, path: &Path) {
-        // Do not load the same directory again.
-        // Use reload_directory if the content of the directory should be updated.
-        if let Some(x) = self.current_directory() {
-            if x == path {
-                return;
-            }
-        }
+    #[test]
+    fn path_edit_selection_validator() {
+        let file_system =
+            TestFileSystem::new(&["/dir", "/other"], &["/other/a.txt", "/other/b.txt"]);
 
-        if self.directory_offset != 0 && self.directory_stack.len() > self.directory_offset {
-            self.directory_stack
-                .drain(self.directory_stack.len() - self.directory_offset..);
-        }
+        let mut dialog =
+            test_dialog(file_system, "/dir").selection_validator(Arc::new(|path, _mode| {
+                if path.ends_with("a.txt") {
+                    Err("Not allowed".to_string())
+                } else {
+                    Ok(())
+                }
+            }));
 
-        self.directory_stack.push(path.to_path_buf());
-        self.directory_offset = 0;
+        dialog.pick_file();
 
-        self.load_directory_content(path);
+        // A rejected path entered in the path edit does not finish the dialog
+        dialog.path_edit_value = String::from("/other/a.txt");
+        dialog.submit_path_edit();
+        assert_eq!(dialog.state, DialogState::Open);
+        assert_eq!(
+            dialog.confirm_validation_error.as_deref(),
+            Some("Not allowed")
+        );
+        assert_eq!(dialog.config.storage.last_picked_dir, None);
 
-        // Clear the entry filter buffer.
-        // It's unlikely the user wants to keep the current filter when entering a new directory.
-        self.search_value.clear();
+        dialog.path_edit_value = String::from("/other/b.txt");
+        dialog.submit_path_edit();
+        assert_eq!(
+            dialog.state,
+            DialogState::Picked(PathBuf::from("/other/b.txt"))
+        );
+        assert_eq!(
+            dialog.config.storage.last_picked_dir,
+            Some(PathBuf::from("/other"))
+        );
     }
 
-    /// Loads the directory content of the given path.
-    fn load_directory_content(&mut self, path: &Path) {
-        self.config.storage.last_visited_dir = Some(path.to_path_buf());
+    #[test]
+    fn take_result_once() {
+        let file_system = TestFileSystem::new(&["/dir"], &["/dir/a.txt"]);
 
-        let selected_file_filter = match self.mode {
-            DialogMode::PickFile | DialogMode::PickMultiple => self.get_selected_file_filter(),
-            _ => None,
-        };
+        let mut dialog = test_dialog(file_system, "/dir");
 
-        let selected_save_extension = if self.mode == DialogMode::SaveFile {
-            self.get_selected_save_extension()
-                .map(|e| e.file_extension.as_str())
-        } else {
-            None
-        };
+        dialog.pick_file();
+        assert_eq!(dialog.take_result(), None);
 
-        self.directory_content = DirectoryContent::from_path(
-            &self.config,
-            path,
-            self.show_files,
-            selected_file_filter,
-            selected_save_extension,
-            self.config.file_system.clone(),
+        assert!(dialog.scroll_to(Path::new("/dir/a.txt")));
+        dialog.submit();
+
+        // The result is only returned in the first frame after the user picked the file
+        assert_eq!(
+            dialog.take_result(),
+            Some(DialogResult::Selected(PathBuf::from("/dir/a.txt")))
         );
+        assert_eq!(dialog.take_result(), None);
+        assert_eq!(dialog.state(), DialogState::Closed);
 
-        self.create_directory_dialog.close();
-        self.scroll_to_selection = true;
+        dialog.pick_file();
+        dialog.cancel();
 
-        if self.mode == DialogMode::SaveFile {
-            self.file_name_input_error = self.validate_file_name_input();
-        }
+        assert_eq!(dialog.take_result(), Some(DialogResult::Cancelled));
+        assert_eq!(dialog.take_result(), None);
+    }
+
+    #[test]
+    fn increment_file_name() {
+        let dialog = FileDialog::new().allowed_save_extensions(&["tar.gz"]);
+        let increment = |file_name, counter| {
+            let (stem, extension) = dialog.split_file_extension(file_name);
+            FileDialog::increment_file_name(stem, extension, counter)
+        };
+
+        assert_eq!(increment("report.pdf", 1), "report (1).pdf");
+        assert_eq!(increment("report (1).pdf", 2), "report (2).pdf");
+        assert_eq!(increment("notes", 3), "notes (3)");
+        assert_eq!(increment(".bashrc", 1), ".bashrc (1)");
+        assert_eq!(increment("a (b).txt", 1), "a (b) (1).txt");
+
+        // Configured extensions containing a dot are kept together
+        assert_eq!(increment("a.tar.gz", 1), "a (1).tar.gz");
+        assert_eq!(increment("a (1).TAR.GZ", 2), "a (2).TAR.GZ");
+        assert_eq!(increment("a.b.gz", 1), "a.b (1).gz");
+    }
+
+    #[test]
+    fn file_name_suggestion_probes() {
+        let files: Vec<String> = std::iter::once("/dir/a.txt".to_string())
+            .chain((1..=MAX_COPY_NAME_PROBES).map(|i| format!("/dir/a ({i}).txt")))
+            .collect();
+        let files: Vec<&str> = files.iter().map(String::as_str).collect();
+        let file_system = TestFileSystem::new(&["/dir"], &files);
+
+        let mut dialog = test_dialog(file_system, "/dir").suggest_copy_name(true);
+        dialog.save_file();
+
+        // No name is suggested if all probed counters are taken
+        dialog.file_name_input = "a.txt".to_string();
+        dialog.update_file_name_suggestion();
+        assert_eq!(dialog.file_name_suggestion, None);
     }
 }
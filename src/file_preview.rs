@@ -0,0 +1,143 @@
+use egui::mutex::Mutex;
+use std::path::{Path, PathBuf};
+use std::sync::{mpsc, Arc};
+use std::thread;
+
+use crate::{DirectoryEntry, FileDialogConfig, FileSystem};
+
+/// Files larger than this size in bytes are not previewed.
+const MAX_FILE_SIZE: u64 = 64 * 1024;
+/// The maximum number of lines shown inside the preview.
+const MAX_LINES: usize = 100;
+
+type FilePreviewReceiver = Option<Arc<Mutex<mpsc::Receiver<Option<String>>>>>;
+
+/// A pane that shows a preview of the currently selected file.
+/// Currently only small text files encoded as UTF-8 can be previewed.
+#[derive(Default)]
+pub struct FilePreview {
+    /// The path of the file that is currently previewed.
+    path: Option<PathBuf>,
+    /// The loaded preview. None if the file cannot be previewed.
+    content: Option<String>,
+    /// Receiver when the preview is loaded on a different thread.
+    content_recv: FilePreviewReceiver,
+}
+
+impl std::fmt::Debug for FilePreview {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FilePreview")
+            .field("path", &self.path)
+            .field("content", &self.content)
+            .field(
+                "content_recv",
+                if self.content_recv.is_some() {
+                    &"<Receiver>"
+                } else {
+                    &"None"
+                },
+            )
+            .finish()
+    }
+}
+
+impl FilePreview {
+    /// Main update function of the preview. Should be called in every frame
+    /// in which the preview is to be displayed.
+    ///
+    /// Loads the preview of the given item if it changed since the last frame.
+    pub fn update(
+        &mut self,
+        ui: &mut egui::Ui,
+        config: &FileDialogConfig,
+        item: Option<&DirectoryEntry>,
+    ) {
+        let path = item.filter(|i| i.is_file()).map(DirectoryEntry::as_path);
+
+        if self.path.as_deref() != path {
+            self.load(config, path);
+        }
+
+        self.update_pending_state();
+
+        if self.content_recv.is_some() {
+            ui.centered_and_justified(egui::Ui::spinner);
+
+            // Prevent egui from not updating the UI when there is no user input
+            ui.ctx().request_repaint();
+            return;
+        }
+
+        if let Some(content) = &self.content {
+            egui::ScrollArea::both()
+                .auto_shrink([false, false])
+                .show(ui, |ui| {
+                    ui.add(egui::TextEdit::multiline(&mut content.as_str()).code_editor());
+                });
+        } else {
+            ui.centered_and_justified(|ui| ui.label(&config.labels.no_preview_available));
+        }
+    }
+
+    /// Starts loading the preview of the given path.
+    /// The preview is loaded via a separate thread if `load_via_thread` is enabled.
+    fn load(&mut self, config: &FileDialogConfig, path: Option<&Path>) {
+        self.path = path.map(Path::to_path_buf);
+        self.content = None;
+        self.content_recv = None;
+
+        let Some(path) = path else {
+            return;
+        };
+
+        if !config.load_via_thread {
+            self.content = load_preview(&*config.file_system, path);
+            return;
+        }
+
+        let (tx, rx) = mpsc::channel();
+
+        let file_system = config.file_system.clone();
+        let p = path.to_path_buf();
+        thread::spawn(move || {
+            let _ = tx.send(load_preview(&*file_system, &p));
+        });
+
+        self.content_recv = Some(Arc::new(Mutex::new(rx)));
+    }
+
+    /// Checks if the preview finished loading on the other thread.
+    fn update_pending_state(&mut self) {
+        let Some(recv) = &self.content_recv else {
+            return;
+        };
+
+        let value = recv.lock().try_recv();
+        match value {
+            Ok(content) => {
+                self.content = content;
+                self.content_recv = None;
+            }
+            Err(mpsc::TryRecvError::Disconnected) => {
+                self.content_recv = None;
+            }
+            Err(mpsc::TryRecvError::Empty) => {}
+        }
+    }
+}
+
+/// Loads the first lines of the given file.
+/// Returns None if the file is too large or is not a valid UTF-8 text file.
+fn load_preview(file_system: &dyn FileSystem, path: &Path) -> Option<String> {
+    let size = file_system.metadata(path).ok()?.size?;
+
+    if size > MAX_FILE_SIZE {
+        return None;
+    }
+
+    let text = file_system
+        .load_text_file_preview(path, usize::try_from(size).unwrap_or(usize::MAX))
+        .ok()?;
+
+    Some(text.lines().take(MAX_LINES).collect::<Vec<_>>().join("\n"))
+}
@@ -0,0 +1,91 @@
+//! Formatting of file sizes.
+
+/// The unit system used to display file sizes.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum SizeUnits {
+    /// IEC binary units based on powers of 1024, like KiB, MiB and GiB.
+    #[default]
+    Binary,
+    /// SI decimal units based on powers of 1000, like KB, MB and GB.
+    Decimal,
+}
+
+impl SizeUnits {
+    /// Returns the number of bytes of the first unit and the names of the units.
+    const fn units(self) -> (u64, [&'static str; 4]) {
+        match self {
+            Self::Binary => (1024, ["KiB", "MiB", "GiB", "TiB"]),
+            Self::Decimal => (1000, ["KB", "MB", "GB", "TB"]),
+        }
+    }
+}
+
+/// Formats a file size in bytes into a human-readable string using the given unit system,
+/// for example `1.50 MiB` or `1.57 MB`. Sizes below one kilobyte are displayed in bytes.
+///
+/// # Examples
+///
+/// ```
+/// use egui_file_dialog::{format_size, SizeUnits};
+///
+/// assert_eq!(format_size(1023, SizeUnits::Binary), "1023 B");
+/// assert_eq!(format_size(1024, SizeUnits::Binary), "1.00 KiB");
+/// assert_eq!(format_size(1024, SizeUnits::Decimal), "1.02 KB");
+/// ```
+pub fn format_size(bytes: u64, units: SizeUnits) -> String {
+    let (base, names) = units.units();
+
+    if bytes < base {
+        return format!("{bytes} B");
+    }
+
+    let base = base as f64;
+    let mut value = bytes as f64 / base;
+    let mut name = names[0];
+
+    for next in &names[1..] {
+        // The value is compared after rounding it to the displayed precision,
+        // so that for example 1023.999 KiB is displayed as 1.00 MiB
+        if (value * 100.0).round() / 100.0 < base {
+            break;
+        }
+
+        value /= base;
+        name = next;
+    }
+
+    format!("{value:.2} {name}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn binary_units() {
+        assert_eq!(format_size(0, SizeUnits::Binary), "0 B");
+        assert_eq!(format_size(1023, SizeUnits::Binary), "1023 B");
+        assert_eq!(format_size(1024, SizeUnits::Binary), "1.00 KiB");
+        assert_eq!(format_size(1536, SizeUnits::Binary), "1.50 KiB");
+        assert_eq!(format_size(1024 * 1024 - 1, SizeUnits::Binary), "1.00 MiB");
+        assert_eq!(
+            format_size(1024 * 1024 - 6, SizeUnits::Binary),
+            "1023.99 KiB"
+        );
+        assert_eq!(format_size(1024 * 1024, SizeUnits::Binary), "1.00 MiB");
+        assert_eq!(format_size(5 << 40, SizeUnits::Binary), "5.00 TiB");
+        assert_eq!(format_size(2048 << 40, SizeUnits::Binary), "2048.00 TiB");
+    }
+
+    #[test]
+    fn decimal_units() {
+        assert_eq!(format_size(0, SizeUnits::Decimal), "0 B");
+        assert_eq!(format_size(999, SizeUnits::Decimal), "999 B");
+        assert_eq!(format_size(1000, SizeUnits::Decimal), "1.00 KB");
+        assert_eq!(format_size(1023, SizeUnits::Decimal), "1.02 KB");
+        assert_eq!(format_size(1024, SizeUnits::Decimal), "1.02 KB");
+        assert_eq!(format_size(1_500_000, SizeUnits::Decimal), "1.50 MB");
+        assert_eq!(format_size(3_000_000_000, SizeUnits::Decimal), "3.00 GB");
+        assert_eq!(format_size(999_999, SizeUnits::Decimal), "1.00 MB");
+    }
+}
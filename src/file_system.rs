@@ -1,4 +1,5 @@
 use std::io::{self, Read};
+use std::ops::ControlFlow;
 use std::path::{Path, PathBuf};
 
 use crate::data::{Disks, Metadata, UserDirectories};
@@ -40,9 +41,44 @@ pub trait FileSystem {
     /// Returns true if the path exists and is a file
     fn is_file(&self, path: &Path) -> bool;
 
+    /// Returns true if the path exists
+    ///
+    /// This is used to ask the user before overwriting an existing item.
+    /// By default, only directories and files are considered to exist. Implementations that
+    /// can contain other items, like broken symbolic links or sockets, should override this
+    /// method, so that these items are not silently replaced.
+    fn exists(&self, path: &Path) -> bool {
+        self.is_dir(path) || self.is_file(path)
+    }
+
     /// Gets the children of a directory
+    ///
+    /// Returns an error if one of the children could not be read, so that no children are
+    /// silently missing. Use `read_dir_incremental` to get the children that could be read.
     fn read_dir(&self, path: &Path) -> io::Result<Vec<PathBuf>>;
 
+    /// Gets the children of a directory one by one and passes each of them to `on_entry`,
+    /// so that the progress of a slow directory can be displayed while it is read.
+    /// Reading stops early if `on_entry` returns `ControlFlow::Break`.
+    ///
+    /// An entry that could not be read is passed as an error, while an error returned by
+    /// this function means that the directory itself could not be read.
+    ///
+    /// By default, the children returned by `read_dir` are passed one by one.
+    fn read_dir_incremental(
+        &self,
+        path: &Path,
+        on_entry: &mut dyn FnMut(io::Result<PathBuf>) -> ControlFlow<()>,
+    ) -> io::Result<()> {
+        for entry in self.read_dir(path)? {
+            if on_entry(Ok(entry)).is_break() {
+                break;
+            }
+        }
+
+        Ok(())
+    }
+
     /// List out the disks in the system
     fn get_disks(&self, canonicalize_paths: bool) -> Disks;
 
@@ -65,6 +101,42 @@ pub trait FileSystem {
             "load_text_file_preview not implemented.".to_string(),
         ))
     }
+
+    /// Reads the whole content of a text file
+    ///
+    /// By default, `load_text_file_preview` is used without a limit.
+    fn read_to_string(&self, path: &Path) -> io::Result<String> {
+        self.load_text_file_preview(path, usize::MAX)
+    }
+
+    /// Returns the canonical, absolute form of the path
+    fn canonicalize(&self, _path: &Path) -> io::Result<PathBuf> {
+        Err(std::io::Error::new(
+            std::io::ErrorKind::Unsupported,
+            "canonicalize not implemented.".to_string(),
+        ))
+    }
+
+    /// Renames a file or directory
+    fn rename(&self, _from: &Path, _to: &Path) -> io::Result<()> {
+        Err(std::io::Error::new(
+            std::io::ErrorKind::Unsupported,
+            "rename not implemented.".to_string(),
+        ))
+    }
+
+    /// Removes a file or an empty directory
+    fn remove(&self, _path: &Path) -> io::Result<()> {
+        Err(std::io::Error::new(
+            std::io::ErrorKind::Unsupported,
+            "remove not implemented.".to_string(),
+        ))
+    }
+
+    /// Returns true if no directories can be created inside the given directory
+    fn is_read_only(&self, _path: &Path) -> bool {
+        false
+    }
 }
 
 impl std::fmt::Debug for dyn FileSystem + Send + Sync {
@@ -85,6 +157,15 @@ impl FileSystem for NativeFileSystem {
         metadata.last_modified = md.modified().ok();
         metadata.created = md.created().ok();
         metadata.file_type = Some(format!("{:?}", md.file_type()));
+        metadata = metadata.with_executable(md.is_file() && is_executable(path, &md));
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::MetadataExt;
+            metadata = metadata
+                .with_permissions(md.mode(), crate::permissions::is_read_only(&md))
+                .with_owner_ids(md.uid(), md.gid());
+        }
 
         Ok(metadata)
     }
@@ -97,34 +178,54 @@ impl FileSystem for NativeFileSystem {
         path.is_file()
     }
 
+    fn exists(&self, path: &Path) -> bool {
+        // Unlike `Path::exists`, this does not follow symbolic links, so that broken links
+        // are detected as well
+        std::fs::symlink_metadata(path).is_ok()
+    }
+
     fn read_dir(&self, path: &Path) -> io::Result<Vec<PathBuf>> {
-        Ok(std::fs::read_dir(path)?
-            .filter_map(Result::ok)
-            .map(|entry| entry.path())
-            .collect())
+        std::fs::read_dir(path)?
+            .map(|entry| entry.map(|entry| entry.path()))
+            .collect()
+    }
+
+    fn read_dir_incremental(
+        &self,
+        path: &Path,
+        on_entry: &mut dyn FnMut(io::Result<PathBuf>) -> ControlFlow<()>,
+    ) -> io::Result<()> {
+        for entry in std::fs::read_dir(path)? {
+            if on_entry(entry.map(|entry| entry.path())).is_break() {
+                break;
+            }
+        }
+
+        Ok(())
     }
 
     fn load_text_file_preview(&self, path: &Path, max_chars: usize) -> io::Result<String> {
-        let mut file = std::fs::File::open(path)?;
-        let mut chunk = [0; 96]; // Temporary buffer
-        let mut buffer = String::new();
-
-        // Add the first chunk to the buffer as text
-        let mut total_read = 0;
-
-        // Continue reading if needed
-        while total_read < max_chars {
-            let bytes_read = file.read(&mut chunk)?;
-            if bytes_read == 0 {
-                break; // End of file
+        let mut bytes = Vec::new();
+        std::fs::File::open(path)?
+            .take(u64::try_from(max_chars).unwrap_or(u64::MAX))
+            .read_to_end(&mut bytes)?;
+
+        match String::from_utf8(bytes) {
+            Ok(text) => Ok(text),
+            // The last character was cut off by the limit
+            Err(err) if err.utf8_error().error_len().is_none() => {
+                let valid = err.utf8_error().valid_up_to();
+                let mut bytes = err.into_bytes();
+                bytes.truncate(valid);
+
+                String::from_utf8(bytes).map_err(|_| io::Error::from(io::ErrorKind::InvalidData))
             }
-            let chars_read: String = String::from_utf8(chunk[..bytes_read].to_vec())
-                .map_err(|_| io::Error::from(io::ErrorKind::InvalidData))?;
-            total_read += chars_read.len();
-            buffer.push_str(&chars_read);
+            Err(_) => Err(io::Error::from(io::ErrorKind::InvalidData)),
         }
+    }
 
-        Ok(buffer.to_string())
+    fn read_to_string(&self, path: &Path) -> io::Result<String> {
+        std::fs::read_to_string(path)
     }
 
     fn get_disks(&self, canonicalize_paths: bool) -> Disks {
@@ -141,15 +242,25 @@ impl FileSystem for NativeFileSystem {
 
     fn user_dirs(&self, canonicalize_paths: bool) -> Option<UserDirectories> {
         if let Some(dirs) = directories::UserDirs::new() {
-            return Some(UserDirectories::new(
-                UserDirectories::canonicalize(Some(dirs.home_dir()), canonicalize_paths),
-                UserDirectories::canonicalize(dirs.audio_dir(), canonicalize_paths),
-                UserDirectories::canonicalize(dirs.desktop_dir(), canonicalize_paths),
-                UserDirectories::canonicalize(dirs.document_dir(), canonicalize_paths),
-                UserDirectories::canonicalize(dirs.download_dir(), canonicalize_paths),
-                UserDirectories::canonicalize(dirs.picture_dir(), canonicalize_paths),
-                UserDirectories::canonicalize(dirs.video_dir(), canonicalize_paths),
-            ));
+            return Some(
+                UserDirectories::new(
+                    UserDirectories::canonicalize(Some(dirs.home_dir()), canonicalize_paths),
+                    UserDirectories::canonicalize(dirs.audio_dir(), canonicalize_paths),
+                    UserDirectories::canonicalize(dirs.desktop_dir(), canonicalize_paths),
+                    UserDirectories::canonicalize(dirs.document_dir(), canonicalize_paths),
+                    UserDirectories::canonicalize(dirs.download_dir(), canonicalize_paths),
+                    UserDirectories::canonicalize(dirs.picture_dir(), canonicalize_paths),
+                    UserDirectories::canonicalize(dirs.video_dir(), canonicalize_paths),
+                )
+                .with_public_dir(UserDirectories::canonicalize(
+                    dirs.public_dir(),
+                    canonicalize_paths,
+                ))
+                .with_template_dir(UserDirectories::canonicalize(
+                    dirs.template_dir(),
+                    canonicalize_paths,
+                )),
+            );
         }
 
         None
@@ -158,27 +269,218 @@ impl FileSystem for NativeFileSystem {
     fn current_dir(&self) -> io::Result<PathBuf> {
         std::env::current_dir()
     }
+
+    fn canonicalize(&self, path: &Path) -> io::Result<PathBuf> {
+        dunce::canonicalize(path)
+    }
+
+    fn rename(&self, from: &Path, to: &Path) -> io::Result<()> {
+        std::fs::rename(from, to)
+    }
+
+    fn remove(&self, path: &Path) -> io::Result<()> {
+        // Links to directories are removed like files, without touching their target
+        if std::fs::symlink_metadata(path)?.is_dir() {
+            std::fs::remove_dir(path)
+        } else {
+            std::fs::remove_file(path)
+        }
+    }
 }
 
-#[cfg(windows)]
+/// Checks if the two paths point to the same location without accessing the file system.
+///
+/// The paths are compared component-wise, so trailing separators are ignored.
+/// On Windows, the comparison is case-insensitive, so that `c:\Users` and `C:\users`
+/// are equal, like they are on NTFS.
+pub fn paths_equal(a: &Path, b: &Path) -> bool {
+    if a == b {
+        return true;
+    }
+
+    #[cfg(windows)]
+    return paths_equal_ignore_case(a, b);
+
+    #[cfg(not(windows))]
+    false
+}
+
+/// Compares the components of the two paths case-insensitively.
+#[cfg(any(windows, test))]
+fn paths_equal_ignore_case(a: &Path, b: &Path) -> bool {
+    let lowercase = |c: std::path::Component| c.as_os_str().to_string_lossy().to_lowercase();
+
+    a.components().count() == b.components().count()
+        && a.components()
+            .zip(b.components())
+            .all(|(a, b)| lowercase(a) == lowercase(b))
+}
+
+/// Checks if the file is executable.
+/// On Unix, a file is executable if any of its execute permissions is set.
+#[cfg(unix)]
+fn is_executable(_path: &Path, metadata: &std::fs::Metadata) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    metadata.permissions().mode() & 0o111 != 0
+}
+
+/// Checks if the file is executable.
+/// On other platforms, the extension of the file is used, like `.exe` or `.bat`.
+#[cfg(not(unix))]
+fn is_executable(path: &Path, _metadata: &std::fs::Metadata) -> bool {
+    has_executable_extension(path)
+}
+
+/// Checks if the path has the extension of a file that Windows can execute.
+#[cfg(any(not(unix), test))]
+fn has_executable_extension(path: &Path) -> bool {
+    const EXTENSIONS: [&str; 4] = ["exe", "bat", "cmd", "com"];
+
+    path.extension()
+        .is_some_and(|ext| EXTENSIONS.iter().any(|e| ext.eq_ignore_ascii_case(e)))
+}
+
+/// Windows file attribute that marks a file or directory as hidden.
+#[cfg(any(windows, test))]
+const FILE_ATTRIBUTE_HIDDEN: u32 = 0x2;
+
+/// Checks if the path is hidden.
+/// A path is hidden if its name starts with a dot, like `.DS_Store`, or if it is marked as
+/// hidden by the operating system. This is the hidden file attribute on Windows, used for
+/// example by `desktop.ini` and `Thumbs.db`, and the `UF_HIDDEN` flag on macOS.
 fn is_path_hidden(path: &Path) -> bool {
+    is_dotfile(path) || has_hidden_attribute(path)
+}
+
+/// Checks if the file name of the path starts with a dot.
+fn is_dotfile(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| name.starts_with('.'))
+}
+
+#[cfg(windows)]
+fn has_hidden_attribute(path: &Path) -> bool {
     use std::os::windows::fs::MetadataExt;
 
-    std::fs::metadata(path).is_ok_and(|metadata| metadata.file_attributes() & 0x2 > 0)
+    std::fs::metadata(path).is_ok_and(|metadata| is_hidden_attribute(metadata.file_attributes()))
 }
 
-#[cfg(not(windows))]
-fn is_path_hidden(path: &Path) -> bool {
-    let Some(file_name) = path.file_name() else {
-        return false;
-    };
-    let Some(s) = file_name.to_str() else {
-        return false;
-    };
-
-    if s.starts_with('.') {
-        return true;
-    }
+#[cfg(target_os = "macos")]
+fn has_hidden_attribute(path: &Path) -> bool {
+    use std::os::macos::fs::MetadataExt;
 
+    std::fs::symlink_metadata(path).is_ok_and(|metadata| is_hidden_flag(metadata.st_flags()))
+}
+
+#[cfg(not(any(windows, target_os = "macos")))]
+const fn has_hidden_attribute(_path: &Path) -> bool {
     false
 }
+
+/// Checks if the given Windows file attributes contain the hidden attribute.
+#[cfg(any(windows, test))]
+const fn is_hidden_attribute(attributes: u32) -> bool {
+    attributes & FILE_ATTRIBUTE_HIDDEN != 0
+}
+
+/// BSD file flag that marks a file or directory as hidden on macOS.
+#[cfg(any(target_os = "macos", test))]
+const UF_HIDDEN: u32 = 0x8000;
+
+/// Checks if the given macOS file flags contain the hidden flag.
+#[cfg(any(target_os = "macos", test))]
+const fn is_hidden_flag(flags: u32) -> bool {
+    flags & UF_HIDDEN != 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dotfiles_are_hidden() {
+        assert!(is_dotfile(Path::new("/home/user/.config")));
+        assert!(is_dotfile(Path::new(".gitignore")));
+        assert!(!is_dotfile(Path::new("/home/user/desktop.ini")));
+        assert!(!is_dotfile(Path::new("/")));
+    }
+
+    #[test]
+    fn paths_equal_ignoring_case() {
+        assert!(paths_equal_ignore_case(
+            Path::new("/Users/Foo/"),
+            Path::new("/users/foo")
+        ));
+        assert!(!paths_equal_ignore_case(
+            Path::new("/users/foo"),
+            Path::new("/users/foo/bar")
+        ));
+        assert!(!paths_equal_ignore_case(
+            Path::new("/users/foo"),
+            Path::new("/users/fob")
+        ));
+    }
+
+    #[test]
+    #[cfg(windows)]
+    fn paths_equal_drive_letter() {
+        assert!(paths_equal(Path::new("c:\\"), Path::new("C:\\")));
+        assert!(paths_equal(
+            Path::new("c:\\Users\\Foo"),
+            Path::new("C:\\users\\foo\\")
+        ));
+        assert!(!paths_equal(Path::new("C:\\"), Path::new("D:\\")));
+    }
+
+    #[test]
+    #[cfg(not(windows))]
+    fn paths_equal_case_sensitive() {
+        assert!(paths_equal(Path::new("/home/foo/"), Path::new("/home/foo")));
+        assert!(!paths_equal(Path::new("/home/Foo"), Path::new("/home/foo")));
+    }
+
+    #[test]
+    fn hidden_attribute() {
+        // FILE_ATTRIBUTE_HIDDEN | FILE_ATTRIBUTE_SYSTEM, as used by `desktop.ini`
+        assert!(is_hidden_attribute(0x2 | 0x4));
+        assert!(is_hidden_attribute(0x2));
+        // FILE_ATTRIBUTE_ARCHIVE
+        assert!(!is_hidden_attribute(0x20));
+        assert!(!is_hidden_attribute(0));
+    }
+
+    #[test]
+    fn hidden_flag() {
+        assert!(is_hidden_flag(0x8000));
+        // UF_HIDDEN | UF_NODUMP
+        assert!(is_hidden_flag(0x8000 | 0x1));
+        assert!(!is_hidden_flag(0x1));
+        assert!(!is_hidden_flag(0));
+    }
+
+    #[test]
+    fn preview_multibyte_text() -> io::Result<()> {
+        let dir = std::env::temp_dir().join(format!("egui-file-dialog-{}", std::process::id()));
+        std::fs::create_dir_all(&dir)?;
+        let path = dir.join("umlauts.txt");
+        std::fs::write(&path, "ä".repeat(100))?;
+
+        // The limit cuts the last character in half, which is removed
+        let preview = NativeFileSystem.load_text_file_preview(&path, 101)?;
+        assert_eq!(preview, "ä".repeat(50));
+        assert_eq!(NativeFileSystem.read_to_string(&path)?, "ä".repeat(100));
+
+        std::fs::remove_file(&path)?;
+        Ok(())
+    }
+
+    #[test]
+    fn executable_extensions() {
+        assert!(has_executable_extension(Path::new("C:\\tools\\setup.exe")));
+        assert!(has_executable_extension(Path::new("build.BAT")));
+        assert!(has_executable_extension(Path::new("run.cmd")));
+        assert!(!has_executable_extension(Path::new("notes.txt")));
+        assert!(!has_executable_extension(Path::new("exe")));
+    }
+}
@@ -0,0 +1,298 @@
+//! Minimal glob matcher used to filter the entries of a directory by their file name.
+
+/// A compiled glob pattern.
+///
+/// The following syntax is supported:
+///   - `*` matches any number of characters
+///   - `?` matches exactly one character
+///   - `[abc]`, `[a-z]` match one of the given characters, `[!abc]` matches any other character
+///   - `{a,b}` matches one of the comma separated alternatives
+///
+/// Matching is case-insensitive on Windows and case-sensitive on all other platforms.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Glob {
+    /// The alternatives created by expanding the braces of the pattern.
+    alternatives: Vec<Vec<Token>>,
+    /// If the pattern and the file names are compared case-insensitively.
+    case_insensitive: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    /// A literal character.
+    Char(char),
+    /// `?`
+    AnyChar,
+    /// `*`
+    AnyString,
+    /// `[...]` containing the inclusive character ranges.
+    Class {
+        negated: bool,
+        ranges: Vec<(char, char)>,
+    },
+}
+
+impl Token {
+    /// Checks if the token matches the given character.
+    /// Must not be called with `Token::AnyString`.
+    fn matches(&self, c: char) -> bool {
+        match self {
+            Self::Char(expected) => *expected == c,
+            Self::AnyChar => true,
+            Self::AnyString => false,
+            Self::Class { negated, ranges } => {
+                ranges
+                    .iter()
+                    .any(|(start, end)| (*start..=*end).contains(&c))
+                    != *negated
+            }
+        }
+    }
+}
+
+impl Glob {
+    /// Compiles the given pattern using the case sensitivity of the current platform.
+    pub fn new(pattern: &str) -> Self {
+        Self::with_case_sensitivity(pattern, cfg!(windows))
+    }
+
+    /// Compiles the given pattern using the case sensitivity of the current platform.
+    /// Braces are treated as literal characters instead of alternatives.
+    pub fn without_braces(pattern: &str) -> Self {
+        Self::compile(pattern, cfg!(windows), false)
+    }
+
+    /// Compiles the given pattern.
+    pub fn with_case_sensitivity(pattern: &str, case_insensitive: bool) -> Self {
+        Self::compile(pattern, case_insensitive, true)
+    }
+
+    fn compile(pattern: &str, case_insensitive: bool, expand: bool) -> Self {
+        let pattern = if case_insensitive {
+            pattern.to_lowercase()
+        } else {
+            pattern.to_string()
+        };
+
+        let alternatives = if expand {
+            expand_braces(&pattern)
+        } else {
+            vec![pattern]
+        };
+
+        Self {
+            alternatives: alternatives.iter().map(|p| parse_tokens(p)).collect(),
+            case_insensitive,
+        }
+    }
+
+    /// Checks if the given file name matches the pattern.
+    pub fn is_match(&self, name: &str) -> bool {
+        let name: Vec<char> = if self.case_insensitive {
+            name.to_lowercase().chars().collect()
+        } else {
+            name.chars().collect()
+        };
+
+        self.alternatives
+            .iter()
+            .any(|tokens| match_tokens(tokens, &name))
+    }
+}
+
+/// Expands the first top level brace group of the pattern recursively.
+/// For example, `*.tar.{gz,zst}` is expanded to `*.tar.gz` and `*.tar.zst`.
+/// Braces without a matching closing brace are treated as literal characters.
+fn expand_braces(pattern: &str) -> Vec<String> {
+    let Some(start) = pattern.find('{') else {
+        return vec![pattern.to_string()];
+    };
+
+    let mut depth = 0;
+    let mut alternatives = Vec::new();
+    let mut alternative_start = start + 1;
+
+    for (i, c) in pattern.char_indices().skip_while(|(i, _)| *i <= start) {
+        match c {
+            '{' => depth += 1,
+            '}' if depth > 0 => depth -= 1,
+            ',' if depth == 0 => {
+                alternatives.push(&pattern[alternative_start..i]);
+                alternative_start = i + 1;
+            }
+            '}' => {
+                alternatives.push(&pattern[alternative_start..i]);
+
+                let prefix = &pattern[..start];
+                let suffix = &pattern[i + 1..];
+
+                return alternatives
+                    .iter()
+                    .flat_map(|alternative| {
+                        expand_braces(&format!("{prefix}{alternative}{suffix}"))
+                    })
+                    .collect();
+            }
+            _ => {}
+        }
+    }
+
+    // No closing brace, so the opening brace is a literal character
+    let (prefix, suffix) = pattern.split_at(start + 1);
+    expand_braces(suffix)
+        .into_iter()
+        .map(|s| format!("{prefix}{s}"))
+        .collect()
+}
+
+/// Parses a pattern without braces into tokens.
+fn parse_tokens(pattern: &str) -> Vec<Token> {
+    let chars: Vec<char> = pattern.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        match chars[i] {
+            '*' => {
+                // Multiple stars behave like a single star
+                if tokens.last() != Some(&Token::AnyString) {
+                    tokens.push(Token::AnyString);
+                }
+            }
+            '?' => tokens.push(Token::AnyChar),
+            '[' => {
+                if let Some((token, end)) = parse_class(&chars, i) {
+                    tokens.push(token);
+                    i = end;
+                } else {
+                    tokens.push(Token::Char('['));
+                }
+            }
+            c => tokens.push(Token::Char(c)),
+        }
+
+        i += 1;
+    }
+
+    tokens
+}
+
+/// Parses the character class starting at the given index.
+/// Returns the token and the index of the closing bracket,
+/// or None if the class is not closed.
+fn parse_class(chars: &[char], start: usize) -> Option<(Token, usize)> {
+    let mut i = start + 1;
+
+    let negated = matches!(chars.get(i), Some('!' | '^'));
+    if negated {
+        i += 1;
+    }
+
+    let mut ranges = Vec::new();
+
+    // A closing bracket directly after the opening bracket is a literal character
+    if chars.get(i) == Some(&']') {
+        ranges.push((']', ']'));
+        i += 1;
+    }
+
+    while i < chars.len() {
+        match (chars[i], chars.get(i + 1), chars.get(i + 2)) {
+            (']', _, _) => return Some((Token::Class { negated, ranges }, i)),
+            (first, Some('-'), Some(&last)) if last != ']' => {
+                ranges.push((first, last));
+                i += 3;
+            }
+            (c, _, _) => {
+                ranges.push((c, c));
+                i += 1;
+            }
+        }
+    }
+
+    None
+}
+
+/// Checks if the tokens match the whole name.
+/// Backtracks to the last `*` if a token does not match.
+fn match_tokens(tokens: &[Token], name: &[char]) -> bool {
+    let mut t = 0;
+    let mut n = 0;
+    // Position of the last star token and the name index it is currently matched up to
+    let mut star: Option<(usize, usize)> = None;
+
+    while n < name.len() {
+        match tokens.get(t) {
+            Some(Token::AnyString) => {
+                star = Some((t, n));
+                t += 1;
+                continue;
+            }
+            Some(token) if token.matches(name[n]) => {
+                t += 1;
+                n += 1;
+                continue;
+            }
+            _ => {}
+        }
+
+        // Let the last star consume one more character
+        let Some((star_t, star_n)) = star else {
+            return false;
+        };
+
+        t = star_t + 1;
+        n = star_n + 1;
+        star = Some((star_t, n));
+    }
+
+    tokens[t..].iter().all(|token| *token == Token::AnyString)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn is_match(pattern: &str, name: &str) -> bool {
+        Glob::with_case_sensitivity(pattern, false).is_match(name)
+    }
+
+    #[test]
+    fn wildcards() {
+        assert!(is_match("*.txt", "notes.txt"));
+        assert!(is_match("*.txt", ".txt"));
+        assert!(!is_match("*.txt", "notes.txt.bak"));
+        assert!(is_match("a*b*c", "axxbyyc"));
+        assert!(!is_match("a*b*c", "axxbyy"));
+        assert!(is_match("IMG_????.jpg", "IMG_0042.jpg"));
+        assert!(!is_match("IMG_????.jpg", "IMG_042.jpg"));
+        assert!(is_match("**", ""));
+    }
+
+    #[test]
+    fn braces() {
+        assert!(is_match("*.tar.{gz,zst}", "backup.tar.gz"));
+        assert!(is_match("*.tar.{gz,zst}", "backup.tar.zst"));
+        assert!(!is_match("*.tar.{gz,zst}", "backup.tar.xz"));
+        assert!(is_match("{a,b{c,d}}.txt", "bd.txt"));
+        assert!(is_match("{a.txt", "{a.txt"));
+    }
+
+    #[test]
+    fn character_classes() {
+        assert!(is_match("file[0-9].txt", "file7.txt"));
+        assert!(!is_match("file[0-9].txt", "filex.txt"));
+        assert!(is_match("file[!0-9].txt", "filex.txt"));
+        assert!(is_match("[abc]", "b"));
+        assert!(is_match("[]]", "]"));
+        assert!(is_match("[a-]", "-"));
+        assert!(is_match("[a", "[a"));
+    }
+
+    #[test]
+    fn case_sensitivity() {
+        assert!(!Glob::with_case_sensitivity("*.JPG", false).is_match("photo.jpg"));
+        assert!(Glob::with_case_sensitivity("*.JPG", true).is_match("photo.jpg"));
+        assert!(Glob::with_case_sensitivity("[A-Z]*", true).is_match("photo.jpg"));
+    }
+}
@@ -0,0 +1,132 @@
+use egui::text::{CCursor, CCursorRange};
+use std::path::{Path, PathBuf};
+
+use crate::ui::ui_error_label;
+use crate::FileDialogConfig;
+
+/// A small popup with a single text field to open a folder by entering or pasting its path.
+#[derive(Debug, Default)]
+pub struct GoToDialog {
+    /// If the dialog is currently open
+    open: bool,
+    /// If the update method is called for the first time after opening the dialog.
+    /// Used to focus the text input.
+    init: bool,
+    /// Buffer to hold the data of the path input
+    input: String,
+    /// This contains the error message if the entered path could not be opened
+    error: Option<String>,
+}
+
+impl GoToDialog {
+    /// Resets the dialog and opens it.
+    /// The input is initialized with the given directory, so that the user can
+    /// edit the current path.
+    pub fn open(&mut self, directory: Option<&Path>) {
+        self.reset();
+
+        self.open = true;
+        self.init = true;
+        self.input = directory
+            .map(|d| d.display().to_string())
+            .unwrap_or_default();
+    }
+
+    /// Closes and resets the dialog.
+    pub fn close(&mut self) {
+        self.reset();
+    }
+
+    /// Returns if the dialog is currently open
+    pub const fn is_open(&self) -> bool {
+        self.open
+    }
+
+    /// Displays the given error below the text input.
+    /// Used when the entered path could not be opened.
+    pub fn set_error(&mut self, error: String) {
+        self.error = Some(error);
+    }
+
+    /// Main update function of the dialog. Should be called in every frame
+    /// in which the dialog is to be displayed.
+    ///
+    /// The dialog is displayed at the top center of the given rect.
+    /// Returns the entered path if the user submitted the input by pressing enter.
+    pub fn update(
+        &mut self,
+        ctx: &egui::Context,
+        id: egui::Id,
+        rect: egui::Rect,
+        config: &FileDialogConfig,
+    ) -> Option<PathBuf> {
+        if !self.open {
+            return None;
+        }
+
+        let mut result = None;
+        let mut open = true;
+
+        egui::Window::new(&config.labels.go_to_folder)
+            .id(id)
+            .open(&mut open)
+            .order(egui::Order::Foreground)
+            .collapsible(false)
+            .resizable(false)
+            .pivot(egui::Align2::CENTER_TOP)
+            .fixed_pos(rect.center_top() + egui::vec2(0.0, 40.0))
+            .show(ctx, |ui| {
+                let response = ui.add(
+                    egui::TextEdit::singleline(&mut self.input)
+                        .desired_width(rect.width().clamp(100.0, 400.0)),
+                );
+
+                if self.init {
+                    response.request_focus();
+                    self.set_cursor_to_end(&response);
+                    self.init = false;
+                }
+
+                if response.changed() {
+                    self.error = None;
+                }
+
+                if response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                    result = Some(PathBuf::from(&self.input));
+
+                    // Keep the focus in case the path cannot be opened
+                    response.request_focus();
+                }
+
+                if let Some(err) = &self.error {
+                    ui_error_label(ui, &config.err_icon, err);
+                }
+            });
+
+        if !open || ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
+            self.close();
+        }
+
+        result
+    }
+
+    /// Sets the cursor of the text input to the end of the path.
+    fn set_cursor_to_end(&self, re: &egui::Response) {
+        if let Some(mut state) = egui::TextEdit::load_state(&re.ctx, re.id) {
+            state
+                .cursor
+                .set_char_range(Some(CCursorRange::one(CCursor::new(
+                    self.input.chars().count(),
+                ))));
+            state.store(&re.ctx, re.id);
+        }
+    }
+
+    /// Resets the dialog.
+    fn reset(&mut self) {
+        self.open = false;
+        self.init = false;
+        self.input.clear();
+        self.error = None;
+    }
+}
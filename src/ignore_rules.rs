@@ -0,0 +1,303 @@
+//! Minimal matcher for the rules of `.gitignore` files.
+
+use std::path::{Path, PathBuf};
+
+use crate::glob::Glob;
+use crate::FileSystem;
+
+/// The name of the files containing the ignore rules.
+const IGNORE_FILE_NAME: &str = ".gitignore";
+/// Ignore files larger than this size in bytes are not read.
+const MAX_IGNORE_FILE_SIZE: u64 = 1024 * 1024;
+
+/// The ignore rules that apply to the entries of a single directory.
+///
+/// The rules are read from the `.gitignore` files inside the directory and its parents, up to
+/// the root of the git repository. The following syntax is supported:
+///   - Blank lines and lines starting with `#` are ignored
+///   - `!` negates the pattern, so that a previously ignored path is included again
+///   - A trailing `/` only matches directories
+///   - A pattern containing a `/` is matched relative to the directory of the `.gitignore`,
+///     otherwise only against the name at any depth
+///   - `**` matches any number of directories
+///   - Everything else is matched using the syntax of `Glob`, except that braces are
+///     literal characters, like they are for git
+#[derive(Debug, Default)]
+pub struct IgnoreRules {
+    /// The ignore files, ordered from the outermost to the innermost directory.
+    files: Vec<IgnoreFile>,
+    /// If the directory itself is ignored, which means that all of its entries are ignored.
+    directory_ignored: bool,
+}
+
+/// The rules of a single ignore file.
+#[derive(Debug)]
+struct IgnoreFile {
+    /// The directory containing the ignore file. The rules are relative to this directory.
+    base: PathBuf,
+    rules: Vec<Rule>,
+}
+
+/// A single line of an ignore file.
+#[derive(Debug)]
+struct Rule {
+    /// If matching paths are included again.
+    negated: bool,
+    /// If the rule only matches directories.
+    dir_only: bool,
+    /// The components of the pattern, separated by `/`.
+    components: Vec<PatternComponent>,
+}
+
+#[derive(Debug)]
+enum PatternComponent {
+    /// `**`
+    AnyDirs,
+    /// A pattern matching a single name.
+    Name(Glob),
+}
+
+impl IgnoreRules {
+    /// Reads the ignore rules that apply to the entries of the given directory.
+    pub fn load(file_system: &dyn FileSystem, dir: &Path) -> Self {
+        let mut files = Vec::new();
+
+        for parent in dir.ancestors() {
+            let path = parent.join(IGNORE_FILE_NAME);
+
+            if file_system.is_file(&path) && !is_too_large(file_system, &path) {
+                if let Ok(text) = file_system.read_to_string(&path) {
+                    files.push(IgnoreFile::parse(parent, &text));
+                }
+            }
+
+            // Rules outside of the repository do not apply.
+            // The `.git` file is used by worktrees and submodules.
+            let git = parent.join(".git");
+            if file_system.is_dir(&git) || file_system.is_file(&git) {
+                break;
+            }
+        }
+
+        files.reverse();
+
+        let mut rules = Self {
+            files,
+            directory_ignored: false,
+        };
+        rules.directory_ignored = rules.is_directory_ignored(dir);
+
+        rules
+    }
+
+    /// Checks if the given entry of the directory is ignored.
+    pub fn is_ignored(&self, path: &Path, is_dir: bool) -> bool {
+        self.directory_ignored || self.matches(path, is_dir)
+    }
+
+    /// Checks if the directory or one of its parents inside the repository is ignored.
+    fn is_directory_ignored(&self, dir: &Path) -> bool {
+        let Some(root) = self.files.first().map(|file| file.base.as_path()) else {
+            return false;
+        };
+
+        dir.ancestors()
+            .take_while(|parent| *parent != root && parent.starts_with(root))
+            .any(|parent| self.matches(parent, true))
+    }
+
+    /// Checks if the rules ignore the given path. Later rules take precedence over earlier
+    /// rules and the rules of inner ignore files take precedence over outer ignore files.
+    fn matches(&self, path: &Path, is_dir: bool) -> bool {
+        let mut ignored = false;
+
+        for file in &self.files {
+            let Ok(relative) = path.strip_prefix(&file.base) else {
+                continue;
+            };
+
+            let names: Vec<String> = relative
+                .iter()
+                .map(|name| name.to_string_lossy().into_owned())
+                .collect();
+
+            for rule in &file.rules {
+                if (!rule.dir_only || is_dir) && match_components(&rule.components, &names) {
+                    ignored = !rule.negated;
+                }
+            }
+        }
+
+        ignored
+    }
+}
+
+impl IgnoreFile {
+    /// Parses the content of an ignore file located inside the given directory.
+    fn parse(base: &Path, text: &str) -> Self {
+        Self {
+            base: base.to_path_buf(),
+            rules: text.lines().filter_map(Rule::parse).collect(),
+        }
+    }
+}
+
+impl Rule {
+    /// Parses a single line of an ignore file.
+    /// Returns None if the line does not contain a pattern.
+    fn parse(line: &str) -> Option<Self> {
+        let line = line.trim_end();
+
+        if line.is_empty() || line.starts_with('#') {
+            return None;
+        }
+
+        let (negated, line) = line
+            .strip_prefix('!')
+            .map_or((false, line), |line| (true, line));
+
+        // A leading backslash escapes `#` and `!`
+        let line = line.strip_prefix('\\').unwrap_or(line);
+
+        let (dir_only, line) = line
+            .strip_suffix('/')
+            .map_or((false, line), |line| (true, line));
+
+        // Patterns without a slash match the name at any depth
+        let anchored = line.contains('/');
+        let line = line.strip_prefix('/').unwrap_or(line);
+
+        if line.is_empty() {
+            return None;
+        }
+
+        let mut components = Vec::new();
+        if !anchored {
+            components.push(PatternComponent::AnyDirs);
+        }
+
+        components.extend(line.split('/').map(|name| {
+            if name == "**" {
+                PatternComponent::AnyDirs
+            } else {
+                PatternComponent::Name(Glob::without_braces(name))
+            }
+        }));
+
+        Some(Self {
+            negated,
+            dir_only,
+            components,
+        })
+    }
+}
+
+/// Checks if the ignore file is too large to be read.
+/// Files of an unknown size are read.
+fn is_too_large(file_system: &dyn FileSystem, path: &Path) -> bool {
+    file_system
+        .metadata(path)
+        .ok()
+        .and_then(|metadata| metadata.size)
+        .is_some_and(|size| size > MAX_IGNORE_FILE_SIZE)
+}
+
+/// Checks if the pattern components match all names of the path.
+fn match_components(pattern: &[PatternComponent], names: &[String]) -> bool {
+    match pattern.split_first() {
+        None => names.is_empty(),
+        // A trailing `**` matches everything inside, but not the directory itself
+        Some((PatternComponent::AnyDirs, [])) => !names.is_empty(),
+        Some((PatternComponent::AnyDirs, rest)) => {
+            (0..=names.len()).any(|i| match_components(rest, &names[i..]))
+        }
+        Some((PatternComponent::Name(glob), rest)) => names
+            .split_first()
+            .is_some_and(|(name, names)| glob.is_match(name) && match_components(rest, names)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules(files: &[(&str, &str)]) -> IgnoreRules {
+        IgnoreRules {
+            files: files
+                .iter()
+                .map(|(base, text)| IgnoreFile::parse(Path::new(base), text))
+                .collect(),
+            directory_ignored: false,
+        }
+    }
+
+    #[test]
+    fn patterns() {
+        let rules = rules(&[(
+            "/repo",
+            "# build output\n\
+             target/\n\
+             *.log\n\
+             !keep.log\n\
+             /docs/*.html\n\
+             assets/**/*.psd\n\
+             \\#notes\n",
+        )]);
+
+        assert!(rules.matches(Path::new("/repo/target"), true));
+        assert!(rules.matches(Path::new("/repo/crates/a/target"), true));
+        assert!(!rules.matches(Path::new("/repo/target"), false));
+        assert!(rules.matches(Path::new("/repo/logs/debug.log"), false));
+        assert!(!rules.matches(Path::new("/repo/keep.log"), false));
+        assert!(rules.matches(Path::new("/repo/docs/index.html"), false));
+        assert!(!rules.matches(Path::new("/repo/src/docs/index.html"), false));
+        assert!(rules.matches(Path::new("/repo/assets/psd/a/b.psd"), false));
+        assert!(rules.matches(Path::new("/repo/assets/b.psd"), false));
+        assert!(rules.matches(Path::new("/repo/#notes"), false));
+        assert!(!rules.matches(Path::new("/repo/src/main.rs"), false));
+    }
+
+    #[test]
+    fn inner_files_take_precedence() {
+        let rules = rules(&[("/repo", "*.txt\n"), ("/repo/docs", "!readme.txt\n")]);
+
+        assert!(rules.matches(Path::new("/repo/docs/notes.txt"), false));
+        assert!(!rules.matches(Path::new("/repo/docs/readme.txt"), false));
+        assert!(rules.matches(Path::new("/repo/readme.txt"), false));
+    }
+
+    #[test]
+    fn entries_of_ignored_directories() {
+        let rules = rules(&[("/repo", "target/\n")]);
+
+        assert!(rules.is_directory_ignored(Path::new("/repo/target/debug")));
+        assert!(!rules.is_directory_ignored(Path::new("/repo/src")));
+        assert!(!rules.is_directory_ignored(Path::new("/repo")));
+    }
+
+    #[test]
+    fn braces_are_literal() {
+        let rules = rules(&[(
+            "/repo",
+            "{a,b}.txt
+",
+        )]);
+
+        assert!(rules.matches(Path::new("/repo/{a,b}.txt"), false));
+        assert!(!rules.matches(Path::new("/repo/a.txt"), false));
+    }
+
+    #[test]
+    fn load_multibyte_rules() {
+        // Long enough that the rule starts far behind the first bytes of the file
+        let comment = format!("# {}\n", "ä".repeat(100));
+        let file_system = crate::MemoryFileSystem::new()
+            .with_dir("/repo/.git")
+            .with_file("/repo/.gitignore", format!("{comment}*.lög\n"));
+
+        let rules = IgnoreRules::load(&file_system, Path::new("/repo"));
+
+        assert!(rules.is_ignored(Path::new("/repo/debug.lög"), false));
+        assert!(!rules.is_ignored(Path::new("/repo/debug.txt"), false));
+    }
+}
@@ -1,6 +1,6 @@
 #![cfg(feature = "information_view")]
 
-use crate::{DirectoryEntry, FileDialog, FileSystem, NativeFileSystem};
+use crate::{format_size, DirectoryEntry, FileDialog, FileSystem, NativeFileSystem, SizeUnits};
 use chrono::{DateTime, Local};
 use egui::ahash::{HashMap, HashMapExt};
 use egui::{Direction, Layout, Ui, Vec2};
@@ -249,6 +249,7 @@ impl InformationPanel {
 
         // Display metadata in a grid format
         let width = file_dialog.config_mut().right_panel_width.unwrap_or(100.0) / 2.0;
+        let size_units = file_dialog.config_mut().size_units;
 
         if let Some(item) = file_dialog.selected_entry() {
             // load file content and additional metadata if it's a new file
@@ -263,7 +264,7 @@ impl InformationPanel {
             ui.add_space(spacing);
 
             // show all metadata
-            self.display_meta_data(ui, file_dialog.get_window_id(), width, item);
+            self.display_meta_data(ui, file_dialog.get_window_id(), width, size_units, item);
         }
     }
 
@@ -363,7 +364,14 @@ impl InformationPanel {
         }
     }
 
-    fn display_meta_data(&self, ui: &mut Ui, id: egui::Id, width: f32, item: &DirectoryEntry) {
+    fn display_meta_data(
+        &self,
+        ui: &mut Ui,
+        id: egui::Id,
+        width: f32,
+        size_units: SizeUnits,
+        item: &DirectoryEntry,
+    ) {
         egui::ScrollArea::vertical()
             .id_salt(id.with("meta_data_scroll"))
             .show(ui, |ui| {
@@ -380,7 +388,7 @@ impl InformationPanel {
                         if let Some(size) = item.metadata().size {
                             ui.label("File Size: ");
                             if item.is_file() {
-                                ui.label(format_bytes(size));
+                                ui.label(format_size(size, size_units));
                             } else {
                                 ui.label("NAN");
                             }
@@ -411,29 +419,3 @@ impl InformationPanel {
             });
     }
 }
-
-/// Formats a file size (in bytes) into a human-readable string (e.g., KB, MB).
-///
-/// # Arguments
-/// - `bytes`: The file size in bytes.
-///
-/// # Returns
-/// A string representing the file size in an appropriate unit.
-fn format_bytes(bytes: u64) -> String {
-    const KB: u64 = 1024;
-    const MB: u64 = KB * 1024;
-    const GB: u64 = MB * 1024;
-    const TB: u64 = GB * 1024;
-
-    if bytes >= TB {
-        format!("{:.2} TB", bytes as f64 / TB as f64)
-    } else if bytes >= GB {
-        format!("{:.2} GB", bytes as f64 / GB as f64)
-    } else if bytes >= MB {
-        format!("{:.2} MB", bytes as f64 / MB as f64)
-    } else if bytes >= KB {
-        format!("{:.2} KB", bytes as f64 / KB as f64)
-    } else {
-        format!("{bytes} B")
-    }
-}
@@ -59,7 +59,7 @@
 //!         }
 //!
 //!         // Update the dialog and check if the user picked a file
-//!         if let Some(path) = self.file_dialog.update(ctx).picked() {
+//!         if let Some(path) = self.file_dialog.update(ctx, ui).selected() {
 //!             println!("Picked file: {:?}", path);
 //!         }
 //!     }
@@ -81,9 +81,16 @@
 //! | `new_folder` | Open the dialog to create a new folder | `CTRL` + `N` on linux/windows or `CMD` + `N` on macOS |
 //! | `edit_path` | Text edit the current path | `/` |
 //! | `home_edit_path` | Open the home directory and start text editing the path | `~` |
+//! | `go_to` | Open a popup to enter the path of a folder to open | `CTRL` + `L` on linux/windows or `CMD` + `L` on macOS |
 //! | `selection_up` | Move the selection one item up | `↑` |
 //! | `selection_down` | Move the selection one item down | `↓` |
+//! | `selection_first` | Select the first item | `Home` |
+//! | `selection_last` | Select the last item | `End` |
+//! | `selection_page_up` | Move the selection one page up | `Page Up` |
+//! | `selection_page_down` | Move the selection one page down | `Page Down` |
 //! | `select_all` | Select every item in the directory when using the file dialog to select multiple files and folders | `CTRL` + `A` on linux/windows or `CMD` + `A` on macOS |
+//! | `focus_next` | Move the keyboard focus to the next area of the dialog | `Tab` |
+//! | `focus_previous` | Move the keyboard focus to the previous area of the dialog | `SHIFT` + `Tab` |
 //!
 //! ### Customization
 //! Many things can be customized so that the dialog can be used in different situations. \
@@ -194,20 +201,49 @@
 
 #![warn(missing_docs)] // Let's keep the public API well documented!
 
+#[cfg(feature = "zip")]
+mod archive_file_system;
+mod background_lookup;
 mod config;
 mod create_directory_dialog;
 mod data;
+mod date;
+mod directory_item_counts;
+mod directory_sizes;
+#[cfg(feature = "eject")]
+mod eject;
 mod file_dialog;
+mod file_preview;
+mod file_size;
 mod file_system;
+mod glob;
+mod go_to_dialog;
+mod ignore_rules;
 /// Information panel showing the preview and metadata of the selected item
 pub mod information_panel;
+mod location_availability;
+mod memory_file_system;
 mod modals;
+#[cfg(feature = "open_externally")]
+mod open_externally;
+#[cfg(unix)]
+mod permissions;
+mod ui;
 
 pub use config::{
-    FileDialogConfig, FileDialogKeyBindings, FileDialogLabels, FileDialogStorage, IconFilter,
-    KeyBinding, OpeningMode, QuickAccess, QuickAccessPath,
+    Comparator, DetailsColumn, DetailsColumnState, FileDialogConfig, FileDialogKeyBindings,
+    FileDialogLabels, FileDialogStorage, FileSorting, IconFilter, KeyBinding, LeftPanelSection,
+    OpeningMode, PathFilter, PathValidator, Place, QuickAccess, QuickAccessPath, RenderMode,
+    SelectionValidator, SortComparator, ViewMode,
 };
 pub use data::{DirectoryEntry, Disk, Disks, Metadata, UserDirectories};
-pub use file_dialog::{DialogMode, DialogState, FileDialog};
+pub use file_dialog::{
+    CustomBottomUi, CustomRightPanelUi, CustomUiContext, DialogMode, DialogResult, DialogState,
+    DirectoryChangedCallback, DirectoryCreatedCallback, FileDialog, FileDialogResponse,
+};
 
+#[cfg(feature = "zip")]
+pub use archive_file_system::ArchiveFileSystem;
+pub use file_size::{format_size, SizeUnits};
 pub use file_system::{FileSystem, NativeFileSystem};
+pub use memory_file_system::MemoryFileSystem;
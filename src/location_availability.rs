@@ -0,0 +1,52 @@
+use std::path::Path;
+
+use crate::background_lookup::BackgroundLookup;
+use crate::FileDialogConfig;
+
+/// Cache of whether the paths listed in the left panel exist.
+/// The paths are checked lazily, only for paths that are requested.
+#[derive(Debug, Default)]
+pub struct LocationAvailability {
+    /// If the paths exist, mapped by the path.
+    locations: BackgroundLookup<bool>,
+}
+
+impl LocationAvailability {
+    /// Checks if the given path is available.
+    /// Paths that were not checked yet or that are still being checked are considered
+    /// available, so that locations that are slow to respond can still be opened.
+    pub fn is_available(&self, path: &Path) -> bool {
+        self.locations.get(path).unwrap_or(true)
+    }
+
+    /// Starts checking if the given path exists, unless it was already checked.
+    /// The path is checked via a separate thread if `load_via_thread` is enabled, because
+    /// the check blocks as long as a network mount is not responding.
+    pub fn request(&mut self, config: &FileDialogConfig, path: &Path) {
+        if self.locations.contains(path) {
+            return;
+        }
+
+        if !config.load_via_thread {
+            let available = config.file_system.is_dir(path);
+            self.locations.insert(path.to_path_buf(), available);
+            return;
+        }
+
+        let file_system = config.file_system.clone();
+        self.locations
+            .request(path, move |path, _| file_system.is_dir(path));
+    }
+
+    /// Receives the paths that finished checking on the other thread.
+    /// Returns true if there are still paths being checked.
+    pub fn update(&mut self) -> bool {
+        self.locations.update();
+        self.locations.is_pending()
+    }
+
+    /// Removes all cached paths, so that they are checked again when requested.
+    pub fn clear(&mut self) {
+        self.locations.clear();
+    }
+}
@@ -0,0 +1,494 @@
+use egui::mutex::Mutex;
+use std::collections::BTreeMap;
+use std::io;
+use std::path::{Component, Path, PathBuf};
+
+use crate::data::{Disks, Metadata, UserDirectories};
+use crate::FileSystem;
+
+/// Implementation of `FileSystem` that keeps all directories and files in memory.
+///
+/// This can be used to let the user browse data that does not exist on the native file
+/// system, or to test an application using the file dialog without touching the disk.
+/// The root directory `/` always exists. All paths are absolute after they are resolved
+/// against the current directory, and `.` and `..` components are resolved without
+/// following any links.
+///
+/// Directories created by the user via the dialog are added to the file system, and
+/// entries can be renamed and removed using `FileSystem::rename` and `FileSystem::remove`.
+///
+/// # Examples
+///
+/// ```
+/// use egui_file_dialog::{FileDialog, FileSystem, MemoryFileSystem};
+/// use std::path::Path;
+/// use std::sync::Arc;
+///
+/// let file_system = MemoryFileSystem::new()
+///     .with_dir("/home/user/documents")
+///     .with_file("/home/user/notes.txt", "Hello world")
+///     .with_current_dir("/home/user");
+///
+/// assert!(file_system.is_file(Path::new("notes.txt")));
+///
+/// let dialog = FileDialog::with_file_system(Arc::new(file_system));
+/// ```
+pub struct MemoryFileSystem {
+    /// All directories and files, mapped by their absolute path.
+    entries: Mutex<BTreeMap<PathBuf, MemoryEntry>>,
+    /// The directory relative paths are resolved against.
+    current_dir: PathBuf,
+}
+
+impl std::fmt::Debug for MemoryFileSystem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MemoryFileSystem")
+            .field("current_dir", &self.current_dir)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A single directory or file of a `MemoryFileSystem`.
+#[derive(Debug)]
+enum MemoryEntry {
+    Directory,
+    File(Vec<u8>),
+}
+
+impl Default for MemoryFileSystem {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MemoryFileSystem {
+    /// Creates a new file system that only contains the root directory `/`.
+    pub fn new() -> Self {
+        let root = PathBuf::from("/");
+        let mut entries = BTreeMap::new();
+        entries.insert(root.clone(), MemoryEntry::Directory);
+
+        Self {
+            entries: Mutex::new(entries),
+            current_dir: root,
+        }
+    }
+
+    /// Adds a directory, including all of its missing parent directories.
+    pub fn with_dir(self, path: impl AsRef<Path>) -> Self {
+        let path = self.resolve(path.as_ref());
+        self.insert(path, MemoryEntry::Directory);
+        self
+    }
+
+    /// Adds a file with the given content, including all of its missing parent directories.
+    /// An existing file with the same path is replaced.
+    pub fn with_file(self, path: impl AsRef<Path>, content: impl Into<Vec<u8>>) -> Self {
+        let path = self.resolve(path.as_ref());
+        self.insert(path, MemoryEntry::File(content.into()));
+        self
+    }
+
+    /// Sets the current directory, which is used to resolve relative paths.
+    /// The directory is created if it does not exist yet.
+    pub fn with_current_dir(mut self, path: impl AsRef<Path>) -> Self {
+        let path = self.resolve(path.as_ref());
+        self.insert(path.clone(), MemoryEntry::Directory);
+        self.current_dir = path;
+        self
+    }
+
+    /// Inserts the entry and all of its missing parent directories.
+    fn insert(&self, path: PathBuf, entry: MemoryEntry) {
+        let mut entries = self.entries.lock();
+
+        for parent in path.ancestors().skip(1) {
+            entries
+                .entry(parent.to_path_buf())
+                .or_insert(MemoryEntry::Directory);
+        }
+
+        entries.insert(path, entry);
+    }
+
+    /// Converts the path to an absolute path and resolves its `.` and `..` components.
+    /// The parent of the root directory is the root directory itself.
+    fn resolve(&self, path: &Path) -> PathBuf {
+        let mut resolved = if path.has_root() {
+            PathBuf::from("/")
+        } else {
+            self.current_dir.clone()
+        };
+
+        for component in path.components() {
+            match component {
+                Component::Normal(name) => resolved.push(name),
+                Component::ParentDir => {
+                    resolved.pop();
+                }
+                Component::CurDir | Component::RootDir | Component::Prefix(_) => {}
+            }
+        }
+
+        resolved
+    }
+
+    /// Calls the given function with the entry at the given path.
+    /// Returns None if the path does not exist.
+    fn with_entry<R>(&self, path: &Path, f: impl FnOnce(&MemoryEntry) -> R) -> Option<R> {
+        self.entries.lock().get(&self.resolve(path)).map(f)
+    }
+}
+
+impl FileSystem for MemoryFileSystem {
+    fn metadata(&self, path: &Path) -> io::Result<Metadata> {
+        self.with_entry(path, |entry| match entry {
+            MemoryEntry::Directory => Metadata::default(),
+            MemoryEntry::File(content) => {
+                Metadata::new(Some(content.len() as u64), None, None, None)
+            }
+        })
+        .ok_or_else(|| io::ErrorKind::NotFound.into())
+    }
+
+    fn is_dir(&self, path: &Path) -> bool {
+        self.with_entry(path, |entry| matches!(entry, MemoryEntry::Directory))
+            .unwrap_or(false)
+    }
+
+    fn is_file(&self, path: &Path) -> bool {
+        self.with_entry(path, |entry| matches!(entry, MemoryEntry::File(_)))
+            .unwrap_or(false)
+    }
+
+    fn exists(&self, path: &Path) -> bool {
+        self.with_entry(path, |_| ()).is_some()
+    }
+
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<PathBuf>> {
+        let path = self.resolve(path);
+        let entries = self.entries.lock();
+
+        match entries.get(&path) {
+            Some(MemoryEntry::Directory) => Ok(entries
+                .keys()
+                .filter(|p| p.parent() == Some(path.as_path()))
+                .cloned()
+                .collect()),
+            Some(MemoryEntry::File(_)) => Err(io::ErrorKind::NotADirectory.into()),
+            None => Err(io::ErrorKind::NotFound.into()),
+        }
+    }
+
+    fn get_disks(&self, _canonicalize_paths: bool) -> Disks {
+        Disks::new_empty()
+    }
+
+    fn is_path_hidden(&self, path: &Path) -> bool {
+        path.file_name()
+            .is_some_and(|name| name.to_string_lossy().starts_with('.'))
+    }
+
+    fn create_dir(&self, path: &Path) -> io::Result<()> {
+        let path = self.resolve(path);
+        let mut entries = self.entries.lock();
+
+        if entries.contains_key(&path) {
+            return Err(io::ErrorKind::AlreadyExists.into());
+        }
+
+        match path.parent().map(|parent| entries.get(parent)) {
+            Some(Some(MemoryEntry::Directory)) => {}
+            Some(Some(MemoryEntry::File(_))) => return Err(io::ErrorKind::NotADirectory.into()),
+            Some(None) | None => return Err(io::ErrorKind::NotFound.into()),
+        }
+
+        entries.insert(path, MemoryEntry::Directory);
+        drop(entries);
+
+        Ok(())
+    }
+
+    fn user_dirs(&self, _canonicalize_paths: bool) -> Option<UserDirectories> {
+        None
+    }
+
+    fn current_dir(&self) -> io::Result<PathBuf> {
+        Ok(self.current_dir.clone())
+    }
+
+    fn load_text_file_preview(&self, path: &Path, max_chars: usize) -> io::Result<String> {
+        Ok(self.read_to_string(path)?.chars().take(max_chars).collect())
+    }
+
+    fn read_to_string(&self, path: &Path) -> io::Result<String> {
+        self.with_entry(path, |entry| match entry {
+            MemoryEntry::Directory => Err(io::Error::from(io::ErrorKind::IsADirectory)),
+            MemoryEntry::File(content) => String::from_utf8(content.clone())
+                .map_err(|_| io::Error::from(io::ErrorKind::InvalidData)),
+        })
+        .ok_or_else(|| io::Error::from(io::ErrorKind::NotFound))?
+    }
+
+    fn canonicalize(&self, path: &Path) -> io::Result<PathBuf> {
+        let path = self.resolve(path);
+
+        if self.entries.lock().contains_key(&path) {
+            Ok(path)
+        } else {
+            Err(io::ErrorKind::NotFound.into())
+        }
+    }
+
+    /// Moves the entry and everything inside it to the new path. An existing file at the
+    /// new path is replaced, like on Unix.
+    fn rename(&self, from: &Path, to: &Path) -> io::Result<()> {
+        let from = self.resolve(from);
+        let to = self.resolve(to);
+        let mut entries = self.entries.lock();
+
+        let is_dir = match entries.get(&from) {
+            Some(MemoryEntry::Directory) => true,
+            Some(MemoryEntry::File(_)) => false,
+            None => return Err(io::ErrorKind::NotFound.into()),
+        };
+
+        if from == to {
+            return Ok(());
+        }
+
+        // The root directory cannot be moved and a directory cannot be moved into itself
+        if from.parent().is_none() || to.starts_with(&from) {
+            return Err(io::ErrorKind::InvalidInput.into());
+        }
+
+        match to.parent().map(|parent| entries.get(parent)) {
+            Some(Some(MemoryEntry::Directory)) => {}
+            Some(Some(MemoryEntry::File(_))) => return Err(io::ErrorKind::NotADirectory.into()),
+            Some(None) | None => return Err(io::ErrorKind::NotFound.into()),
+        }
+
+        match entries.get(&to) {
+            Some(MemoryEntry::Directory) => return Err(io::ErrorKind::AlreadyExists.into()),
+            Some(MemoryEntry::File(_)) if is_dir => return Err(io::ErrorKind::NotADirectory.into()),
+            Some(MemoryEntry::File(_)) | None => {}
+        }
+
+        let moved: Vec<PathBuf> = entries
+            .keys()
+            .filter(|path| path.starts_with(&from))
+            .cloned()
+            .collect();
+
+        for path in moved {
+            if let (Some(entry), Ok(relative)) = (entries.remove(&path), path.strip_prefix(&from)) {
+                entries.insert(to.join(relative), entry);
+            }
+        }
+        drop(entries);
+
+        Ok(())
+    }
+
+    fn remove(&self, path: &Path) -> io::Result<()> {
+        let path = self.resolve(path);
+        let mut entries = self.entries.lock();
+
+        match entries.get(&path) {
+            None => return Err(io::ErrorKind::NotFound.into()),
+            Some(MemoryEntry::Directory) if path.parent().is_none() => {
+                return Err(io::ErrorKind::InvalidInput.into())
+            }
+            Some(MemoryEntry::Directory)
+                if entries.keys().any(|p| p.parent() == Some(path.as_path())) =>
+            {
+                return Err(io::ErrorKind::DirectoryNotEmpty.into())
+            }
+            Some(_) => {}
+        }
+
+        entries.remove(&path);
+        drop(entries);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file_system() -> MemoryFileSystem {
+        MemoryFileSystem::new()
+            .with_dir("/home/user/documents")
+            .with_file("/home/user/notes.txt", "Hello world")
+            .with_current_dir("/home/user")
+    }
+
+    #[test]
+    fn resolve_paths() {
+        let fs = file_system();
+
+        assert_eq!(
+            fs.resolve(Path::new("a/./b/../c")),
+            Path::new("/home/user/a/c")
+        );
+        assert_eq!(fs.resolve(Path::new("/../..")), Path::new("/"));
+        assert_eq!(fs.resolve(Path::new("/home/")), Path::new("/home"));
+        assert_eq!(fs.resolve(Path::new("")), Path::new("/home/user"));
+    }
+
+    #[test]
+    fn parent_directories_are_created() {
+        let fs = file_system();
+
+        assert!(fs.is_dir(Path::new("/")));
+        assert!(fs.is_dir(Path::new("/home")));
+        assert!(fs.is_dir(Path::new("documents")));
+        assert!(fs.is_file(Path::new("/home/user/notes.txt")));
+        assert!(!fs.is_dir(Path::new("notes.txt")));
+        assert!(!fs.exists(Path::new("/home/other")));
+    }
+
+    #[test]
+    fn read_dir() {
+        let fs = file_system();
+
+        let Ok(children) = fs.read_dir(Path::new("/home/user")) else {
+            panic!("failed to read the directory");
+        };
+        assert_eq!(
+            children,
+            [
+                PathBuf::from("/home/user/documents"),
+                PathBuf::from("/home/user/notes.txt")
+            ]
+        );
+
+        let Err(err) = fs.read_dir(Path::new("notes.txt")) else {
+            panic!("a file can not be read as a directory");
+        };
+        assert_eq!(err.kind(), io::ErrorKind::NotADirectory);
+
+        let Err(err) = fs.read_dir(Path::new("/missing")) else {
+            panic!("a missing directory can not be read");
+        };
+        assert_eq!(err.kind(), io::ErrorKind::NotFound);
+    }
+
+    #[test]
+    fn create_dir() {
+        let fs = file_system();
+
+        assert!(fs.create_dir(Path::new("music")).is_ok());
+        assert!(fs.is_dir(Path::new("/home/user/music")));
+
+        let errors = [
+            ("music", io::ErrorKind::AlreadyExists),
+            ("notes.txt", io::ErrorKind::AlreadyExists),
+            ("missing/music", io::ErrorKind::NotFound),
+            ("notes.txt/music", io::ErrorKind::NotADirectory),
+            ("/", io::ErrorKind::AlreadyExists),
+        ];
+        for (path, kind) in errors {
+            let Err(err) = fs.create_dir(Path::new(path)) else {
+                panic!("{path} should not be created");
+            };
+            assert_eq!(err.kind(), kind, "{path}");
+        }
+    }
+
+    #[test]
+    fn file_content() {
+        let fs = file_system().with_file("/data.bin", [0xff, 0xfe]);
+
+        let Ok(metadata) = fs.metadata(Path::new("notes.txt")) else {
+            panic!("failed to read the metadata");
+        };
+        assert_eq!(metadata.size, Some(11));
+
+        let Ok(preview) = fs.load_text_file_preview(Path::new("notes.txt"), 5) else {
+            panic!("failed to read the preview");
+        };
+        assert_eq!(preview, "Hello");
+
+        assert!(fs
+            .load_text_file_preview(Path::new("/data.bin"), 5)
+            .is_err());
+        assert!(fs
+            .load_text_file_preview(Path::new("documents"), 5)
+            .is_err());
+    }
+
+    #[test]
+    fn rename() {
+        let fs = file_system().with_file("/home/user/documents/report.pdf", "");
+
+        assert!(fs
+            .rename(Path::new("documents"), Path::new("papers"))
+            .is_ok());
+        assert!(!fs.exists(Path::new("documents")));
+        assert!(fs.is_file(Path::new("/home/user/papers/report.pdf")));
+
+        // An existing file is replaced
+        assert!(fs
+            .rename(Path::new("papers/report.pdf"), Path::new("notes.txt"))
+            .is_ok());
+        assert!(!fs.exists(Path::new("papers/report.pdf")));
+        assert_eq!(
+            fs.metadata(Path::new("notes.txt"))
+                .ok()
+                .and_then(|m| m.size),
+            Some(0)
+        );
+
+        let errors = [
+            ("missing", "other", io::ErrorKind::NotFound),
+            ("notes.txt", "missing/notes.txt", io::ErrorKind::NotFound),
+            ("notes.txt", "papers", io::ErrorKind::AlreadyExists),
+            ("papers", "notes.txt", io::ErrorKind::NotADirectory),
+            ("papers", "papers/inner", io::ErrorKind::InvalidInput),
+            ("/", "/root", io::ErrorKind::InvalidInput),
+        ];
+        for (from, to, kind) in errors {
+            let Err(err) = fs.rename(Path::new(from), Path::new(to)) else {
+                panic!("{from} should not be renamed to {to}");
+            };
+            assert_eq!(err.kind(), kind, "{from} -> {to}");
+        }
+    }
+
+    #[test]
+    fn remove() {
+        let fs = file_system();
+
+        let errors = [
+            ("missing", io::ErrorKind::NotFound),
+            ("/home", io::ErrorKind::DirectoryNotEmpty),
+            ("/", io::ErrorKind::InvalidInput),
+        ];
+        for (path, kind) in errors {
+            let Err(err) = fs.remove(Path::new(path)) else {
+                panic!("{path} should not be removed");
+            };
+            assert_eq!(err.kind(), kind, "{path}");
+        }
+
+        assert!(fs.remove(Path::new("notes.txt")).is_ok());
+        assert!(fs.remove(Path::new("documents")).is_ok());
+        assert!(fs.remove(Path::new("/home/user")).is_ok());
+        assert!(!fs.exists(Path::new("/home/user")));
+        assert!(fs.is_dir(Path::new("/home")));
+    }
+
+    #[test]
+    fn canonicalize() {
+        let fs = file_system();
+
+        let Ok(path) = fs.canonicalize(Path::new("documents/../notes.txt")) else {
+            panic!("failed to canonicalize the path");
+        };
+        assert_eq!(path, Path::new("/home/user/notes.txt"));
+        assert!(fs.canonicalize(Path::new("missing")).is_err());
+    }
+}
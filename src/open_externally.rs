@@ -0,0 +1,41 @@
+//! Opening files with the default application of the operating system.
+
+use std::io;
+use std::path::Path;
+use std::process::Command;
+
+/// The program used to open files with their default application.
+#[cfg(all(unix, not(target_os = "macos")))]
+const PROGRAM: Option<&str> = Some("xdg-open");
+/// The program used to open files with their default application.
+#[cfg(target_os = "macos")]
+const PROGRAM: Option<&str> = Some("open");
+/// The program used to open files with their default application.
+#[cfg(windows)]
+const PROGRAM: Option<&str> = Some("explorer");
+/// Opening files is not supported on other platforms.
+#[cfg(not(any(unix, windows)))]
+const PROGRAM: Option<&str> = None;
+
+/// Opens the file at the given path using the default application of the platform:
+///   - Linux and other Unix platforms: `xdg-open`
+///   - macOS: `open`
+///   - Windows: the Windows Explorer
+///
+/// This does not block until the application was started. An error is only returned
+/// if the command could not be executed.
+pub fn open(path: &Path) -> io::Result<()> {
+    let program = PROGRAM.ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::Unsupported,
+            "no command available to open files",
+        )
+    })?;
+
+    let mut child = Command::new(program).arg(path).spawn()?;
+
+    // Wait for the command in the background so it does not remain as a zombie process
+    std::thread::spawn(move || child.wait());
+
+    Ok(())
+}
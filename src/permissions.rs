@@ -0,0 +1,206 @@
+//! Helpers to display the Unix permissions and owners of files and directories.
+
+use egui::mutex::Mutex;
+use std::collections::HashMap;
+use std::os::unix::fs::MetadataExt;
+use std::sync::OnceLock;
+
+/// Cache of resolved user or group names, mapped by their ID.
+type NameCache = OnceLock<Mutex<HashMap<u32, String>>>;
+
+/// Formats the given mode bits like `ls -l`, for example `-rw-r--r--` or `drwxr-xr-x`.
+///
+/// The first character is the file type. The setuid, setgid and sticky bits are displayed
+/// as `s` and `t` in place of the execute permission, or as `S` and `T` if the
+/// execute permission is not set.
+pub fn format_permissions(mode: u32) -> String {
+    const TYPE_MASK: u32 = 0o170_000;
+
+    let file_type = match mode & TYPE_MASK {
+        0o040_000 => 'd',
+        0o120_000 => 'l',
+        0o020_000 => 'c',
+        0o060_000 => 'b',
+        0o010_000 => 'p',
+        0o140_000 => 's',
+        _ => '-',
+    };
+
+    let mut result = String::with_capacity(10);
+    result.push(file_type);
+
+    // The read, write and execute bits of the owner, the group and others,
+    // together with the special bit displayed in place of the execute permission
+    let classes = [
+        (0o400, 0o200, 0o100, 0o4000, 's'),
+        (0o040, 0o020, 0o010, 0o2000, 's'),
+        (0o004, 0o002, 0o001, 0o1000, 't'),
+    ];
+
+    for (read, write, execute, special, special_char) in classes {
+        result.push(if mode & read == 0 { '-' } else { 'r' });
+        result.push(if mode & write == 0 { '-' } else { 'w' });
+        result.push(match (mode & execute != 0, mode & special != 0) {
+            (true, true) => special_char,
+            (false, true) => special_char.to_ascii_uppercase(),
+            (true, false) => 'x',
+            (false, false) => '-',
+        });
+    }
+
+    result
+}
+
+/// Checks if the current user is not allowed to write to the item with the given metadata.
+///
+/// The group memberships of the user are not known, so items are assumed to be writable if
+/// they are writable by their group.
+pub fn is_read_only(metadata: &std::fs::Metadata) -> bool {
+    let mode = metadata.mode();
+
+    match current_uid() {
+        // The root user can write to everything
+        0 => false,
+        uid if uid == metadata.uid() => mode & 0o200 == 0,
+        _ => mode & 0o022 == 0,
+    }
+}
+
+/// Gets the effective ID of the user running the process.
+fn current_uid() -> u32 {
+    #[allow(unsafe_code)]
+    // SAFETY: `geteuid` has no preconditions and always succeeds
+    unsafe {
+        libc::geteuid()
+    }
+}
+
+/// Gets the name of the user with the given ID.
+/// Falls back to the numeric ID if the user cannot be found.
+pub fn user_name(uid: u32) -> String {
+    static USERS: NameCache = OnceLock::new();
+
+    resolve_name(&USERS, uid, |id| {
+        lookup_name(
+            |entry, buffer, length, result| {
+                #[allow(unsafe_code)]
+                // SAFETY: All pointers are valid for the duration of the call and `length`
+                // is the size of `buffer`
+                unsafe {
+                    libc::getpwuid_r(id, entry, buffer, length, result)
+                }
+            },
+            |entry: &libc::passwd| entry.pw_name,
+        )
+    })
+}
+
+/// Gets the name of the group with the given ID.
+/// Falls back to the numeric ID if the group cannot be found.
+pub fn group_name(gid: u32) -> String {
+    static GROUPS: NameCache = OnceLock::new();
+
+    resolve_name(&GROUPS, gid, |id| {
+        lookup_name(
+            |entry, buffer, length, result| {
+                #[allow(unsafe_code)]
+                // SAFETY: All pointers are valid for the duration of the call and `length`
+                // is the size of `buffer`
+                unsafe {
+                    libc::getgrgid_r(id, entry, buffer, length, result)
+                }
+            },
+            |entry: &libc::group| entry.gr_name,
+        )
+    })
+}
+
+/// Gets the name of the given ID from the cache, or looks it up using `lookup`.
+/// Each ID is only looked up once, the result is cached for all further calls.
+fn resolve_name(cache: &NameCache, id: u32, lookup: impl FnOnce(u32) -> Option<String>) -> String {
+    let cache = cache.get_or_init(Mutex::default);
+
+    if let Some(name) = cache.lock().get(&id) {
+        return name.clone();
+    }
+
+    let name = lookup(id).unwrap_or_else(|| id.to_string());
+
+    cache.lock().insert(id, name.clone());
+    name
+}
+
+/// Looks up a name using one of the reentrant functions of the user and group database,
+/// like `getpwuid_r`, which also takes user and groups of network directories like LDAP
+/// into account. The buffer is enlarged as long as the entry does not fit into it.
+fn lookup_name<T>(
+    get_entry: impl Fn(*mut T, *mut libc::c_char, usize, *mut *mut T) -> libc::c_int,
+    get_name: impl Fn(&T) -> *const libc::c_char,
+) -> Option<String> {
+    /// The largest buffer used, so that a broken database cannot allocate endlessly.
+    const MAX_BUFFER_SIZE: usize = 1 << 20;
+
+    let mut buffer: Vec<libc::c_char> = vec![0; 1024];
+
+    loop {
+        let mut entry = std::mem::MaybeUninit::<T>::uninit();
+        let mut result = std::ptr::null_mut();
+
+        let err = get_entry(
+            entry.as_mut_ptr(),
+            buffer.as_mut_ptr(),
+            buffer.len(),
+            &raw mut result,
+        );
+
+        if err == libc::ERANGE && buffer.len() < MAX_BUFFER_SIZE {
+            buffer.resize(buffer.len() * 2, 0);
+            continue;
+        }
+
+        if err != 0 || result.is_null() {
+            return None;
+        }
+
+        #[allow(unsafe_code)]
+        // SAFETY: The entry was initialized because `result` points to it, and the name
+        // is a null-terminated string stored inside `buffer`, which is still alive
+        let name = unsafe { std::ffi::CStr::from_ptr(get_name(&*result)) };
+
+        return Some(name.to_string_lossy().into_owned());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn permissions() {
+        assert_eq!(format_permissions(0o100_644), "-rw-r--r--");
+        assert_eq!(format_permissions(0o040_755), "drwxr-xr-x");
+        assert_eq!(format_permissions(0o120_777), "lrwxrwxrwx");
+        assert_eq!(format_permissions(0o100_000), "----------");
+        assert_eq!(format_permissions(0o104_755), "-rwsr-xr-x");
+        assert_eq!(format_permissions(0o102_644), "-rw-r-Sr--");
+        assert_eq!(format_permissions(0o041_777), "drwxrwxrwt");
+        assert_eq!(format_permissions(0o041_776), "drwxrwxrwT");
+    }
+
+    #[test]
+    fn names() {
+        let cache = NameCache::new();
+        assert_eq!(
+            resolve_name(&cache, 1, |_| Some(String::from("user"))),
+            "user"
+        );
+
+        // Each ID is only looked up once
+        assert_eq!(resolve_name(&cache, 1, |_| None), "user");
+        assert_eq!(resolve_name(&cache, 2, |_| None), "2");
+
+        // Unknown IDs are displayed as numbers
+        assert_eq!(user_name(4_000_000_000), "4000000000");
+        assert_eq!(group_name(4_000_000_000), "4000000000");
+    }
+}
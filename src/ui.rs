@@ -0,0 +1,19 @@
+//! Small UI helpers that are shared between the different parts of the file dialog.
+
+use egui::text::LayoutJob;
+use egui::{Align, FontSelection, RichText};
+
+/// Adds a label showing the given error message prefixed with the given error icon.
+///
+/// The icon is colored using the error color of the current theme, so the label
+/// adapts to both light and dark mode.
+pub fn ui_error_label(ui: &mut egui::Ui, icon: &str, msg: &str) -> egui::Response {
+    let mut job = LayoutJob::default();
+
+    RichText::new(format!("{icon} "))
+        .color(ui.visuals().error_fg_color)
+        .append_to(&mut job, ui.style(), FontSelection::Default, Align::Center);
+    RichText::new(msg).append_to(&mut job, ui.style(), FontSelection::Default, Align::Center);
+
+    ui.label(job)
+}